@@ -0,0 +1,60 @@
+//! Compares the per-feature and columnar collection layouts.
+//!
+//! Run with `cargo bench --bench columnar`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bincode::config;
+use geo_types::{Geometry, LineString};
+use hilbert_geometry::collection::{ColumnarCollection, HilbertCollection, HilbertFeature};
+use hilbert_geometry::{decode_geometry, encode_geometry};
+
+fn sample(features: usize, vertices: usize) -> HilbertCollection {
+    (0..features)
+        .map(|i| {
+            let line: LineString = (0..vertices)
+                .map(|j| {
+                    let t = (i * vertices + j) as f64 * 1e-4;
+                    (24.0 + t, 60.0 + t.sin())
+                })
+                .collect();
+            HilbertFeature::new(encode_geometry(&Geometry::LineString(line)))
+        })
+        .collect()
+}
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    let start = Instant::now();
+    let mut runs = 0u32;
+    while start.elapsed() < Duration::from_millis(500) {
+        f();
+        runs += 1;
+    }
+    println!("{name:<32} {:>10.1?} / iter", start.elapsed() / runs);
+}
+
+fn main() {
+    let config = config::standard();
+    let collection = sample(10_000, 50);
+    let columnar = ColumnarCollection::from(&collection);
+    let per_feature = collection.encode_bincode(&config).unwrap();
+    let columns = columnar.encode_bincode(&config).unwrap();
+    println!("per-feature size: {} bytes", per_feature.len());
+    println!("columnar size:    {} bytes", columns.len());
+
+    bench("per-feature decode", || {
+        black_box(HilbertCollection::decode_bincode(&per_feature, &config).unwrap());
+    });
+    bench("columnar decode", || {
+        black_box(ColumnarCollection::decode_bincode(&columns, &config).unwrap());
+    });
+    bench("per-feature coords", || {
+        for f in &collection.features {
+            black_box(decode_geometry(&f.geometry));
+        }
+    });
+    bench("columnar coords", || {
+        black_box(columnar.decode_coords());
+    });
+}
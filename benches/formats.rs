@@ -0,0 +1,55 @@
+//! Compares encoding and decoding speed against WKB, TWKB and GeoJSON.
+//!
+//! Run with `cargo bench --bench formats --features benchmark`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use geo_types::{Geometry, LineString, Polygon};
+use hilbert_geometry::benchmark::{compare_for, Format};
+
+fn sample() -> Vec<Geometry> {
+    (0..1_000)
+        .map(|i| {
+            let (x, y) = (24.0 + (i % 40) as f64 * 0.01, 60.0 + (i / 40) as f64 * 0.01);
+            let ring: LineString = (0..=32)
+                .map(|j| {
+                    let t = j as f64 / 32.0 * std::f64::consts::TAU;
+                    (x + 0.004 * t.cos(), y + 0.002 * t.sin())
+                })
+                .collect();
+            if i % 2 == 0 {
+                Geometry::Polygon(Polygon::new(ring, vec![]))
+            } else {
+                Geometry::LineString(ring)
+            }
+        })
+        .collect()
+}
+
+fn formats(c: &mut Criterion) {
+    let geoms = sample();
+    println!("{}", compare_for(&geoms, std::time::Duration::ZERO));
+
+    let mut group = c.benchmark_group("encode");
+    for format in Format::ALL {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format.name()),
+            &geoms,
+            |b, geoms| b.iter(|| geoms.iter().map(|g| format.encode(g)).collect::<Vec<_>>()),
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("decode");
+    for format in Format::ALL {
+        let encoded: Vec<Vec<u8>> = geoms.iter().filter_map(|g| format.encode(g)).collect();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format.name()),
+            &encoded,
+            |b, data| b.iter(|| data.iter().map(|d| format.decode(d)).collect::<Vec<_>>()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, formats);
+criterion_main!(benches);
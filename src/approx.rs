@@ -0,0 +1,95 @@
+use geo_types::{Coord, Geometry, LineString, Polygon};
+
+use crate::{decode_geometry, HilbertGeometry};
+
+fn coords_eq(a: Coord, b: Coord, epsilon: f64) -> bool {
+    (a.x - b.x).abs() <= epsilon && (a.y - b.y).abs() <= epsilon
+}
+
+fn lines_eq(a: &[Coord], b: &[Coord], epsilon: f64) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| coords_eq(*a, *b, epsilon))
+}
+
+/// Closed rings are equal if they visit the same vertices in the same order,
+/// starting from any vertex.
+fn rings_eq(a: &LineString, b: &LineString, epsilon: f64) -> bool {
+    if lines_eq(&a.0, &b.0, epsilon) {
+        return true;
+    }
+    let n = a.0.len();
+    if n != b.0.len() || n < 3 || !a.is_closed() || !b.is_closed() {
+        return false;
+    }
+    // Leave out the closing vertex, which moves with the rotation.
+    let (a, b) = (&a.0[..n - 1], &b.0[..n - 1]);
+    (1..a.len())
+        .any(|shift| (0..a.len()).all(|i| coords_eq(a[(i + shift) % a.len()], b[i], epsilon)))
+}
+
+fn polygons_eq(a: &Polygon, b: &Polygon, epsilon: f64) -> bool {
+    rings_eq(a.exterior(), b.exterior(), epsilon)
+        && a.interiors().len() == b.interiors().len()
+        && a.interiors()
+            .iter()
+            .zip(b.interiors())
+            .all(|(a, b)| rings_eq(a, b, epsilon))
+}
+
+/// Compares two geometries vertex by vertex, allowing each coordinate to
+/// differ by up to `epsilon` on both axes.
+///
+/// Geometries must have the same type and structure. Polygon rings may start
+/// from a different vertex, as long as the vertex order is the same.
+pub fn geometries_approx_eq(a: &Geometry, b: &Geometry, epsilon: f64) -> bool {
+    match (a, b) {
+        (Geometry::Point(a), Geometry::Point(b)) => coords_eq(a.0, b.0, epsilon),
+        (Geometry::Line(a), Geometry::Line(b)) => {
+            coords_eq(a.start, b.start, epsilon) && coords_eq(a.end, b.end, epsilon)
+        }
+        (Geometry::Rect(a), Geometry::Rect(b)) => {
+            coords_eq(a.min(), b.min(), epsilon) && coords_eq(a.max(), b.max(), epsilon)
+        }
+        (Geometry::Triangle(a), Geometry::Triangle(b)) => {
+            polygons_eq(&a.to_polygon(), &b.to_polygon(), epsilon)
+        }
+        (Geometry::LineString(a), Geometry::LineString(b)) => lines_eq(&a.0, &b.0, epsilon),
+        (Geometry::Polygon(a), Geometry::Polygon(b)) => polygons_eq(a, b, epsilon),
+        (Geometry::MultiPoint(a), Geometry::MultiPoint(b)) => {
+            a.0.len() == b.0.len()
+                && a.0
+                    .iter()
+                    .zip(&b.0)
+                    .all(|(a, b)| coords_eq(a.0, b.0, epsilon))
+        }
+        (Geometry::MultiLineString(a), Geometry::MultiLineString(b)) => {
+            a.0.len() == b.0.len()
+                && a.0
+                    .iter()
+                    .zip(&b.0)
+                    .all(|(a, b)| lines_eq(&a.0, &b.0, epsilon))
+        }
+        (Geometry::MultiPolygon(a), Geometry::MultiPolygon(b)) => {
+            a.0.len() == b.0.len()
+                && a.0
+                    .iter()
+                    .zip(&b.0)
+                    .all(|(a, b)| polygons_eq(a, b, epsilon))
+        }
+        (Geometry::GeometryCollection(a), Geometry::GeometryCollection(b)) => {
+            a.0.len() == b.0.len()
+                && a.0
+                    .iter()
+                    .zip(&b.0)
+                    .all(|(a, b)| geometries_approx_eq(a, b, epsilon))
+        }
+        _ => false,
+    }
+}
+
+impl HilbertGeometry {
+    /// Compares the decoded geometries with [`geometries_approx_eq`].
+    pub fn approx_eq(&self, other: &HilbertGeometry, epsilon: f64) -> bool {
+        self == other
+            || geometries_approx_eq(&decode_geometry(self), &decode_geometry(other), epsilon)
+    }
+}
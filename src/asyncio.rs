@@ -0,0 +1,159 @@
+use bincode::{
+    config::{self, Configuration},
+    error::{DecodeError, EncodeError},
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+
+use crate::collection::{
+    check_magic, footer_range, CollectionFooter, HilbertFeature, COLLECTION_MAGIC, TRAILER_LEN,
+};
+use crate::decode::limited;
+use crate::HilbertGeometry;
+
+/// Largest encoded geometry written by [`encode_to_async_writer`] and accepted
+/// by [`decode_from_async_reader`], 16 MiB.
+pub const MAX_GEOMETRY_LEN: usize = 16 << 20;
+
+fn encode_io(inner: std::io::Error) -> EncodeError {
+    EncodeError::Io { inner, index: 0 }
+}
+
+fn decode_io(inner: std::io::Error) -> DecodeError {
+    DecodeError::Io {
+        inner,
+        additional: 0,
+    }
+}
+
+/// Writes a geometry to an async writer, prefixed with its length as a
+/// little-endian `u32`. Fails for geometries longer than [`MAX_GEOMETRY_LEN`].
+pub async fn encode_to_async_writer<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    geom: &HilbertGeometry,
+    config: &Configuration,
+) -> Result<(), EncodeError> {
+    let data = bincode::encode_to_vec(geom, *config)?;
+    if data.len() > MAX_GEOMETRY_LEN {
+        return Err(EncodeError::Other("geometry too large"));
+    }
+    let len = data.len() as u32;
+    writer
+        .write_all(&len.to_le_bytes())
+        .await
+        .map_err(encode_io)?;
+    writer.write_all(&data).await.map_err(encode_io)
+}
+
+/// Reads a length-prefixed geometry written by [`encode_to_async_writer`].
+///
+/// Fails without reading further if the length prefix exceeds
+/// [`MAX_GEOMETRY_LEN`].
+pub async fn decode_from_async_reader<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    config: &Configuration,
+) -> Result<HilbertGeometry, DecodeError> {
+    let len = reader.read_u32_le().await.map_err(decode_io)? as usize;
+    if len > MAX_GEOMETRY_LEN {
+        return Err(DecodeError::Other("geometry exceeds maximum length"));
+    }
+    let mut data = vec![0; len];
+    reader.read_exact(&mut data).await.map_err(decode_io)?;
+    let (geom, _) = bincode::decode_from_slice(&data, limited(*config))?;
+    Ok(geom)
+}
+
+/// Async random-access reader for collection files.
+///
+/// Reads the footer on creation and fetches single features on demand by
+/// seeking to their offsets.
+pub struct AsyncCollectionReader<R> {
+    reader: R,
+    config: Configuration,
+    footer: CollectionFooter,
+    footer_start: u64,
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> AsyncCollectionReader<R> {
+    pub async fn new(mut reader: R) -> Result<Self, DecodeError> {
+        let file_len = reader
+            .seek(std::io::SeekFrom::End(0))
+            .await
+            .map_err(decode_io)?;
+        let trailer_start = file_len
+            .checked_sub(TRAILER_LEN as u64)
+            .ok_or(DecodeError::Other("not a hilbert collection file"))?;
+        let mut trailer = [0u8; TRAILER_LEN];
+        reader
+            .seek(std::io::SeekFrom::Start(trailer_start))
+            .await
+            .map_err(decode_io)?;
+        reader.read_exact(&mut trailer).await.map_err(decode_io)?;
+        let mut magic = [0u8; COLLECTION_MAGIC.len()];
+        reader
+            .seek(std::io::SeekFrom::Start(0))
+            .await
+            .map_err(decode_io)?;
+        reader.read_exact(&mut magic).await.map_err(decode_io)?;
+        check_magic(&magic)?;
+
+        let range = footer_range(file_len, &trailer)?;
+        let mut data = vec![0; (range.end - range.start) as usize];
+        reader
+            .seek(std::io::SeekFrom::Start(range.start))
+            .await
+            .map_err(decode_io)?;
+        reader.read_exact(&mut data).await.map_err(decode_io)?;
+        let config = config::standard();
+        let (footer, _): (CollectionFooter, usize) =
+            bincode::decode_from_slice(&data, limited(config))?;
+        footer.validate(range.start)?;
+
+        Ok(Self {
+            reader,
+            config,
+            footer,
+            footer_start: range.start,
+        })
+    }
+
+    pub fn footer(&self) -> &CollectionFooter {
+        &self.footer
+    }
+
+    pub fn len(&self) -> usize {
+        self.footer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.footer.is_empty()
+    }
+
+    /// Reads the encoded bytes of feature `i`.
+    pub async fn raw(&mut self, i: usize) -> Option<Result<Vec<u8>, DecodeError>> {
+        let start = *self.footer.offsets.get(i)?;
+        let end = self
+            .footer
+            .offsets
+            .get(i + 1)
+            .map_or(self.footer_start, |o| *o);
+        let mut data = vec![0; end.saturating_sub(start) as usize];
+        let read = async {
+            self.reader
+                .seek(std::io::SeekFrom::Start(start))
+                .await
+                .map_err(decode_io)?;
+            self.reader.read_exact(&mut data).await.map_err(decode_io)?;
+            Ok(data)
+        };
+        Some(read.await)
+    }
+
+    /// Reads and decodes feature `i`.
+    pub async fn get(&mut self, i: usize) -> Option<Result<HilbertFeature, DecodeError>> {
+        let config = self.config;
+        let raw = self.raw(i).await?;
+        Some(raw.and_then(|data| {
+            bincode::decode_from_slice(&data, limited(config)).map(|(feature, _)| feature)
+        }))
+    }
+}
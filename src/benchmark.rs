@@ -0,0 +1,382 @@
+//! Size and speed of encoded geometries against other formats.
+//!
+//! [`compare`] encodes and decodes a dataset with [`HilbertSerializer`] and
+//! with WKB, TWKB and GeoJSON, so adopters can judge the format on their
+//! own data rather than on published numbers. Run the `formats` bench for
+//! the same comparison on a synthetic dataset under criterion.
+//!
+//! TWKB is written at 7 decimals, about a centimetre, which is close to
+//! the precision of the curve encoding for lon/lat coordinates. Only points,
+//! linestrings and polygons and their multi variants can be compared; other
+//! geometries are skipped.
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use geo_types::{
+    Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+
+use crate::ewkb::{geometry_from_ewkb, geometry_to_ewkb};
+use crate::{supported, HilbertSerializer};
+
+/// Time spent measuring each direction of each format in [`compare`].
+pub const DEFAULT_MIN_TIME: Duration = Duration::from_millis(200);
+
+/// A geometry format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// [`HilbertSerializer`] with default settings.
+    Hwkb,
+    /// Little-endian ISO WKB.
+    Wkb,
+    /// Tiny WKB at 7 decimals.
+    Twkb,
+    /// GeoJSON geometry objects.
+    GeoJson,
+}
+
+impl Format {
+    pub const ALL: [Format; 4] = [Format::Hwkb, Format::Wkb, Format::Twkb, Format::GeoJson];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Hwkb => "HWKB",
+            Format::Wkb => "WKB",
+            Format::Twkb => "TWKB",
+            Format::GeoJson => "GeoJSON",
+        }
+    }
+
+    /// Encodes `geom` in this format, `None` for geometry types other than
+    /// points, linestrings and polygons and their multi variants.
+    pub fn encode(&self, geom: &Geometry) -> Option<Vec<u8>> {
+        supported(geom).ok()?;
+        match self {
+            Format::Hwkb => HilbertSerializer::new().encode(geom).ok(),
+            Format::Wkb => Some(geometry_to_ewkb(geom, None)),
+            Format::Twkb => {
+                let mut writer = TwkbWriter::default();
+                writer.geometry(geom)?;
+                Some(writer.buf)
+            }
+            Format::GeoJson => Some(
+                geojson::Geometry::new(geojson::Value::from(geom))
+                    .to_string()
+                    .into_bytes(),
+            ),
+        }
+    }
+
+    /// Decodes data written by [`encode`](Self::encode), `None` if it is
+    /// invalid.
+    pub fn decode(&self, data: &[u8]) -> Option<Geometry> {
+        match self {
+            Format::Hwkb => HilbertSerializer::new().decode(data).ok(),
+            Format::Wkb => geometry_from_ewkb(data).ok().map(|(geom, _)| geom),
+            Format::Twkb => TwkbReader {
+                data,
+                pos: 0,
+                last: [0; 2],
+            }
+            .geometry(),
+            Format::GeoJson => {
+                let json: geojson::Geometry = std::str::from_utf8(data).ok()?.parse().ok()?;
+                Geometry::try_from(&json.value).ok()
+            }
+        }
+    }
+}
+
+/// Size and speed of one format over a dataset. Times are for one pass
+/// over all geometries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatStats {
+    pub format: Format,
+    /// Total encoded size in bytes.
+    pub bytes: usize,
+    pub encode_time: Duration,
+    pub decode_time: Duration,
+}
+
+/// Results of [`compare`], one entry per format in [`Format::ALL`] order.
+/// Displays as a table with sizes relative to WKB.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    /// Number of geometries compared.
+    pub geometries: usize,
+    /// Number of geometries skipped as no format encodes them.
+    pub skipped: usize,
+    pub formats: Vec<FormatStats>,
+}
+
+impl Comparison {
+    pub fn get(&self, format: Format) -> Option<&FormatStats> {
+        self.formats.iter().find(|s| s.format == format)
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wkb = self.get(Format::Wkb).map_or(0, |s| s.bytes).max(1) as f64;
+        let rate = |time: Duration| self.geometries as f64 / time.as_secs_f64().max(1e-9);
+        writeln!(
+            f,
+            "{:<8} {:>12} {:>7} {:>14} {:>14}",
+            "format", "bytes", "vs WKB", "encode geom/s", "decode geom/s"
+        )?;
+        for s in &self.formats {
+            writeln!(
+                f,
+                "{:<8} {:>12} {:>6.1}% {:>14.0} {:>14.0}",
+                s.format.name(),
+                s.bytes,
+                100.0 * s.bytes as f64 / wkb,
+                rate(s.encode_time),
+                rate(s.decode_time)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Encodes and decodes `geoms` in every format, measuring each direction
+/// for about [`DEFAULT_MIN_TIME`]. Geometries [`Format::encode`] rejects
+/// are skipped.
+pub fn compare(geoms: &[Geometry]) -> Comparison {
+    compare_for(geoms, DEFAULT_MIN_TIME)
+}
+
+/// Like [`compare`], repeating each measurement for at least `min_time`
+/// and at least once.
+pub fn compare_for(geoms: &[Geometry], min_time: Duration) -> Comparison {
+    let compared: Vec<&Geometry> = geoms.iter().filter(|g| supported(g).is_ok()).collect();
+    let formats = Format::ALL
+        .into_iter()
+        .map(|format| {
+            let encoded: Vec<Vec<u8>> = compared.iter().filter_map(|g| format.encode(g)).collect();
+            let encode_time = time_passes(min_time, || {
+                for g in &compared {
+                    black_box(format.encode(g));
+                }
+            });
+            let decode_time = time_passes(min_time, || {
+                for data in &encoded {
+                    black_box(format.decode(data));
+                }
+            });
+            FormatStats {
+                format,
+                bytes: encoded.iter().map(Vec::len).sum(),
+                encode_time,
+                decode_time,
+            }
+        })
+        .collect();
+    Comparison {
+        geometries: compared.len(),
+        skipped: geoms.len() - compared.len(),
+        formats,
+    }
+}
+
+/// Mean time of `pass`, repeated until `min_time` has passed.
+fn time_passes(min_time: Duration, mut pass: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    let mut runs = 0u32;
+    while runs == 0 || start.elapsed() < min_time {
+        pass();
+        runs += 1;
+    }
+    start.elapsed() / runs
+}
+
+const TWKB_PRECISION: i64 = 7;
+const TWKB_SCALE: f64 = 1e7;
+
+/// TWKB writer. Coordinates are deltas from the previous coordinate of
+/// the same geometry, across parts and rings.
+#[derive(Default)]
+struct TwkbWriter {
+    buf: Vec<u8>,
+    last: [i64; 2],
+}
+
+impl TwkbWriter {
+    fn varint(&mut self, mut v: u64) {
+        while v >= 0x80 {
+            self.buf.push(v as u8 | 0x80);
+            v >>= 7;
+        }
+        self.buf.push(v as u8);
+    }
+
+    fn signed(&mut self, v: i64) {
+        self.varint(((v << 1) ^ (v >> 63)) as u64);
+    }
+
+    fn header(&mut self, kind: u8) {
+        let precision = ((TWKB_PRECISION << 1) ^ (TWKB_PRECISION >> 63)) as u8;
+        self.buf.extend([kind | precision << 4, 0]);
+    }
+
+    fn coord(&mut self, c: Coord) {
+        for (i, v) in [c.x, c.y].into_iter().enumerate() {
+            let v = (v * TWKB_SCALE).round() as i64;
+            self.signed(v - self.last[i]);
+            self.last[i] = v;
+        }
+    }
+
+    fn coords(&mut self, ls: &LineString) {
+        self.varint(ls.0.len() as u64);
+        for c in &ls.0 {
+            self.coord(*c);
+        }
+    }
+
+    fn rings(&mut self, poly: &Polygon) {
+        self.varint(1 + poly.interiors().len() as u64);
+        self.coords(poly.exterior());
+        for ring in poly.interiors() {
+            self.coords(ring);
+        }
+    }
+
+    /// Writes `geom`, `None` for geometry types TWKB has no code for here.
+    fn geometry(&mut self, geom: &Geometry) -> Option<()> {
+        match geom {
+            Geometry::Point(p) => {
+                self.header(1);
+                self.coord(p.0);
+            }
+            Geometry::LineString(ls) => {
+                self.header(2);
+                self.coords(ls);
+            }
+            Geometry::Polygon(poly) => {
+                self.header(3);
+                self.rings(poly);
+            }
+            Geometry::MultiPoint(mp) => {
+                self.header(4);
+                self.varint(mp.0.len() as u64);
+                for p in &mp.0 {
+                    self.coord(p.0);
+                }
+            }
+            Geometry::MultiLineString(mls) => {
+                self.header(5);
+                self.varint(mls.0.len() as u64);
+                for ls in &mls.0 {
+                    self.coords(ls);
+                }
+            }
+            Geometry::MultiPolygon(mp) => {
+                self.header(6);
+                self.varint(mp.0.len() as u64);
+                for poly in &mp.0 {
+                    self.rings(poly);
+                }
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
+struct TwkbReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    last: [i64; 2],
+}
+
+impl TwkbReader<'_> {
+    fn byte(&mut self) -> Option<u8> {
+        let b = *self.data.get(self.pos)?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn varint(&mut self) -> Option<u64> {
+        let mut v = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = self.byte()?;
+            v |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Some(v);
+            }
+        }
+        None
+    }
+
+    fn signed(&mut self) -> Option<i64> {
+        let v = self.varint()?;
+        Some((v >> 1) as i64 ^ -((v & 1) as i64))
+    }
+
+    /// A count, bounded by the remaining bytes so corrupt input can't
+    /// allocate unbounded memory.
+    fn count(&mut self) -> Option<usize> {
+        let n = self.varint()?;
+        (n <= (self.data.len() - self.pos) as u64).then_some(n as usize)
+    }
+
+    fn coord(&mut self, scale: f64) -> Option<Coord> {
+        let mut c = [0.0; 2];
+        for (i, v) in c.iter_mut().enumerate() {
+            self.last[i] += self.signed()?;
+            *v = self.last[i] as f64 / scale;
+        }
+        Some(Coord { x: c[0], y: c[1] })
+    }
+
+    fn line(&mut self, scale: f64) -> Option<LineString> {
+        let n = self.count()?;
+        (0..n).map(|_| self.coord(scale)).collect()
+    }
+
+    fn polygon(&mut self, scale: f64) -> Option<Polygon> {
+        let n = self.count()?;
+        let mut rings = (0..n)
+            .map(|_| self.line(scale))
+            .collect::<Option<Vec<_>>>()?
+            .into_iter();
+        let exterior = rings.next().unwrap_or_else(|| LineString::new(vec![]));
+        Some(Polygon::new(exterior, rings.collect()))
+    }
+
+    fn geometry(&mut self) -> Option<Geometry> {
+        let header = self.byte()?;
+        let precision = (header >> 4) as i32;
+        let scale = 10f64.powi((precision >> 1) ^ -(precision & 1));
+        if self.byte()? != 0 {
+            // Bounding boxes, sizes, id lists and extended dimensions are
+            // never written.
+            return None;
+        }
+        let geom = match header & 0x0f {
+            1 => Point(self.coord(scale)?).into(),
+            2 => self.line(scale)?.into(),
+            3 => self.polygon(scale)?.into(),
+            4 => {
+                let n = self.count()?;
+                let points = (0..n).map(|_| self.coord(scale).map(Point));
+                MultiPoint(points.collect::<Option<_>>()?).into()
+            }
+            5 => {
+                let n = self.count()?;
+                let lines = (0..n).map(|_| self.line(scale));
+                MultiLineString(lines.collect::<Option<_>>()?).into()
+            }
+            6 => {
+                let n = self.count()?;
+                let polys = (0..n).map(|_| self.polygon(scale));
+                MultiPolygon(polys.collect::<Option<_>>()?).into()
+            }
+            _ => return None,
+        };
+        Some(geom)
+    }
+}
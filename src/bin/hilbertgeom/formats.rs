@@ -0,0 +1,300 @@
+use std::error::Error;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use geo_types::Geometry;
+use geojson::{FeatureCollection, GeoJson, JsonObject, JsonValue};
+use hilbert_geometry::collection::{
+    CollectionReader, HilbertCollectionWriter, HilbertFeature, PropertyValue,
+};
+use hilbert_geometry::ewkb::{geometry_from_hex_ewkb, geometry_to_hex_ewkb};
+use hilbert_geometry::{decode_geometry, quantize, try_encode_geometry};
+use wkt::{ToWkt, TryFromWkt};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// File formats understood by the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// GeoJSON FeatureCollection, Feature or Geometry.
+    Geojson,
+    /// One hex-encoded (E)WKB geometry per line.
+    Wkb,
+    /// One WKT geometry per line.
+    Wkt,
+    /// Hilbert collection file.
+    Hgc,
+}
+
+impl Format {
+    /// Guesses the format from a file extension, ignoring a trailing `.gz`.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        let name = name.strip_suffix(".gz").unwrap_or(&name);
+        match name.rsplit_once('.')?.1 {
+            "geojson" | "json" => Some(Format::Geojson),
+            "wkb" | "hex" => Some(Format::Wkb),
+            "wkt" => Some(Format::Wkt),
+            "hgc" => Some(Format::Hgc),
+            _ => None,
+        }
+    }
+}
+
+/// How coordinates are rounded to the requested number of decimal places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Rounding {
+    /// Round half away from zero.
+    #[default]
+    Round,
+    /// Round toward zero.
+    Truncate,
+    /// Round half to even (banker's rounding).
+    HalfEven,
+    /// Keep coordinates as they are, even if a precision is given.
+    None,
+}
+
+/// Decimal places to round coordinates to, and how.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Precision {
+    pub places: u32,
+    pub rounding: quantize::Rounding,
+}
+
+impl Precision {
+    /// Returns the precision to apply, or `None` if rounding is disabled.
+    pub fn new(places: Option<u32>, rounding: Rounding) -> Option<Precision> {
+        let rounding = match rounding {
+            Rounding::Round => quantize::Rounding::Round,
+            Rounding::Truncate => quantize::Rounding::Truncate,
+            Rounding::HalfEven => quantize::Rounding::HalfEven,
+            Rounding::None => return None,
+        };
+        places.map(|places| Precision { places, rounding })
+    }
+}
+
+/// Rounds coordinates to the given precision.
+pub fn round_geometry(geom: &Geometry, precision: Precision) -> Geometry {
+    quantize::quantize_geometry(geom, precision.places, precision.rounding)
+}
+
+fn to_feature(geom: Geometry, precision: Option<Precision>) -> Result<HilbertFeature> {
+    let geom = match precision {
+        Some(p) => round_geometry(&geom, p),
+        None => geom,
+    };
+    Ok(HilbertFeature::new(try_encode_geometry(&geom)?))
+}
+
+fn from_json(value: &JsonValue) -> PropertyValue {
+    match value {
+        JsonValue::Null => PropertyValue::Null,
+        JsonValue::Bool(b) => PropertyValue::Bool(*b),
+        JsonValue::Number(n) => n
+            .as_i64()
+            .map(PropertyValue::Int)
+            .unwrap_or_else(|| PropertyValue::Double(n.as_f64().unwrap_or(f64::NAN))),
+        JsonValue::String(s) => PropertyValue::String(s.clone()),
+        other => PropertyValue::String(other.to_string()),
+    }
+}
+
+fn to_json(value: &PropertyValue) -> JsonValue {
+    match value {
+        PropertyValue::Null => JsonValue::Null,
+        PropertyValue::Bool(b) => JsonValue::Bool(*b),
+        PropertyValue::Int(i) => JsonValue::from(*i),
+        PropertyValue::Double(d) => JsonValue::from(*d),
+        PropertyValue::String(s) => JsonValue::String(s.clone()),
+    }
+}
+
+/// Opens a file for streaming, transparently decompressing gzip.
+pub fn open_reader(path: &Path) -> Result<Box<dyn BufRead>> {
+    let mut file = BufReader::new(std::fs::File::open(path)?);
+    if file.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        return Ok(Box::new(BufReader::new(GzDecoder::new(file))));
+    }
+    Ok(Box::new(file))
+}
+
+/// Reads a file, transparently decompressing gzip.
+pub fn read_bytes(path: &Path) -> Result<Vec<u8>> {
+    let data = std::fs::read(path)?;
+    if data.starts_with(&GZIP_MAGIC) {
+        let mut out = vec![];
+        GzDecoder::new(data.as_slice()).read_to_end(&mut out)?;
+        return Ok(out);
+    }
+    Ok(data)
+}
+
+/// Writes a file, optionally gzip-compressed.
+pub fn write_bytes(path: &Path, data: &[u8], compress: bool) -> Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    if compress {
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        encoder.write_all(data)?;
+        encoder.finish()?;
+    } else {
+        file.write_all(data)?;
+    }
+    Ok(())
+}
+
+/// Returns the non-empty lines of a line-based file with their line numbers.
+pub fn lines(data: &[u8]) -> Result<impl Iterator<Item = (usize, &str)>> {
+    Ok(std::str::from_utf8(data)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, line)))
+}
+
+/// Parses one line of a WKT or WKB file.
+pub fn parse_line(format: Format, line: &str) -> Result<Geometry> {
+    match format {
+        Format::Wkt => Ok(Geometry::try_from_wkt_str(line)?),
+        Format::Wkb => Ok(geometry_from_hex_ewkb(line)?.0),
+        _ => Err(format!("{format:?} is not a line-based format").into()),
+    }
+}
+
+/// Parses GeoJSON into a list of features.
+pub fn parse_geojson(data: &[u8]) -> Result<Vec<geojson::Feature>> {
+    Ok(match std::str::from_utf8(data)?.parse::<GeoJson>()? {
+        GeoJson::FeatureCollection(fc) => fc.features,
+        GeoJson::Feature(f) => vec![f],
+        GeoJson::Geometry(g) => vec![geojson::Feature::from(g)],
+    })
+}
+
+/// Reads all geometries of a file as they are stored, without encoding them.
+/// Collection files are decoded.
+pub fn read_geometries(path: &Path, format: Format) -> Result<Vec<Geometry>> {
+    let data = read_bytes(path)?;
+    match format {
+        Format::Hgc => CollectionReader::new(data)?
+            .iter()
+            .map(|f| Ok(decode_geometry(&f?.geometry)))
+            .collect(),
+        Format::Wkt | Format::Wkb => lines(&data)?
+            .map(|(n, line)| parse_line(format, line).map_err(|e| format!("line {n}: {e}").into()))
+            .collect(),
+        Format::Geojson => parse_geojson(&data)?
+            .into_iter()
+            .filter_map(|f| f.geometry)
+            .map(|g| Ok(Geometry::try_from(&g.value)?))
+            .collect(),
+    }
+}
+
+/// Reads all features of a file. GeoJSON features without a geometry are skipped.
+pub fn read_features(
+    path: &Path,
+    format: Format,
+    precision: Option<Precision>,
+) -> Result<Vec<HilbertFeature>> {
+    match format {
+        Format::Hgc => {
+            return Ok(CollectionReader::new(read_bytes(path)?)?
+                .iter()
+                .collect::<std::result::Result<_, _>>()?);
+        }
+        Format::Wkt | Format::Wkb => {
+            return read_geometries(path, format)?
+                .into_iter()
+                .map(|geom| to_feature(geom, precision))
+                .collect();
+        }
+        Format::Geojson => {}
+    }
+    let mut out = vec![];
+    for f in parse_geojson(&read_bytes(path)?)? {
+        out.extend(from_geojson_feature(f, precision)?);
+    }
+    Ok(out)
+}
+
+/// Encodes a GeoJSON feature with its properties, or `None` if it has no
+/// geometry.
+pub fn from_geojson_feature(
+    f: geojson::Feature,
+    precision: Option<Precision>,
+) -> Result<Option<HilbertFeature>> {
+    let Some(geometry) = f.geometry else {
+        return Ok(None);
+    };
+    let mut feature = to_feature(Geometry::try_from(&geometry.value)?, precision)?;
+    for (key, value) in f.properties.iter().flatten() {
+        feature = feature.with_property(key, from_json(value));
+    }
+    Ok(Some(feature))
+}
+
+/// Writes features to a file, optionally gzip-compressed.
+pub fn write_features(
+    path: &Path,
+    format: Format,
+    features: &[HilbertFeature],
+    precision: Option<Precision>,
+    compress: bool,
+) -> Result<()> {
+    let geometry = |f: &HilbertFeature| {
+        let geom = decode_geometry(&f.geometry);
+        match precision {
+            Some(p) => round_geometry(&geom, p),
+            None => geom,
+        }
+    };
+    let data = match format {
+        Format::Hgc => {
+            let mut writer = HilbertCollectionWriter::new(vec![])?;
+            for f in features {
+                writer.write(f)?;
+            }
+            writer.finish()?
+        }
+        Format::Wkt => features
+            .iter()
+            .map(|f| geometry(f).wkt_string() + "\n")
+            .collect::<String>()
+            .into_bytes(),
+        Format::Wkb => features
+            .iter()
+            .map(|f| geometry_to_hex_ewkb(&geometry(f), None) + "\n")
+            .collect::<String>()
+            .into_bytes(),
+        Format::Geojson => {
+            let features = features
+                .iter()
+                .map(|f| {
+                    let properties: JsonObject = f
+                        .properties
+                        .iter()
+                        .map(|(k, v)| (k.clone(), to_json(v)))
+                        .collect();
+                    let mut feature = geojson::Feature::from(geojson::Geometry::new(
+                        geojson::Value::from(&geometry(f)),
+                    ));
+                    feature.properties = Some(properties);
+                    feature
+                })
+                .collect();
+            let fc = FeatureCollection {
+                bbox: None,
+                features,
+                foreign_members: None,
+            };
+            GeoJson::from(fc).to_string().into_bytes()
+        }
+    };
+
+    write_bytes(path, &data, compress)
+}
@@ -0,0 +1,142 @@
+//! Command line tool for converting datasets to and from Hilbert collection files.
+
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Parser, Subcommand};
+use hilbert_geometry::kv::make_key;
+
+mod formats;
+mod sort;
+mod stats;
+mod stream;
+
+use formats::{
+    read_features, read_geometries, write_features, Format, Precision, Result, Rounding,
+};
+
+#[derive(Parser)]
+#[command(name = "hilbertgeom", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert between GeoJSON, WKB, WKT and Hilbert collection files.
+    Convert(ConvertArgs),
+    /// Order the features of a GeoJSON, WKB, WKT or collection file along
+    /// the Hilbert curve, keeping their coordinates and attributes.
+    Sort(SortArgs),
+    /// Report encoded sizes compared to WKB, and encoding error bounds.
+    Stats(StatsArgs),
+}
+
+#[derive(Args)]
+struct ConvertArgs {
+    input: PathBuf,
+    output: PathBuf,
+    /// Input format, guessed from the file extension if not given.
+    #[arg(long, value_enum)]
+    from: Option<Format>,
+    /// Output format, guessed from the file extension if not given.
+    #[arg(long, value_enum)]
+    to: Option<Format>,
+    /// Round coordinates to this many decimal places.
+    #[arg(long)]
+    precision: Option<u32>,
+    /// How to round coordinates with --precision.
+    #[arg(long, value_enum, default_value_t)]
+    rounding: Rounding,
+    /// Gzip-compress the output. Compressed inputs are detected automatically.
+    #[arg(long)]
+    compress: bool,
+    /// Order features along the Hilbert curve.
+    #[arg(long)]
+    sort: bool,
+    /// Convert a GeoJSON FeatureCollection to a collection file feature by
+    /// feature, without loading the whole input into memory.
+    #[arg(long, conflicts_with = "sort")]
+    stream: bool,
+}
+
+#[derive(Args)]
+struct SortArgs {
+    input: PathBuf,
+    output: PathBuf,
+    /// File format, guessed from the input file extension if not given.
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+    /// Gzip-compress the output.
+    #[arg(long)]
+    compress: bool,
+}
+
+#[derive(Args)]
+struct StatsArgs {
+    input: PathBuf,
+    /// Input format, guessed from the file extension if not given.
+    #[arg(long, value_enum)]
+    from: Option<Format>,
+    /// Only print totals.
+    #[arg(long)]
+    summary: bool,
+}
+
+fn format_of(path: &Path, explicit: Option<Format>) -> Result<Format> {
+    explicit.or_else(|| Format::from_path(path)).ok_or_else(|| {
+        format!(
+            "cannot guess the format of {}, use --from/--to",
+            path.display()
+        )
+        .into()
+    })
+}
+
+fn convert(args: ConvertArgs) -> Result<()> {
+    let from = format_of(&args.input, args.from)?;
+    let to = format_of(&args.output, args.to)?;
+    let precision = Precision::new(args.precision, args.rounding);
+    if args.stream {
+        if (from, to) != (Format::Geojson, Format::Hgc) {
+            return Err("--stream converts GeoJSON to collection files only".into());
+        }
+        let count = stream::geojson_to_hgc(&args.input, &args.output, precision, args.compress)?;
+        eprintln!("converted {count} features");
+        return Ok(());
+    }
+    let mut features = read_features(&args.input, from, precision)?;
+    if args.sort {
+        features.sort_by_cached_key(|f| make_key(&f.geometry));
+    }
+    write_features(&args.output, to, &features, precision, args.compress)?;
+    eprintln!("converted {} features", features.len());
+    Ok(())
+}
+
+fn sort(args: SortArgs) -> Result<()> {
+    let format = format_of(&args.input, args.format)?;
+    let count = sort::sort_file(&args.input, &args.output, format, args.compress)?;
+    eprintln!("sorted {count} features");
+    Ok(())
+}
+
+fn stats(args: StatsArgs) -> Result<()> {
+    let format = format_of(&args.input, args.from)?;
+    let geoms = read_geometries(&args.input, format)?;
+    stats::print_stats(&geoms, args.summary);
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Convert(args) => convert(args),
+        Command::Sort(args) => sort(args),
+        Command::Stats(args) => stats(args),
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
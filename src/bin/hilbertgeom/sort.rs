@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use geo_types::Geometry;
+use geojson::GeoJson;
+use hilbert_geometry::collection::HilbertCollectionWriter;
+use hilbert_geometry::kv::make_key;
+use hilbert_geometry::try_encode_geometry;
+
+use crate::formats::{lines, parse_line, read_bytes, read_features, write_bytes, Format, Result};
+
+/// Sort key of a geometry: the Hilbert key of its bounding box center, or of
+/// the first keyed member of a geometry collection.
+pub fn geometry_key(geom: &Geometry) -> Option<Vec<u8>> {
+    match geom {
+        Geometry::GeometryCollection(gc) => gc.0.iter().find_map(geometry_key),
+        _ => make_key(&try_encode_geometry(geom).ok()?),
+    }
+}
+
+/// Reorders the features of a file along the Hilbert curve. Apart from
+/// collection files, features are written back as they were read, so
+/// coordinates and attributes are preserved exactly. Returns the number of
+/// features.
+pub fn sort_file(input: &Path, output: &Path, format: Format, compress: bool) -> Result<usize> {
+    match format {
+        Format::Hgc => {
+            let mut features = read_features(input, format, None)?;
+            features.sort_by_cached_key(|f| make_key(&f.geometry));
+            let mut writer = HilbertCollectionWriter::new(vec![])?;
+            for f in &features {
+                writer.write(f)?;
+            }
+            write_bytes(output, &writer.finish()?, compress)?;
+            Ok(features.len())
+        }
+        Format::Wkt | Format::Wkb => {
+            let data = read_bytes(input)?;
+            let mut keyed = lines(&data)?
+                .map(|(n, line)| {
+                    let geom = parse_line(format, line).map_err(|e| format!("line {n}: {e}"))?;
+                    Ok((geometry_key(&geom), line))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            keyed.sort_by(|a, b| a.0.cmp(&b.0));
+            let out: String = keyed.iter().map(|(_, line)| format!("{line}\n")).collect();
+            write_bytes(output, out.as_bytes(), compress)?;
+            Ok(keyed.len())
+        }
+        Format::Geojson => {
+            let data = read_bytes(input)?;
+            let mut fc = match std::str::from_utf8(&data)?.parse::<GeoJson>()? {
+                GeoJson::FeatureCollection(fc) => fc,
+                // A single feature or geometry is already sorted.
+                _ => {
+                    write_bytes(output, &data, compress)?;
+                    return Ok(1);
+                }
+            };
+            let keys = fc
+                .features
+                .iter()
+                .map(|f| match &f.geometry {
+                    Some(g) => Ok(geometry_key(&Geometry::try_from(&g.value)?)),
+                    None => Ok(None),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let mut order: Vec<usize> = (0..keys.len()).collect();
+            order.sort_by(|a, b| keys[*a].cmp(&keys[*b]));
+            let mut features: Vec<_> = fc.features.drain(..).map(Some).collect();
+            fc.features = order.iter().filter_map(|i| features[*i].take()).collect();
+            write_bytes(output, GeoJson::from(fc).to_string().as_bytes(), compress)?;
+            Ok(keys.len())
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use bincode::error::EncodeError;
+use geo_types::{Coord, Geometry};
+use hilbert_geometry::ewkb::geometry_to_ewkb;
+use hilbert_geometry::{decode_geometry, try_encode_geometry};
+
+/// Approximate length of one degree of latitude, in meters.
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+/// Size and precision of a single encoded geometry.
+pub struct GeometryStats {
+    pub kind: &'static str,
+    pub points: usize,
+    pub encoded_bytes: usize,
+    pub wkb_bytes: usize,
+    /// Largest distance between an input and a decoded coordinate, in degrees.
+    pub max_error: f64,
+}
+
+fn coords(geom: &Geometry) -> Vec<Coord> {
+    match geom {
+        Geometry::Point(p) => vec![p.0],
+        Geometry::LineString(ls) => ls.0.clone(),
+        Geometry::Polygon(p) => std::iter::once(p.exterior())
+            .chain(p.interiors())
+            .flat_map(|ring| ring.0.iter().copied())
+            .collect(),
+        Geometry::MultiPoint(mp) => mp.0.iter().map(|p| p.0).collect(),
+        Geometry::MultiLineString(mls) => mls.0.iter().flat_map(|ls| ls.0.clone()).collect(),
+        Geometry::MultiPolygon(mp) => {
+            mp.0.iter()
+                .flat_map(|p| coords(&Geometry::Polygon(p.clone())))
+                .collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// Computes statistics for a geometry. Fails if its type can't be encoded.
+pub fn geometry_stats(geom: &Geometry) -> Result<GeometryStats, EncodeError> {
+    let encoded = try_encode_geometry(geom)?;
+    let encoded_bytes = bincode::encode_to_vec(&encoded, bincode::config::standard())?.len();
+    let input = coords(geom);
+    let max_error = input
+        .iter()
+        .zip(coords(&decode_geometry(&encoded)))
+        .map(|(a, b)| (a.x - b.x).hypot(a.y - b.y))
+        .fold(0.0, f64::max);
+    Ok(GeometryStats {
+        kind: encoded.kind(),
+        points: encoded.num_coords(),
+        encoded_bytes,
+        wkb_bytes: geometry_to_ewkb(geom, None).len(),
+        max_error,
+    })
+}
+
+/// Prints per-geometry statistics (unless `summary` is set) followed by totals.
+pub fn print_stats(geoms: &[Geometry], summary: bool) {
+    if !summary {
+        println!("index\ttype\tpoints\tbytes\twkb_bytes\tmax_error_deg");
+    }
+    let (mut count, mut points, mut encoded, mut wkb, mut max_error) = (0, 0, 0, 0, 0.0f64);
+    for (i, geom) in geoms.iter().enumerate() {
+        let stats = match geometry_stats(geom) {
+            Ok(stats) => stats,
+            Err(e) => {
+                eprintln!("skipping geometry {i}: {e}");
+                continue;
+            }
+        };
+        if !summary {
+            println!(
+                "{i}\t{}\t{}\t{}\t{}\t{:e}",
+                stats.kind, stats.points, stats.encoded_bytes, stats.wkb_bytes, stats.max_error
+            );
+        }
+        count += 1;
+        points += stats.points;
+        encoded += stats.encoded_bytes;
+        wkb += stats.wkb_bytes;
+        max_error = max_error.max(stats.max_error);
+    }
+
+    println!("geometries: {count}");
+    println!("points: {points}");
+    println!("encoded bytes: {encoded}");
+    println!("wkb bytes: {wkb}");
+    if wkb > 0 {
+        println!("ratio: {:.1}% of wkb", 100.0 * encoded as f64 / wkb as f64);
+    }
+    println!(
+        "max error: {max_error:e} degrees (~{:.3} m)",
+        max_error * METERS_PER_DEGREE
+    );
+}
@@ -0,0 +1,55 @@
+//! Streaming conversion of GeoJSON FeatureCollections to collection files.
+
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use flate2::{write::GzEncoder, Compression};
+use geojson::FeatureReader;
+use hilbert_geometry::collection::HilbertCollectionWriter;
+
+use crate::formats::{from_geojson_feature, open_reader, Precision, Result};
+
+/// Converts a GeoJSON FeatureCollection to a collection file one feature at a
+/// time, so memory use doesn't grow with the input size. Features without a
+/// geometry are skipped. Returns the number of features written.
+pub fn geojson_to_hgc(
+    input: &Path,
+    output: &Path,
+    precision: Option<Precision>,
+    compress: bool,
+) -> Result<usize> {
+    let file = BufWriter::new(std::fs::File::create(output)?);
+    if compress {
+        let (count, encoder) = write_features(
+            input,
+            GzEncoder::new(file, Compression::default()),
+            precision,
+        )?;
+        encoder.finish()?.flush()?;
+        Ok(count)
+    } else {
+        let (count, mut file) = write_features(input, file, precision)?;
+        file.flush()?;
+        Ok(count)
+    }
+}
+
+fn write_features<W: Write>(
+    input: &Path,
+    writer: W,
+    precision: Option<Precision>,
+) -> Result<(usize, W)> {
+    let mut writer = HilbertCollectionWriter::new(writer)?;
+    for (i, feature) in FeatureReader::from_reader(open_reader(input)?)
+        .features()
+        .enumerate()
+    {
+        let feature = from_geojson_feature(feature?, precision)
+            .map_err(|e| format!("feature {}: {e}", i + 1))?;
+        if let Some(feature) = feature {
+            writer.write(&feature)?;
+        }
+    }
+    let count = writer.len();
+    Ok((count, writer.finish()?))
+}
@@ -0,0 +1,141 @@
+//! Cache of decoded geometries for servers that decode the same payloads
+//! over and over, e.g. tile servers.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use geo_types::Geometry;
+
+use crate::{GeometryDecodeError, HilbertSerializer};
+
+/// Hit and miss counts of a [`DecodingCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+struct Entry {
+    data: Box<[u8]>,
+    geometry: Arc<Geometry>,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Lru {
+    entries: HashMap<u64, Entry>,
+    /// Payload hashes by last use, least recent first.
+    order: BTreeMap<u64, u64>,
+    tick: u64,
+}
+
+impl Lru {
+    fn get(&mut self, hash: u64, data: &[u8]) -> Option<Arc<Geometry>> {
+        let entry = self.entries.get_mut(&hash).filter(|e| *e.data == *data)?;
+        self.order.remove(&entry.last_used);
+        self.tick += 1;
+        entry.last_used = self.tick;
+        self.order.insert(self.tick, hash);
+        Some(entry.geometry.clone())
+    }
+
+    fn insert(&mut self, hash: u64, data: &[u8], geometry: Arc<Geometry>, capacity: usize) {
+        if let Some(old) = self.entries.remove(&hash) {
+            self.order.remove(&old.last_used);
+        }
+        while self.entries.len() >= capacity {
+            let Some((_, evicted)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&evicted);
+        }
+        self.tick += 1;
+        self.order.insert(self.tick, hash);
+        self.entries.insert(
+            hash,
+            Entry {
+                data: data.into(),
+                geometry,
+                last_used: self.tick,
+            },
+        );
+    }
+}
+
+/// Thread-safe least recently used cache in front of
+/// [`HilbertSerializer::decode`].
+///
+/// Entries are keyed by a hash of the payload bytes. The bytes are kept to
+/// rule out hash collisions, so the memory used is about the size of the
+/// cached payloads plus their decoded geometries.
+pub struct DecodingCache {
+    serializer: HilbertSerializer,
+    capacity: usize,
+    lru: Mutex<Lru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl DecodingCache {
+    /// Caches up to `capacity` geometries decoded by `serializer`.
+    pub fn new(serializer: HilbertSerializer, capacity: usize) -> Self {
+        assert!(capacity > 0, "cache capacity must be positive");
+        Self {
+            serializer,
+            capacity,
+            lru: Mutex::new(Lru::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Decodes `data`, or returns the geometry decoded from the same bytes
+    /// before. Errors are not cached.
+    pub fn decode(&self, data: &[u8]) -> Result<Arc<Geometry>, GeometryDecodeError> {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        if let Some(geometry) = self.lock().get(hash, data) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(geometry);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        // Decode without holding the lock; concurrent misses on the same
+        // payload may both decode it.
+        let geometry = Arc::new(self.serializer.decode_detailed(data)?);
+        self.lock()
+            .insert(hash, data, geometry.clone(), self.capacity);
+        Ok(geometry)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Number of cached geometries.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all cached geometries. The stats are kept.
+    pub fn clear(&self) {
+        *self.lock() = Lru::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Lru> {
+        // The cache holds no invariants a panicking thread could break
+        // halfway, so a poisoned lock is still usable.
+        self.lru.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,74 @@
+//! Aligned cells of the curve.
+//!
+//! A cell at level `n` is the block of indices sharing their top `2 * n`
+//! bits. It covers a square of the `f32` bit space with a side of
+//! `2^(32 - n)` steps, and its four children at level `n + 1` split it into
+//! quadrants in curve order. Level 0 is the whole curve and level 32 a
+//! single index.
+
+use std::ops::RangeInclusive;
+
+use crate::HilbertPoint;
+
+/// Deepest cell level, where each cell holds a single index.
+pub const MAX_LEVEL: u32 = 32;
+
+/// Aligned curve cell: all indices sharing the top `2 * level` bits of
+/// `key`. `level` is at most [`MAX_LEVEL`] and `key` below `4^level`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HilbertCell {
+    pub level: u32,
+    pub key: u64,
+}
+
+impl HilbertCell {
+    /// The level 0 cell holding every index.
+    pub const ROOT: HilbertCell = HilbertCell { level: 0, key: 0 };
+
+    /// The cell at `level` holding `point`.
+    ///
+    /// # Panics
+    ///
+    /// If `level` is above [`MAX_LEVEL`].
+    pub fn containing(point: HilbertPoint, level: u32) -> Self {
+        assert!(level <= MAX_LEVEL, "cell level {level} above {MAX_LEVEL}");
+        let key = point.0.checked_shr(2 * (MAX_LEVEL - level)).unwrap_or(0);
+        Self { level, key }
+    }
+
+    fn shift(&self) -> u32 {
+        2 * (MAX_LEVEL - self.level)
+    }
+
+    /// Indices in the cell.
+    pub fn range(&self) -> RangeInclusive<u64> {
+        let start = self.key.checked_shl(self.shift()).unwrap_or(0);
+        let mask = 1u64.checked_shl(self.shift()).map_or(u64::MAX, |s| s - 1);
+        start..=start | mask
+    }
+
+    pub fn contains(&self, point: HilbertPoint) -> bool {
+        self.range().contains(&point.0)
+    }
+
+    /// The enclosing cell one level up, `None` for the root.
+    pub fn parent(&self) -> Option<Self> {
+        let level = self.level.checked_sub(1)?;
+        Some(Self {
+            level,
+            key: self.key >> 2,
+        })
+    }
+
+    /// The four cells one level down, in curve order. `None` at
+    /// [`MAX_LEVEL`].
+    pub fn children(&self) -> Option<[Self; 4]> {
+        if self.level == MAX_LEVEL {
+            return None;
+        }
+        Some([0, 1, 2, 3].map(|i| Self {
+            level: self.level + 1,
+            key: self.key << 2 | i,
+        }))
+    }
+}
@@ -0,0 +1,234 @@
+//! Append-only change log of a feature dataset.
+//!
+//! A log starts with [`CHANGELOG_MAGIC`] followed by one frame per
+//! [`ChangeRecord`], framed as in [`frame`](crate::frame). Records are only
+//! ever appended, so producers can write them to a file or a message queue
+//! as changes happen, and consumers replicate the dataset by replaying them
+//! with [`ChangeEvent::apply`], resuming from the last sequence number or
+//! byte position they saw.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Write;
+
+use bincode::{
+    config::{self, Configuration},
+    error::{DecodeError, EncodeError},
+    Decode, Encode,
+};
+
+use crate::collection::HilbertFeature;
+use crate::decode::limited;
+use crate::frame::{read_frame, write_frame, FRAME_HEADER_LEN};
+use crate::patch::HilbertPatch;
+
+/// Magic bytes at the start of a change log.
+pub const CHANGELOG_MAGIC: [u8; 4] = *b"HGL1";
+
+/// New contents of an updated feature.
+#[derive(Debug, Clone, PartialEq, Decode, Encode)]
+pub enum FeatureChange {
+    /// Replaces the whole feature.
+    Replace(HilbertFeature),
+    /// Patches the geometry, keeping properties and metadata.
+    Patch(HilbertPatch),
+}
+
+/// A change to the feature with id `id`.
+#[derive(Debug, Clone, PartialEq, Decode, Encode)]
+pub enum ChangeEvent {
+    Insert { id: u64, feature: HilbertFeature },
+    Update { id: u64, change: FeatureChange },
+    Delete { id: u64 },
+}
+
+/// Error from [`ChangeEvent::apply`].
+#[derive(Debug)]
+pub enum ApplyError {
+    /// An insert of an id that is already present.
+    Exists(u64),
+    /// An update or delete of an id that is not present.
+    Missing(u64),
+    /// A patch that doesn't fit the feature's geometry.
+    Patch { id: u64, source: DecodeError },
+}
+
+impl fmt::Display for ApplyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApplyError::Exists(id) => write!(f, "feature {id} already exists"),
+            ApplyError::Missing(id) => write!(f, "feature {id} does not exist"),
+            ApplyError::Patch { id, source } => {
+                write!(f, "failed to patch feature {id}: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApplyError {}
+
+impl ChangeEvent {
+    /// Id of the changed feature.
+    pub fn id(&self) -> u64 {
+        match self {
+            ChangeEvent::Insert { id, .. }
+            | ChangeEvent::Update { id, .. }
+            | ChangeEvent::Delete { id } => *id,
+        }
+    }
+
+    /// Applies the change to a replica of the dataset. Features are left
+    /// unchanged on error.
+    pub fn apply(&self, features: &mut BTreeMap<u64, HilbertFeature>) -> Result<(), ApplyError> {
+        match self {
+            ChangeEvent::Insert { id, feature } => {
+                if features.contains_key(id) {
+                    return Err(ApplyError::Exists(*id));
+                }
+                features.insert(*id, feature.clone());
+            }
+            ChangeEvent::Update { id, change } => {
+                let current = features.get_mut(id).ok_or(ApplyError::Missing(*id))?;
+                match change {
+                    FeatureChange::Replace(feature) => *current = feature.clone(),
+                    FeatureChange::Patch(patch) => {
+                        current.geometry = current
+                            .geometry
+                            .apply_patch(patch)
+                            .map_err(|source| ApplyError::Patch { id: *id, source })?;
+                    }
+                }
+            }
+            ChangeEvent::Delete { id } => {
+                features.remove(id).ok_or(ApplyError::Missing(*id))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A change event with its position in the log.
+#[derive(Debug, Clone, PartialEq, Decode, Encode)]
+pub struct ChangeRecord {
+    /// Sequence number, counting up from 0 at the start of the log.
+    pub seq: u64,
+    pub event: ChangeEvent,
+}
+
+/// Appends change records to a log.
+pub struct ChangeLogWriter<W: Write> {
+    writer: W,
+    config: Configuration,
+    next_seq: u64,
+}
+
+impl<W: Write> ChangeLogWriter<W> {
+    /// Starts a new log, writing the magic bytes.
+    pub fn new(mut writer: W) -> Result<Self, EncodeError> {
+        writer
+            .write_all(&CHANGELOG_MAGIC)
+            .map_err(|inner| EncodeError::Io { inner, index: 0 })?;
+        Ok(Self::append_to(writer, 0))
+    }
+
+    /// Continues an existing log, e.g. a file opened for appending, whose
+    /// next record has sequence number `next_seq`.
+    pub fn append_to(writer: W, next_seq: u64) -> Self {
+        Self {
+            writer,
+            config: config::standard(),
+            next_seq,
+        }
+    }
+
+    /// Sequence number of the next record.
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// Appends `event` and returns its sequence number.
+    pub fn append(&mut self, event: ChangeEvent) -> Result<u64, EncodeError> {
+        let record = ChangeRecord {
+            seq: self.next_seq,
+            event,
+        };
+        let mut frame = vec![];
+        write_frame(&mut frame, &bincode::encode_to_vec(&record, self.config)?)?;
+        self.writer
+            .write_all(&frame)
+            .map_err(|inner| EncodeError::Io { inner, index: 0 })?;
+        self.next_seq += 1;
+        Ok(record.seq)
+    }
+
+    /// Flushes and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, EncodeError> {
+        self.writer
+            .flush()
+            .map_err(|inner| EncodeError::Io { inner, index: 0 })?;
+        Ok(self.writer)
+    }
+}
+
+/// Iterator over the records of a log.
+///
+/// A log may end in a partly written record while a producer is appending.
+/// The iterator then yields a [`DecodeError::UnexpectedEnd`] error and
+/// stops; read again from [`position`](Self::position) once more data has
+/// arrived.
+pub struct ChangeLogReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    config: Configuration,
+}
+
+impl<'a> ChangeLogReader<'a> {
+    /// Reads a complete log, starting with the magic bytes.
+    pub fn new(data: &'a [u8]) -> Result<Self, DecodeError> {
+        if data.len() < CHANGELOG_MAGIC.len() {
+            return Err(DecodeError::UnexpectedEnd {
+                additional: CHANGELOG_MAGIC.len() - data.len(),
+            });
+        }
+        if data[..CHANGELOG_MAGIC.len()] != CHANGELOG_MAGIC {
+            return Err(DecodeError::Other("not a hilbert change log"));
+        }
+        Ok(Self::resume(data, CHANGELOG_MAGIC.len()))
+    }
+
+    /// Reads the records of `data` from byte `position`, a value previously
+    /// returned by [`position`](Self::position).
+    pub fn resume(data: &'a [u8], position: usize) -> Self {
+        Self {
+            data,
+            position: position.min(data.len()),
+            config: config::standard(),
+        }
+    }
+
+    /// Byte offset of the next record.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Iterator for ChangeLogReader<'_> {
+    type Item = Result<ChangeRecord, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.data[self.position..];
+        if rest.is_empty() {
+            return None;
+        }
+        let (payload, _) = match read_frame(rest) {
+            Ok(frame) => frame,
+            Err(e) => {
+                // Stop without advancing, so the record is read again on resume.
+                self.data = &self.data[..self.position];
+                return Some(Err(e));
+            }
+        };
+        self.position += FRAME_HEADER_LEN + payload.len();
+        Some(bincode::decode_from_slice(payload, limited(self.config)).map(|(record, _)| record))
+    }
+}
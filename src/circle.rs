@@ -0,0 +1,88 @@
+use geo_types::Coord;
+
+use crate::{decode_coord, encode_coord, HilbertGeometry, HilbertPoint};
+
+type Circle = (Coord, f64);
+
+fn distance(a: Coord, b: Coord) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+fn contains(circle: Circle, p: Coord) -> bool {
+    // Slack for the rounding of circumcentres.
+    distance(circle.0, p) <= circle.1 * (1.0 + 1e-12) + 1e-12
+}
+
+fn circle_two(a: Coord, b: Coord) -> Circle {
+    let center = Coord {
+        x: (a.x + b.x) / 2.0,
+        y: (a.y + b.y) / 2.0,
+    };
+    (center, distance(a, b) / 2.0)
+}
+
+fn circle_three(a: Coord, b: Coord, c: Coord) -> Circle {
+    let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+    if d == 0.0 {
+        // Collinear: the widest pair spans the others.
+        return [circle_two(a, b), circle_two(a, c), circle_two(b, c)]
+            .into_iter()
+            .max_by(|x, y| x.1.total_cmp(&y.1))
+            .expect("three candidates");
+    }
+    let (a2, b2, c2) = (
+        a.x * a.x + a.y * a.y,
+        b.x * b.x + b.y * b.y,
+        c.x * c.x + c.y * c.y,
+    );
+    let center = Coord {
+        x: (a2 * (b.y - c.y) + b2 * (c.y - a.y) + c2 * (a.y - b.y)) / d,
+        y: (a2 * (c.x - b.x) + b2 * (a.x - c.x) + c2 * (b.x - a.x)) / d,
+    };
+    (center, distance(center, a))
+}
+
+/// Shuffles `coords` with a fixed xorshift sequence, which gives Welzl's
+/// algorithm its expected linear time while keeping results reproducible.
+fn shuffle(coords: &mut [Coord]) {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    for i in (1..coords.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        coords.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
+
+impl HilbertGeometry {
+    /// Smallest circle enclosing all vertices, by Welzl's algorithm, as
+    /// its encoded center and its radius in degrees. `None` for geometries
+    /// without vertices.
+    ///
+    /// The radius is measured from the exact center. Encoding the center
+    /// moves it by the precision of the encoding, so pad the radius by that
+    /// much for containment tests on the encoded center.
+    pub fn bounding_circle(&self) -> Option<(HilbertPoint, f64)> {
+        let mut coords: Vec<Coord> = self.points().map(|p| decode_coord(*p)).collect();
+        shuffle(&mut coords);
+        let mut circle = (*coords.first()?, 0.0);
+        for i in 1..coords.len() {
+            if contains(circle, coords[i]) {
+                continue;
+            }
+            circle = (coords[i], 0.0);
+            for j in 0..i {
+                if contains(circle, coords[j]) {
+                    continue;
+                }
+                circle = circle_two(coords[i], coords[j]);
+                for k in 0..j {
+                    if !contains(circle, coords[k]) {
+                        circle = circle_three(coords[i], coords[j], coords[k]);
+                    }
+                }
+            }
+        }
+        Some((encode_coord(circle.0), circle.1))
+    }
+}
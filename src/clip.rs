@@ -0,0 +1,199 @@
+use geo_types::{Coord, Rect};
+
+use crate::{decode_coord, encode_coord, HilbertGeometry, HilbertPoint};
+
+impl HilbertGeometry {
+    /// Clips the geometry to a rectangle, e.g. a tile boundary.
+    ///
+    /// Lines are clipped with Cohen–Sutherland and polygon rings with
+    /// Sutherland–Hodgman. A linestring that leaves and re-enters the rectangle
+    /// becomes a `MultiLineString`. Returns `None` if nothing remains.
+    pub fn clip_to_rect(&self, rect: &Rect) -> Option<HilbertGeometry> {
+        match self {
+            HilbertGeometry::Point(p) => {
+                inside(rect, decode_coord(*p)).then_some(HilbertGeometry::Point(*p))
+            }
+            HilbertGeometry::MultiPoint(ps) => {
+                let kept: Vec<HilbertPoint> = ps
+                    .iter()
+                    .copied()
+                    .filter(|p| inside(rect, decode_coord(*p)))
+                    .collect();
+                (!kept.is_empty()).then_some(HilbertGeometry::MultiPoint(kept))
+            }
+            HilbertGeometry::LineString(ps) => {
+                let mut lines = clip_line(ps, rect);
+                match lines.len() {
+                    0 => None,
+                    1 => lines.pop().map(HilbertGeometry::LineString),
+                    _ => Some(HilbertGeometry::MultiLineString(lines)),
+                }
+            }
+            HilbertGeometry::MultiLineString(ls) => {
+                let lines: Vec<Vec<HilbertPoint>> =
+                    ls.iter().flat_map(|l| clip_line(l, rect)).collect();
+                (!lines.is_empty()).then_some(HilbertGeometry::MultiLineString(lines))
+            }
+            HilbertGeometry::Polygon(rings) => {
+                clip_polygon(rings, rect).map(HilbertGeometry::Polygon)
+            }
+            HilbertGeometry::MultiPolygon(polys) => {
+                let polys: Vec<Vec<Vec<HilbertPoint>>> =
+                    polys.iter().filter_map(|p| clip_polygon(p, rect)).collect();
+                (!polys.is_empty()).then_some(HilbertGeometry::MultiPolygon(polys))
+            }
+        }
+    }
+}
+
+fn inside(rect: &Rect, c: Coord) -> bool {
+    let (min, max) = (rect.min(), rect.max());
+    c.x >= min.x && c.x <= max.x && c.y >= min.y && c.y <= max.y
+}
+
+const LEFT: u8 = 1;
+const RIGHT: u8 = 2;
+const BOTTOM: u8 = 4;
+const TOP: u8 = 8;
+
+fn outcode(rect: &Rect, c: Coord) -> u8 {
+    let (min, max) = (rect.min(), rect.max());
+    let mut code = 0;
+    if c.x < min.x {
+        code |= LEFT;
+    } else if c.x > max.x {
+        code |= RIGHT;
+    }
+    if c.y < min.y {
+        code |= BOTTOM;
+    } else if c.y > max.y {
+        code |= TOP;
+    }
+    code
+}
+
+/// Cohen–Sutherland segment clipping.
+pub(crate) fn clip_segment(rect: &Rect, mut a: Coord, mut b: Coord) -> Option<(Coord, Coord)> {
+    let (min, max) = (rect.min(), rect.max());
+    let mut code_a = outcode(rect, a);
+    let mut code_b = outcode(rect, b);
+    loop {
+        if code_a | code_b == 0 {
+            return Some((a, b));
+        }
+        if code_a & code_b != 0 {
+            return None;
+        }
+        let code = if code_a != 0 { code_a } else { code_b };
+        let c = if code & TOP != 0 {
+            Coord {
+                x: a.x + (b.x - a.x) * (max.y - a.y) / (b.y - a.y),
+                y: max.y,
+            }
+        } else if code & BOTTOM != 0 {
+            Coord {
+                x: a.x + (b.x - a.x) * (min.y - a.y) / (b.y - a.y),
+                y: min.y,
+            }
+        } else if code & RIGHT != 0 {
+            Coord {
+                x: max.x,
+                y: a.y + (b.y - a.y) * (max.x - a.x) / (b.x - a.x),
+            }
+        } else {
+            Coord {
+                x: min.x,
+                y: a.y + (b.y - a.y) * (min.x - a.x) / (b.x - a.x),
+            }
+        };
+        if code == code_a {
+            a = c;
+            code_a = outcode(rect, a);
+        } else {
+            b = c;
+            code_b = outcode(rect, b);
+        }
+    }
+}
+
+/// Clips a linestring into the runs that lie inside the rectangle.
+fn clip_line(points: &[HilbertPoint], rect: &Rect) -> Vec<Vec<HilbertPoint>> {
+    let coords: Vec<Coord> = points.iter().map(|p| decode_coord(*p)).collect();
+    let mut runs: Vec<Vec<Coord>> = vec![];
+    for seg in coords.windows(2) {
+        let Some((a, b)) = clip_segment(rect, seg[0], seg[1]) else {
+            continue;
+        };
+        match runs.last_mut() {
+            Some(run) if run.last() == Some(&a) => run.push(b),
+            _ => runs.push(vec![a, b]),
+        }
+    }
+    runs.into_iter()
+        .filter(|run| run.len() >= 2)
+        .map(|run| run.into_iter().map(encode_coord).collect())
+        .collect()
+}
+
+/// Sutherland–Hodgman clipping of a single ring against one rectangle edge.
+fn clip_ring_edge(
+    ring: &[Coord],
+    keep: impl Fn(Coord) -> bool,
+    cross: impl Fn(Coord, Coord) -> Coord,
+) -> Vec<Coord> {
+    let mut out = Vec::with_capacity(ring.len() + 4);
+    for (i, &cur) in ring.iter().enumerate() {
+        let prev = ring[(i + ring.len() - 1) % ring.len()];
+        match (keep(prev), keep(cur)) {
+            (true, true) => out.push(cur),
+            (true, false) => out.push(cross(prev, cur)),
+            (false, true) => {
+                out.push(cross(prev, cur));
+                out.push(cur);
+            }
+            (false, false) => {}
+        }
+    }
+    out
+}
+
+fn clip_ring(ring: &[HilbertPoint], rect: &Rect) -> Option<Vec<HilbertPoint>> {
+    let (min, max) = (rect.min(), rect.max());
+    let mut coords: Vec<Coord> = ring.iter().map(|p| decode_coord(*p)).collect();
+    // Work on the open ring; closure is restored afterwards.
+    if coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    let at_x = |x: f64| {
+        move |a: Coord, b: Coord| Coord {
+            x,
+            y: a.y + (b.y - a.y) * (x - a.x) / (b.x - a.x),
+        }
+    };
+    let at_y = |y: f64| {
+        move |a: Coord, b: Coord| Coord {
+            x: a.x + (b.x - a.x) * (y - a.y) / (b.y - a.y),
+            y,
+        }
+    };
+    coords = clip_ring_edge(&coords, |c| c.x >= min.x, at_x(min.x));
+    coords = clip_ring_edge(&coords, |c| c.x <= max.x, at_x(max.x));
+    coords = clip_ring_edge(&coords, |c| c.y >= min.y, at_y(min.y));
+    coords = clip_ring_edge(&coords, |c| c.y <= max.y, at_y(max.y));
+    coords.dedup();
+    if coords.len() < 3 {
+        return None;
+    }
+    coords.push(coords[0]);
+    Some(coords.into_iter().map(encode_coord).collect())
+}
+
+fn clip_polygon(rings: &[Vec<HilbertPoint>], rect: &Rect) -> Option<Vec<Vec<HilbertPoint>>> {
+    let (exterior, interiors) = rings.split_first()?;
+    let exterior = clip_ring(exterior, rect)?;
+    Some(
+        std::iter::once(exterior)
+            .chain(interiors.iter().filter_map(|r| clip_ring(r, rect)))
+            .collect(),
+    )
+}
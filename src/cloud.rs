@@ -0,0 +1,159 @@
+use std::fmt;
+use std::sync::Arc;
+
+use bincode::{
+    config::{self, Configuration},
+    error::DecodeError,
+};
+use object_store::{path::Path, ObjectStore};
+
+use crate::collection::{
+    check_magic, footer_range, BoundingBox, CollectionFooter, HilbertFeature, COLLECTION_MAGIC,
+    TRAILER_LEN,
+};
+use crate::decode::limited;
+
+/// Error returned by [`ObjectStoreCollectionReader`].
+#[derive(Debug)]
+pub enum ObjectStoreReaderError {
+    Store(object_store::Error),
+    Decode(DecodeError),
+}
+
+impl fmt::Display for ObjectStoreReaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectStoreReaderError::Store(e) => write!(f, "object store error: {e}"),
+            ObjectStoreReaderError::Decode(e) => write!(f, "decode error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ObjectStoreReaderError {}
+
+impl From<object_store::Error> for ObjectStoreReaderError {
+    fn from(e: object_store::Error) -> Self {
+        ObjectStoreReaderError::Store(e)
+    }
+}
+
+impl From<DecodeError> for ObjectStoreReaderError {
+    fn from(e: DecodeError) -> Self {
+        ObjectStoreReaderError::Decode(e)
+    }
+}
+
+/// Reads collection files from an [`ObjectStore`] (S3, GCS, Azure, local...)
+/// using range requests.
+///
+/// Opening the reader fetches only the trailer and footer; features are then
+/// fetched individually, or in batches with coalesced range requests.
+pub struct ObjectStoreCollectionReader {
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    config: Configuration,
+    footer: CollectionFooter,
+    footer_start: u64,
+}
+
+impl ObjectStoreCollectionReader {
+    pub async fn open(
+        store: Arc<dyn ObjectStore>,
+        path: Path,
+    ) -> Result<Self, ObjectStoreReaderError> {
+        let size = store.head(&path).await?.size;
+        let trailer_start = size
+            .checked_sub(TRAILER_LEN as u64)
+            .ok_or(DecodeError::Other("not a hilbert collection file"))?;
+        let trailer = store.get_range(&path, trailer_start..size).await?;
+        let range = footer_range(size, &trailer)?;
+        let magic = store
+            .get_range(&path, 0..COLLECTION_MAGIC.len() as u64)
+            .await?;
+        check_magic(&magic)?;
+        let data = store.get_range(&path, range.clone()).await?;
+        let config = config::standard();
+        let (footer, _): (CollectionFooter, usize) =
+            bincode::decode_from_slice(&data, limited(config))?;
+        footer.validate(range.start)?;
+        Ok(Self {
+            store,
+            path,
+            config,
+            footer,
+            footer_start: range.start,
+        })
+    }
+
+    pub fn footer(&self) -> &CollectionFooter {
+        &self.footer
+    }
+
+    pub fn len(&self) -> usize {
+        self.footer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.footer.is_empty()
+    }
+
+    fn byte_range(&self, i: usize) -> Option<std::ops::Range<u64>> {
+        let start = *self.footer.offsets.get(i)?;
+        let end = self
+            .footer
+            .offsets
+            .get(i + 1)
+            .map_or(self.footer_start, |o| *o);
+        Some(start..end)
+    }
+
+    /// Fetches and decodes feature `i`.
+    pub async fn get(&self, i: usize) -> Option<Result<HilbertFeature, ObjectStoreReaderError>> {
+        let range = self.byte_range(i)?;
+        let fetch = async {
+            let data = self.store.get_range(&self.path, range).await?;
+            let (feature, _) = bincode::decode_from_slice(&data, limited(self.config))?;
+            Ok(feature)
+        };
+        Some(fetch.await)
+    }
+
+    /// Fetches and decodes the given features. Adjacent byte ranges are
+    /// coalesced by the store into fewer requests.
+    pub async fn get_many(
+        &self,
+        indices: &[usize],
+    ) -> Result<Vec<HilbertFeature>, ObjectStoreReaderError> {
+        let ranges = indices
+            .iter()
+            .map(|i| {
+                self.byte_range(*i)
+                    .ok_or(DecodeError::Other("feature index out of range"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let chunks = self.store.get_ranges(&self.path, &ranges).await?;
+        chunks
+            .iter()
+            .map(|data| {
+                let (feature, _) = bincode::decode_from_slice(data, limited(self.config))?;
+                Ok(feature)
+            })
+            .collect()
+    }
+
+    /// Fetches all features whose bounding box intersects `bbox`, selected
+    /// using the footer index.
+    pub async fn query(
+        &self,
+        bbox: &BoundingBox,
+    ) -> Result<Vec<HilbertFeature>, ObjectStoreReaderError> {
+        let indices: Vec<usize> = self
+            .footer
+            .bboxes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, b)| b.filter(|b| b.intersects(bbox)).map(|_| i))
+            .collect();
+        self.get_many(&indices).await
+    }
+}
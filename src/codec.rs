@@ -0,0 +1,143 @@
+//! [`tokio_util::codec`] support for streams of geometries.
+//!
+//! [`FramedCodec`] reads and writes the frames of [`frame`](crate::frame),
+//! so a geometry stream can run over TCP or WebSocket with `FramedRead`,
+//! `FramedWrite` or `Framed`, and a buffer written by
+//! [`HilbertSerializer::encode_many`] can be streamed as is.
+
+use std::fmt;
+use std::io;
+
+use bincode::error::EncodeError;
+use bytes::{Buf, BufMut, BytesMut};
+use geo_types::Geometry;
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::frame::FRAME_HEADER_LEN;
+use crate::{GeometryDecodeError, HilbertSerializer};
+
+/// Largest frame accepted by default, 16 MiB.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 16 << 20;
+
+/// Error returned by [`FramedCodec`].
+#[derive(Debug)]
+pub enum CodecError {
+    Io(io::Error),
+    Encode(EncodeError),
+    Decode(GeometryDecodeError),
+    /// A frame longer than the codec's maximum, in bytes.
+    FrameTooLarge(usize),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::Io(e) => write!(f, "io error: {e}"),
+            CodecError::Encode(e) => write!(f, "encode error: {e}"),
+            CodecError::Decode(e) => write!(f, "decode error: {e}"),
+            CodecError::FrameTooLarge(len) => write!(f, "frame of {len} bytes is too large"),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+impl From<io::Error> for CodecError {
+    fn from(e: io::Error) -> Self {
+        CodecError::Io(e)
+    }
+}
+
+impl From<EncodeError> for CodecError {
+    fn from(e: EncodeError) -> Self {
+        CodecError::Encode(e)
+    }
+}
+
+impl From<GeometryDecodeError> for CodecError {
+    fn from(e: GeometryDecodeError) -> Self {
+        CodecError::Decode(e)
+    }
+}
+
+/// Codec of length-prefixed geometry frames, encoded with a
+/// [`HilbertSerializer`].
+///
+/// Frames longer than the maximum are rejected on both ends, so a corrupt
+/// or hostile length prefix can't make the decoder buffer unbounded data.
+pub struct FramedCodec {
+    serializer: HilbertSerializer,
+    max_frame_len: usize,
+}
+
+impl Default for FramedCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FramedCodec {
+    /// A codec using [`HilbertSerializer::new`].
+    pub fn new() -> Self {
+        Self::with_serializer(HilbertSerializer::new())
+    }
+
+    pub fn with_serializer(serializer: HilbertSerializer) -> Self {
+        Self {
+            serializer,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        }
+    }
+
+    /// Sets the largest accepted frame, not counting its length prefix.
+    pub fn max_frame_len(mut self, len: usize) -> Self {
+        self.max_frame_len = len;
+        self
+    }
+}
+
+impl Decoder for FramedCodec {
+    type Item = Geometry;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Geometry>, CodecError> {
+        let Some(header) = src.first_chunk::<FRAME_HEADER_LEN>() else {
+            return Ok(None);
+        };
+        let len = u32::from_le_bytes(*header) as usize;
+        if len > self.max_frame_len {
+            return Err(CodecError::FrameTooLarge(len));
+        }
+        if src.len() < FRAME_HEADER_LEN + len {
+            src.reserve(FRAME_HEADER_LEN + len - src.len());
+            return Ok(None);
+        }
+        src.advance(FRAME_HEADER_LEN);
+        let payload = src.split_to(len);
+        Ok(Some(self.serializer.decode_detailed(&payload)?))
+    }
+}
+
+impl Encoder<&Geometry> for FramedCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: &Geometry, dst: &mut BytesMut) -> Result<(), CodecError> {
+        let payload = self.serializer.encode(item)?;
+        let len = u32::try_from(payload.len())
+            .ok()
+            .filter(|len| *len as usize <= self.max_frame_len)
+            .ok_or(CodecError::FrameTooLarge(payload.len()))?;
+        dst.reserve(FRAME_HEADER_LEN + payload.len());
+        dst.put_u32_le(len);
+        dst.extend_from_slice(&payload);
+        Ok(())
+    }
+}
+
+impl Encoder<Geometry> for FramedCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: Geometry, dst: &mut BytesMut) -> Result<(), CodecError> {
+        self.encode(&item, dst)
+    }
+}
@@ -0,0 +1,138 @@
+use bincode::{
+    config::Configuration,
+    error::{DecodeError, EncodeError},
+    Decode, Encode,
+};
+use geo_types::Coord;
+
+use super::{FeatureMetadata, HilbertCollection, HilbertFeature, PropertyValue};
+use crate::decode::limited;
+use crate::layout::{flatten, rebuild, Layout};
+use crate::{decode_coord, HilbertPoint};
+
+/// A collection stored as one contiguous array of Hilbert indices plus
+/// per-feature vertex offsets (struct of arrays).
+///
+/// The indices of feature `i` are `indices()[offsets()[i]..offsets()[i + 1]]`,
+/// so the two arrays can be handed to Arrow as the values and offsets of a
+/// list column without copying, and all vertices can be decoded in one pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnarCollection {
+    indices: Vec<u64>,
+    offsets: Vec<u64>,
+    layouts: Vec<Layout>,
+    properties: Vec<Vec<(String, PropertyValue)>>,
+}
+
+#[derive(Decode, Encode)]
+struct StoredColumnar {
+    layouts: Vec<Layout>,
+    indices: Vec<u64>,
+    properties: Vec<Vec<(String, PropertyValue)>>,
+}
+
+impl ColumnarCollection {
+    pub fn len(&self) -> usize {
+        self.layouts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layouts.is_empty()
+    }
+
+    /// Hilbert indices of all vertices of all features.
+    pub fn indices(&self) -> &[u64] {
+        &self.indices
+    }
+
+    /// Start of each feature in [`indices`](Self::indices), followed by the
+    /// total vertex count.
+    pub fn offsets(&self) -> &[u64] {
+        &self.offsets
+    }
+
+    /// Decodes every vertex of the collection in storage order.
+    pub fn decode_coords(&self) -> Vec<Coord> {
+        self.indices
+            .iter()
+            .map(|h| decode_coord(HilbertPoint(*h)))
+            .collect()
+    }
+
+    /// Returns feature `i`, rebuilt from its slice of the index array.
+    pub fn feature(&self, i: usize) -> Option<HilbertFeature> {
+        let layout = self.layouts.get(i)?;
+        let (start, end) = (self.offsets[i] as usize, self.offsets[i + 1] as usize);
+        let points = self.indices[start..end]
+            .iter()
+            .map(|h| HilbertPoint(*h))
+            .collect();
+        Some(HilbertFeature {
+            geometry: rebuild(layout, points),
+            properties: self.properties[i].clone(),
+            metadata: FeatureMetadata::default(),
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = HilbertFeature> + '_ {
+        (0..self.len()).filter_map(|i| self.feature(i))
+    }
+
+    pub fn encode_bincode(&self, config: &Configuration) -> Result<Vec<u8>, EncodeError> {
+        let stored = StoredColumnar {
+            layouts: self.layouts.clone(),
+            indices: self.indices.clone(),
+            properties: self.properties.clone(),
+        };
+        bincode::encode_to_vec(stored, *config)
+    }
+
+    pub fn decode_bincode(
+        data: &[u8],
+        config: &Configuration,
+    ) -> Result<ColumnarCollection, DecodeError> {
+        let (stored, _): (StoredColumnar, usize) =
+            bincode::decode_from_slice(data, limited(*config))?;
+        if stored.layouts.len() != stored.properties.len() {
+            return Err(DecodeError::Other("feature count mismatch"));
+        }
+        let mut offsets = vec![0u64];
+        for layout in &stored.layouts {
+            offsets.push(offsets[offsets.len() - 1] + layout.num_points() as u64);
+        }
+        if offsets[offsets.len() - 1] != stored.indices.len() as u64 {
+            return Err(DecodeError::Other("vertex count doesn't match layouts"));
+        }
+        Ok(ColumnarCollection {
+            indices: stored.indices,
+            offsets,
+            layouts: stored.layouts,
+            properties: stored.properties,
+        })
+    }
+}
+
+impl From<&HilbertCollection> for ColumnarCollection {
+    fn from(collection: &HilbertCollection) -> Self {
+        let mut columnar = ColumnarCollection {
+            indices: vec![],
+            offsets: vec![0],
+            layouts: vec![],
+            properties: vec![],
+        };
+        for f in &collection.features {
+            let (layout, points) = flatten(&f.geometry);
+            columnar.indices.extend(points.iter().map(|p| p.0));
+            columnar.offsets.push(columnar.indices.len() as u64);
+            columnar.layouts.push(layout);
+            columnar.properties.push(f.properties.clone());
+        }
+        columnar
+    }
+}
+
+impl From<&ColumnarCollection> for HilbertCollection {
+    fn from(columnar: &ColumnarCollection) -> Self {
+        columnar.iter().collect()
+    }
+}
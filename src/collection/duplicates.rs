@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use geo_types::{Coord, Geometry, Rect};
+
+use super::HilbertCollection;
+use crate::covering::{cover_rect, DEFAULT_MAX_RANGES};
+use crate::payload::canonicalize_rings;
+use crate::{
+    decode_geometry, encode_coord, encode_geometry, geometries_approx_eq, HilbertGeometry,
+};
+
+/// Features whose geometries are the same or nearly the same, as found by
+/// [`HilbertCollection::find_duplicates`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateGroup {
+    /// Feature indices in ascending order.
+    pub features: Vec<usize>,
+    /// True if all geometries in the group are identical up to the start
+    /// and direction of their polygon rings.
+    pub exact: bool,
+}
+
+/// Polygons with their rings oriented and rotated to start at the smallest
+/// curve index, see [`canonicalize_rings`]. Other geometries as they are.
+fn canonical(geom: &HilbertGeometry) -> HilbertGeometry {
+    match geom {
+        HilbertGeometry::Polygon(_) | HilbertGeometry::MultiPolygon(_) => {
+            encode_geometry(&canonicalize_rings(&decode_geometry(geom)).0)
+        }
+        other => other.clone(),
+    }
+}
+
+/// Disjoint sets of feature indices, each represented by its smallest index.
+struct Groups(Vec<usize>);
+
+impl Groups {
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.0[i] != i {
+            self.0[i] = self.0[self.0[i]];
+            i = self.0[i];
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.0[a.max(b)] = a.min(b);
+    }
+}
+
+impl HilbertCollection {
+    /// Reports groups of features with duplicate geometries.
+    ///
+    /// Geometries with the same canonical form are always grouped: polygon
+    /// rings that trace the same vertices from another start or in the other
+    /// direction count as identical. With `epsilon > 0`,
+    /// geometries that match vertex by vertex within `epsilon` on both axes
+    /// are grouped too, see [`geometries_approx_eq`]. Candidates are found by
+    /// looking up the Hilbert key of each geometry's bounding box center in
+    /// the covering of the `epsilon` box around the others, so the scan stays
+    /// close to linear for spread out data.
+    ///
+    /// Near duplicates are grouped transitively. Only groups with more than
+    /// one feature are returned, ordered by their first feature.
+    pub fn find_duplicates(&self, epsilon: f64) -> Vec<DuplicateGroup> {
+        let mut groups = Groups((0..self.len()).collect());
+        let canonical: Vec<HilbertGeometry> = self
+            .features
+            .iter()
+            .map(|f| canonical(&f.geometry))
+            .collect();
+        let mut first: HashMap<&HilbertGeometry, usize> = HashMap::new();
+        for (i, geom) in canonical.iter().enumerate() {
+            let j = *first.entry(geom).or_insert(i);
+            groups.union(i, j);
+        }
+
+        if epsilon > 0.0 {
+            let mut centers: Vec<(u64, usize, Coord)> = first
+                .values()
+                .filter_map(|i| {
+                    let center = canonical[*i].bounding_rect()?.center();
+                    Some((encode_coord(center).0, *i, center))
+                })
+                .collect();
+            centers.sort_by_key(|(key, i, _)| (*key, *i));
+            let mut decoded: HashMap<usize, Geometry> = HashMap::new();
+            for &(_, i, center) in &centers {
+                let delta = Coord {
+                    x: epsilon,
+                    y: epsilon,
+                };
+                let query = Rect::new(center - delta, center + delta);
+                for range in cover_rect(&query, DEFAULT_MAX_RANGES) {
+                    let start = centers.partition_point(|(key, _, _)| key < range.start());
+                    for &(key, j, _) in &centers[start..] {
+                        if key > *range.end() {
+                            break;
+                        }
+                        if j <= i || groups.find(i) == groups.find(j) {
+                            continue;
+                        }
+                        for k in [i, j] {
+                            decoded
+                                .entry(k)
+                                .or_insert_with(|| decode_geometry(&canonical[k]));
+                        }
+                        if geometries_approx_eq(&decoded[&i], &decoded[&j], epsilon) {
+                            groups.union(i, j);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut members: Vec<Vec<usize>> = vec![vec![]; self.len()];
+        for i in 0..self.len() {
+            let root = groups.find(i);
+            members[root].push(i);
+        }
+        members
+            .into_iter()
+            .filter(|m| m.len() > 1)
+            .map(|features| DuplicateGroup {
+                exact: features
+                    .iter()
+                    .all(|i| canonical[*i] == canonical[features[0]]),
+                features,
+            })
+            .collect()
+    }
+}
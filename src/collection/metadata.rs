@@ -0,0 +1,125 @@
+use bincode::{
+    de::{read::Reader, Decoder},
+    enc::{write::Writer, Encoder},
+    error::{DecodeError, EncodeError},
+};
+
+/// Size of the header in front of features with metadata: a byte of
+/// presence flags and three 8-byte little-endian fields. Features without
+/// metadata only have the flags byte, set to zero.
+pub const FEATURE_HEADER_LEN: usize = 25;
+
+const HAS_TIMESTAMP: u8 = 1;
+const HAS_ID: u8 = 2;
+const HAS_TTL: u8 = 4;
+const ALL_FLAGS: u8 = HAS_TIMESTAMP | HAS_ID | HAS_TTL;
+
+/// Routing fields of a feature, stored in a fixed-size header before the
+/// geometry so stream processors can read them with [`read_metadata`]
+/// without decoding the rest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FeatureMetadata {
+    /// Event time, in a unit of the application's choosing, e.g.
+    /// milliseconds since the Unix epoch.
+    pub timestamp: Option<i64>,
+    pub id: Option<u64>,
+    /// Time to live, in the unit of `timestamp`.
+    pub ttl: Option<u64>,
+}
+
+impl FeatureMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Time after which the feature expires, if it has a timestamp and a
+    /// time to live.
+    pub fn expires_at(&self) -> Option<i64> {
+        let ttl = i64::try_from(self.ttl?).unwrap_or(i64::MAX);
+        Some(self.timestamp?.saturating_add(ttl))
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at().is_some_and(|t| t <= now)
+    }
+
+    fn to_bytes(self) -> [u8; FEATURE_HEADER_LEN] {
+        let mut buf = [0; FEATURE_HEADER_LEN];
+        let fields = [
+            (HAS_TIMESTAMP, self.timestamp.map(|t| t as u64)),
+            (HAS_ID, self.id),
+            (HAS_TTL, self.ttl),
+        ];
+        for (i, (flag, value)) in fields.into_iter().enumerate() {
+            if let Some(value) = value {
+                buf[0] |= flag;
+                buf[1 + 8 * i..9 + 8 * i].copy_from_slice(&value.to_le_bytes());
+            }
+        }
+        buf
+    }
+
+    fn from_bytes(buf: &[u8; FEATURE_HEADER_LEN]) -> Self {
+        let field = |i: usize, flag: u8| {
+            let bytes = buf[1 + 8 * i..9 + 8 * i].try_into().unwrap();
+            (buf[0] & flag != 0).then(|| u64::from_le_bytes(bytes))
+        };
+        Self {
+            timestamp: field(0, HAS_TIMESTAMP).map(|t| t as i64),
+            id: field(1, HAS_ID),
+            ttl: field(2, HAS_TTL),
+        }
+    }
+}
+
+/// Checks the flags byte of a header, returning whether fields follow.
+fn check_flags(flags: u8) -> Result<bool, DecodeError> {
+    if flags & !ALL_FLAGS != 0 {
+        return Err(DecodeError::Other("invalid feature metadata flags"));
+    }
+    Ok(flags != 0)
+}
+
+/// Reads the metadata of an encoded feature, e.g. from
+/// [`CollectionReader::raw`](super::CollectionReader::raw), looking only at
+/// its header.
+pub fn read_metadata(data: &[u8]) -> Result<FeatureMetadata, DecodeError> {
+    let flags = *data
+        .first()
+        .ok_or(DecodeError::UnexpectedEnd { additional: 1 })?;
+    if !check_flags(flags)? {
+        return Ok(FeatureMetadata::default());
+    }
+    let header = data
+        .first_chunk()
+        .ok_or_else(|| DecodeError::UnexpectedEnd {
+            additional: FEATURE_HEADER_LEN - data.len(),
+        })?;
+    Ok(FeatureMetadata::from_bytes(header))
+}
+
+/// Writes the header, only the flags byte if there is no metadata.
+pub(super) fn encode_header<E: Encoder>(
+    metadata: &FeatureMetadata,
+    encoder: &mut E,
+) -> Result<(), EncodeError> {
+    let header = metadata.to_bytes();
+    let len = if metadata.is_empty() {
+        1
+    } else {
+        FEATURE_HEADER_LEN
+    };
+    encoder.writer().write(&header[..len])
+}
+
+pub(super) fn decode_header<D: Decoder>(decoder: &mut D) -> Result<FeatureMetadata, DecodeError> {
+    decoder.claim_bytes_read(1)?;
+    let mut header = [0; FEATURE_HEADER_LEN];
+    decoder.reader().read(&mut header[..1])?;
+    if !check_flags(header[0])? {
+        return Ok(FeatureMetadata::default());
+    }
+    decoder.claim_bytes_read(FEATURE_HEADER_LEN - 1)?;
+    decoder.reader().read(&mut header[1..])?;
+    Ok(FeatureMetadata::from_bytes(&header))
+}
@@ -0,0 +1,120 @@
+use std::fmt;
+use std::io::Write;
+
+use bincode::error::{DecodeError, EncodeError};
+
+use super::{CollectionReader, HilbertCollectionWriter};
+use crate::payload::EncodingMode;
+use crate::{decode_geometry, encode_geometry, HilbertSerializer};
+
+/// Settings of [`migrate`].
+#[derive(Default)]
+pub struct MigrateOptions {
+    serializer: HilbertSerializer,
+    encoding: Option<EncodingMode>,
+    skip_invalid: bool,
+}
+
+impl MigrateOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Re-encodes geometries with `serializer`, as
+    /// [`HilbertSerializer::encode`] would: converting from its datum,
+    /// repairing, validating, transforming and orienting rings. Features
+    /// fail with [`MigrateError::Encode`] where encoding does, and any
+    /// feature fails with a CRS set, as collections have no room for one.
+    pub fn serializer(mut self, serializer: HilbertSerializer) -> Self {
+        self.serializer = serializer;
+        self
+    }
+
+    /// Stores vertices at the precision of `mode`, in place of the
+    /// serializer's encoding. Each geometry is encoded to a payload and
+    /// decoded again, so e.g. [`Encoding::Half`](crate::payload::Encoding::Half)
+    /// rounds coordinates to half floats. Collections still store bincode
+    /// geometries.
+    pub fn encoding(mut self, mode: EncodingMode) -> Self {
+        self.encoding = Some(mode);
+        self
+    }
+
+    /// Drops features that fail to decode instead of aborting.
+    pub fn skip_invalid(mut self) -> Self {
+        self.skip_invalid = true;
+        self
+    }
+}
+
+/// Outcome of a [`migrate`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MigrateReport {
+    pub written: usize,
+    /// Features dropped by [`MigrateOptions::skip_invalid`].
+    pub skipped: usize,
+}
+
+/// Error returned by [`migrate`].
+#[derive(Debug)]
+pub enum MigrateError {
+    /// Feature `index` of the source could not be decoded.
+    Decode {
+        index: usize,
+        source: DecodeError,
+    },
+    Encode(EncodeError),
+}
+
+impl fmt::Display for MigrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrateError::Decode { index, source } => {
+                write!(f, "failed to decode feature {index}: {source}")
+            }
+            MigrateError::Encode(e) => write!(f, "failed to encode feature: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrateError {}
+
+impl From<EncodeError> for MigrateError {
+    fn from(e: EncodeError) -> Self {
+        MigrateError::Encode(e)
+    }
+}
+
+/// Copies a collection into a new collection file, re-encoding every
+/// feature according to `options`.
+///
+/// Features are read, transformed and written one at a time, so memory use
+/// does not grow with feature size beyond the footer index. Back the reader
+/// with a memory map to migrate files larger than memory. Feature order and
+/// properties are kept.
+pub fn migrate<B: AsRef<[u8]>, W: Write>(
+    reader: &CollectionReader<B>,
+    writer: W,
+    options: &MigrateOptions,
+) -> Result<(W, MigrateReport), MigrateError> {
+    let mut out = HilbertCollectionWriter::new(writer)?;
+    let mut report = MigrateReport::default();
+    for (index, feature) in reader.iter().enumerate() {
+        let mut feature = match feature {
+            Ok(feature) => feature,
+            Err(_) if options.skip_invalid => {
+                report.skipped += 1;
+                continue;
+            }
+            Err(source) => return Err(MigrateError::Decode { index, source }),
+        };
+        let encoding = options.encoding.or(options.serializer.encoding);
+        let geom = options
+            .serializer
+            .reencode(&decode_geometry(&feature.geometry), encoding)?;
+        feature.geometry = encode_geometry(&geom);
+        out.write(&feature)?;
+        report.written += 1;
+    }
+    Ok((out.finish()?, report))
+}
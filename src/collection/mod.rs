@@ -0,0 +1,358 @@
+use std::collections::HashMap;
+
+use bincode::{
+    config::Configuration,
+    de::Decoder,
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    Decode, Encode,
+};
+
+use crate::decode::{limited, DECODE_LIMIT};
+use crate::{HilbertGeometry, HilbertPoint};
+
+mod columnar;
+mod duplicates;
+mod metadata;
+mod migrate;
+mod reader;
+mod stats;
+mod writer;
+
+pub use columnar::ColumnarCollection;
+pub use duplicates::DuplicateGroup;
+pub use metadata::{read_metadata, FeatureMetadata, FEATURE_HEADER_LEN};
+pub use migrate::{migrate, MigrateError, MigrateOptions, MigrateReport};
+pub use reader::CollectionReader;
+#[cfg(feature = "rayon")]
+pub use reader::PAR_CHUNK_LEN;
+pub use stats::{CollectionStats, TypeStats, KEY_QUANTILES};
+#[cfg(any(feature = "tokio", feature = "object_store"))]
+pub(crate) use writer::check_magic;
+pub use writer::{
+    footer_range, BoundingBox, CollectionFooter, HilbertCollectionWriter, COLLECTION_MAGIC,
+    TRAILER_LEN,
+};
+
+/// Feature property value.
+#[derive(Debug, Clone, PartialEq, Decode, Encode)]
+pub enum PropertyValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    String(String),
+}
+
+/// Encoded geometry with its properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HilbertFeature {
+    pub geometry: HilbertGeometry,
+    pub properties: Vec<(String, PropertyValue)>,
+    /// Routing fields, encoded in a header before the geometry. Not kept by
+    /// [`ColumnarCollection`].
+    pub metadata: FeatureMetadata,
+}
+
+impl Encode for HilbertFeature {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        metadata::encode_header(&self.metadata, encoder)?;
+        self.geometry.encode(encoder)?;
+        self.properties.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for HilbertFeature {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let metadata = metadata::decode_header(decoder)?;
+        Ok(Self {
+            geometry: Decode::decode(decoder)?,
+            properties: Decode::decode(decoder)?,
+            metadata,
+        })
+    }
+}
+
+bincode::impl_borrow_decode!(HilbertFeature);
+
+impl HilbertFeature {
+    pub fn new(geometry: HilbertGeometry) -> Self {
+        Self {
+            geometry,
+            properties: vec![],
+            metadata: FeatureMetadata::default(),
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: FeatureMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn with_property(mut self, key: &str, value: PropertyValue) -> Self {
+        self.properties.push((key.to_string(), value));
+        self
+    }
+
+    pub fn property(&self, key: &str) -> Option<&PropertyValue> {
+        self.properties
+            .iter()
+            .find_map(|(k, v)| (k == key).then_some(v))
+    }
+}
+
+/// A collection of encoded features.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HilbertCollection {
+    pub features: Vec<HilbertFeature>,
+}
+
+/// A linestring or ring, either stored inline or as a reference into the
+/// collection's shared part dictionary.
+#[derive(Debug, Clone, Decode, Encode)]
+enum Part {
+    Inline(Vec<HilbertPoint>),
+    Shared(u32),
+}
+
+#[derive(Debug, Clone, Decode, Encode)]
+enum StoredGeometry {
+    Point(HilbertPoint),
+    LineString(Part),
+    Polygon(Vec<Part>),
+    MultiPoint(Vec<HilbertPoint>),
+    MultiLineString(Vec<Part>),
+    MultiPolygon(Vec<Vec<Part>>),
+}
+
+#[derive(Debug, Clone)]
+struct StoredFeature {
+    geometry: StoredGeometry,
+    properties: Vec<(String, PropertyValue)>,
+    metadata: FeatureMetadata,
+}
+
+impl Encode for StoredFeature {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        metadata::encode_header(&self.metadata, encoder)?;
+        self.geometry.encode(encoder)?;
+        self.properties.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for StoredFeature {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let metadata = metadata::decode_header(decoder)?;
+        Ok(Self {
+            geometry: Decode::decode(decoder)?,
+            properties: Decode::decode(decoder)?,
+            metadata,
+        })
+    }
+}
+
+bincode::impl_borrow_decode!(StoredFeature);
+
+#[derive(Debug, Clone, Decode, Encode)]
+struct StoredCollection {
+    parts: Vec<Vec<HilbertPoint>>,
+    features: Vec<StoredFeature>,
+}
+
+impl HilbertCollection {
+    pub fn new(features: Vec<HilbertFeature>) -> Self {
+        Self { features }
+    }
+
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    pub fn push(&mut self, feature: HilbertFeature) {
+        self.features.push(feature);
+    }
+
+    pub fn encode_bincode(&self, config: &Configuration) -> Result<Vec<u8>, EncodeError> {
+        self.encode_with(config, false)
+    }
+
+    /// Encodes the collection, storing linestrings and rings that occur more
+    /// than once in a shared dictionary and referencing them from features.
+    pub fn encode_bincode_deduplicated(
+        &self,
+        config: &Configuration,
+    ) -> Result<Vec<u8>, EncodeError> {
+        self.encode_with(config, true)
+    }
+
+    pub fn decode_bincode(
+        data: &[u8],
+        config: &Configuration,
+    ) -> Result<HilbertCollection, DecodeError> {
+        let (stored, _): (StoredCollection, usize) =
+            bincode::decode_from_slice(data, limited(*config))?;
+        let mut budget = MAX_RESTORED_VERTICES;
+        let features = stored
+            .features
+            .into_iter()
+            .map(|f| {
+                Ok(HilbertFeature {
+                    geometry: restore_geometry(f.geometry, &stored.parts, &mut budget)?,
+                    properties: f.properties,
+                    metadata: f.metadata,
+                })
+            })
+            .collect::<Result<_, DecodeError>>()?;
+        Ok(HilbertCollection { features })
+    }
+
+    fn encode_with(&self, config: &Configuration, dedup: bool) -> Result<Vec<u8>, EncodeError> {
+        let mut dict = PartDictionary::default();
+        if dedup {
+            let mut counts: HashMap<&[HilbertPoint], usize> = HashMap::new();
+            for f in &self.features {
+                for_each_part(&f.geometry, |p| *counts.entry(p).or_default() += 1);
+            }
+            // Assign ids in order of first occurrence to keep the output deterministic.
+            for f in &self.features {
+                for_each_part(&f.geometry, |p| {
+                    if counts[p] > 1 && !dict.index.contains_key(p) {
+                        dict.index.insert(p.to_vec(), dict.parts.len() as u32);
+                        dict.parts.push(p.to_vec());
+                    }
+                });
+            }
+        }
+        let stored = StoredCollection {
+            features: self
+                .features
+                .iter()
+                .map(|f| StoredFeature {
+                    geometry: dict.store(&f.geometry),
+                    properties: f.properties.clone(),
+                    metadata: f.metadata,
+                })
+                .collect(),
+            parts: dict.parts,
+        };
+        bincode::encode_to_vec(stored, *config)
+    }
+}
+
+impl FromIterator<HilbertFeature> for HilbertCollection {
+    fn from_iter<T: IntoIterator<Item = HilbertFeature>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+/// Calls `f` for each linestring or ring of a geometry.
+fn for_each_part<'a>(geom: &'a HilbertGeometry, mut f: impl FnMut(&'a [HilbertPoint])) {
+    match geom {
+        HilbertGeometry::Point(_) | HilbertGeometry::MultiPoint(_) => {}
+        HilbertGeometry::LineString(ps) => f(ps),
+        HilbertGeometry::Polygon(rings) | HilbertGeometry::MultiLineString(rings) => {
+            rings.iter().for_each(|r| f(r))
+        }
+        HilbertGeometry::MultiPolygon(polys) => polys.iter().flatten().for_each(|r| f(r)),
+    }
+}
+
+#[derive(Default)]
+struct PartDictionary {
+    parts: Vec<Vec<HilbertPoint>>,
+    index: HashMap<Vec<HilbertPoint>, u32>,
+}
+
+impl PartDictionary {
+    fn part(&self, ps: &[HilbertPoint]) -> Part {
+        match self.index.get(ps) {
+            Some(i) => Part::Shared(*i),
+            None => Part::Inline(ps.to_vec()),
+        }
+    }
+
+    fn parts(&self, parts: &[Vec<HilbertPoint>]) -> Vec<Part> {
+        parts.iter().map(|p| self.part(p)).collect()
+    }
+
+    fn store(&self, geom: &HilbertGeometry) -> StoredGeometry {
+        match geom {
+            HilbertGeometry::Point(p) => StoredGeometry::Point(*p),
+            HilbertGeometry::MultiPoint(ps) => StoredGeometry::MultiPoint(ps.clone()),
+            HilbertGeometry::LineString(ps) => StoredGeometry::LineString(self.part(ps)),
+            HilbertGeometry::Polygon(rings) => StoredGeometry::Polygon(self.parts(rings)),
+            HilbertGeometry::MultiLineString(lines) => {
+                StoredGeometry::MultiLineString(self.parts(lines))
+            }
+            HilbertGeometry::MultiPolygon(polys) => {
+                StoredGeometry::MultiPolygon(polys.iter().map(|p| self.parts(p)).collect())
+            }
+        }
+    }
+}
+
+/// Vertices a deduplicated collection may expand to when decoded: as many
+/// as fit in [`DECODE_LIMIT`] bytes. Each shared part reference copies its
+/// part, so a small input could otherwise expand without bound.
+const MAX_RESTORED_VERTICES: usize = DECODE_LIMIT / std::mem::size_of::<HilbertPoint>();
+
+/// Restores a part, taking the vertices of shared parts from `budget`.
+fn restore_part(
+    part: Part,
+    dict: &[Vec<HilbertPoint>],
+    budget: &mut usize,
+) -> Result<Vec<HilbertPoint>, DecodeError> {
+    match part {
+        Part::Inline(ps) => Ok(ps),
+        Part::Shared(i) => {
+            let ps = dict.get(i as usize).ok_or_else(|| {
+                DecodeError::OtherString(format!("invalid shared part reference {i}"))
+            })?;
+            *budget = budget.checked_sub(ps.len()).ok_or(DecodeError::Other(
+                "shared parts expand beyond the decode limit",
+            ))?;
+            Ok(ps.clone())
+        }
+    }
+}
+
+fn restore_parts(
+    parts: Vec<Part>,
+    dict: &[Vec<HilbertPoint>],
+    budget: &mut usize,
+) -> Result<Vec<Vec<HilbertPoint>>, DecodeError> {
+    parts
+        .into_iter()
+        .map(|p| restore_part(p, dict, budget))
+        .collect()
+}
+
+fn restore_geometry(
+    geom: StoredGeometry,
+    dict: &[Vec<HilbertPoint>],
+    budget: &mut usize,
+) -> Result<HilbertGeometry, DecodeError> {
+    Ok(match geom {
+        StoredGeometry::Point(p) => HilbertGeometry::Point(p),
+        StoredGeometry::MultiPoint(ps) => HilbertGeometry::MultiPoint(ps),
+        StoredGeometry::LineString(p) => {
+            HilbertGeometry::LineString(restore_part(p, dict, budget)?)
+        }
+        StoredGeometry::Polygon(rings) => {
+            HilbertGeometry::Polygon(restore_parts(rings, dict, budget)?)
+        }
+        StoredGeometry::MultiLineString(lines) => {
+            HilbertGeometry::MultiLineString(restore_parts(lines, dict, budget)?)
+        }
+        StoredGeometry::MultiPolygon(polys) => HilbertGeometry::MultiPolygon(
+            polys
+                .into_iter()
+                .map(|p| restore_parts(p, dict, budget))
+                .collect::<Result<_, _>>()?,
+        ),
+    })
+}
@@ -0,0 +1,138 @@
+use std::ops::Range;
+
+use bincode::{
+    config::{self, Configuration},
+    error::DecodeError,
+};
+
+use super::writer::footer_start;
+use super::{read_metadata, BoundingBox, CollectionFooter, FeatureMetadata, HilbertFeature};
+use crate::decode::limited;
+
+/// Minimum number of features decoded per task by
+/// [`CollectionReader::par_iter`].
+#[cfg(feature = "rayon")]
+pub const PAR_CHUNK_LEN: usize = 1024;
+
+/// Random-access reader for collection files written by
+/// [`HilbertCollectionWriter`](super::HilbertCollectionWriter).
+///
+/// Only the footer is decoded up front. Features are decoded lazily, and their
+/// raw bytes can be accessed without copying. Any byte container can back the
+/// reader, e.g. a `Vec<u8>` or a memory map (see [`CollectionReader::open_mmap`]).
+pub struct CollectionReader<B: AsRef<[u8]>> {
+    data: B,
+    config: Configuration,
+    footer: CollectionFooter,
+    footer_start: usize,
+}
+
+impl<B: AsRef<[u8]>> CollectionReader<B> {
+    pub fn new(data: B) -> Result<Self, DecodeError> {
+        let bytes = data.as_ref();
+        let footer_start = footer_start(bytes)?;
+        let footer = CollectionFooter::read(bytes)?;
+        footer.validate(footer_start as u64)?;
+        Ok(Self {
+            data,
+            config: config::standard(),
+            footer,
+            footer_start,
+        })
+    }
+
+    pub fn footer(&self) -> &CollectionFooter {
+        &self.footer
+    }
+
+    pub fn len(&self) -> usize {
+        self.footer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.footer.is_empty()
+    }
+
+    /// Returns the encoded bytes of feature `i` without decoding them.
+    pub fn raw(&self, i: usize) -> Option<&[u8]> {
+        let start = *self.footer.offsets.get(i)? as usize;
+        let end = self
+            .footer
+            .offsets
+            .get(i + 1)
+            .map_or(self.footer_start, |o| *o as usize);
+        Some(&self.data.as_ref()[start..end])
+    }
+
+    /// Decodes feature `i`.
+    pub fn get(&self, i: usize) -> Option<Result<HilbertFeature, DecodeError>> {
+        self.raw(i).map(|raw| {
+            bincode::decode_from_slice(raw, limited(self.config)).map(|(feature, _)| feature)
+        })
+    }
+
+    /// Reads the metadata of feature `i` from its header, without decoding
+    /// the geometry.
+    pub fn metadata(&self, i: usize) -> Option<Result<FeatureMetadata, DecodeError>> {
+        self.raw(i).map(read_metadata)
+    }
+
+    /// Decodes the features in `range`.
+    pub fn range(
+        &self,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = Result<HilbertFeature, DecodeError>> + '_ {
+        range.filter_map(|i| self.get(i))
+    }
+
+    /// Decodes all features in order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<HilbertFeature, DecodeError>> + '_ {
+        self.range(0..self.len())
+    }
+
+    /// Returns the indices of features whose bounding box intersects `bbox`,
+    /// using only the footer index.
+    pub fn query(&self, bbox: &BoundingBox) -> impl Iterator<Item = usize> + '_ {
+        let bbox = *bbox;
+        self.footer
+            .bboxes
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, b)| b.filter(|b| b.intersects(&bbox)).map(|_| i))
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<B: AsRef<[u8]> + Sync> CollectionReader<B> {
+    /// Decodes all features in parallel. Features are split into chunks of
+    /// at least [`PAR_CHUNK_LEN`] per task, and collecting the iterator
+    /// keeps their order.
+    pub fn par_iter(
+        &self,
+    ) -> impl rayon::iter::IndexedParallelIterator<Item = Result<HilbertFeature, DecodeError>> + '_
+    {
+        use rayon::prelude::*;
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(PAR_CHUNK_LEN)
+            .map(|i| self.get(i).expect("index within len"))
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl CollectionReader<memmap2::Mmap> {
+    /// Memory-maps a collection file.
+    ///
+    /// The file must not be modified while it is mapped.
+    pub fn open_mmap(path: impl AsRef<std::path::Path>) -> Result<Self, DecodeError> {
+        let io = |inner| DecodeError::Io {
+            inner,
+            additional: 0,
+        };
+        let file = std::fs::File::open(path).map_err(io)?;
+        // SAFETY: the mapping is read-only and callers must not modify the file
+        // while it is in use, as documented above.
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.map_err(io)?;
+        Self::new(mmap)
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use bincode::{config, enc::write::SizeWriter, Decode, Encode};
+
+use super::{BoundingBox, HilbertFeature};
+use crate::kv::{make_key, point_from_key};
+
+/// Number of intervals between the [`CollectionStats::key_quantiles`].
+pub const KEY_QUANTILES: usize = 10;
+
+/// Feature and vertex counts of one geometry type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Decode, Encode)]
+pub struct TypeStats {
+    pub features: u64,
+    pub vertices: u64,
+}
+
+/// Summary of a collection, stored in the footer of collection files for
+/// catalog-level introspection without reading the features.
+#[derive(Debug, Clone, Default, PartialEq, Decode, Encode)]
+pub struct CollectionStats {
+    pub bbox: Option<BoundingBox>,
+    pub features: u64,
+    /// Encoded vertices, including closing vertices of rings.
+    pub vertices: u64,
+    /// Encoded size of the features.
+    pub bytes: u64,
+    /// Counts per geometry type, keyed by [`HilbertGeometry::kind`](crate::HilbertGeometry::kind).
+    pub types: BTreeMap<String, TypeStats>,
+    /// Hilbert indices of the [`make_key`] of the features at the 0th, 10th,
+    /// ..., 100th percentile. Empty if no feature has a key.
+    pub key_quantiles: Vec<u64>,
+}
+
+impl CollectionStats {
+    pub fn compute<'a>(features: impl IntoIterator<Item = &'a HilbertFeature>) -> Self {
+        let mut builder = StatsBuilder::default();
+        for feature in features {
+            let mut size = SizeWriter::default();
+            // Encoding into a size counter can't fail.
+            let _ = bincode::encode_into_writer(feature, &mut size, config::standard());
+            builder.push(feature, size.bytes_written as u64);
+        }
+        builder.finish()
+    }
+}
+
+/// Accumulates [`CollectionStats`] one feature at a time.
+#[derive(Debug, Default)]
+pub(crate) struct StatsBuilder {
+    stats: CollectionStats,
+    keys: Vec<u64>,
+}
+
+impl StatsBuilder {
+    /// Adds a feature whose encoded size is `bytes`.
+    pub(crate) fn push(&mut self, feature: &HilbertFeature, bytes: u64) {
+        let geom = &feature.geometry;
+        let vertices = geom.num_coords() as u64;
+        let stats = &mut self.stats;
+        stats.features += 1;
+        stats.vertices += vertices;
+        stats.bytes += bytes;
+        let by_type = stats.types.entry(geom.kind().to_string()).or_default();
+        by_type.features += 1;
+        by_type.vertices += vertices;
+        if let Some(rect) = geom.bounding_rect() {
+            let bbox = BoundingBox::from(rect);
+            stats.bbox = Some(stats.bbox.map_or(bbox, |b| b.union(&bbox)));
+        }
+        if let Some(key) = make_key(geom).as_deref().and_then(point_from_key) {
+            self.keys.push(key.0);
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> CollectionStats {
+        self.keys.sort_unstable();
+        if let Some(last) = self.keys.len().checked_sub(1) {
+            self.stats.key_quantiles = (0..=KEY_QUANTILES)
+                .map(|q| self.keys[q * last / KEY_QUANTILES])
+                .collect();
+        }
+        self.stats
+    }
+}
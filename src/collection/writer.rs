@@ -0,0 +1,230 @@
+use std::io::Write;
+use std::ops::Range;
+
+use bincode::{
+    config::{self, Configuration},
+    error::{DecodeError, EncodeError},
+    Decode, Encode,
+};
+use geo_types::{Coord, Rect};
+
+use super::stats::StatsBuilder;
+use super::{CollectionStats, HilbertFeature};
+use crate::decode::limited;
+
+/// Magic bytes at the start and end of a collection file.
+pub const COLLECTION_MAGIC: [u8; 4] = *b"HGC1";
+
+/// Size of the trailer: footer length (u64, little endian) followed by the magic.
+pub const TRAILER_LEN: usize = 12;
+
+/// Bounding box stored in collection footers.
+#[derive(Debug, Clone, Copy, PartialEq, Decode, Encode)]
+pub struct BoundingBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl BoundingBox {
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min_x <= other.max_x
+            && other.min_x <= self.max_x
+            && self.min_y <= other.max_y
+            && other.min_y <= self.max_y
+    }
+
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+}
+
+impl From<Rect> for BoundingBox {
+    fn from(rect: Rect) -> Self {
+        Self {
+            min_x: rect.min().x,
+            min_y: rect.min().y,
+            max_x: rect.max().x,
+            max_y: rect.max().y,
+        }
+    }
+}
+
+impl From<BoundingBox> for Rect {
+    fn from(bbox: BoundingBox) -> Self {
+        Rect::new(
+            Coord {
+                x: bbox.min_x,
+                y: bbox.min_y,
+            },
+            Coord {
+                x: bbox.max_x,
+                y: bbox.max_y,
+            },
+        )
+    }
+}
+
+/// Index written at the end of a collection file.
+#[derive(Debug, Clone, Default, PartialEq, Decode, Encode)]
+pub struct CollectionFooter {
+    /// Byte offset of each feature from the start of the file. The end of the
+    /// last feature is the start of the footer.
+    pub offsets: Vec<u64>,
+    /// Bounding box of each feature, `None` for empty geometries.
+    pub bboxes: Vec<Option<BoundingBox>>,
+    /// Bounding box of the whole collection.
+    pub bbox: Option<BoundingBox>,
+    /// Summary of the features, written by
+    /// [`HilbertCollectionWriter::finish`].
+    pub stats: Option<CollectionStats>,
+}
+
+impl CollectionFooter {
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Reads the footer from a complete collection file.
+    pub fn read(data: &[u8]) -> Result<CollectionFooter, DecodeError> {
+        let start = footer_start(data)?;
+        let (footer, _) = bincode::decode_from_slice(
+            &data[start..data.len() - TRAILER_LEN],
+            limited(config::standard()),
+        )?;
+        Ok(footer)
+    }
+
+    /// Checks the index against a footer starting at byte `footer_start`: one
+    /// bounding box per feature, and offsets in order between the leading
+    /// magic and the footer.
+    pub(crate) fn validate(&self, footer_start: u64) -> Result<(), DecodeError> {
+        if self.offsets.len() != self.bboxes.len()
+            || self.offsets.windows(2).any(|w| w[0] > w[1])
+            || self
+                .offsets
+                .first()
+                .is_some_and(|o| *o < COLLECTION_MAGIC.len() as u64)
+            || self.offsets.last().is_some_and(|o| *o > footer_start)
+        {
+            return Err(DecodeError::Other("invalid collection footer"));
+        }
+        Ok(())
+    }
+}
+
+/// Checks the magic bytes at the start of a collection file.
+pub(crate) fn check_magic(head: &[u8]) -> Result<(), DecodeError> {
+    if head.len() < COLLECTION_MAGIC.len() || head[..4] != COLLECTION_MAGIC {
+        return Err(DecodeError::Other("not a hilbert collection file"));
+    }
+    Ok(())
+}
+
+/// Returns the offset of the footer from a complete collection file.
+pub(crate) fn footer_start(data: &[u8]) -> Result<usize, DecodeError> {
+    if data.len() < COLLECTION_MAGIC.len() + TRAILER_LEN {
+        return Err(DecodeError::UnexpectedEnd {
+            additional: COLLECTION_MAGIC.len() + TRAILER_LEN - data.len(),
+        });
+    }
+    check_magic(data)?;
+    let range = footer_range(data.len() as u64, &data[data.len() - TRAILER_LEN..])?;
+    Ok(range.start as usize)
+}
+
+/// Returns the byte range of the footer given the file length and its last
+/// `TRAILER_LEN` bytes. Useful for readers that fetch byte ranges on demand.
+pub fn footer_range(file_len: u64, trailer: &[u8]) -> Result<Range<u64>, DecodeError> {
+    if trailer.len() != TRAILER_LEN || trailer[8..] != COLLECTION_MAGIC {
+        return Err(DecodeError::Other("not a hilbert collection file"));
+    }
+    let footer_len = u64::from_le_bytes(trailer[..8].try_into().unwrap());
+    let end = file_len
+        .checked_sub(TRAILER_LEN as u64)
+        .ok_or(DecodeError::Other("invalid footer length"))?;
+    end.checked_sub(footer_len)
+        .filter(|start| *start >= COLLECTION_MAGIC.len() as u64)
+        .map(|start| start..end)
+        .ok_or(DecodeError::Other("invalid footer length"))
+}
+
+/// Appends encoded features to a stream and writes an offset index and
+/// bounding boxes as a footer when finished, so datasets can be written in a
+/// single pass.
+///
+/// File layout: magic, features (bincode), footer (bincode), footer length
+/// (u64 LE), magic.
+pub struct HilbertCollectionWriter<W: Write> {
+    writer: W,
+    config: Configuration,
+    position: u64,
+    footer: CollectionFooter,
+    stats: StatsBuilder,
+}
+
+impl<W: Write> HilbertCollectionWriter<W> {
+    pub fn new(mut writer: W) -> Result<Self, EncodeError> {
+        writer
+            .write_all(&COLLECTION_MAGIC)
+            .map_err(|inner| EncodeError::Io { inner, index: 0 })?;
+        Ok(Self {
+            writer,
+            config: config::standard(),
+            position: COLLECTION_MAGIC.len() as u64,
+            footer: CollectionFooter::default(),
+            stats: StatsBuilder::default(),
+        })
+    }
+
+    /// Number of features written so far.
+    pub fn len(&self) -> usize {
+        self.footer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.footer.is_empty()
+    }
+
+    pub fn write(&mut self, feature: &HilbertFeature) -> Result<(), EncodeError> {
+        let written = bincode::encode_into_std_write(feature, &mut self.writer, self.config)?;
+        let bbox = feature.geometry.bounding_rect().map(BoundingBox::from);
+        self.footer.offsets.push(self.position);
+        self.footer.bboxes.push(bbox);
+        self.footer.bbox = match (self.footer.bbox, bbox) {
+            (Some(a), Some(b)) => Some(a.union(&b)),
+            (a, b) => a.or(b),
+        };
+        self.position += written as u64;
+        self.stats.push(feature, written as u64);
+        Ok(())
+    }
+
+    /// Writes the footer, including the collection statistics, and returns
+    /// the underlying writer.
+    pub fn finish(mut self) -> Result<W, EncodeError> {
+        self.footer.stats = Some(std::mem::take(&mut self.stats).finish());
+        let footer = bincode::encode_to_vec(&self.footer, self.config)?;
+        let io = |inner| EncodeError::Io {
+            inner,
+            index: self.position as usize,
+        };
+        self.writer.write_all(&footer).map_err(io)?;
+        self.writer
+            .write_all(&(footer.len() as u64).to_le_bytes())
+            .map_err(io)?;
+        self.writer.write_all(&COLLECTION_MAGIC).map_err(io)?;
+        self.writer.flush().map_err(io)?;
+        Ok(self.writer)
+    }
+}
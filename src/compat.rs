@@ -0,0 +1,220 @@
+//! Known-good encodings for checking that a build still reads and writes
+//! the persisted format.
+//!
+//! Call [`verify_compatibility`] at startup, or from a test, to catch a
+//! dependency upgrade or platform difference that changes the bytes before
+//! any stored data is touched.
+
+use std::fmt;
+
+use bincode::error::EncodeError;
+use geo_types::{line_string, point, polygon, Geometry};
+
+use crate::payload::{decode_payload, encode_payload, Encoding, EncodingMode, FORMAT_VERSION};
+use crate::{GeometryDecodeError, HilbertSerializer};
+
+/// A geometry and its encoding in one format version.
+///
+/// The coordinates are exact in every encoding, so decoding the bytes gives
+/// back the geometry unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestVector {
+    pub name: &'static str,
+    /// Payload format version, see [`FORMAT_VERSION`]. Untagged bincode
+    /// geometries have no version of their own and use 0.
+    pub version: u8,
+    /// `None` for untagged bincode geometries written by
+    /// [`HilbertSerializer::new`], otherwise the tagged payload encoding.
+    pub encoding: Option<Encoding>,
+    pub geometry: Geometry,
+    pub bytes: &'static [u8],
+}
+
+fn vector(
+    name: &'static str,
+    encoding: Option<Encoding>,
+    geometry: impl Into<Geometry>,
+    bytes: &'static [u8],
+) -> TestVector {
+    TestVector {
+        name,
+        version: 0,
+        encoding,
+        geometry: geometry.into(),
+        bytes,
+    }
+}
+
+/// All test vectors, oldest format version first.
+pub fn test_vectors() -> Vec<TestVector> {
+    let point = point!(x: 24.5, y: 60.25);
+    let line = line_string![(x: 24.5, y: 60.25), (x: 24.75, y: 60.5), (x: 25.0, y: 60.125)];
+    let polygon = polygon!(
+        exterior: [(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 4.0)],
+        interiors: [[(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 2.0, y: 2.0)]],
+    );
+    vec![
+        vector(
+            "bincode point",
+            None,
+            point,
+            &[0, 253, 170, 170, 170, 170, 179, 69, 13, 32],
+        ),
+        vector(
+            "bincode linestring",
+            None,
+            line.clone(),
+            &[
+                1, 3, 253, 170, 170, 170, 170, 179, 69, 13, 32, 253, 0, 0, 0, 0, 184, 69, 13, 32,
+                253, 170, 170, 170, 234, 192, 69, 13, 32,
+            ],
+        ),
+        vector(
+            "bincode polygon",
+            None,
+            polygon,
+            &[
+                2, 2, 5, 0, 253, 0, 0, 0, 0, 0, 64, 0, 16, 253, 0, 0, 0, 0, 0, 128, 0, 32, 253, 0,
+                0, 0, 0, 0, 192, 170, 58, 0, 4, 253, 0, 0, 0, 0, 0, 128, 170, 10, 253, 170, 170,
+                170, 170, 170, 234, 255, 31, 253, 0, 0, 0, 0, 0, 0, 0, 32, 253, 0, 0, 0, 0, 0, 128,
+                170, 10,
+            ],
+        ),
+        vector(
+            "hilbert linestring",
+            Some(Encoding::Hilbert),
+            line.clone(),
+            &[
+                128, 1, 3, 253, 170, 170, 170, 170, 179, 69, 13, 32, 253, 0, 0, 0, 0, 184, 69, 13,
+                32, 253, 170, 170, 170, 234, 192, 69, 13, 32,
+            ],
+        ),
+        vector(
+            "hilbert delta linestring",
+            Some(Encoding::HilbertDelta),
+            line.clone(),
+            &[
+                129, 1, 3, 3, 253, 84, 85, 85, 85, 103, 139, 26, 64, 253, 172, 170, 170, 170, 8, 0,
+                0, 0, 253, 84, 85, 85, 213, 17, 0, 0, 0,
+            ],
+        ),
+        vector(
+            "raw linestring",
+            Some(Encoding::Raw),
+            line.clone(),
+            &[
+                130, 1, 3, 3, 0, 0, 0, 0, 0, 128, 56, 64, 0, 0, 0, 0, 0, 32, 78, 64, 0, 0, 0, 0, 0,
+                192, 56, 64, 0, 0, 0, 0, 0, 64, 78, 64, 0, 0, 0, 0, 0, 0, 57, 64, 0, 0, 0, 0, 0,
+                16, 78, 64,
+            ],
+        ),
+        vector(
+            "quantized linestring",
+            Some(Encoding::Quantized),
+            line.clone(),
+            &[
+                131, 1, 3, 6, 252, 128, 206, 52, 29, 252, 64, 215, 210, 71, 252, 64, 75, 76, 0,
+                252, 64, 75, 76, 0, 252, 64, 75, 76, 0, 252, 223, 112, 114, 0,
+            ],
+        ),
+        vector(
+            "raw keyed linestring",
+            Some(Encoding::RawKeyed),
+            line.clone(),
+            &[
+                132, 32, 13, 69, 189, 154, 170, 170, 170, 1, 3, 3, 0, 0, 0, 0, 0, 128, 56, 64, 0,
+                0, 0, 0, 0, 32, 78, 64, 0, 0, 0, 0, 0, 192, 56, 64, 0, 0, 0, 0, 0, 64, 78, 64, 0,
+                0, 0, 0, 0, 0, 57, 64, 0, 0, 0, 0, 0, 16, 78, 64,
+            ],
+        ),
+        vector(
+            "hilbert128 linestring",
+            Some(Encoding::Hilbert128),
+            line.clone(),
+            &[
+                133, 1, 3, 3, 254, 170, 170, 106, 250, 209, 18, 80, 115, 178, 90, 219, 152, 250,
+                209, 18, 146, 254, 0, 0, 128, 46, 195, 215, 140, 235, 215, 134, 65, 125, 46, 195,
+                23, 146, 254, 170, 170, 170, 144, 26, 248, 49, 144, 82, 147, 26, 248, 49, 144, 18,
+                146,
+            ],
+        ),
+        vector(
+            "half linestring",
+            Some(Encoding::Half),
+            line,
+            &[134, 1, 3, 32, 78, 136, 83, 48, 78, 144, 83, 64, 78, 132, 83],
+        ),
+    ]
+}
+
+/// What went wrong with a [`TestVector`].
+#[derive(Debug)]
+pub enum CompatErrorKind {
+    Encode(EncodeError),
+    Decode(GeometryDecodeError),
+    /// Encoding the geometry gave different bytes.
+    BytesMismatch(Vec<u8>),
+    /// Decoding the bytes gave a different geometry.
+    GeometryMismatch(Geometry),
+}
+
+/// Error returned by [`verify_compatibility`].
+#[derive(Debug)]
+pub struct CompatError {
+    /// Name of the failing test vector.
+    pub vector: &'static str,
+    pub kind: CompatErrorKind,
+}
+
+impl fmt::Display for CompatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "test vector {:?}: ", self.vector)?;
+        match &self.kind {
+            CompatErrorKind::Encode(e) => write!(f, "failed to encode: {e}"),
+            CompatErrorKind::Decode(e) => write!(f, "{e}"),
+            CompatErrorKind::BytesMismatch(bytes) => {
+                write!(f, "encoded to different bytes {bytes:?}")
+            }
+            CompatErrorKind::GeometryMismatch(geom) => {
+                write!(f, "decoded to different geometry {geom:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CompatError {}
+
+/// Checks every test vector: the bytes must decode to the geometry, and
+/// vectors of the current [`FORMAT_VERSION`] must also encode to the
+/// bytes. Returns the first failure.
+pub fn verify_compatibility() -> Result<(), CompatError> {
+    test_vectors().iter().try_for_each(verify_vector)
+}
+
+/// Checks a single test vector, see [`verify_compatibility`].
+pub fn verify_vector(v: &TestVector) -> Result<(), CompatError> {
+    let err = |kind| CompatError {
+        vector: v.name,
+        kind,
+    };
+    let serializer = HilbertSerializer::new();
+    let decoded = match v.encoding {
+        None => serializer.decode_detailed(v.bytes),
+        Some(_) => decode_payload(v.bytes),
+    }
+    .map_err(|e| err(CompatErrorKind::Decode(e)))?;
+    if decoded != v.geometry {
+        return Err(err(CompatErrorKind::GeometryMismatch(decoded)));
+    }
+    if v.version == FORMAT_VERSION {
+        let bytes = match v.encoding {
+            None => serializer.encode(&v.geometry),
+            Some(encoding) => encode_payload(&v.geometry, EncodingMode::Fixed(encoding)),
+        }
+        .map_err(|e| err(CompatErrorKind::Encode(e)))?;
+        if bytes != v.bytes {
+            return Err(err(CompatErrorKind::BytesMismatch(bytes)));
+        }
+    }
+    Ok(())
+}
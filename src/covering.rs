@@ -0,0 +1,390 @@
+use std::ops::RangeInclusive;
+
+use fast_hilbert::h2xy;
+use geo_types::{Coord, Line, Polygon, Rect};
+
+use crate::cell::{HilbertCell, MAX_LEVEL};
+use crate::clip::clip_segment;
+
+/// Default range budget used by [`cover_rect`] callers that don't need to tune it.
+pub const DEFAULT_MAX_RANGES: usize = 32;
+
+/// Returns sorted, non-overlapping ranges of Hilbert indices that together
+/// contain every point of `rect`.
+///
+/// The covering is computed by subdividing curve cells until either the cells
+/// are fully inside the rectangle or `max_ranges` would be exceeded, so it may
+/// include indices outside of `rect`. Coordinates are compared in the same
+/// `f32` space used by the encoding, so points on the rectangle boundary are
+/// always covered.
+pub fn cover_rect(rect: &Rect, max_ranges: usize) -> Vec<RangeInclusive<u64>> {
+    cover_rect_with_interior(rect, max_ranges).exterior()
+}
+
+/// Like [`cover_rect`], keeping the ranges fully inside `rect` apart from
+/// those crossing its edges.
+pub fn cover_rect_with_interior(rect: &Rect, max_ranges: usize) -> Covering {
+    let boxes = bit_boxes(rect);
+    cover(max_ranges, |cell| cell.classify(&boxes))
+}
+
+/// Which part of a [`Covering`] an index falls into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeKind {
+    /// Fully inside the shape; no exact check needed.
+    Inner,
+    /// Possibly outside the shape; needs an exact check.
+    Boundary,
+}
+
+/// Ranges of Hilbert indices covering a shape, split like S2's interior and
+/// exterior coverings. Both lists are sorted and non-overlapping.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Covering {
+    /// Ranges whose points all lie inside the shape, e.g. for bulk deletes
+    /// without reading the keys.
+    pub inner: Vec<RangeInclusive<u64>>,
+    /// Ranges crossed by the shape boundary. Their points need to be
+    /// checked one by one.
+    pub boundary: Vec<RangeInclusive<u64>>,
+}
+
+impl Covering {
+    /// Ranges guaranteed to contain only points of the shape.
+    pub fn interior(&self) -> &[RangeInclusive<u64>] {
+        &self.inner
+    }
+
+    /// Ranges containing every point of the shape: the inner and boundary
+    /// ranges merged.
+    pub fn exterior(&self) -> Vec<RangeInclusive<u64>> {
+        merge_ranges(self.inner.iter().chain(&self.boundary).cloned().collect())
+    }
+
+    /// Returns which part of the covering `index` falls into, or `None` if
+    /// it lies outside the shape.
+    pub fn kind_of(&self, index: u64) -> Option<RangeKind> {
+        let within = |ranges: &[RangeInclusive<u64>]| {
+            let i = ranges.partition_point(|r| *r.end() < index);
+            ranges.get(i).is_some_and(|r| r.contains(&index))
+        };
+        if within(&self.inner) {
+            Some(RangeKind::Inner)
+        } else if within(&self.boundary) {
+            Some(RangeKind::Boundary)
+        } else {
+            None
+        }
+    }
+}
+
+/// Splits the indices of points inside `polygon` into ranges fully inside
+/// it and ranges crossed by its boundary.
+///
+/// Cells are subdivided like in [`cover_rect`] until they no longer touch
+/// the boundary or about `max_ranges` ranges are reached in total. Points
+/// are classified by their encoded `f32` position, and cells merely touching
+/// the boundary count as boundary cells, so every inner index decodes to a
+/// point inside the polygon or on its boundary.
+pub fn cover_polygon(polygon: &Polygon, max_ranges: usize) -> Covering {
+    cover_edges(&polygon_edges(polygon), max_ranges)
+}
+
+/// Edges of all rings of a polygon.
+pub(crate) fn polygon_edges(polygon: &Polygon) -> Vec<Line> {
+    std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .flat_map(|ring| ring.lines())
+        .collect()
+}
+
+/// Like [`cover_polygon`], for the area enclosed by `edges` under the
+/// even-odd rule, e.g. the edges of all polygons of a multipolygon.
+pub(crate) fn cover_edges(edges: &[Line], max_ranges: usize) -> Covering {
+    let Some(bbox) = edges_bbox(edges) else {
+        return Covering::default();
+    };
+    cover(max_ranges, |cell| cell.classify_polygon(&bbox, edges))
+}
+
+/// Subdivides curve cells from the root down, keeping contained cells as
+/// inner ranges, until no partial cells are left or the budget is reached.
+fn cover(max_ranges: usize, classify: impl Fn(&HilbertCell) -> Overlap) -> Covering {
+    let mut inner = vec![];
+    let mut boundary = vec![];
+    let mut frontier = vec![HilbertCell::ROOT];
+
+    while !frontier.is_empty() {
+        let mut partial = vec![];
+        for cell in frontier {
+            match classify(&cell) {
+                Overlap::Disjoint => {}
+                Overlap::Contained => inner.push(cell.range()),
+                Overlap::Partial => partial.push(cell),
+            }
+        }
+        let at_budget = inner.len() + 4 * partial.len() > max_ranges.max(1);
+        if partial.first().is_some_and(|c| c.level == MAX_LEVEL) || at_budget {
+            boundary.extend(partial.iter().map(HilbertCell::range));
+            break;
+        }
+        frontier = partial
+            .iter()
+            .filter_map(HilbertCell::children)
+            .flatten()
+            .collect();
+    }
+    Covering {
+        inner: merge_ranges(inner),
+        boundary: merge_ranges(boundary),
+    }
+}
+
+/// Cells intersecting `rect`, level by level from the root down and in
+/// curve order within each level. See [`CellTraversal`].
+pub fn traverse_rect(rect: &Rect) -> CellTraversal {
+    let boxes = bit_boxes(rect);
+    CellTraversal::new(move |cell| cell.classify(&boxes))
+}
+
+/// Cells intersecting `polygon`, level by level from the root down and in
+/// curve order within each level. See [`CellTraversal`].
+pub fn traverse_polygon(polygon: &Polygon) -> CellTraversal {
+    let edges = polygon_edges(polygon);
+    let Some(bbox) = edges_bbox(&edges) else {
+        return CellTraversal::new(|_| Overlap::Disjoint);
+    };
+    CellTraversal::new(move |cell| cell.classify_polygon(&bbox, &edges))
+}
+
+/// Breadth-first walk of the curve cells intersecting a shape, coarse to
+/// fine. Yields each cell with whether it lies inside the shape or on its
+/// boundary.
+///
+/// Only boundary cells are refined: the children of an inner cell are all
+/// inner too, so index builders can take its range whole. The walk is
+/// lazy, so consumers stop at the level of detail they need; walking to
+/// the end visits every boundary cell down to
+/// [`MAX_LEVEL`](crate::cell::MAX_LEVEL), which is rarely useful.
+pub struct CellTraversal {
+    classify: Box<dyn Fn(&HilbertCell) -> Overlap + Send + Sync>,
+    current: std::vec::IntoIter<HilbertCell>,
+    next: Vec<HilbertCell>,
+}
+
+impl std::fmt::Debug for CellTraversal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CellTraversal").finish_non_exhaustive()
+    }
+}
+
+impl CellTraversal {
+    fn new(classify: impl Fn(&HilbertCell) -> Overlap + Send + Sync + 'static) -> Self {
+        Self {
+            classify: Box::new(classify),
+            current: vec![HilbertCell::ROOT].into_iter(),
+            next: vec![],
+        }
+    }
+}
+
+impl Iterator for CellTraversal {
+    type Item = (HilbertCell, RangeKind);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(cell) = self.current.next() else {
+                if self.next.is_empty() {
+                    return None;
+                }
+                self.current = std::mem::take(&mut self.next).into_iter();
+                continue;
+            };
+            match (self.classify)(&cell) {
+                Overlap::Disjoint => {}
+                Overlap::Contained => return Some((cell, RangeKind::Inner)),
+                Overlap::Partial => {
+                    self.next.extend(cell.children().into_iter().flatten());
+                    return Some((cell, RangeKind::Boundary));
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn edges_bbox(edges: &[Line]) -> Option<Rect> {
+    let mut coords = edges.iter().flat_map(|e| [e.start, e.end]);
+    let first = coords.next()?;
+    let (min, max) = coords.fold((first, first), |(min, max), c| {
+        (
+            Coord {
+                x: min.x.min(c.x),
+                y: min.y.min(c.y),
+            },
+            Coord {
+                x: max.x.max(c.x),
+                y: max.y.max(c.y),
+            },
+        )
+    });
+    Some(Rect::new(min, max))
+}
+
+/// Even-odd rule over all rings, counting edge crossings to the right of
+/// `c`.
+pub(crate) fn polygon_contains<'a>(edges: impl IntoIterator<Item = &'a Line>, c: Coord) -> bool {
+    edges
+        .into_iter()
+        .filter(|e| (e.start.y > c.y) != (e.end.y > c.y))
+        .filter(|e| {
+            c.x < e.start.x + (c.y - e.start.y) * (e.end.x - e.start.x) / (e.end.y - e.start.y)
+        })
+        .count()
+        % 2
+        == 1
+}
+
+/// Coordinates covered by the `f32` bit patterns from `min` to `max`, which
+/// must share their sign bit. NaN and infinite patterns are clamped to the
+/// largest finite value.
+fn value_interval(min: u64, max: u64) -> (f64, f64) {
+    let value = |bits: u64| {
+        let v = f32::from_bits(bits as u32);
+        let limit = if v.is_sign_negative() {
+            -f32::MAX
+        } else {
+            f32::MAX
+        };
+        if v.is_finite() {
+            v as f64
+        } else {
+            limit as f64
+        }
+    };
+    let (a, b) = (value(min), value(max));
+    (a.min(b), a.max(b))
+}
+
+/// Sorts ranges and merges overlapping or adjacent ones.
+pub(crate) fn merge_ranges(mut ranges: Vec<RangeInclusive<u64>>) -> Vec<RangeInclusive<u64>> {
+    ranges.sort_by_key(|r| *r.start());
+    let mut merged: Vec<RangeInclusive<u64>> = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if last.end().saturating_add(1) >= *r.start() => {
+                *last = *last.start()..=(*last.end()).max(*r.end());
+            }
+            _ => merged.push(r),
+        }
+    }
+    merged
+}
+
+/// Inclusive box in the `f32` bit space used by the curve.
+#[derive(Debug, Clone, Copy)]
+struct BitBox {
+    min_x: u64,
+    min_y: u64,
+    max_x: u64,
+    max_y: u64,
+}
+
+/// Splits `rect` into boxes in `f32` bit space. Bit patterns of negative
+/// floats grow with magnitude, so each axis maps to up to two intervals.
+fn bit_boxes(rect: &Rect) -> Vec<BitBox> {
+    let xs = bit_intervals(rect.min().x, rect.max().x);
+    let ys = bit_intervals(rect.min().y, rect.max().y);
+    xs.iter()
+        .flat_map(|x| {
+            ys.iter().map(move |y| BitBox {
+                min_x: x.0,
+                min_y: y.0,
+                max_x: x.1,
+                max_y: y.1,
+            })
+        })
+        .collect()
+}
+
+fn bit_intervals(min: f64, max: f64) -> Vec<(u64, u64)> {
+    let (lo, hi) = (min as f32, max as f32);
+    let bits = |v: f32| v.to_bits() as u64;
+    let mut intervals = vec![];
+    if lo <= 0.0 {
+        let lo = if lo == 0.0 { -0.0 } else { lo };
+        let hi = if hi < 0.0 { hi } else { -0.0 };
+        intervals.push((bits(hi), bits(lo)));
+    }
+    if hi >= 0.0 {
+        let lo = if lo > 0.0 { lo } else { 0.0 };
+        let hi = if hi == 0.0 { 0.0 } else { hi };
+        intervals.push((bits(lo), bits(hi)));
+    }
+    intervals
+}
+
+enum Overlap {
+    Disjoint,
+    Partial,
+    Contained,
+}
+
+impl HilbertCell {
+    fn bounds(&self) -> BitBox {
+        let (x, y) = h2xy::<u32>(*self.range().start(), 32);
+        let side = 1u64 << (32 - self.level);
+        let (x, y) = (x as u64 & !(side - 1), y as u64 & !(side - 1));
+        BitBox {
+            min_x: x,
+            min_y: y,
+            max_x: x + side - 1,
+            max_y: y + side - 1,
+        }
+    }
+
+    fn classify_polygon(&self, bbox: &Rect, edges: &[Line]) -> Overlap {
+        if self.level == 0 {
+            // The root cell spans both signs of each axis.
+            return Overlap::Partial;
+        }
+        let c = self.bounds();
+        let (min_x, max_x) = value_interval(c.min_x, c.max_x);
+        let (min_y, max_y) = value_interval(c.min_y, c.max_y);
+        let rect = Rect::new(Coord { x: min_x, y: min_y }, Coord { x: max_x, y: max_y });
+        if min_x > bbox.max().x
+            || max_x < bbox.min().x
+            || min_y > bbox.max().y
+            || max_y < bbox.min().y
+        {
+            return Overlap::Disjoint;
+        }
+        if edges
+            .iter()
+            .any(|e| clip_segment(&rect, e.start, e.end).is_some())
+        {
+            return Overlap::Partial;
+        }
+        // No edge enters the cell, so it lies entirely on one side.
+        if polygon_contains(edges, rect.center()) {
+            Overlap::Contained
+        } else {
+            Overlap::Disjoint
+        }
+    }
+
+    fn classify(&self, boxes: &[BitBox]) -> Overlap {
+        let c = self.bounds();
+        let mut overlap = Overlap::Disjoint;
+        for b in boxes {
+            if c.min_x >= b.min_x && c.max_x <= b.max_x && c.min_y >= b.min_y && c.max_y <= b.max_y
+            {
+                return Overlap::Contained;
+            }
+            if c.min_x <= b.max_x && b.min_x <= c.max_x && c.min_y <= b.max_y && b.min_y <= c.max_y
+            {
+                overlap = Overlap::Partial;
+            }
+        }
+        overlap
+    }
+}
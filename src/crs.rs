@@ -0,0 +1,128 @@
+//! Registry of coordinate reference systems and how their coordinates map
+//! onto the curve.
+//!
+//! Indices are built from the `f32` bits of each coordinate, which suits
+//! longitudes and latitudes. Projected coordinates in metres lose
+//! precision: at a northing of 6 672 000 an `f32` step is half a metre.
+//! Registering a [`Normalization`] for a CRS maps its coordinates to the
+//! unit square first, which brings the steps over a 1000 km extent down
+//! to a few centimetres.
+//!
+//! Serializers with a [`crs`](crate::HilbertSerializer::crs) look it up in
+//! [`CrsRegistry::global`], so every serializer in a process maps a CRS the
+//! same way. Payloads written with a normalization must be read with the
+//! same one; don't change a registration once data has been written. The
+//! functions in [`payload`](crate::payload) return normalized coordinates.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use geo_types::{Coord, Geometry, Rect};
+
+use crate::map_geometry_coords;
+use crate::payload::Crs;
+
+type CoordFn = Arc<dyn Fn(Coord) -> Coord + Send + Sync>;
+
+/// Maps coordinates of a CRS to the unit square and back.
+#[derive(Clone)]
+pub struct Normalization {
+    forward: CoordFn,
+    inverse: CoordFn,
+}
+
+impl fmt::Debug for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Normalization").finish_non_exhaustive()
+    }
+}
+
+impl Normalization {
+    /// Normalization with custom functions. `inverse` must undo `forward`,
+    /// which should map the area of use of the CRS into the unit square.
+    pub fn new(
+        forward: impl Fn(Coord) -> Coord + Send + Sync + 'static,
+        inverse: impl Fn(Coord) -> Coord + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            forward: Arc::new(forward),
+            inverse: Arc::new(inverse),
+        }
+    }
+
+    /// Linear map of `bounds` onto the unit square.
+    pub fn from_bounds(bounds: Rect) -> Self {
+        let (min, size) = (bounds.min(), Coord::from((bounds.width(), bounds.height())));
+        Self::new(
+            move |c| Coord {
+                x: (c.x - min.x) / size.x,
+                y: (c.y - min.y) / size.y,
+            },
+            move |c| Coord {
+                x: c.x * size.x + min.x,
+                y: c.y * size.y + min.y,
+            },
+        )
+    }
+
+    pub fn normalize(&self, c: Coord) -> Coord {
+        (self.forward)(c)
+    }
+
+    pub fn denormalize(&self, c: Coord) -> Coord {
+        (self.inverse)(c)
+    }
+
+    pub fn normalize_geometry(&self, geom: &Geometry) -> Geometry {
+        map_geometry_coords(geom, &|c| self.normalize(c))
+    }
+
+    pub fn denormalize_geometry(&self, geom: &Geometry) -> Geometry {
+        map_geometry_coords(geom, &|c| self.denormalize(c))
+    }
+}
+
+/// Normalizations by CRS. Thread-safe; registering is expected at startup
+/// and lookups on every encode and decode.
+#[derive(Debug, Default)]
+pub struct CrsRegistry {
+    entries: RwLock<HashMap<Crs, Normalization>>,
+}
+
+static GLOBAL: LazyLock<CrsRegistry> = LazyLock::new(CrsRegistry::default);
+
+impl CrsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide registry used by
+    /// [`HilbertSerializer`](crate::HilbertSerializer).
+    pub fn global() -> &'static CrsRegistry {
+        &GLOBAL
+    }
+
+    /// Registers the normalization of `crs`, returning the one it replaces.
+    pub fn register(&self, crs: Crs, normalization: Normalization) -> Option<Normalization> {
+        self.write().insert(crs, normalization)
+    }
+
+    pub fn unregister(&self, crs: &Crs) -> Option<Normalization> {
+        self.write().remove(crs)
+    }
+
+    pub fn get(&self, crs: &Crs) -> Option<Normalization> {
+        self.entries
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(crs)
+            .cloned()
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<Crs, Normalization>> {
+        // Entries are inserted and removed whole, so a poisoned lock still
+        // holds a consistent map.
+        self.entries.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,160 @@
+//! Datum shifts between WGS 84 and national datums.
+//!
+//! Encoded coordinates are WGS 84. Survey data is usually published in a
+//! national datum fixed to a tectonic plate, which drifts from WGS 84 by a
+//! few centimetres a year: ETRS89 is about 0.8 m off in Europe by 2024,
+//! NAD83 over a metre in North America. [`DatumShift`] applies the
+//! published 14-parameter Helmert transformations between the frames, so
+//! decoded data can be emitted in the national datum directly.
+//!
+//! WGS 84 is taken to equal ITRF, which its recent realizations match at
+//! the centimetre level. Heights are taken as zero on the ellipsoid; their
+//! effect on horizontal positions is well below a millimetre.
+
+use std::f64::consts::PI;
+
+use geo_types::{Coord, Geometry};
+
+use crate::map_geometry_coords;
+
+/// Milliarcseconds in radians.
+const MAS: f64 = PI / 180.0 / 3_600_000.0;
+
+/// Semi-major axis shared by the WGS 84 and GRS80 ellipsoids.
+const A: f64 = 6_378_137.0;
+const F_WGS84: f64 = 1.0 / 298.257_223_563;
+const F_GRS80: f64 = 1.0 / 298.257_222_101;
+
+/// A national datum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Datum {
+    /// European Terrestrial Reference System 1989, realized as ETRF2000.
+    Etrs89,
+    /// North American Datum 1983, realized as NAD83(2011).
+    Nad83,
+}
+
+/// Time-dependent Helmert parameters from ITRF to a datum, in the IERS
+/// convention. Translations in mm, scale in ppb, rotations in mas, rates
+/// per year.
+struct Helmert {
+    epoch: f64,
+    t: [f64; 3],
+    d: f64,
+    r: [f64; 3],
+    t_rate: [f64; 3],
+    d_rate: f64,
+    r_rate: [f64; 3],
+}
+
+/// ITRF2014 to ETRF2000, from EUREF Technical Note 1.
+const ETRF2000: Helmert = Helmert {
+    epoch: 2010.0,
+    t: [54.7, 52.2, -74.1],
+    d: 2.12,
+    r: [1.701, 10.290, -16.632],
+    t_rate: [0.1, 0.1, -1.9],
+    d_rate: 0.11,
+    r_rate: [0.081, 0.490, -0.792],
+};
+
+/// ITRF2008 to NAD83(2011), from Pearson and Snay (2013). Their rotations
+/// use the opposite sign convention and are negated here.
+const NAD83_2011: Helmert = Helmert {
+    epoch: 1997.0,
+    t: [993.43, -1903.31, -526.55],
+    d: 1.71504,
+    r: [-25.91467, -9.42645, -11.59935],
+    t_rate: [0.79, -0.60, -1.34],
+    d_rate: -0.10201,
+    r_rate: [-0.06667, 0.75744, 0.05133],
+};
+
+impl Helmert {
+    /// Transforms geocentric `x` at `epoch`, or back for `sign == -1`.
+    fn apply(&self, x: [f64; 3], epoch: f64, sign: f64) -> [f64; 3] {
+        let dt = epoch - self.epoch;
+        let t: [f64; 3] = std::array::from_fn(|i| (self.t[i] + self.t_rate[i] * dt) * 1e-3);
+        let d = (self.d + self.d_rate * dt) * 1e-9;
+        let r: [f64; 3] = std::array::from_fn(|i| (self.r[i] + self.r_rate[i] * dt) * MAS);
+        let rx = [
+            -r[2] * x[1] + r[1] * x[2],
+            r[2] * x[0] - r[0] * x[2],
+            -r[1] * x[0] + r[0] * x[1],
+        ];
+        std::array::from_fn(|i| x[i] + sign * (t[i] + d * x[i] + rx[i]))
+    }
+}
+
+/// Geocentric coordinates of a lon/lat position on the ellipsoid.
+fn to_geocentric(c: Coord, f: f64) -> [f64; 3] {
+    let e2 = f * (2.0 - f);
+    let (lon, lat) = (c.x.to_radians(), c.y.to_radians());
+    let n = A / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+    [
+        n * lat.cos() * lon.cos(),
+        n * lat.cos() * lon.sin(),
+        n * (1.0 - e2) * lat.sin(),
+    ]
+}
+
+fn from_geocentric(x: [f64; 3], f: f64) -> Coord {
+    let e2 = f * (2.0 - f);
+    let p = x[0].hypot(x[1]);
+    let mut lat = x[2].atan2(p * (1.0 - e2));
+    // Converges to well below a micrometre near the surface.
+    for _ in 0..4 {
+        let n = A / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+        let h = p / lat.cos() - n;
+        lat = x[2].atan2(p * (1.0 - e2 * n / (n + h)));
+    }
+    Coord {
+        x: x[1].atan2(x[0]).to_degrees(),
+        y: lat.to_degrees(),
+    }
+}
+
+/// Transformation between WGS 84 and a datum at an observation epoch,
+/// given as a decimal year such as `2024.5`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatumShift {
+    pub datum: Datum,
+    pub epoch: f64,
+}
+
+impl DatumShift {
+    pub fn new(datum: Datum, epoch: f64) -> Self {
+        Self { datum, epoch }
+    }
+
+    fn helmert(&self) -> &'static Helmert {
+        match self.datum {
+            Datum::Etrs89 => &ETRF2000,
+            Datum::Nad83 => &NAD83_2011,
+        }
+    }
+
+    /// Converts a WGS 84 lon/lat coordinate to the datum.
+    pub fn from_wgs84(&self, c: Coord) -> Coord {
+        let x = self
+            .helmert()
+            .apply(to_geocentric(c, F_WGS84), self.epoch, 1.0);
+        from_geocentric(x, F_GRS80)
+    }
+
+    /// Converts a lon/lat coordinate in the datum to WGS 84.
+    pub fn to_wgs84(&self, c: Coord) -> Coord {
+        let x = self
+            .helmert()
+            .apply(to_geocentric(c, F_GRS80), self.epoch, -1.0);
+        from_geocentric(x, F_WGS84)
+    }
+
+    pub fn geometry_from_wgs84(&self, geom: &Geometry) -> Geometry {
+        map_geometry_coords(geom, &|c| self.from_wgs84(c))
+    }
+
+    pub fn geometry_to_wgs84(&self, geom: &Geometry) -> Geometry {
+        map_geometry_coords(geom, &|c| self.to_wgs84(c))
+    }
+}
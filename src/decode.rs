@@ -0,0 +1,281 @@
+use std::fmt;
+
+use bincode::{
+    config::{Config, Configuration},
+    error::DecodeError,
+    Decode,
+};
+use geo_types::{Coord, Geometry, Polygon};
+
+use crate::{HilbertGeometry, HilbertPoint};
+
+/// Bytes a decoder may claim for sequences. Lengths come before their
+/// elements, so without a limit a few corrupt bytes could request gigabytes
+/// of memory. Allows about 16 million vertices per geometry.
+pub(crate) const DECODE_LIMIT: usize = 1 << 28;
+
+/// Applies [`DECODE_LIMIT`] to `config`, for decoding untrusted bytes.
+pub(crate) fn limited(config: Configuration) -> impl Config {
+    config.with_limit::<DECODE_LIMIT>()
+}
+
+const KINDS: [&str; 6] = [
+    "Point",
+    "LineString",
+    "Polygon",
+    "MultiPoint",
+    "MultiLineString",
+    "MultiPolygon",
+];
+
+/// Error from decoding a bincode-encoded geometry, with the position of the
+/// value that failed.
+///
+/// Part, ring and vertex indices are set when decoding reached that level:
+/// `part` indexes the members of a multi-geometry, `ring` the rings of a
+/// polygon and `vertex` the coordinates of a ring, linestring or multipoint.
+#[derive(Debug)]
+pub struct GeometryDecodeError {
+    /// Geometry type, if the type tag could be read.
+    pub kind: Option<&'static str>,
+    pub part: Option<usize>,
+    pub ring: Option<usize>,
+    pub vertex: Option<usize>,
+    /// Byte offset of the value that failed to decode, 0 for coordinates
+    /// rejected after decoding.
+    pub offset: usize,
+    pub source: DecodeError,
+}
+
+impl GeometryDecodeError {
+    /// Number of additional bytes needed if the buffer was truncated. For a
+    /// sequence whose length exceeds the remaining data this is a lower bound.
+    pub fn expected_bytes(&self) -> Option<usize> {
+        match self.source {
+            DecodeError::UnexpectedEnd { additional } => Some(additional),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GeometryDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to decode {}", self.kind.unwrap_or("geometry"))?;
+        let location = [
+            ("part", self.part),
+            ("ring", self.ring),
+            ("vertex", self.vertex),
+        ]
+        .iter()
+        .filter_map(|(name, i)| i.map(|i| format!("{name} {i}")))
+        .collect::<Vec<_>>();
+        if !location.is_empty() {
+            write!(f, " {}", location.join(", "))?;
+        }
+        write!(f, " at byte {}: ", self.offset)?;
+        match self.expected_bytes() {
+            Some(n) => write!(f, "unexpected end of data, {n} more bytes expected"),
+            None => write!(f, "{}", self.source),
+        }
+    }
+}
+
+impl std::error::Error for GeometryDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Allows `?` in functions returning bincode errors, keeping the location in
+/// the message.
+impl From<GeometryDecodeError> for DecodeError {
+    fn from(e: GeometryDecodeError) -> Self {
+        DecodeError::OtherString(e.to_string())
+    }
+}
+
+/// Wraps an error that has no position within a geometry.
+impl From<DecodeError> for GeometryDecodeError {
+    fn from(source: DecodeError) -> Self {
+        GeometryDecodeError {
+            kind: None,
+            part: None,
+            ring: None,
+            vertex: None,
+            offset: 0,
+            source,
+        }
+    }
+}
+
+/// Decodes a geometry value by value, tracking the position for errors. The
+/// layout matches the derived `Decode` of `HilbertGeometry`.
+struct GeometryReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+    config: Configuration,
+    kind: Option<&'static str>,
+    part: Option<usize>,
+    ring: Option<usize>,
+    vertex: Option<usize>,
+}
+
+impl GeometryReader<'_> {
+    fn error(&self, source: DecodeError) -> GeometryDecodeError {
+        GeometryDecodeError {
+            kind: self.kind,
+            part: self.part,
+            ring: self.ring,
+            vertex: self.vertex,
+            offset: self.offset,
+            source,
+        }
+    }
+
+    fn read<T: Decode<()>>(&mut self) -> Result<T, GeometryDecodeError> {
+        let (value, len) = bincode::decode_from_slice(&self.data[self.offset..], self.config)
+            .map_err(|e| self.error(e))?;
+        self.offset += len;
+        Ok(value)
+    }
+
+    /// Reads a sequence length. Every element takes at least one byte, so
+    /// longer sequences than the remaining data are rejected up front.
+    fn read_len(&mut self) -> Result<usize, GeometryDecodeError> {
+        let len: u64 = self.read()?;
+        let remaining = (self.data.len() - self.offset) as u64;
+        if len > remaining {
+            return Err(self.error(DecodeError::UnexpectedEnd {
+                additional: (len - remaining) as usize,
+            }));
+        }
+        Ok(len as usize)
+    }
+
+    /// Reads a sequence, setting the index selected by `index` for each element.
+    fn read_seq<T>(
+        &mut self,
+        index: fn(&mut Self) -> &mut Option<usize>,
+        mut element: impl FnMut(&mut Self) -> Result<T, GeometryDecodeError>,
+    ) -> Result<Vec<T>, GeometryDecodeError> {
+        let len = self.read_len()?;
+        let mut out = Vec::with_capacity(len);
+        for i in 0..len {
+            *index(self) = Some(i);
+            out.push(element(self)?);
+        }
+        *index(self) = None;
+        Ok(out)
+    }
+
+    fn point(&mut self) -> Result<HilbertPoint, GeometryDecodeError> {
+        self.read().map(HilbertPoint)
+    }
+
+    fn points(&mut self) -> Result<Vec<HilbertPoint>, GeometryDecodeError> {
+        self.read_seq(|r| &mut r.vertex, Self::point)
+    }
+
+    fn rings(&mut self) -> Result<Vec<Vec<HilbertPoint>>, GeometryDecodeError> {
+        self.read_seq(|r| &mut r.ring, Self::points)
+    }
+
+    fn geometry(&mut self) -> Result<HilbertGeometry, GeometryDecodeError> {
+        let tag: u32 = self.read()?;
+        self.kind = KINDS.get(tag as usize).copied();
+        Ok(match tag {
+            0 => HilbertGeometry::Point(self.point()?),
+            1 => HilbertGeometry::LineString(self.points()?),
+            2 => HilbertGeometry::Polygon(self.rings()?),
+            3 => HilbertGeometry::MultiPoint(self.points()?),
+            4 => HilbertGeometry::MultiLineString(self.read_seq(|r| &mut r.part, Self::points)?),
+            5 => HilbertGeometry::MultiPolygon(self.read_seq(|r| &mut r.part, Self::rings)?),
+            found => {
+                return Err(self.error(DecodeError::UnexpectedVariant {
+                    type_name: "HilbertGeometry",
+                    allowed: &bincode::error::AllowedEnumVariants::Range { min: 0, max: 5 },
+                    found,
+                }));
+            }
+        })
+    }
+}
+
+/// Decodes a geometry from the start of `data`.
+pub(crate) fn decode_geometry_bytes(
+    data: &[u8],
+    config: &Configuration,
+) -> Result<HilbertGeometry, GeometryDecodeError> {
+    GeometryReader {
+        data,
+        offset: 0,
+        config: *config,
+        kind: None,
+        part: None,
+        ring: None,
+        vertex: None,
+    }
+    .geometry()
+}
+
+/// Rejects NaN and infinite coordinates, which corrupted indices decode to.
+/// Such inputs encode without complaint too, so data written from them fails
+/// this check. Reports the position of the first one.
+pub(crate) fn check_finite(geom: &Geometry) -> Result<(), GeometryDecodeError> {
+    let position = |coords: &[Coord]| {
+        coords
+            .iter()
+            .position(|c| !(c.x.is_finite() && c.y.is_finite()))
+    };
+    let rings = |p: &Polygon| {
+        std::iter::once(p.exterior())
+            .chain(p.interiors())
+            .enumerate()
+            .find_map(|(ring, ls)| Some((ring, position(&ls.0)?)))
+    };
+    let points: Vec<Coord>;
+    let (kind, part, ring, vertex) = match geom {
+        Geometry::Point(p) => (0, None, None, position(&[p.0])),
+        Geometry::LineString(ls) => (1, None, None, position(&ls.0)),
+        Geometry::Polygon(p) => {
+            let found = rings(p);
+            (2, None, found.map(|f| f.0), found.map(|f| f.1))
+        }
+        Geometry::MultiPoint(mp) => {
+            points = mp.0.iter().map(|p| p.0).collect();
+            (3, None, None, position(&points))
+        }
+        Geometry::MultiLineString(mls) => {
+            let found = mls
+                .0
+                .iter()
+                .enumerate()
+                .find_map(|(part, ls)| Some((part, position(&ls.0)?)));
+            (4, found.map(|f| f.0), None, found.map(|f| f.1))
+        }
+        Geometry::MultiPolygon(mp) => {
+            let found =
+                mp.0.iter()
+                    .enumerate()
+                    .find_map(|(part, p)| Some((part, rings(p)?)));
+            (
+                5,
+                found.map(|f| f.0),
+                found.map(|f| f.1 .0),
+                found.map(|f| f.1 .1),
+            )
+        }
+        _ => return Ok(()),
+    };
+    match vertex {
+        None => Ok(()),
+        Some(_) => Err(GeometryDecodeError {
+            kind: Some(KINDS[kind]),
+            part,
+            ring,
+            vertex,
+            offset: 0,
+            source: DecodeError::Other("non-finite coordinate"),
+        }),
+    }
+}
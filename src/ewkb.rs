@@ -0,0 +1,273 @@
+use bincode::error::DecodeError;
+use geo_types::{
+    Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+/// Deepest nesting of geometry collections accepted when reading, so corrupt
+/// input can't overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+/// Reads a geometry from (E)WKB, as produced by PostGIS `ST_AsEWKB` or the
+/// binary protocol. Returns the geometry and its SRID, if present.
+///
+/// Both EWKB and ISO WKB Z/M variants are accepted; Z and M values are dropped.
+pub fn geometry_from_ewkb(data: &[u8]) -> Result<(Geometry, Option<i32>), DecodeError> {
+    let mut reader = Reader { data, pos: 0 };
+    let (geom, srid) = reader.geometry(0)?;
+    Ok((geom, srid))
+}
+
+/// Writes a geometry as little-endian EWKB, including the SRID if given.
+///
+/// Lines, rectangles and triangles are written as linestrings and polygons.
+pub fn geometry_to_ewkb(geom: &Geometry, srid: Option<i32>) -> Vec<u8> {
+    let mut buf = vec![];
+    write_geometry(&mut buf, geom, srid);
+    buf
+}
+
+/// Reads a geometry from hex-encoded (E)WKB, the text form used by PostGIS.
+pub fn geometry_from_hex_ewkb(hex: &str) -> Result<(Geometry, Option<i32>), DecodeError> {
+    geometry_from_ewkb(&decode_hex(hex.trim())?)
+}
+
+/// Writes a geometry as upper-case hex-encoded little-endian EWKB.
+pub fn geometry_to_hex_ewkb(geom: &Geometry, srid: Option<i32>) -> String {
+    geometry_to_ewkb(geom, srid)
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect()
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, DecodeError> {
+    let digit = |c: u8| {
+        (c as char)
+            .to_digit(16)
+            .ok_or(DecodeError::Other("invalid hex in WKB text"))
+    };
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [hi, lo] => Ok((digit(*hi)? << 4 | digit(*lo)?) as u8),
+            _ => Err(DecodeError::Other("invalid hex in WKB text")),
+        })
+        .collect()
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let end = self.pos + N;
+        let bytes = self
+            .data
+            .get(self.pos..end)
+            .ok_or_else(|| DecodeError::UnexpectedEnd {
+                additional: end - self.data.len(),
+            })?;
+        self.pos = end;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u32(&mut self, le: bool) -> Result<u32, DecodeError> {
+        let b = self.bytes::<4>()?;
+        Ok(if le {
+            u32::from_le_bytes(b)
+        } else {
+            u32::from_be_bytes(b)
+        })
+    }
+
+    fn f64(&mut self, le: bool) -> Result<f64, DecodeError> {
+        let b = self.bytes::<8>()?;
+        Ok(if le {
+            f64::from_le_bytes(b)
+        } else {
+            f64::from_be_bytes(b)
+        })
+    }
+
+    fn count(&mut self, le: bool) -> Result<usize, DecodeError> {
+        let n = self.u32(le)? as usize;
+        // Guard allocations against corrupt counts: every element needs at least one byte.
+        if n > self.data.len() - self.pos {
+            return Err(DecodeError::Other("invalid element count in WKB"));
+        }
+        Ok(n)
+    }
+
+    fn geometry(&mut self, depth: usize) -> Result<(Geometry, Option<i32>), DecodeError> {
+        if depth > MAX_DEPTH {
+            return Err(DecodeError::Other("WKB geometry nested too deeply"));
+        }
+        let le = match self.bytes::<1>()?[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(DecodeError::Other("invalid WKB byte order")),
+        };
+        let raw = self.u32(le)?;
+        let srid = if raw & EWKB_SRID != 0 {
+            Some(self.u32(le)? as i32)
+        } else {
+            None
+        };
+        let iso = raw & 0xFFFF;
+        let (kind, iso_dims) = (iso % 1000, iso / 1000);
+        let dims = 2
+            + usize::from(raw & EWKB_Z != 0 || iso_dims == 1 || iso_dims == 3)
+            + usize::from(raw & EWKB_M != 0 || iso_dims == 2 || iso_dims == 3);
+
+        let geom = match kind {
+            1 => Geometry::Point(Point(self.coord(le, dims)?)),
+            2 => Geometry::LineString(self.line(le, dims)?),
+            3 => Geometry::Polygon(self.polygon(le, dims)?),
+            4 => Geometry::MultiPoint(MultiPoint(self.parts(le, depth, |g| match g {
+                Geometry::Point(p) => Some(p),
+                _ => None,
+            })?)),
+            5 => {
+                Geometry::MultiLineString(MultiLineString(self.parts(le, depth, |g| match g {
+                    Geometry::LineString(l) => Some(l),
+                    _ => None,
+                })?))
+            }
+            6 => Geometry::MultiPolygon(MultiPolygon(self.parts(le, depth, |g| match g {
+                Geometry::Polygon(p) => Some(p),
+                _ => None,
+            })?)),
+            7 => Geometry::GeometryCollection(self.parts(le, depth, Some)?.into_iter().collect()),
+            _ => return Err(DecodeError::Other("unsupported WKB geometry type")),
+        };
+        Ok((geom, srid))
+    }
+
+    fn coord(&mut self, le: bool, dims: usize) -> Result<Coord, DecodeError> {
+        let x = self.f64(le)?;
+        let y = self.f64(le)?;
+        for _ in 2..dims {
+            self.f64(le)?;
+        }
+        Ok(Coord { x, y })
+    }
+
+    fn line(&mut self, le: bool, dims: usize) -> Result<LineString, DecodeError> {
+        let n = self.count(le)?;
+        (0..n)
+            .map(|_| self.coord(le, dims))
+            .collect::<Result<_, _>>()
+            .map(LineString)
+    }
+
+    fn polygon(&mut self, le: bool, dims: usize) -> Result<Polygon, DecodeError> {
+        let n = self.count(le)?;
+        let mut rings = (0..n)
+            .map(|_| self.line(le, dims))
+            .collect::<Result<Vec<_>, _>>()?;
+        if rings.is_empty() {
+            return Ok(Polygon::new(LineString::new(vec![]), vec![]));
+        }
+        let exterior = rings.remove(0);
+        Ok(Polygon::new(exterior, rings))
+    }
+
+    fn parts<T>(
+        &mut self,
+        le: bool,
+        depth: usize,
+        cast: impl Fn(Geometry) -> Option<T>,
+    ) -> Result<Vec<T>, DecodeError> {
+        let n = self.count(le)?;
+        (0..n)
+            .map(|_| {
+                let (geom, _) = self.geometry(depth + 1)?;
+                cast(geom).ok_or(DecodeError::Other("unexpected WKB multi-geometry member"))
+            })
+            .collect()
+    }
+}
+
+fn write_header(buf: &mut Vec<u8>, kind: u32, srid: Option<i32>) {
+    buf.push(1);
+    match srid {
+        Some(srid) => {
+            buf.extend((kind | EWKB_SRID).to_le_bytes());
+            buf.extend(srid.to_le_bytes());
+        }
+        None => buf.extend(kind.to_le_bytes()),
+    }
+}
+
+fn write_coords<'a>(buf: &mut Vec<u8>, coords: impl ExactSizeIterator<Item = &'a Coord>) {
+    buf.extend((coords.len() as u32).to_le_bytes());
+    for c in coords {
+        buf.extend(c.x.to_le_bytes());
+        buf.extend(c.y.to_le_bytes());
+    }
+}
+
+fn write_polygon(buf: &mut Vec<u8>, poly: &Polygon) {
+    let rings = std::iter::once(poly.exterior()).chain(poly.interiors());
+    buf.extend((1 + poly.interiors().len() as u32).to_le_bytes());
+    for ring in rings {
+        write_coords(buf, ring.0.iter());
+    }
+}
+
+fn write_geometry(buf: &mut Vec<u8>, geom: &Geometry, srid: Option<i32>) {
+    match geom {
+        Geometry::Point(p) => {
+            write_header(buf, 1, srid);
+            buf.extend(p.x().to_le_bytes());
+            buf.extend(p.y().to_le_bytes());
+        }
+        Geometry::LineString(ls) => {
+            write_header(buf, 2, srid);
+            write_coords(buf, ls.0.iter());
+        }
+        Geometry::Polygon(poly) => {
+            write_header(buf, 3, srid);
+            write_polygon(buf, poly);
+        }
+        Geometry::MultiPoint(mp) => {
+            write_header(buf, 4, srid);
+            buf.extend((mp.0.len() as u32).to_le_bytes());
+            for p in &mp.0 {
+                write_geometry(buf, &Geometry::Point(*p), None);
+            }
+        }
+        Geometry::MultiLineString(mls) => {
+            write_header(buf, 5, srid);
+            buf.extend((mls.0.len() as u32).to_le_bytes());
+            for ls in &mls.0 {
+                write_header(buf, 2, None);
+                write_coords(buf, ls.0.iter());
+            }
+        }
+        Geometry::MultiPolygon(mp) => {
+            write_header(buf, 6, srid);
+            buf.extend((mp.0.len() as u32).to_le_bytes());
+            for poly in &mp.0 {
+                write_header(buf, 3, None);
+                write_polygon(buf, poly);
+            }
+        }
+        Geometry::GeometryCollection(gc) => {
+            write_header(buf, 7, srid);
+            buf.extend((gc.0.len() as u32).to_le_bytes());
+            for g in &gc.0 {
+                write_geometry(buf, g, None);
+            }
+        }
+        Geometry::Line(line) => {
+            write_geometry(buf, &Geometry::LineString(LineString::from(*line)), srid)
+        }
+        Geometry::Rect(rect) => write_geometry(buf, &Geometry::Polygon(rect.to_polygon()), srid),
+        Geometry::Triangle(tri) => write_geometry(buf, &Geometry::Polygon(tri.to_polygon()), srid),
+    }
+}
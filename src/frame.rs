@@ -0,0 +1,102 @@
+//! Several encoded geometries back to back in one buffer.
+//!
+//! Each frame is the length of the encoded geometry as a little-endian
+//! `u32` followed by the bytes from [`HilbertSerializer::encode`]. This is
+//! the framing of [`asyncio`](crate::asyncio) too, so a buffer of frames
+//! can be stored as one blob, e.g. per row group, and read back in one pass.
+
+use bincode::error::{DecodeError, EncodeError};
+use geo_types::Geometry;
+
+use crate::{GeometryDecodeError, HilbertSerializer};
+
+/// Length of the prefix before each frame.
+pub const FRAME_HEADER_LEN: usize = 4;
+
+/// Appends `payload` to `buf` as one frame.
+pub fn write_frame(buf: &mut Vec<u8>, payload: &[u8]) -> Result<(), EncodeError> {
+    let len = u32::try_from(payload.len()).map_err(|_| EncodeError::Other("geometry too large"))?;
+    buf.extend_from_slice(&len.to_le_bytes());
+    buf.extend_from_slice(payload);
+    Ok(())
+}
+
+/// Splits the first frame off `data`, returning its payload and the rest.
+/// Fails with [`DecodeError::UnexpectedEnd`] if the frame is truncated.
+pub fn read_frame(data: &[u8]) -> Result<(&[u8], &[u8]), DecodeError> {
+    let Some((header, rest)) = data.split_first_chunk::<FRAME_HEADER_LEN>() else {
+        return Err(DecodeError::UnexpectedEnd {
+            additional: FRAME_HEADER_LEN - data.len(),
+        });
+    };
+    let len = u32::from_le_bytes(*header) as usize;
+    if rest.len() < len {
+        return Err(DecodeError::UnexpectedEnd {
+            additional: len - rest.len(),
+        });
+    }
+    Ok(rest.split_at(len))
+}
+
+/// Iterator over the geometries of a framed buffer, returned by
+/// [`HilbertSerializer::decode_many`].
+///
+/// Error offsets count from the start of the whole buffer. A truncated frame
+/// yields one error and ends the iteration; other errors only fail their
+/// own frame.
+pub struct DecodeMany<'a> {
+    serializer: &'a HilbertSerializer,
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Iterator for DecodeMany<'_> {
+    type Item = Result<Geometry, GeometryDecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let (payload, rest) = match read_frame(self.data) {
+            Ok(frame) => frame,
+            Err(e) => {
+                self.data = &[];
+                let mut e = GeometryDecodeError::from(e);
+                e.offset = self.offset;
+                return Some(Err(e));
+            }
+        };
+        let start = self.offset + FRAME_HEADER_LEN;
+        self.offset = start + payload.len();
+        self.data = rest;
+        Some(self.serializer.decode_detailed(payload).map_err(|mut e| {
+            e.offset += start;
+            e
+        }))
+    }
+}
+
+impl HilbertSerializer {
+    /// Encodes `geoms` into one buffer of frames, readable with
+    /// [`decode_many`](Self::decode_many).
+    pub fn encode_many<'a>(
+        &self,
+        geoms: impl IntoIterator<Item = &'a Geometry>,
+    ) -> Result<Vec<u8>, EncodeError> {
+        let mut buf = vec![];
+        for geom in geoms {
+            write_frame(&mut buf, &self.encode(geom)?)?;
+        }
+        Ok(buf)
+    }
+
+    /// Decodes the frames written by [`encode_many`](Self::encode_many), in
+    /// order.
+    pub fn decode_many<'a>(&'a self, data: &'a [u8]) -> DecodeMany<'a> {
+        DecodeMany {
+            serializer: self,
+            data,
+            offset: 0,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Great-circle distances and bearings between encoded points.
+//!
+//! Points are decoded, the operation is done on a sphere of radius
+//! [`EARTH_RADIUS`], and results that are points are encoded again.
+//! Spherical formulas are off by up to 0.5% from the ellipsoid, which is
+//! fine for proximity and routing heuristics but not for surveying.
+
+use geo_types::Coord;
+
+use crate::{decode_coord, encode_coord, HilbertPoint};
+
+/// Mean Earth radius in metres.
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Great-circle distance between two points in metres, by the haversine
+/// formula.
+pub fn haversine_distance(a: HilbertPoint, b: HilbertPoint) -> f64 {
+    let (a, b) = (decode_coord(a), decode_coord(b));
+    let (lat1, lat2) = (a.y.to_radians(), b.y.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (b.x - a.x).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * h.sqrt().min(1.0).asin()
+}
+
+/// Initial bearing from `a` towards `b` in degrees clockwise from north,
+/// in `[0, 360)`. The bearing between equal points is 0.
+pub fn bearing(a: HilbertPoint, b: HilbertPoint) -> f64 {
+    let (a, b) = (decode_coord(a), decode_coord(b));
+    let (lat1, lat2) = (a.y.to_radians(), b.y.to_radians());
+    let dlon = (b.x - a.x).to_radians();
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// Point reached by travelling `distance` metres from `point` along the
+/// great circle with initial bearing `bearing` degrees.
+pub fn destination(point: HilbertPoint, bearing: f64, distance: f64) -> HilbertPoint {
+    let start = decode_coord(point);
+    let (lon1, lat1) = (start.x.to_radians(), start.y.to_radians());
+    let theta = bearing.to_radians();
+    let delta = distance / EARTH_RADIUS;
+    let lat2 = (lat1.sin() * delta.cos() + lat1.cos() * delta.sin() * theta.cos()).asin();
+    let lon2 = lon1
+        + (theta.sin() * delta.sin() * lat1.cos()).atan2(delta.cos() - lat1.sin() * lat2.sin());
+    encode_coord(Coord {
+        x: (lon2.to_degrees() + 540.0).rem_euclid(360.0) - 180.0,
+        y: lat2.to_degrees(),
+    })
+}
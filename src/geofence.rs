@@ -0,0 +1,141 @@
+//! Enter and exit events for points moving through many geofences.
+
+use std::ops::Range;
+
+use crate::prepared::PreparedPolygon;
+use crate::HilbertPoint;
+
+/// Named [`PreparedPolygon`]s indexed by their coverings.
+///
+/// The exterior coverings of all fences are flattened into one sorted list
+/// of curve intervals, each with the fences covering it. A point is thus
+/// tested only against the fences whose covering contains its index, found
+/// by a single binary search, which keeps thousands of fences cheap.
+#[derive(Debug, Clone, Default)]
+pub struct GeofenceSet {
+    names: Vec<String>,
+    fences: Vec<PreparedPolygon>,
+    /// Start of each interval, sorted. An interval ends where the next one
+    /// starts.
+    starts: Vec<u64>,
+    /// Fences covering each interval, as ranges into `ids`.
+    lists: Vec<Range<usize>>,
+    ids: Vec<u32>,
+}
+
+impl GeofenceSet {
+    pub fn new(fences: impl IntoIterator<Item = (String, PreparedPolygon)>) -> Self {
+        let (names, fences): (Vec<_>, Vec<_>) = fences.into_iter().unzip();
+        // Sweep over range starts (+) and ends (-). A fence's ranges are
+        // disjoint, so it is active at most once.
+        let mut events: Vec<(u64, bool, u32)> = vec![];
+        for (i, fence) in fences.iter().enumerate() {
+            for r in fence.covering().exterior() {
+                events.push((*r.start(), true, i as u32));
+                if let Some(end) = r.end().checked_add(1) {
+                    events.push((end, false, i as u32));
+                }
+            }
+        }
+        events.sort_unstable();
+
+        let mut set = Self {
+            names,
+            fences,
+            ..Self::default()
+        };
+        let mut active: Vec<u32> = vec![];
+        let mut events = events.into_iter().peekable();
+        while let Some((position, _, _)) = events.peek().copied() {
+            while let Some((_, enter, fence)) = events.next_if(|e| e.0 == position) {
+                if enter {
+                    active.push(fence);
+                } else {
+                    active.retain(|f| *f != fence);
+                }
+            }
+            set.starts.push(position);
+            set.lists.push(set.ids.len()..set.ids.len() + active.len());
+            set.ids.extend(&active);
+        }
+        set
+    }
+
+    pub fn len(&self) -> usize {
+        self.fences.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fences.is_empty()
+    }
+
+    /// Indices of the fences containing `p`, in ascending order.
+    pub fn containing(&self, p: HilbertPoint) -> Vec<usize> {
+        let i = self.starts.partition_point(|s| *s <= p.0);
+        let Some(list) = i.checked_sub(1).map(|i| self.lists[i].clone()) else {
+            return vec![];
+        };
+        let mut found: Vec<usize> = self.ids[list]
+            .iter()
+            .map(|f| *f as usize)
+            .filter(|f| self.fences[*f].contains(p))
+            .collect();
+        found.sort_unstable();
+        found
+    }
+
+    /// Name of fence `i`.
+    pub fn name(&self, i: usize) -> Option<&str> {
+        self.names.get(i).map(String::as_str)
+    }
+
+    /// Starts tracking a moving point, initially outside all fences.
+    pub fn watch(&self) -> GeofenceWatcher<'_> {
+        GeofenceWatcher {
+            set: self,
+            inside: vec![],
+        }
+    }
+}
+
+/// A point entering or leaving a fence, see [`GeofenceWatcher::update`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeofenceEvent<'a> {
+    Enter(&'a str),
+    Exit(&'a str),
+}
+
+/// Tracks which fences of a [`GeofenceSet`] one moving point is inside.
+/// Use one watcher per tracked object.
+#[derive(Debug, Clone)]
+pub struct GeofenceWatcher<'a> {
+    set: &'a GeofenceSet,
+    /// Fences containing the last position, sorted.
+    inside: Vec<usize>,
+}
+
+impl<'a> GeofenceWatcher<'a> {
+    /// Moves the point to `p` and returns the fences it left, followed by
+    /// the fences it entered.
+    pub fn update(&mut self, p: HilbertPoint) -> Vec<GeofenceEvent<'a>> {
+        let inside = self.set.containing(p);
+        let names = &self.set.names;
+        let exits = self
+            .inside
+            .iter()
+            .filter(|f| inside.binary_search(f).is_err())
+            .map(|f| GeofenceEvent::Exit(&names[*f]));
+        let enters = inside
+            .iter()
+            .filter(|f| self.inside.binary_search(f).is_err())
+            .map(|f| GeofenceEvent::Enter(&names[*f]));
+        let events = exits.chain(enters).collect();
+        self.inside = inside;
+        events
+    }
+
+    /// Names of the fences the point is currently inside.
+    pub fn inside(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.inside.iter().map(|f| self.set.names[*f].as_str())
+    }
+}
@@ -0,0 +1,168 @@
+//! GeoParquet output for collections.
+//!
+//! Geometries are stored in their bincode-encoded Hilbert form, named by the
+//! [`ENCODING`] in the `geo` file metadata, next to a GeoParquet 1.1 `bbox`
+//! covering column and one column per property (see [`crate::ipc`]). Rows are
+//! sorted along the Hilbert curve before being split into row groups, so the
+//! row group statistics of the bbox column are tight and spatial filters can
+//! skip most row groups.
+
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Float64Array, RecordBatch, StructArray};
+use arrow::datatypes::{DataType, Field, Fields, Schema};
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::WriterProperties;
+
+use crate::collection::{BoundingBox, HilbertCollection, HilbertFeature};
+use crate::ipc::{feature_schema, features_to_record_batch, GEOMETRY_COLUMN};
+use crate::kv::make_key;
+
+/// Encoding name of the geometry column in the `geo` metadata.
+pub const ENCODING: &str = "hilbert-geometry";
+
+/// Name of the bounding box covering column.
+pub const BBOX_COLUMN: &str = "bbox";
+
+const GEOPARQUET_VERSION: &str = "1.1.0";
+
+/// Writes collections as GeoParquet files.
+pub struct GeoParquetWriter {
+    row_group_size: usize,
+    crs: Option<String>,
+}
+
+impl Default for GeoParquetWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn bbox_fields() -> Fields {
+    ["xmin", "ymin", "xmax", "ymax"]
+        .into_iter()
+        .map(|name| Field::new(name, DataType::Float64, false))
+        .collect()
+}
+
+fn bbox_column(features: &[HilbertFeature]) -> StructArray {
+    let bboxes: Vec<Option<BoundingBox>> = features
+        .iter()
+        .map(|f| f.geometry.bounding_rect().map(BoundingBox::from))
+        .collect();
+    let column = |get: fn(&BoundingBox) -> f64| -> ArrayRef {
+        Arc::new(Float64Array::from_iter(
+            bboxes.iter().map(|b| b.as_ref().map(get)),
+        ))
+    };
+    let columns = vec![
+        column(|b| b.min_x),
+        column(|b| b.min_y),
+        column(|b| b.max_x),
+        column(|b| b.max_y),
+    ];
+    let nulls = bboxes.iter().map(Option::is_some).collect();
+    // Empty geometries have a null bbox; the child values are nulls too.
+    StructArray::new(bbox_fields(), columns, Some(nulls))
+}
+
+impl GeoParquetWriter {
+    pub fn new() -> Self {
+        Self {
+            row_group_size: 64 * 1024,
+            crs: None,
+        }
+    }
+
+    /// Maximum number of rows per row group.
+    pub fn row_group_size(mut self, rows: usize) -> Self {
+        self.row_group_size = rows.max(1);
+        self
+    }
+
+    /// PROJJSON description of the coordinate reference system. Without
+    /// one, readers assume `OGC:CRS84` longitudes and latitudes.
+    pub fn crs(mut self, projjson: &str) -> Self {
+        self.crs = Some(projjson.to_string());
+        self
+    }
+
+    /// Returns the GeoParquet `geo` metadata JSON for `collection`.
+    pub fn geo_metadata(&self, collection: &HilbertCollection) -> String {
+        let types: BTreeSet<&str> = collection
+            .features
+            .iter()
+            .map(|f| f.geometry.kind())
+            .collect();
+        let types: Vec<String> = types.iter().map(|t| format!("\"{t}\"")).collect();
+        let bbox = collection
+            .features
+            .iter()
+            .filter_map(|f| f.geometry.bounding_rect().map(BoundingBox::from))
+            .reduce(|a, b| a.union(&b))
+            .map(|b| {
+                format!(
+                    ",\"bbox\":[{},{},{},{}]",
+                    b.min_x, b.min_y, b.max_x, b.max_y
+                )
+            })
+            .unwrap_or_default();
+        let crs = self
+            .crs
+            .as_ref()
+            .map(|crs| format!(",\"crs\":{crs}"))
+            .unwrap_or_default();
+        let covering = format!(
+            "\"covering\":{{\"bbox\":{{\"xmin\":[\"{BBOX_COLUMN}\",\"xmin\"],\"ymin\":[\"{BBOX_COLUMN}\",\"ymin\"],\"xmax\":[\"{BBOX_COLUMN}\",\"xmax\"],\"ymax\":[\"{BBOX_COLUMN}\",\"ymax\"]}}}}"
+        );
+        format!(
+            "{{\"version\":\"{GEOPARQUET_VERSION}\",\"primary_column\":\"{GEOMETRY_COLUMN}\",\"columns\":{{\"{GEOMETRY_COLUMN}\":{{\"encoding\":\"{ENCODING}\",\"geometry_types\":[{}]{bbox}{crs},{covering}}}}}}}",
+            types.join(",")
+        )
+    }
+
+    /// Writes `collection` to `writer`, sorted along the Hilbert curve, and
+    /// returns the writer.
+    pub fn write<W: Write + Send>(
+        &self,
+        writer: W,
+        collection: &HilbertCollection,
+    ) -> Result<W, ParquetError> {
+        let mut features: Vec<&HilbertFeature> = collection.features.iter().collect();
+        features.sort_by_cached_key(|f| make_key(&f.geometry));
+        let features: Vec<HilbertFeature> = features.into_iter().cloned().collect();
+
+        let properties = feature_schema(&features);
+        let mut fields: Vec<Field> = properties
+            .fields()
+            .iter()
+            .map(|f| f.as_ref().clone())
+            .collect();
+        fields.insert(
+            1,
+            Field::new(BBOX_COLUMN, DataType::Struct(bbox_fields()), true),
+        );
+        let schema = Arc::new(Schema::new(fields));
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(self.row_group_size)
+            .set_key_value_metadata(Some(vec![KeyValue::new(
+                "geo".to_string(),
+                self.geo_metadata(collection),
+            )]))
+            .build();
+        let mut out = ArrowWriter::try_new(writer, schema.clone(), Some(props))?;
+        for chunk in features.chunks(self.row_group_size) {
+            let batch = features_to_record_batch(chunk, Arc::new(properties.clone()))?;
+            let mut columns = batch.columns().to_vec();
+            columns.insert(1, Arc::new(bbox_column(chunk)));
+            out.write(&RecordBatch::try_new(schema.clone(), columns)?)?;
+            out.flush()?;
+        }
+        out.into_inner()
+    }
+}
@@ -0,0 +1,92 @@
+//! Content hashes of encoded geometries.
+
+use std::collections::HashSet;
+
+use bincode::config;
+
+use crate::collection::HilbertCollection;
+use crate::{HilbertGeometry, HilbertPoint};
+
+/// Rotates a closed ring to start at its smallest index, so rings tracing
+/// the same vertices from different starts hash the same.
+fn canonical_ring(ring: &[HilbertPoint]) -> Vec<HilbertPoint> {
+    let open = match ring {
+        [first, .., last] if first == last => &ring[..ring.len() - 1],
+        _ => return ring.to_vec(),
+    };
+    let start = (0..open.len()).min_by_key(|i| open[*i]).unwrap_or(0);
+    let mut rotated: Vec<HilbertPoint> = open[start..]
+        .iter()
+        .chain(&open[..start])
+        .copied()
+        .collect();
+    rotated.push(rotated[0]);
+    rotated
+}
+
+fn canonical_polygon(rings: &[Vec<HilbertPoint>]) -> Vec<Vec<HilbertPoint>> {
+    rings.iter().map(|r| canonical_ring(r)).collect()
+}
+
+impl HilbertGeometry {
+    /// BLAKE3 hash of the geometry, for keying dedup stores and caches.
+    ///
+    /// The hash covers the geometry type and its indices. Polygon rings are
+    /// rotated to a canonical start first, so polygons that differ only in
+    /// where their rings start hash the same. Anything else, such as ring
+    /// order or orientation, changes the hash.
+    pub fn content_hash(&self) -> [u8; 32] {
+        let canonical = match self {
+            HilbertGeometry::Polygon(rings) => HilbertGeometry::Polygon(canonical_polygon(rings)),
+            HilbertGeometry::MultiPolygon(polys) => {
+                HilbertGeometry::MultiPolygon(polys.iter().map(|p| canonical_polygon(p)).collect())
+            }
+            other => other.clone(),
+        };
+        let bytes = bincode::encode_to_vec(&canonical, config::standard())
+            .expect("encoding to a Vec doesn't fail");
+        *blake3::hash(&bytes).as_bytes()
+    }
+}
+
+impl HilbertCollection {
+    /// Sets `metadata.id` of every feature to an id derived from its
+    /// [`content_hash`](HilbertGeometry::content_hash), and returns the ids in
+    /// feature order.
+    ///
+    /// An id is the first 8 bytes of the hash. When ids collide, e.g. for
+    /// duplicate geometries, features are ranked by hash and properties and
+    /// later ones rehash with a counter until their id is free. Ids thus only
+    /// depend on the set of features, not on their order, and a dataset
+    /// rebuilt from the same source gets the same ids.
+    pub fn assign_ids(&mut self) -> Vec<u64> {
+        let mut keyed: Vec<([u8; 32], Vec<u8>, usize)> = self
+            .features
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let properties = bincode::encode_to_vec(&f.properties, config::standard())
+                    .expect("encoding to a Vec doesn't fail");
+                (f.geometry.content_hash(), properties, i)
+            })
+            .collect();
+        keyed.sort();
+
+        let mut used = HashSet::with_capacity(keyed.len());
+        let mut ids = vec![0; keyed.len()];
+        for (hash, _, i) in keyed {
+            let mut id = u64::from_le_bytes(hash[..8].try_into().unwrap());
+            let mut attempt = 0u64;
+            while !used.insert(id) {
+                attempt += 1;
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(&hash);
+                hasher.update(&attempt.to_le_bytes());
+                id = u64::from_le_bytes(hasher.finalize().as_bytes()[..8].try_into().unwrap());
+            }
+            ids[i] = id;
+            self.features[i].metadata.id = Some(id);
+        }
+        ids
+    }
+}
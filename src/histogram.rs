@@ -0,0 +1,103 @@
+//! Density histograms along the curve.
+//!
+//! Binning Hilbert indices gives a quick picture of how data is spread,
+//! e.g. for heatmaps or to spot skew before sharding by key range, without
+//! decoding any geometry.
+
+use std::ops::RangeInclusive;
+
+use geo_types::{Coord, Rect};
+
+use crate::cell::HilbertCell;
+use crate::{decode_coord, HilbertPoint};
+
+/// A range of Hilbert indices and the number of keys that fall into it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bucket {
+    pub range: RangeInclusive<u64>,
+    pub count: usize,
+    /// Bounding box of the decoded keys in the bucket, `None` if empty or
+    /// not requested.
+    pub bbox: Option<Rect>,
+}
+
+/// Splits the span from the smallest to the largest key into `buckets`
+/// ranges of equal width and counts the keys in each. Returns fewer buckets
+/// if the span is narrower than `buckets` indices, and none for no keys or
+/// zero `buckets`.
+pub fn key_histogram(keys: &[HilbertPoint], buckets: usize) -> Vec<Bucket> {
+    histogram(keys, buckets, false)
+}
+
+/// Like [`key_histogram`], also computing the bounding box of each bucket.
+pub fn key_histogram_with_bboxes(keys: &[HilbertPoint], buckets: usize) -> Vec<Bucket> {
+    histogram(keys, buckets, true)
+}
+
+/// Counts `points` per curve cell at `level`, for density tiles and
+/// choropleths. Returns the non-empty cells in curve order.
+///
+/// The points are binned in one pass after sorting their cell keys, which
+/// is close to linear for input already in curve order.
+///
+/// # Panics
+///
+/// If `level` is above [`MAX_LEVEL`](crate::cell::MAX_LEVEL).
+pub fn aggregate_to_cells(points: &[HilbertPoint], level: u32) -> Vec<(HilbertCell, usize)> {
+    let mut cells: Vec<HilbertCell> = points
+        .iter()
+        .map(|p| HilbertCell::containing(*p, level))
+        .collect();
+    cells.sort_unstable_by_key(|c| c.key);
+    let mut result: Vec<(HilbertCell, usize)> = vec![];
+    for cell in cells {
+        match result.last_mut() {
+            Some((last, count)) if *last == cell => *count += 1,
+            _ => result.push((cell, 1)),
+        }
+    }
+    result
+}
+
+fn histogram(keys: &[HilbertPoint], buckets: usize, bboxes: bool) -> Vec<Bucket> {
+    let (Some(min), Some(max)) = (keys.iter().min(), keys.iter().max()) else {
+        return vec![];
+    };
+    if buckets == 0 {
+        return vec![];
+    }
+    let (min, max) = (min.0, max.0);
+    // Number of indices in the span, up to 2^64.
+    let width = (max - min) as u128 + 1;
+    let n = (buckets as u128).min(width);
+    // First index of bucket `i`, rounded up so that bucket `i` holds the
+    // keys `k` with `(k - min) * n / width == i`.
+    let start = |i: u128| (min as u128 + (i * width).div_ceil(n)) as u64;
+    let mut result: Vec<Bucket> = (0..n)
+        .map(|i| Bucket {
+            range: start(i)..=start(i + 1).wrapping_sub(1),
+            count: 0,
+            bbox: None,
+        })
+        .collect();
+    for key in keys {
+        let bucket = &mut result[((key.0 - min) as u128 * n / width) as usize];
+        bucket.count += 1;
+        if bboxes {
+            let c = decode_coord(*key);
+            bucket.bbox = Some(bucket.bbox.map_or(Rect::new(c, c), |b| {
+                Rect::new(
+                    Coord {
+                        x: b.min().x.min(c.x),
+                        y: b.min().y.min(c.y),
+                    },
+                    Coord {
+                        x: b.max().x.max(c.x),
+                        y: b.max().y.max(c.y),
+                    },
+                )
+            }));
+        }
+    }
+    result
+}
@@ -0,0 +1,56 @@
+use geo_types::Coord;
+
+use crate::{decode_coord, HilbertGeometry, HilbertPoint};
+
+fn cross(o: Coord, a: Coord, b: Coord) -> f64 {
+    (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+}
+
+/// Pushes `p` onto a hull chain, first popping the points above `floor`
+/// that would not make a left turn.
+fn push_turning(hull: &mut Vec<(Coord, HilbertPoint)>, floor: usize, p: (Coord, HilbertPoint)) {
+    while hull.len() >= floor + 2
+        && cross(hull[hull.len() - 2].0, hull[hull.len() - 1].0, p.0) <= 0.0
+    {
+        hull.pop();
+    }
+    hull.push(p);
+}
+
+impl HilbertGeometry {
+    /// Convex hull of all vertices as a polygon with a closed,
+    /// counter-clockwise exterior, by Andrew's monotone chain. `None` for
+    /// geometries without vertices.
+    ///
+    /// Hull vertices are the original encoded points, so the hull is exact
+    /// and needs no re-encoding. Collinear vertices are dropped; a hull of
+    /// collinear points is a degenerate ring over its two extremes.
+    pub fn convex_hull(&self) -> Option<HilbertGeometry> {
+        let mut points: Vec<(Coord, HilbertPoint)> =
+            self.points().map(|p| (decode_coord(*p), *p)).collect();
+        points.sort_by(|a, b| a.0.x.total_cmp(&b.0.x).then(a.0.y.total_cmp(&b.0.y)));
+        points.dedup_by_key(|p| p.1);
+        let first = *points.first()?;
+        if points.len() < 3 {
+            let last = *points.last()?;
+            return Some(HilbertGeometry::Polygon(vec![vec![
+                first.1, last.1, first.1,
+            ]]));
+        }
+
+        let mut hull: Vec<(Coord, HilbertPoint)> = Vec::with_capacity(points.len() + 1);
+        // Lower chain left to right, then the upper chain back to the
+        // first point, anchored on the last point of the lower chain.
+        for &p in &points {
+            push_turning(&mut hull, 0, p);
+        }
+        let floor = hull.len() - 1;
+        for &p in points.iter().rev().skip(1) {
+            push_turning(&mut hull, floor, p);
+        }
+        Some(HilbertGeometry::Polygon(vec![hull
+            .into_iter()
+            .map(|p| p.1)
+            .collect()]))
+    }
+}
@@ -0,0 +1,230 @@
+//! Encoders for geometries built one vertex at a time.
+//!
+//! [`LineStringEncoder`] and [`PolygonEncoder`] keep the
+//! [`Encoding::HilbertDelta`] body of the vertices pushed so far, so a GPS
+//! trace can be snapshotted after every fix without encoding the whole trace
+//! again. [`RecentTrackEncoder`] keeps only the tail of a live track.
+//! Snapshots are tagged payloads, readable with
+//! [`decode_payload`](crate::payload::decode_payload).
+
+use std::collections::VecDeque;
+
+use bincode::{config, error::EncodeError, Encode};
+use geo_types::Coord;
+
+use crate::layout::Layout;
+use crate::payload::{payload_tag, Encoding};
+use crate::{encode_coord, HilbertGeometry, HilbertPoint};
+
+/// Delta-encoded vertices in bincode form, without the length prefix.
+#[derive(Debug, Clone, Default)]
+struct DeltaBuffer {
+    bytes: Vec<u8>,
+    len: usize,
+    last: Option<HilbertPoint>,
+}
+
+fn encode_into(value: impl Encode, buf: &mut Vec<u8>) -> Result<(), EncodeError> {
+    bincode::encode_into_std_write(value, buf, config::standard()).map(|_| ())
+}
+
+impl DeltaBuffer {
+    fn push(&mut self, p: HilbertPoint) -> Result<(), EncodeError> {
+        let prev = self.last.map_or(0, |l| l.0);
+        encode_into(p.0.wrapping_sub(prev) as i64, &mut self.bytes)?;
+        self.len += 1;
+        self.last = Some(p);
+        Ok(())
+    }
+
+    /// Writes a payload of `layout` with these vertices, plus `extra`
+    /// appended at the end.
+    fn payload(&self, layout: Layout, extra: Option<HilbertPoint>) -> Result<Vec<u8>, EncodeError> {
+        let mut buf = vec![payload_tag(Encoding::HilbertDelta)];
+        encode_into(layout, &mut buf)?;
+        encode_into((self.len + extra.is_some() as usize) as u64, &mut buf)?;
+        buf.extend(&self.bytes);
+        if let Some(p) = extra {
+            let prev = self.last.map_or(0, |l| l.0);
+            encode_into(p.0.wrapping_sub(prev) as i64, &mut buf)?;
+        }
+        Ok(buf)
+    }
+}
+
+/// Builds a linestring payload incrementally.
+#[derive(Debug, Clone, Default)]
+pub struct LineStringEncoder {
+    points: DeltaBuffer,
+}
+
+impl LineStringEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a vertex.
+    pub fn push(&mut self, coord: Coord) -> Result<(), EncodeError> {
+        self.points.push(encode_coord(coord))
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.len == 0
+    }
+
+    /// Returns the payload of the linestring so far.
+    pub fn snapshot(&self) -> Result<Vec<u8>, EncodeError> {
+        let len =
+            u32::try_from(self.points.len).map_err(|_| EncodeError::Other("too many vertices"))?;
+        self.points.payload(Layout::LineString(len), None)
+    }
+}
+
+/// Builds a polygon payload incrementally, ring by ring.
+///
+/// Vertices go to the exterior ring until [`next_ring`](Self::next_ring)
+/// starts an interior ring. Rings are closed automatically.
+#[derive(Debug, Clone, Default)]
+pub struct PolygonEncoder {
+    points: DeltaBuffer,
+    /// Lengths of the finished rings.
+    rings: Vec<u32>,
+    /// First vertex and length of the ring being built.
+    ring_start: Option<HilbertPoint>,
+    ring_len: u32,
+}
+
+impl PolygonEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a vertex to the current ring.
+    pub fn push(&mut self, coord: Coord) -> Result<(), EncodeError> {
+        let p = encode_coord(coord);
+        self.points.push(p)?;
+        self.ring_start.get_or_insert(p);
+        self.ring_len += 1;
+        Ok(())
+    }
+
+    /// Closes the current ring and starts an interior ring. Does nothing if
+    /// the current ring has no vertices.
+    pub fn next_ring(&mut self) -> Result<(), EncodeError> {
+        let Some(closing) = self.closing_vertex() else {
+            return Ok(());
+        };
+        if let Some(p) = closing {
+            self.points.push(p)?;
+            self.ring_len += 1;
+        }
+        self.rings.push(self.ring_len);
+        self.ring_start = None;
+        self.ring_len = 0;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.len == 0
+    }
+
+    /// Returns the payload of the polygon so far, with the current ring
+    /// closed.
+    pub fn snapshot(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut rings = self.rings.clone();
+        let closing = self.closing_vertex();
+        if let Some(closing) = closing {
+            rings.push(self.ring_len + closing.is_some() as u32);
+        }
+        if rings.is_empty() {
+            // A polygon always has an exterior ring, even if empty.
+            rings.push(0);
+        }
+        self.points
+            .payload(Layout::Polygon(rings), closing.flatten())
+    }
+
+    /// `None` if the current ring is empty, otherwise the vertex needed to
+    /// close it, if any.
+    fn closing_vertex(&self) -> Option<Option<HilbertPoint>> {
+        let start = self.ring_start?;
+        Some((self.points.last != Some(start)).then_some(start))
+    }
+}
+
+/// Keeps the most recent vertices of a live track, for trails of bounded
+/// size.
+///
+/// Vertices are stored as encoded indices with their timestamps, in any
+/// consistent unit as in [`TimeRange`](crate::spatiotemporal::TimeRange).
+/// Pushing evicts vertices from the front beyond the length limit or older
+/// than the age limit, without touching the rest.
+#[derive(Debug, Clone)]
+pub struct RecentTrackEncoder {
+    points: VecDeque<(HilbertPoint, f64)>,
+    max_len: usize,
+    max_age: Option<f64>,
+}
+
+impl RecentTrackEncoder {
+    /// Keeps at most `max_len` vertices.
+    pub fn new(max_len: usize) -> Self {
+        assert!(max_len > 0, "track length limit must be positive");
+        Self {
+            points: VecDeque::with_capacity(max_len),
+            max_len,
+            max_age: None,
+        }
+    }
+
+    /// Also evicts vertices more than `max_age` older than the latest one.
+    pub fn max_age(mut self, max_age: f64) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Appends a vertex recorded at time `t` and evicts expired ones.
+    /// Timestamps are expected in ascending order.
+    pub fn push(&mut self, coord: Coord, t: f64) {
+        if self.points.len() == self.max_len {
+            self.points.pop_front();
+        }
+        self.points.push_back((encode_coord(coord), t));
+        if let Some(max_age) = self.max_age {
+            while self.points.front().is_some_and(|(_, t0)| t - t0 > max_age) {
+                self.points.pop_front();
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Timestamps of the kept vertices, oldest first.
+    pub fn times(&self) -> impl Iterator<Item = f64> + '_ {
+        self.points.iter().map(|(_, t)| *t)
+    }
+
+    /// Returns the kept vertices as a linestring.
+    pub fn linestring(&self) -> HilbertGeometry {
+        HilbertGeometry::LineString(self.points.iter().map(|(p, _)| *p).collect())
+    }
+
+    /// Returns the payload of the kept vertices as a linestring.
+    pub fn snapshot(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut points = DeltaBuffer::default();
+        for (p, _) in &self.points {
+            points.push(*p)?;
+        }
+        points.payload(Layout::LineString(self.points.len() as u32), None)
+    }
+}
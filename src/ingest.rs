@@ -0,0 +1,98 @@
+//! Bulk ingestion of coordinate tables.
+
+use std::fmt;
+use std::io::Read;
+
+use csv::StringRecord;
+use geo_types::Coord;
+use rayon::prelude::*;
+
+use crate::{encode_coord, HilbertPoint};
+
+/// Records parsed and encoded per parallel batch.
+const BATCH_SIZE: usize = 64 * 1024;
+
+/// Error returned by [`encode_csv_points`].
+#[derive(Debug)]
+pub enum CsvError {
+    Csv(csv::Error),
+    /// A requested column is not in the header.
+    MissingColumn(String),
+    /// A value that isn't a number, with its 1-based record number.
+    InvalidCoordinate {
+        record: u64,
+        value: String,
+    },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Csv(e) => write!(f, "CSV error: {e}"),
+            CsvError::MissingColumn(c) => write!(f, "missing column {c:?}"),
+            CsvError::InvalidCoordinate { record, value } => {
+                write!(f, "invalid coordinate {value:?} in record {record}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<csv::Error> for CsvError {
+    fn from(e: csv::Error) -> Self {
+        CsvError::Csv(e)
+    }
+}
+
+/// Encodes the points of a CSV table with a header row, taking longitudes
+/// and latitudes from the named columns.
+///
+/// Records are read in batches that are parsed and encoded in parallel, and
+/// the points are returned in input order.
+pub fn encode_csv_points(
+    reader: impl Read,
+    lon_col: &str,
+    lat_col: &str,
+) -> Result<Vec<HilbertPoint>, CsvError> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let headers = reader.headers()?;
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| CsvError::MissingColumn(name.to_string()))
+    };
+    let (lon, lat) = (column(lon_col)?, column(lat_col)?);
+
+    let mut points = vec![];
+    let mut batch = vec![StringRecord::new(); BATCH_SIZE];
+    loop {
+        let mut len = 0;
+        while len < BATCH_SIZE && reader.read_record(&mut batch[len])? {
+            len += 1;
+        }
+        let first = points.len() as u64 + 1;
+        let encoded = batch[..len]
+            .par_iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let value = |col: usize| {
+                    let s = record.get(col).unwrap_or("").trim();
+                    s.parse::<f64>().map_err(|_| CsvError::InvalidCoordinate {
+                        record: first + i as u64,
+                        value: s.to_string(),
+                    })
+                };
+                Ok(encode_coord(Coord {
+                    x: value(lon)?,
+                    y: value(lat)?,
+                }))
+            })
+            .collect::<Result<Vec<_>, CsvError>>()?;
+        points.extend(encoded);
+        if len < BATCH_SIZE {
+            return Ok(points);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! `tracing` events for [`HilbertSerializer`](crate::HilbertSerializer).
+//!
+//! Counters use the `monotonic_counter.` field prefix understood by metrics
+//! layers such as `tracing-opentelemetry`.
+
+use bincode::error::EncodeError;
+use geo_types::{Geometry, Polygon};
+
+use crate::GeometryDecodeError;
+
+/// Number of coordinates of a `geo-types` geometry.
+fn num_coords(geom: &Geometry) -> usize {
+    let polygon =
+        |p: &Polygon| p.exterior().0.len() + p.interiors().iter().map(|r| r.0.len()).sum::<usize>();
+    match geom {
+        Geometry::Point(_) => 1,
+        Geometry::Line(_) | Geometry::Rect(_) => 2,
+        Geometry::Triangle(_) => 3,
+        Geometry::LineString(ls) => ls.0.len(),
+        Geometry::Polygon(p) => polygon(p),
+        Geometry::MultiPoint(mp) => mp.0.len(),
+        Geometry::MultiLineString(mls) => mls.0.iter().map(|ls| ls.0.len()).sum(),
+        Geometry::MultiPolygon(mp) => mp.0.iter().map(polygon).sum(),
+        Geometry::GeometryCollection(gc) => gc.0.iter().map(num_coords).sum(),
+    }
+}
+
+pub(crate) fn encoded(geom: &Geometry, result: &Result<Vec<u8>, EncodeError>) {
+    match result {
+        Ok(bytes) => tracing::debug!(
+            monotonic_counter.hilbert_bytes_encoded = bytes.len() as u64,
+            monotonic_counter.hilbert_vertices_encoded = num_coords(geom) as u64,
+            "encoded geometry"
+        ),
+        Err(e) => tracing::warn!(
+            monotonic_counter.hilbert_encode_errors = 1u64,
+            error = %e,
+            "failed to encode geometry"
+        ),
+    }
+}
+
+pub(crate) fn decoded(data: &[u8], result: &Result<Geometry, GeometryDecodeError>) {
+    match result {
+        Ok(geom) => tracing::debug!(
+            monotonic_counter.hilbert_bytes_decoded = data.len() as u64,
+            monotonic_counter.hilbert_vertices_decoded = num_coords(geom) as u64,
+            "decoded geometry"
+        ),
+        Err(e) => tracing::warn!(
+            monotonic_counter.hilbert_decode_errors = 1u64,
+            error = %e,
+            "failed to decode geometry"
+        ),
+    }
+}
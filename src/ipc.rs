@@ -0,0 +1,179 @@
+//! Arrow IPC streams of encoded features.
+//!
+//! Features become rows of Arrow record batches with the bincode-encoded
+//! geometry in a binary `geometry` column and one nullable column per
+//! property. The stream format is what Arrow Flight sends over the wire, so
+//! batches can be shipped to any Arrow-native consumer.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BinaryBuilder, BooleanBuilder, Float64Builder, Int64Builder, RecordBatch,
+    StringBuilder,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::ipc::writer::StreamWriter;
+use bincode::config;
+
+use crate::collection::{HilbertFeature, PropertyValue};
+
+/// Name of the geometry column.
+pub const GEOMETRY_COLUMN: &str = "geometry";
+
+fn data_type_of(value: &PropertyValue) -> Option<DataType> {
+    match value {
+        PropertyValue::Null => None,
+        PropertyValue::Bool(_) => Some(DataType::Boolean),
+        PropertyValue::Int(_) => Some(DataType::Int64),
+        PropertyValue::Double(_) => Some(DataType::Float64),
+        PropertyValue::String(_) => Some(DataType::Utf8),
+    }
+}
+
+/// Infers a schema for `features`: the geometry column followed by one column
+/// per property key in order of first appearance. Columns mixing integers
+/// and doubles are `Float64`, other mixes `Utf8`.
+pub fn feature_schema(features: &[HilbertFeature]) -> Schema {
+    let mut columns: Vec<(&str, Option<DataType>)> = vec![];
+    for (key, value) in features.iter().flat_map(|f| &f.properties) {
+        let data_type = data_type_of(value);
+        match columns.iter_mut().find(|(k, _)| k == key) {
+            None => columns.push((key, data_type)),
+            Some((_, existing)) => {
+                *existing = match (existing.take(), data_type) {
+                    (None, t) | (t, None) => t,
+                    (Some(a), Some(b)) if a == b => Some(a),
+                    (Some(DataType::Int64), Some(DataType::Float64))
+                    | (Some(DataType::Float64), Some(DataType::Int64)) => Some(DataType::Float64),
+                    _ => Some(DataType::Utf8),
+                }
+            }
+        }
+    }
+    let fields = std::iter::once(Field::new(GEOMETRY_COLUMN, DataType::Binary, false)).chain(
+        columns
+            .into_iter()
+            .map(|(k, t)| Field::new(k, t.unwrap_or(DataType::Utf8), true)),
+    );
+    Schema::new(fields.collect::<Vec<_>>())
+}
+
+fn mismatch(key: &str, value: &PropertyValue, data_type: &DataType) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "property {key:?} value {value:?} doesn't fit column type {data_type}"
+    ))
+}
+
+fn property_column(
+    features: &[HilbertFeature],
+    key: &str,
+    data_type: &DataType,
+) -> Result<ArrayRef, ArrowError> {
+    let values = features
+        .iter()
+        .map(|f| f.property(key).filter(|v| **v != PropertyValue::Null));
+    Ok(match data_type {
+        DataType::Boolean => {
+            let mut builder = BooleanBuilder::new();
+            for value in values {
+                match value {
+                    None => builder.append_null(),
+                    Some(PropertyValue::Bool(b)) => builder.append_value(*b),
+                    Some(v) => return Err(mismatch(key, v, data_type)),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Int64 => {
+            let mut builder = Int64Builder::new();
+            for value in values {
+                match value {
+                    None => builder.append_null(),
+                    Some(PropertyValue::Int(i)) => builder.append_value(*i),
+                    Some(v) => return Err(mismatch(key, v, data_type)),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Float64 => {
+            let mut builder = Float64Builder::new();
+            for value in values {
+                match value {
+                    None => builder.append_null(),
+                    Some(PropertyValue::Int(i)) => builder.append_value(*i as f64),
+                    Some(PropertyValue::Double(d)) => builder.append_value(*d),
+                    Some(v) => return Err(mismatch(key, v, data_type)),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Utf8 => {
+            let mut builder = StringBuilder::new();
+            for value in values {
+                match value {
+                    None | Some(PropertyValue::Null) => builder.append_null(),
+                    Some(PropertyValue::String(s)) => builder.append_value(s),
+                    Some(PropertyValue::Bool(b)) => builder.append_value(b.to_string()),
+                    Some(PropertyValue::Int(i)) => builder.append_value(i.to_string()),
+                    Some(PropertyValue::Double(d)) => builder.append_value(d.to_string()),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "unsupported property column type {other}"
+            )))
+        }
+    })
+}
+
+/// Builds a record batch of `features` with the given schema, as returned by
+/// [`feature_schema`]. Properties missing from the schema are dropped.
+pub fn features_to_record_batch(
+    features: &[HilbertFeature],
+    schema: SchemaRef,
+) -> Result<RecordBatch, ArrowError> {
+    let mut geometries = BinaryBuilder::new();
+    for f in features {
+        let bytes = bincode::encode_to_vec(&f.geometry, config::standard())
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+        geometries.append_value(bytes);
+    }
+    let mut columns: Vec<ArrayRef> = vec![Arc::new(geometries.finish())];
+    for field in schema.fields().iter().skip(1) {
+        columns.push(property_column(features, field.name(), field.data_type())?);
+    }
+    RecordBatch::try_new(schema, columns)
+}
+
+/// Writes features to an Arrow IPC stream in record batches.
+pub struct FeatureStreamWriter<W: Write> {
+    writer: StreamWriter<W>,
+    schema: SchemaRef,
+}
+
+impl<W: Write> FeatureStreamWriter<W> {
+    /// Starts a stream with a fixed schema, e.g. from [`feature_schema`] of
+    /// a sample of the features.
+    pub fn try_new(writer: W, schema: SchemaRef) -> Result<Self, ArrowError> {
+        Ok(Self {
+            writer: StreamWriter::try_new(writer, &schema)?,
+            schema,
+        })
+    }
+
+    /// Writes `features` as one record batch.
+    pub fn write(&mut self, features: &[HilbertFeature]) -> Result<(), ArrowError> {
+        let batch = features_to_record_batch(features, self.schema.clone())?;
+        self.writer.write(&batch)
+    }
+
+    /// Ends the stream and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, ArrowError> {
+        self.writer.finish()?;
+        self.writer.into_inner()
+    }
+}
@@ -0,0 +1,156 @@
+use std::ops::RangeInclusive;
+
+use geo_types::{Polygon, Rect};
+
+use crate::cell::HilbertCell;
+use crate::covering::{cover_polygon, cover_rect, DEFAULT_MAX_RANGES};
+use crate::{encode_coord, HilbertGeometry, HilbertPoint};
+
+/// Inclusive start and exclusive end of a key range, see [`scan_ranges`].
+pub type KeyRange = (Vec<u8>, Vec<u8>);
+
+/// Length of the spatial prefix of keys built by [`make_key`].
+pub const KEY_LEN: usize = 8;
+
+/// Returns a key whose byte order follows the Hilbert curve, for use in
+/// ordered key-value stores (sled, RocksDB, LMDB...).
+///
+/// Points are keyed by their own index. Other geometries are keyed by the
+/// center of their bounding box, so a scan finds them only if the center lies
+/// within the scanned rectangle; expand the query by the largest expected
+/// feature extent when that matters. Empty geometries have no key.
+pub fn make_key(geom: &HilbertGeometry) -> Option<Vec<u8>> {
+    Some(key_point(geom)?.0.to_be_bytes().to_vec())
+}
+
+/// The point a geometry is keyed by: the point itself, or the center of the
+/// bounding box.
+pub(crate) fn key_point(geom: &HilbertGeometry) -> Option<HilbertPoint> {
+    match geom {
+        HilbertGeometry::Point(p) => Some(*p),
+        _ => Some(encode_coord(geom.bounding_rect()?.center())),
+    }
+}
+
+/// Like [`make_key`], with `id` appended so several geometries at the same
+/// position get distinct keys.
+pub fn make_key_with_id(geom: &HilbertGeometry, id: &[u8]) -> Option<Vec<u8>> {
+    let mut key = make_key(geom)?;
+    key.extend_from_slice(id);
+    Some(key)
+}
+
+/// Reads the point from the spatial prefix of a key.
+pub fn point_from_key(key: &[u8]) -> Option<HilbertPoint> {
+    let prefix = key.get(..KEY_LEN)?.try_into().ok()?;
+    Some(HilbertPoint(u64::from_be_bytes(prefix)))
+}
+
+/// Returns `(start, end)` key ranges to scan for keys inside `rect`. `start`
+/// is inclusive and `end` exclusive; an empty `end` means the range is
+/// unbounded.
+///
+/// Ranges may include keys outside of `rect`, so results should be filtered
+/// by position afterwards.
+pub fn scan_ranges(rect: &Rect) -> Vec<KeyRange> {
+    scan_ranges_with_limit(rect, DEFAULT_MAX_RANGES)
+}
+
+/// Like [`scan_ranges`], returning at most roughly `max_ranges` ranges. Fewer
+/// ranges mean fewer seeks but more keys outside of `rect`.
+pub fn scan_ranges_with_limit(rect: &Rect, max_ranges: usize) -> Vec<KeyRange> {
+    cover_rect(rect, max_ranges)
+        .into_iter()
+        .map(key_range)
+        .collect()
+}
+
+/// Returns key ranges for `polygon` as `(inner, boundary)`, in the form of
+/// [`scan_ranges`]. Keys in the inner ranges lie inside the polygon and can
+/// be deleted or returned without checks, keys in the boundary ranges need
+/// to be filtered by position. See [`cover_polygon`].
+pub fn polygon_scan_ranges(polygon: &Polygon, max_ranges: usize) -> (Vec<KeyRange>, Vec<KeyRange>) {
+    let covering = cover_polygon(polygon, max_ranges);
+    (
+        covering.inner.into_iter().map(key_range).collect(),
+        covering.boundary.into_iter().map(key_range).collect(),
+    )
+}
+
+/// Returns a `(partition key, sort key)` pair for `point`, for wide-column
+/// stores such as DynamoDB, Bigtable or Cassandra.
+///
+/// The partition key names the curve cell at `partition_level` holding the
+/// point, so nearby points share a partition. The sort key is the full index
+/// as 16 hex digits, so items sort in curve order within their partition.
+/// Each level splits partitions in four: pick a level where partitions stay
+/// below the store's size and throughput limits.
+///
+/// # Panics
+///
+/// If `partition_level` is above [`MAX_LEVEL`](crate::cell::MAX_LEVEL).
+pub fn partition_and_sort_key(point: HilbertPoint, partition_level: u32) -> (String, String) {
+    let cell = HilbertCell::containing(point, partition_level);
+    (partition_key(&cell), sort_key(point.0))
+}
+
+/// Sort key range within one partition, see [`partition_scan_ranges`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionRange {
+    pub partition: String,
+    /// Inclusive first sort key.
+    pub start: String,
+    /// Inclusive last sort key.
+    pub end: String,
+}
+
+/// Returns the partitions and sort key ranges to query for keys from
+/// [`partition_and_sort_key`] inside `rect`, in curve order.
+///
+/// The rectangle is covered with at most roughly `max_ranges` index ranges,
+/// which are split at partition boundaries, so a rectangle much larger than
+/// a partition cell yields a query per cell. Ranges may include points
+/// outside of `rect`, so results should be filtered by position afterwards.
+pub fn partition_scan_ranges(
+    rect: &Rect,
+    partition_level: u32,
+    max_ranges: usize,
+) -> Vec<PartitionRange> {
+    let mut ranges = vec![];
+    for r in cover_rect(rect, max_ranges) {
+        let mut start = *r.start();
+        loop {
+            let cell = HilbertCell::containing(HilbertPoint(start), partition_level);
+            let end = (*cell.range().end()).min(*r.end());
+            ranges.push(PartitionRange {
+                partition: partition_key(&cell),
+                start: sort_key(start),
+                end: sort_key(end),
+            });
+            if end == *r.end() {
+                break;
+            }
+            start = end + 1;
+        }
+    }
+    ranges
+}
+
+/// Level and key of a cell, the key in hex padded to the level's width.
+fn partition_key(cell: &HilbertCell) -> String {
+    let width = (cell.level as usize).div_ceil(2);
+    format!("{}/{:0width$x}", cell.level, cell.key)
+}
+
+fn sort_key(index: u64) -> String {
+    format!("{index:016x}")
+}
+
+fn key_range(r: RangeInclusive<u64>) -> KeyRange {
+    let start = r.start().to_be_bytes().to_vec();
+    let end = r
+        .end()
+        .checked_add(1)
+        .map_or(vec![], |end| end.to_be_bytes().to_vec());
+    (start, end)
+}
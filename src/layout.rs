@@ -0,0 +1,75 @@
+use bincode::{Decode, Encode};
+
+use crate::{HilbertGeometry, HilbertPoint};
+
+/// Structure of a Hilbert-encoded geometry without its vertices.
+///
+/// Used by encodings that store all vertices of a geometry in a flat sequence.
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode)]
+pub(crate) enum Layout {
+    Point,
+    LineString(u32),
+    Polygon(Vec<u32>),
+    MultiPoint(u32),
+    MultiLineString(Vec<u32>),
+    MultiPolygon(Vec<Vec<u32>>),
+}
+
+fn lengths(parts: &[Vec<HilbertPoint>]) -> Vec<u32> {
+    parts.iter().map(|p| p.len() as u32).collect()
+}
+
+impl Layout {
+    /// Total number of vertices described by the layout.
+    pub(crate) fn num_points(&self) -> usize {
+        match self {
+            Layout::Point => 1,
+            Layout::LineString(n) | Layout::MultiPoint(n) => *n as usize,
+            Layout::Polygon(rings) | Layout::MultiLineString(rings) => {
+                rings.iter().map(|n| *n as usize).sum()
+            }
+            Layout::MultiPolygon(polys) => polys.iter().flatten().map(|n| *n as usize).sum(),
+        }
+    }
+}
+
+/// Splits a geometry into its layout and a flat list of vertices.
+pub(crate) fn flatten(hg: &HilbertGeometry) -> (Layout, Vec<HilbertPoint>) {
+    match hg {
+        HilbertGeometry::Point(p) => (Layout::Point, vec![*p]),
+        HilbertGeometry::LineString(ps) => (Layout::LineString(ps.len() as u32), ps.clone()),
+        HilbertGeometry::MultiPoint(ps) => (Layout::MultiPoint(ps.len() as u32), ps.clone()),
+        HilbertGeometry::Polygon(rings) => (Layout::Polygon(lengths(rings)), rings.concat()),
+        HilbertGeometry::MultiLineString(lines) => {
+            (Layout::MultiLineString(lengths(lines)), lines.concat())
+        }
+        HilbertGeometry::MultiPolygon(polys) => (
+            Layout::MultiPolygon(polys.iter().map(|p| lengths(p)).collect()),
+            polys.iter().flatten().flatten().copied().collect(),
+        ),
+    }
+}
+
+/// Rebuilds a geometry from its layout and flat vertices.
+///
+/// Panics if `points` does not contain exactly `layout.num_points()` vertices.
+pub(crate) fn rebuild(layout: &Layout, points: Vec<HilbertPoint>) -> HilbertGeometry {
+    assert_eq!(layout.num_points(), points.len(), "vertex count mismatch");
+    let mut iter = points.into_iter();
+    let mut take = |n: &u32| iter.by_ref().take(*n as usize).collect::<Vec<_>>();
+    match layout {
+        Layout::Point => HilbertGeometry::Point(take(&1)[0]),
+        Layout::LineString(n) => HilbertGeometry::LineString(take(n)),
+        Layout::MultiPoint(n) => HilbertGeometry::MultiPoint(take(n)),
+        Layout::Polygon(rings) => HilbertGeometry::Polygon(rings.iter().map(take).collect()),
+        Layout::MultiLineString(lines) => {
+            HilbertGeometry::MultiLineString(lines.iter().map(take).collect())
+        }
+        Layout::MultiPolygon(polys) => HilbertGeometry::MultiPolygon(
+            polys
+                .iter()
+                .map(|rings| rings.iter().map(&mut take).collect())
+                .collect(),
+        ),
+    }
+}
@@ -1,3 +1,87 @@
+mod approx;
+#[cfg(feature = "tokio")]
+pub mod asyncio;
+#[cfg(feature = "benchmark")]
+pub mod benchmark;
+pub mod cache;
+pub mod cell;
+pub mod changelog;
+mod circle;
+pub mod clip;
+#[cfg(feature = "object_store")]
+pub mod cloud;
+#[cfg(feature = "codec")]
+pub mod codec;
+pub mod collection;
+pub mod compat;
+pub mod covering;
+pub mod crs;
+#[cfg(feature = "datum")]
+pub mod datum;
+mod decode;
+pub mod ewkb;
+pub mod frame;
+pub mod geo_ops;
+pub mod geofence;
+#[cfg(feature = "geoparquet")]
+pub mod geoparquet;
+#[cfg(feature = "blake3")]
+mod hash;
+pub mod histogram;
+mod hull;
+pub mod incremental;
+#[cfg(feature = "csv")]
+pub mod ingest;
+#[cfg(feature = "tracing")]
+mod instrument;
+#[cfg(feature = "arrow")]
+pub mod ipc;
+pub mod kv;
+mod layout;
+mod linear;
+pub mod locality;
+pub mod lod;
+#[cfg(feature = "mvt")]
+pub mod mvt;
+#[cfg(feature = "osmpbf")]
+pub mod osm;
+pub mod patch;
+pub mod payload;
+#[cfg(feature = "polars")]
+pub mod polars_ext;
+#[cfg(feature = "sqlx-postgres")]
+pub mod postgres;
+pub mod precise;
+pub mod prepared;
+#[cfg(feature = "prost")]
+pub mod proto;
+pub mod qa;
+pub mod quantize;
+pub mod redis;
+#[cfg(feature = "repair")]
+pub mod repair;
+pub mod resample;
+pub mod roads;
+pub mod search;
+pub mod seek;
+pub mod shard;
+#[cfg(feature = "shapefile")]
+pub mod shp;
+pub mod similarity;
+mod simplify;
+pub mod spatiotemporal;
+pub mod testing;
+pub mod tile;
+pub mod topology;
+#[cfg(any(feature = "gpx", feature = "kml"))]
+pub mod trace;
+#[cfg(feature = "datafusion")]
+pub mod udf;
+pub mod validate;
+pub mod visit;
+
+use std::borrow::Cow;
+
 use bincode::{
     config,
     config::Configuration,
@@ -6,15 +90,41 @@ use bincode::{
 };
 use fast_hilbert::{h2xy, xy2h};
 use geo_types::{
-    Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+    Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon, Rect,
 };
 
+pub use approx::geometries_approx_eq;
+pub use decode::GeometryDecodeError;
+
 /// Represents a Hilbert-encoded point.
-#[derive(Debug, Clone, Copy, Decode, Encode)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Decode, Encode)]
 pub struct HilbertPoint(pub u64);
 
+impl HilbertPoint {
+    /// Returns the point at fraction `t` of the way from `self` to `other`
+    /// along the curve. `t` is clamped to `[0, 1]`.
+    pub fn lerp_along_curve(self, other: HilbertPoint, t: f64) -> HilbertPoint {
+        // Fixed point with the precision of an f64 mantissa, so large index
+        // distances don't lose their low bits.
+        const SHIFT: u32 = 53;
+        let t = (t.clamp(0.0, 1.0) * (1u64 << SHIFT) as f64) as u128;
+        let (a, b) = (self.0 as u128, other.0 as u128);
+        let index = if b >= a {
+            a + (((b - a) * t) >> SHIFT)
+        } else {
+            a - (((a - b) * t) >> SHIFT)
+        };
+        HilbertPoint(index as u64)
+    }
+
+    /// Returns the point halfway between `self` and `other` along the curve.
+    pub fn midpoint(self, other: HilbertPoint) -> HilbertPoint {
+        HilbertPoint(self.0 / 2 + other.0 / 2 + (self.0 & other.0 & 1))
+    }
+}
+
 /// Represents a Hilbert-encoded geometry.
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Decode, Encode)]
 pub enum HilbertGeometry {
     Point(HilbertPoint),
     LineString(Vec<HilbertPoint>),
@@ -43,6 +153,8 @@ fn decode_coord(p: HilbertPoint) -> Coord<f64> {
 }
 
 /// Encodes a `geo-types` geometry into a Hilbert-encoded geometry.
+///
+/// Panics on geometry collections, lines, rects and triangles.
 pub fn encode_geometry(geom: &Geometry<f64>) -> HilbertGeometry {
     let make_point = |pt: &Point| HilbertGeometry::Point(encode_coord(pt.0));
     let make_linestring = |ls: &LineString| {
@@ -64,7 +176,7 @@ pub fn encode_geometry(geom: &Geometry<f64>) -> HilbertGeometry {
                     .collect::<Vec<HilbertPoint>>()
             })
             .collect::<Vec<Vec<HilbertPoint>>>();
-        HilbertGeometry::Polygon(vec![vec![exterior], interiors].concat())
+        HilbertGeometry::Polygon([vec![exterior], interiors].concat())
     };
 
     match geom {
@@ -114,6 +226,25 @@ pub fn encode_geometry(geom: &Geometry<f64>) -> HilbertGeometry {
     }
 }
 
+/// Like [`encode_geometry`], but fails on geometry collections, lines, rects
+/// and triangles instead of panicking.
+pub fn try_encode_geometry(geom: &Geometry<f64>) -> Result<HilbertGeometry, EncodeError> {
+    supported(geom).map_err(EncodeError::Other)?;
+    Ok(encode_geometry(geom))
+}
+
+/// Checks that `geom` has a Hilbert counterpart: geometry collections,
+/// lines, rects and triangles don't.
+pub(crate) fn supported(geom: &Geometry) -> Result<(), &'static str> {
+    match geom {
+        Geometry::GeometryCollection(_)
+        | Geometry::Line(_)
+        | Geometry::Rect(_)
+        | Geometry::Triangle(_) => Err("unsupported geometry type"),
+        _ => Ok(()),
+    }
+}
+
 /// Decodes a Hilbert-encoded geometry back into a `geo-types` geometry.
 pub fn decode_geometry(hgeom: &HilbertGeometry) -> Geometry<f64> {
     match hgeom {
@@ -159,51 +290,667 @@ pub fn decode_geometry(hgeom: &HilbertGeometry) -> Geometry<f64> {
     }
 }
 
+/// Applies `f` to every coordinate of a `geo-types` geometry.
+pub(crate) fn map_geometry_coords(geom: &Geometry, f: &impl Fn(Coord) -> Coord) -> Geometry {
+    let line = |ls: &LineString| ls.0.iter().map(|c| f(*c)).collect::<LineString>();
+    let poly =
+        |p: &Polygon| Polygon::new(line(p.exterior()), p.interiors().iter().map(line).collect());
+    match geom {
+        Geometry::Point(p) => Geometry::Point(Point(f(p.0))),
+        Geometry::Line(l) => Geometry::Line(geo_types::Line::new(f(l.start), f(l.end))),
+        Geometry::LineString(ls) => Geometry::LineString(line(ls)),
+        Geometry::Polygon(p) => Geometry::Polygon(poly(p)),
+        Geometry::MultiPoint(mp) => {
+            Geometry::MultiPoint(mp.0.iter().map(|p| Point(f(p.0))).collect())
+        }
+        Geometry::MultiLineString(mls) => {
+            Geometry::MultiLineString(mls.0.iter().map(line).collect())
+        }
+        Geometry::MultiPolygon(mp) => Geometry::MultiPolygon(mp.0.iter().map(poly).collect()),
+        Geometry::GeometryCollection(gc) => {
+            Geometry::GeometryCollection(gc.0.iter().map(|g| map_geometry_coords(g, f)).collect())
+        }
+        Geometry::Rect(r) => Geometry::Rect(Rect::new(f(r.min()), f(r.max()))),
+        Geometry::Triangle(t) => {
+            Geometry::Triangle(geo_types::Triangle::new(f(t.0), f(t.1), f(t.2)))
+        }
+    }
+}
+
 impl From<&Geometry> for HilbertGeometry {
     fn from(geom: &Geometry) -> Self {
-        encode_geometry(&geom)
+        encode_geometry(geom)
     }
 }
 
-impl Into<Geometry> for HilbertGeometry {
-    fn into(self) -> Geometry {
-        decode_geometry(&self)
+impl From<HilbertGeometry> for Geometry {
+    fn from(hg: HilbertGeometry) -> Self {
+        decode_geometry(&hg)
     }
 }
 
 impl HilbertGeometry {
+    /// Name of the geometry type, e.g. `"MultiPolygon"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            HilbertGeometry::Point(_) => "Point",
+            HilbertGeometry::LineString(_) => "LineString",
+            HilbertGeometry::Polygon(_) => "Polygon",
+            HilbertGeometry::MultiPoint(_) => "MultiPoint",
+            HilbertGeometry::MultiLineString(_) => "MultiLineString",
+            HilbertGeometry::MultiPolygon(_) => "MultiPolygon",
+        }
+    }
+
+    /// Number of encoded vertices, including closing vertices of rings.
+    pub fn num_coords(&self) -> usize {
+        match self {
+            HilbertGeometry::Point(_) => 1,
+            HilbertGeometry::LineString(ps) | HilbertGeometry::MultiPoint(ps) => ps.len(),
+            HilbertGeometry::Polygon(rings) | HilbertGeometry::MultiLineString(rings) => {
+                rings.iter().map(Vec::len).sum()
+            }
+            HilbertGeometry::MultiPolygon(polys) => polys.iter().flatten().map(Vec::len).sum(),
+        }
+    }
+
+    /// Number of polygon rings, exterior and interior. Zero for other types.
+    pub fn num_rings(&self) -> usize {
+        match self {
+            HilbertGeometry::Polygon(rings) => rings.len(),
+            HilbertGeometry::MultiPolygon(polys) => polys.iter().map(Vec::len).sum(),
+            _ => 0,
+        }
+    }
+
+    /// Number of member geometries of a multi-geometry, or 1 for single geometries.
+    pub fn num_geometries(&self) -> usize {
+        match self {
+            HilbertGeometry::Point(_)
+            | HilbertGeometry::LineString(_)
+            | HilbertGeometry::Polygon(_) => 1,
+            HilbertGeometry::MultiPoint(ps) => ps.len(),
+            HilbertGeometry::MultiLineString(lines) => lines.len(),
+            HilbertGeometry::MultiPolygon(polys) => polys.len(),
+        }
+    }
+
+    /// Returns `true` if the geometry has no vertices.
+    pub fn is_empty(&self) -> bool {
+        self.num_coords() == 0
+    }
+
+    /// Combines geometries of the same dimension into their multi-geometry
+    /// counterpart, e.g. points and multipoints into a multipoint, without
+    /// decoding them.
+    ///
+    /// Fails if `parts` is empty or mixes points, lines and polygons.
+    pub fn merge(parts: Vec<HilbertGeometry>) -> Result<HilbertGeometry, EncodeError> {
+        let mut iter = parts.into_iter();
+        let mut merged = match iter.next() {
+            None => return Err(EncodeError::Other("no geometries to merge")),
+            Some(HilbertGeometry::Point(p)) => HilbertGeometry::MultiPoint(vec![p]),
+            Some(HilbertGeometry::LineString(ps)) => HilbertGeometry::MultiLineString(vec![ps]),
+            Some(HilbertGeometry::Polygon(rings)) => HilbertGeometry::MultiPolygon(vec![rings]),
+            Some(multi) => multi,
+        };
+        for part in iter {
+            match (&mut merged, part) {
+                (HilbertGeometry::MultiPoint(ps), HilbertGeometry::Point(p)) => ps.push(p),
+                (HilbertGeometry::MultiPoint(ps), HilbertGeometry::MultiPoint(more)) => {
+                    ps.extend(more)
+                }
+                (HilbertGeometry::MultiLineString(lines), HilbertGeometry::LineString(ps)) => {
+                    lines.push(ps)
+                }
+                (
+                    HilbertGeometry::MultiLineString(lines),
+                    HilbertGeometry::MultiLineString(more),
+                ) => lines.extend(more),
+                (HilbertGeometry::MultiPolygon(polys), HilbertGeometry::Polygon(rings)) => {
+                    polys.push(rings)
+                }
+                (HilbertGeometry::MultiPolygon(polys), HilbertGeometry::MultiPolygon(more)) => {
+                    polys.extend(more)
+                }
+                _ => return Err(EncodeError::Other("incompatible geometry types")),
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Splits a multi-geometry into its member geometries, moving their
+    /// vertices. Single geometries are returned as is.
+    pub fn into_parts(self) -> Vec<HilbertGeometry> {
+        match self {
+            HilbertGeometry::MultiPoint(ps) => ps.into_iter().map(HilbertGeometry::Point).collect(),
+            HilbertGeometry::MultiLineString(lines) => {
+                lines.into_iter().map(HilbertGeometry::LineString).collect()
+            }
+            HilbertGeometry::MultiPolygon(polys) => {
+                polys.into_iter().map(HilbertGeometry::Polygon).collect()
+            }
+            single => vec![single],
+        }
+    }
+
+    /// Reverses the vertex order of every linestring, ring and multipoint in
+    /// place, without decoding. Ring orientation flips with it, and the
+    /// order of the parts of a multi-geometry is kept.
+    pub fn reverse(&mut self) {
+        match self {
+            HilbertGeometry::Point(_) => {}
+            HilbertGeometry::LineString(ps) | HilbertGeometry::MultiPoint(ps) => ps.reverse(),
+            HilbertGeometry::Polygon(rings) | HilbertGeometry::MultiLineString(rings) => {
+                rings.iter_mut().for_each(|r| r.reverse())
+            }
+            HilbertGeometry::MultiPolygon(polys) => {
+                polys.iter_mut().flatten().for_each(|r| r.reverse())
+            }
+        }
+    }
+
+    /// Swaps the exterior ring of polygon `part` with its ring `ring`,
+    /// making that hole the shell, e.g. for sources that list a hole before
+    /// its shell. `part` is 0 for a polygon. Ring orientation is kept; see
+    /// [`reverse`](Self::reverse).
+    ///
+    /// Returns `false`, leaving the geometry as it is, for other geometry
+    /// types and indices out of range.
+    pub fn flip_ring_roles(&mut self, part: usize, ring: usize) -> bool {
+        let rings = match self {
+            HilbertGeometry::Polygon(rings) if part == 0 => rings,
+            HilbertGeometry::MultiPolygon(polys) => match polys.get_mut(part) {
+                Some(rings) => rings,
+                None => return false,
+            },
+            _ => return false,
+        };
+        if ring >= rings.len() {
+            return false;
+        }
+        rings.swap(0, ring);
+        true
+    }
+
+    /// Applies `f` to every decoded vertex and re-encodes the result, keeping
+    /// the structure of the geometry.
+    pub fn map_coords(&self, f: impl Fn(Coord) -> Coord) -> HilbertGeometry {
+        let map = |p: &HilbertPoint| encode_coord(f(decode_coord(*p)));
+        let line = |ps: &Vec<HilbertPoint>| ps.iter().map(map).collect::<Vec<_>>();
+        match self {
+            HilbertGeometry::Point(p) => HilbertGeometry::Point(map(p)),
+            HilbertGeometry::LineString(ps) => HilbertGeometry::LineString(line(ps)),
+            HilbertGeometry::Polygon(rings) => {
+                HilbertGeometry::Polygon(rings.iter().map(line).collect())
+            }
+            HilbertGeometry::MultiPoint(ps) => HilbertGeometry::MultiPoint(line(ps)),
+            HilbertGeometry::MultiLineString(lines) => {
+                HilbertGeometry::MultiLineString(lines.iter().map(line).collect())
+            }
+            HilbertGeometry::MultiPolygon(polys) => HilbertGeometry::MultiPolygon(
+                polys
+                    .iter()
+                    .map(|rings| rings.iter().map(line).collect())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Moves the geometry by `dx` and `dy` degrees, re-encoding every vertex
+    /// in place in one pass, e.g. to shift features between tiles.
+    pub fn translate(&mut self, dx: f64, dy: f64) {
+        self.map_coords_in_place(|c| Coord {
+            x: c.x + dx,
+            y: c.y + dy,
+        });
+    }
+
+    /// Scales the geometry by `factor` about `origin`, re-encoding every
+    /// vertex in place in one pass.
+    pub fn scale_about(&mut self, origin: Coord, factor: f64) {
+        self.map_coords_in_place(|c| Coord {
+            x: origin.x + (c.x - origin.x) * factor,
+            y: origin.y + (c.y - origin.y) * factor,
+        });
+    }
+
+    /// Like [`map_coords`](Self::map_coords), overwriting the stored indices
+    /// instead of allocating new vectors.
+    fn map_coords_in_place(&mut self, f: impl Fn(Coord) -> Coord) {
+        let map = |p: &mut HilbertPoint| *p = encode_coord(f(decode_coord(*p)));
+        match self {
+            HilbertGeometry::Point(p) => map(p),
+            HilbertGeometry::LineString(ps) | HilbertGeometry::MultiPoint(ps) => {
+                ps.iter_mut().for_each(map)
+            }
+            HilbertGeometry::Polygon(rings) | HilbertGeometry::MultiLineString(rings) => {
+                rings.iter_mut().flatten().for_each(map)
+            }
+            HilbertGeometry::MultiPolygon(polys) => {
+                polys.iter_mut().flatten().flatten().for_each(map)
+            }
+        }
+    }
+
+    /// Iterates over all encoded vertices of the geometry.
+    pub fn points(&self) -> Box<dyn Iterator<Item = &HilbertPoint> + '_> {
+        match self {
+            HilbertGeometry::Point(p) => Box::new(std::iter::once(p)),
+            HilbertGeometry::LineString(ps) | HilbertGeometry::MultiPoint(ps) => {
+                Box::new(ps.iter())
+            }
+            HilbertGeometry::Polygon(rings) | HilbertGeometry::MultiLineString(rings) => {
+                Box::new(rings.iter().flatten())
+            }
+            HilbertGeometry::MultiPolygon(polys) => Box::new(polys.iter().flatten().flatten()),
+        }
+    }
+
+    /// Returns the bounding rectangle of the decoded geometry, or `None` if it has no vertices.
+    pub fn bounding_rect(&self) -> Option<Rect> {
+        let mut coords = self.points().map(|p| decode_coord(*p));
+        let first = coords.next()?;
+        let (min, max) = coords.fold((first, first), |(min, max), c| {
+            (
+                Coord {
+                    x: min.x.min(c.x),
+                    y: min.y.min(c.y),
+                },
+                Coord {
+                    x: max.x.max(c.x),
+                    y: max.y.max(c.y),
+                },
+            )
+        });
+        Some(Rect::new(min, max))
+    }
+
     pub fn encode_bincode(self, config: &Configuration) -> Result<Vec<u8>, EncodeError> {
         bincode::encode_to_vec(self, *config)
     }
 
+    /// Decodes a geometry. See [`decode_bincode_detailed`](Self::decode_bincode_detailed)
+    /// for where in the geometry a failure happened.
     pub fn decode_bincode(
         data: &[u8],
         config: &Configuration,
     ) -> Result<HilbertGeometry, DecodeError> {
-        let (decoded, _) = bincode::decode_from_slice(data, *config)?;
-        Ok(decoded)
+        Self::decode_bincode_detailed(data, config).map_err(|e| e.source)
+    }
+
+    /// Like [`decode_bincode`](Self::decode_bincode), reporting the part,
+    /// ring and vertex where truncated or corrupted data was found.
+    pub fn decode_bincode_detailed(
+        data: &[u8],
+        config: &Configuration,
+    ) -> Result<HilbertGeometry, GeometryDecodeError> {
+        decode::decode_geometry_bytes(data, config)
     }
 }
 
+/// Decimal places used by [`HilbertSerializer::deterministic`], about
+/// 1 cm on the ground.
+pub const DETERMINISTIC_PLACES: u32 = 7;
+
 // Geometry <-> HWKB
 pub struct HilbertSerializer {
     config: Configuration,
+    max_segment_deg: Option<f64>,
+    tile_grid: Option<(u8, u32)>,
+    quantize: Option<(u32, quantize::Rounding)>,
+    encoding: Option<payload::EncodingMode>,
+    lenient: bool,
+    validation: Option<validate::ValidationOptions>,
+    orient_rings: bool,
+    canonicalize_rings: bool,
+    sort_multipoints: bool,
+    crs: Option<payload::Crs>,
+    #[cfg(feature = "repair")]
+    repair: bool,
+    #[cfg(feature = "datum")]
+    datum: Option<datum::DatumShift>,
+}
+
+impl Default for HilbertSerializer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HilbertSerializer {
     pub fn new() -> Self {
         Self {
             config: config::standard(),
+            max_segment_deg: None,
+            tile_grid: None,
+            quantize: None,
+            encoding: None,
+            lenient: false,
+            validation: None,
+            orient_rings: false,
+            canonicalize_rings: false,
+            sort_multipoints: false,
+            crs: None,
+            #[cfg(feature = "repair")]
+            repair: false,
+            #[cfg(feature = "datum")]
+            datum: None,
         }
     }
 
+    /// Serializer whose output is byte-identical on every platform.
+    ///
+    /// Coordinates are quantized to [`DETERMINISTIC_PLACES`] decimal places
+    /// with [`Rounding::Round`](quantize::Rounding::Round), then cast to
+    /// `f32` and mapped to the curve with integer arithmetic only. The
+    /// float steps are IEEE 754 basic operations, which Rust never fuses
+    /// into FMA instructions, so x86 and ARM builds agree on every bit.
+    ///
+    /// [`densify`](Self::densify) and [`snap_to_tile_grid`](Self::snap_to_tile_grid)
+    /// rely on `libm` functions such as `hypot` and `tan`, which are not
+    /// correctly rounded. Adding them voids the guarantee.
+    pub fn deterministic() -> Self {
+        Self::new().quantize(DETERMINISTIC_PLACES, quantize::Rounding::Round)
+    }
+
+    /// Inserts vertices before encoding so that no segment is longer than
+    /// `max_segment_deg`. See [`resample::densify`].
+    pub fn densify(mut self, max_segment_deg: f64) -> Self {
+        self.max_segment_deg = Some(max_segment_deg);
+        self
+    }
+
+    /// Snaps coordinates to the pixel grid of XYZ tiles at `zoom` before
+    /// encoding. See [`tile::snap_to_tile_grid`]. Encoding fails if `zoom`
+    /// is above [`tile::MAX_ZOOM`].
+    pub fn snap_to_tile_grid(mut self, zoom: u8, extent: u32) -> Self {
+        self.tile_grid = Some((zoom, extent));
+        self
+    }
+
+    /// Rounds coordinates to `places` decimal places before mapping them to
+    /// the curve, so inputs that are equal at that precision always encode
+    /// to the same bytes. See [`quantize`].
+    pub fn quantize(mut self, places: u32, rounding: quantize::Rounding) -> Self {
+        self.quantize = Some((places, rounding));
+        self
+    }
+
+    /// Checks geometries before encoding and fails with the problems found.
+    /// See [`validate`].
+    pub fn validate(mut self, options: validate::ValidationOptions) -> Self {
+        self.validation = Some(options);
+        self
+    }
+
+    /// Rebuilds invalid polygons before validating and encoding them. See
+    /// [`repair::repair_geometry`].
+    #[cfg(feature = "repair")]
+    pub fn repair(mut self) -> Self {
+        self.repair = true;
+        self
+    }
+
+    /// Applies the configured transformations: densifying, snapping, then
+    /// quantizing.
+    fn prepare<'a>(&self, geom: &'a Geometry) -> Cow<'a, Geometry> {
+        let mut geom = Cow::Borrowed(geom);
+        if let Some(max) = self.max_segment_deg {
+            geom = Cow::Owned(resample::densify(&geom, max));
+        }
+        if let Some((zoom, extent)) = self.tile_grid {
+            geom = Cow::Owned(tile::snap_to_tile_grid(&geom, zoom, extent));
+        }
+        if let Some((places, rounding)) = self.quantize {
+            geom = Cow::Owned(quantize::quantize_geometry(&geom, places, rounding));
+        }
+        geom
+    }
+
+    /// Writes tagged payloads with the given encoding instead of plain
+    /// bincode geometries. See [`payload`].
+    pub fn encoding(mut self, mode: payload::EncodingMode) -> Self {
+        self.encoding = Some(mode);
+        self
+    }
+
+    /// Orients exterior rings counter-clockwise and holes clockwise before
+    /// encoding. With an [`encoding`](Self::encoding) set, the payloads
+    /// record which rings were changed, and
+    /// [`payload::decode_payload_original`] restores the original order.
+    /// See [`payload::orient_rings`].
+    pub fn orient_rings(mut self) -> Self {
+        self.orient_rings = true;
+        self
+    }
+
+    /// Like [`orient_rings`](Self::orient_rings), and also rotates rings to
+    /// start at their smallest curve index. The payloads record the
+    /// original start. See [`payload::canonicalize_rings`].
+    pub fn canonicalize_rings(mut self) -> Self {
+        self.canonicalize_rings = true;
+        self
+    }
+
+    /// Sorts the points of multipoints by curve index and drops points that
+    /// encode to the same index as the one before. Point sets with the same
+    /// members then encode to the same bytes, and sorted indices compress
+    /// best, e.g. with [`Encoding::HilbertDelta`](payload::Encoding::HilbertDelta).
+    pub fn sort_multipoints(mut self) -> Self {
+        self.sort_multipoints = true;
+        self
+    }
+
+    /// Takes geometries to encode in `shift.datum` and returns decoded ones
+    /// in it, converting from and to the WGS 84 coordinates that are
+    /// stored. See [`datum`].
+    #[cfg(feature = "datum")]
+    pub fn datum(mut self, shift: datum::DatumShift) -> Self {
+        self.datum = Some(shift);
+        self
+    }
+
+    /// Stores `crs` in every payload, see [`payload::Crs`]. Requires an
+    /// [`encoding`](Self::encoding); plain bincode geometries have no room
+    /// for it and fail to encode.
+    ///
+    /// If `crs` has a normalization in [`crs::CrsRegistry::global`],
+    /// coordinates are normalized before encoding, and [`decode`](Self::decode)
+    /// undoes it for payloads naming a registered CRS.
+    pub fn crs(mut self, crs: payload::Crs) -> Self {
+        self.crs = Some(crs);
+        self
+    }
+
+    /// With an [`encoding`](Self::encoding) set, also decodes untagged
+    /// bincode geometries, e.g. data written before the switch to payloads.
+    /// See [`payload::decode_payload_lenient`].
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
     pub fn encode(&self, geom: &Geometry) -> Result<Vec<u8>, EncodeError> {
-        let hg = HilbertGeometry::from(geom);
-        hg.encode_bincode(&self.config)
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("hilbert_encode").entered();
+        let result = self.encode_untraced(geom);
+        #[cfg(feature = "tracing")]
+        instrument::encoded(geom, &result);
+        result
+    }
+
+    fn encode_untraced(&self, geom: &Geometry) -> Result<Vec<u8>, EncodeError> {
+        self.encode_with(geom, self.encoding)
+    }
+
+    /// Encodes `geom` with `encoding` in place of the configured one and
+    /// decodes the stored geometry back: the transformations and the
+    /// precision of the encoding applied, in WGS 84 with a datum set.
+    /// Fails with a CRS set, as the result has no room for it.
+    fn reencode(
+        &self,
+        geom: &Geometry,
+        encoding: Option<payload::EncodingMode>,
+    ) -> Result<Geometry, EncodeError> {
+        if self.crs.is_some() {
+            return Err(EncodeError::Other(
+                "re-encoded geometries have no room for a CRS",
+            ));
+        }
+        let data = self.encode_with(geom, encoding)?;
+        let geom = match encoding {
+            Some(_) => payload::decode_payload(&data),
+            None => {
+                HilbertGeometry::decode_bincode_detailed(&data, &self.config).map(Geometry::from)
+            }
+        };
+        geom.map_err(|e| EncodeError::OtherString(format!("re-encoded geometry: {e}")))
+    }
+
+    fn encode_with(
+        &self,
+        geom: &Geometry,
+        encoding: Option<payload::EncodingMode>,
+    ) -> Result<Vec<u8>, EncodeError> {
+        #[cfg(feature = "datum")]
+        let shifted;
+        #[cfg(feature = "datum")]
+        let geom = match &self.datum {
+            Some(shift) => {
+                shifted = shift.geometry_to_wgs84(geom);
+                &shifted
+            }
+            None => geom,
+        };
+        #[cfg(feature = "repair")]
+        let repaired;
+        #[cfg(feature = "repair")]
+        let geom = if self.repair {
+            repaired = repair::repair_geometry(geom);
+            &repaired
+        } else {
+            geom
+        };
+        supported(geom).map_err(EncodeError::Other)?;
+        if let Some((zoom, _)) = self.tile_grid.filter(|(z, _)| *z > tile::MAX_ZOOM) {
+            let max = tile::MAX_ZOOM;
+            return Err(EncodeError::OtherString(format!("zoom {zoom} above {max}")));
+        }
+        if let Some(options) = &self.validation {
+            let errors = validate::validate(geom, options);
+            if !errors.is_empty() {
+                let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                return Err(EncodeError::OtherString(errors.join("; ")));
+            }
+        }
+        let mut geom = self.prepare(geom);
+        let mut ring_flags = vec![];
+        if self.canonicalize_rings {
+            let (canonical, flags) = payload::canonicalize_rings(&geom);
+            (geom, ring_flags) = (Cow::Owned(canonical), flags);
+        } else if self.orient_rings {
+            let (oriented, flags) = payload::orient_rings(&geom);
+            (geom, ring_flags) = (Cow::Owned(oriented), flags);
+        }
+        let normalization = self
+            .crs
+            .as_ref()
+            .and_then(|c| crs::CrsRegistry::global().get(c));
+        if let Some(normalization) = normalization {
+            geom = Cow::Owned(normalization.normalize_geometry(&geom));
+        }
+        if let (true, Geometry::MultiPoint(mp)) = (self.sort_multipoints, geom.as_ref()) {
+            let mut keyed: Vec<(HilbertPoint, Point)> =
+                mp.iter().map(|p| (encode_coord(p.0), *p)).collect();
+            keyed.sort_by_key(|(k, _)| *k);
+            keyed.dedup_by_key(|(k, _)| *k);
+            geom = Cow::Owned(Geometry::MultiPoint(MultiPoint(
+                keyed.into_iter().map(|(_, p)| p).collect(),
+            )));
+        }
+        if let Some(mode) = encoding {
+            let metadata = payload::PayloadMetadata {
+                ring_flags,
+                crs: self.crs.clone(),
+            };
+            return payload::encode_payload_with_metadata(&geom, mode, &metadata);
+        }
+        if self.crs.is_some() {
+            return Err(EncodeError::Other("a CRS can only be stored in payloads"));
+        }
+        HilbertGeometry::from(geom.as_ref()).encode_bincode(&self.config)
     }
 
     pub fn decode(&self, data: &[u8]) -> Result<Geometry, DecodeError> {
-        let hg = HilbertGeometry::decode_bincode(data, &self.config)?;
-        Ok(hg.into())
+        self.decode_traced(data, false).map_err(|e| e.source)
+    }
+
+    /// Like [`decode`](Self::decode), reporting the part, ring and vertex
+    /// where truncated or corrupted data was found.
+    ///
+    /// Also rejects NaN and infinite coordinates in plain bincode
+    /// geometries, which corrupted indices decode to. Unlike `decode`, it
+    /// thus fails on geometries that had such coordinates when encoded.
+    /// Payloads are always checked, see [`payload::decode_payload`].
+    pub fn decode_detailed(&self, data: &[u8]) -> Result<Geometry, GeometryDecodeError> {
+        self.decode_traced(data, true)
+    }
+
+    fn decode_traced(&self, data: &[u8], finite: bool) -> Result<Geometry, GeometryDecodeError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("hilbert_decode").entered();
+        let result = self.decode_untraced(data, finite);
+        #[cfg(feature = "tracing")]
+        instrument::decoded(data, &result);
+        result
+    }
+
+    /// Like [`decode`](Self::decode), also returning the CRS stored in the
+    /// payload, if any.
+    pub fn decode_with_crs(
+        &self,
+        data: &[u8],
+    ) -> Result<(Geometry, Option<payload::Crs>), GeometryDecodeError> {
+        let geom = self.decode_detailed(data)?;
+        let crs = if self.encoding.is_some() && payload::is_tagged(data) {
+            payload::payload_crs(data)?
+        } else {
+            None
+        };
+        Ok((geom, crs))
+    }
+
+    fn decode_untraced(&self, data: &[u8], finite: bool) -> Result<Geometry, GeometryDecodeError> {
+        let geom = self.decode_stored(data, finite)?;
+        #[cfg(feature = "datum")]
+        if let Some(shift) = &self.datum {
+            return Ok(shift.geometry_from_wgs84(&geom));
+        }
+        Ok(geom)
+    }
+
+    fn decode_stored(&self, data: &[u8], finite: bool) -> Result<Geometry, GeometryDecodeError> {
+        if self.encoding.is_some() {
+            let geom = if self.lenient {
+                payload::decode_payload_lenient(data)?
+            } else {
+                payload::decode_payload(data)?
+            };
+            if !payload::is_tagged(data) {
+                return Ok(geom);
+            }
+            let normalization =
+                payload::payload_crs(data)?.and_then(|c| crs::CrsRegistry::global().get(&c));
+            return Ok(match normalization {
+                Some(normalization) => normalization.denormalize_geometry(&geom),
+                None => geom,
+            });
+        }
+        let geom = HilbertGeometry::decode_bincode_detailed(data, &self.config)?.into();
+        if finite {
+            decode::check_finite(&geom)?;
+        }
+        Ok(geom)
     }
 }
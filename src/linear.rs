@@ -0,0 +1,82 @@
+use geo_types::Coord;
+
+use crate::{decode_coord, HilbertGeometry, HilbertPoint};
+
+fn distance(a: Coord, b: Coord) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+/// Decoded segments of a linestring, without collecting the vertices.
+fn segments(points: &[HilbertPoint]) -> impl Iterator<Item = (Coord, Coord)> + '_ {
+    let mut coords = points.iter().map(|p| decode_coord(*p));
+    let first = coords.next();
+    coords.scan(first, |prev, c| {
+        let a = prev.replace(c)?;
+        Some((a, c))
+    })
+}
+
+impl HilbertGeometry {
+    /// Returns the point at fraction `t` of the length of a linestring, with
+    /// `t` clamped to `0.0..=1.0`. Lengths are measured in degrees.
+    ///
+    /// `None` for other geometry types and empty linestrings. A linestring
+    /// without length gives its first vertex.
+    pub fn point_at_fraction(&self, t: f64) -> Option<Coord> {
+        let HilbertGeometry::LineString(points) = self else {
+            return None;
+        };
+        let first = decode_coord(*points.first()?);
+        let total: f64 = segments(points).map(|(a, b)| distance(a, b)).sum();
+        let mut remaining = t.clamp(0.0, 1.0) * total;
+        let mut last = first;
+        for (a, b) in segments(points) {
+            let len = distance(a, b);
+            if remaining <= len && len > 0.0 {
+                let f = remaining / len;
+                return Some(Coord {
+                    x: a.x + (b.x - a.x) * f,
+                    y: a.y + (b.y - a.y) * f,
+                });
+            }
+            remaining -= len;
+            last = b;
+        }
+        Some(if total > 0.0 { last } else { first })
+    }
+
+    /// Returns the fraction of the length of a linestring at which it comes
+    /// closest to `point`, the inverse of
+    /// [`point_at_fraction`](Self::point_at_fraction) for points on the
+    /// line. The first closest position wins.
+    ///
+    /// `None` for other geometry types and empty linestrings; 0 for a
+    /// linestring without length.
+    pub fn fraction_at_point(&self, point: Coord) -> Option<f64> {
+        let HilbertGeometry::LineString(points) = self else {
+            return None;
+        };
+        points.first()?;
+        let mut along = 0.0;
+        let mut best = (f64::INFINITY, 0.0);
+        for (a, b) in segments(points) {
+            let d = b - a;
+            let len = distance(a, b);
+            let t = if len > 0.0 {
+                (((point.x - a.x) * d.x + (point.y - a.y) * d.y) / (len * len)).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let closest = Coord {
+                x: a.x + d.x * t,
+                y: a.y + d.y * t,
+            };
+            let dist = distance(point, closest);
+            if dist < best.0 {
+                best = (dist, along + t * len);
+            }
+            along += len;
+        }
+        Some(if along > 0.0 { best.1 / along } else { 0.0 })
+    }
+}
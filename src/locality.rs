@@ -0,0 +1,130 @@
+//! Diagnostics for how well an ordering keeps nearby features together.
+//!
+//! Storage and range scans benefit from the curve only as far as features
+//! that are close in space end up close in key order. [`locality_report`]
+//! measures that on a dataset, and [`locality_report_with_keys`] does the
+//! same for any other ordering, e.g. insertion order or another curve, so
+//! they can be compared.
+
+use geo_types::Coord;
+
+use crate::{encode_coord, HilbertGeometry};
+
+/// Locality metrics of an ordering. Features are represented by the
+/// centers of their bounding boxes; distances are in degrees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalityReport {
+    /// Number of features with vertices.
+    pub features: usize,
+    /// Mean distance in sort position between each feature and its
+    /// nearest spatial neighbour.
+    pub mean_index_gap: f64,
+    /// Expected [`mean_index_gap`](Self::mean_index_gap) of a uniformly
+    /// random order, `(features + 1) / 3`.
+    pub random_index_gap: f64,
+    /// `mean_index_gap / random_index_gap`: 1 for an order no better than
+    /// random, towards 0 for an order that keeps neighbours together.
+    pub clustering_ratio: f64,
+    /// Mean distance between features that follow each other in the order.
+    pub mean_step_distance: f64,
+    /// Mean distance between each feature and its nearest neighbour, the
+    /// lower bound of [`mean_step_distance`](Self::mean_step_distance) for
+    /// any order.
+    pub mean_neighbor_distance: f64,
+}
+
+/// Reports the locality of sorting `geoms` by the curve index of their
+/// bounding box centers. `None` for fewer than two features with vertices.
+pub fn locality_report(geoms: &[HilbertGeometry]) -> Option<LocalityReport> {
+    let keys: Vec<u64> = geoms
+        .iter()
+        .map(|g| g.bounding_rect().map_or(0, |r| encode_coord(r.center()).0))
+        .collect();
+    locality_report_with_keys(geoms, &keys)
+}
+
+/// Like [`locality_report`], ordering `geoms` by `keys` instead, which must
+/// have one key per geometry. Ties keep the input order.
+///
+/// # Panics
+///
+/// If `keys` and `geoms` differ in length.
+pub fn locality_report_with_keys(
+    geoms: &[HilbertGeometry],
+    keys: &[u64],
+) -> Option<LocalityReport> {
+    assert_eq!(geoms.len(), keys.len(), "one key per geometry");
+    let mut features: Vec<(u64, Coord)> = geoms
+        .iter()
+        .zip(keys)
+        .filter_map(|(g, k)| Some((*k, g.bounding_rect()?.center())))
+        .collect();
+    if features.len() < 2 {
+        return None;
+    }
+    features.sort_by_key(|(k, _)| *k);
+    let centers: Vec<Coord> = features.into_iter().map(|(_, c)| c).collect();
+    let n = centers.len() as f64;
+
+    let neighbors = nearest_neighbors(&centers);
+    let mean_index_gap = neighbors
+        .iter()
+        .enumerate()
+        .map(|(i, (j, _))| i.abs_diff(*j) as f64)
+        .sum::<f64>()
+        / n;
+    let random_index_gap = (n + 1.0) / 3.0;
+    let mean_step_distance = centers
+        .windows(2)
+        .map(|w| distance(w[0], w[1]))
+        .sum::<f64>()
+        / (n - 1.0);
+    Some(LocalityReport {
+        features: centers.len(),
+        mean_index_gap,
+        random_index_gap,
+        clustering_ratio: mean_index_gap / random_index_gap,
+        mean_step_distance,
+        mean_neighbor_distance: neighbors.iter().map(|(_, d)| d).sum::<f64>() / n,
+    })
+}
+
+fn distance(a: Coord, b: Coord) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+/// Position and distance of the nearest other point for each of `points`,
+/// by a sweep over the points sorted by x, which stops scanning once the x
+/// gap alone exceeds the best distance found.
+fn nearest_neighbors(points: &[Coord]) -> Vec<(usize, f64)> {
+    let mut by_x: Vec<usize> = (0..points.len()).collect();
+    by_x.sort_by(|a, b| points[*a].x.total_cmp(&points[*b].x));
+    let mut result = vec![(0, f64::INFINITY); points.len()];
+    for (pos, &i) in by_x.iter().enumerate() {
+        let p = points[i];
+        let mut best = (i, f64::INFINITY);
+        let mut visit = |j: usize| {
+            let dx = (points[j].x - p.x).abs();
+            if dx > best.1 {
+                return false;
+            }
+            let d = distance(p, points[j]);
+            if d < best.1 {
+                best = (j, d);
+            }
+            true
+        };
+        for &j in by_x[pos + 1..].iter() {
+            if !visit(j) {
+                break;
+            }
+        }
+        for &j in by_x[..pos].iter().rev() {
+            if !visit(j) {
+                break;
+            }
+        }
+        result[i] = best;
+    }
+    result
+}
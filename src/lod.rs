@@ -0,0 +1,112 @@
+use bincode::{config, error::DecodeError, error::EncodeError};
+use fast_hilbert::{h2xy, xy2h};
+use geo_types::Coord;
+
+use crate::decode::limited;
+use crate::layout::{flatten, rebuild, Layout};
+use crate::{decode_coord, encode_coord, HilbertGeometry, HilbertPoint};
+
+/// Number of refinement passes. Each pass adds one byte (4 curve levels) per vertex.
+pub const LOD_PASSES: usize = 8;
+
+/// Passes [`decode_lod`] needs before it returns a geometry. They hold the sign
+/// and exponent of the `f32` coordinates, so with fewer passes vertices could be
+/// placed orders of magnitude away from their true position.
+pub const MIN_LOD_PASSES: usize = 3;
+
+/// A geometry decoded from a possibly incomplete level-of-detail buffer.
+#[derive(Debug, Clone)]
+pub struct LodGeometry {
+    pub geometry: HilbertGeometry,
+    /// Number of complete refinement passes that were available, `LOD_PASSES` when exact.
+    pub passes: usize,
+}
+
+/// Encodes a geometry into a progressive level-of-detail buffer.
+///
+/// The buffer starts with the geometry layout, followed by `LOD_PASSES` passes
+/// that each store the next most significant byte of every vertex index. Any
+/// prefix of the buffer that includes the layout and the first
+/// [`MIN_LOD_PASSES`] passes can be decoded with [`decode_lod`] into a coarse
+/// approximation of the geometry.
+pub fn encode_lod(hg: &HilbertGeometry) -> Result<Vec<u8>, EncodeError> {
+    let (layout, points) = flatten(hg);
+    let mut buf = bincode::encode_to_vec(&layout, config::standard())?;
+    buf.reserve(points.len() * LOD_PASSES);
+    for pass in 0..LOD_PASSES {
+        let shift = 8 * (LOD_PASSES - 1 - pass);
+        buf.extend(points.iter().map(|p| (p.0 >> shift) as u8));
+    }
+    Ok(buf)
+}
+
+/// Decodes a (possibly truncated) level-of-detail buffer.
+///
+/// Vertices whose index is only partially known are placed at the center of the
+/// curve cell given by the known prefix, clamped to valid longitudes and
+/// latitudes. Fails unless the first [`MIN_LOD_PASSES`] passes are complete.
+pub fn decode_lod(data: &[u8]) -> Result<LodGeometry, DecodeError> {
+    let (layout, read): (Layout, usize) =
+        bincode::decode_from_slice(data, limited(config::standard()))?;
+    let body = &data[read..];
+    let n = layout.num_points();
+    let required = n.saturating_mul(MIN_LOD_PASSES);
+    if body.len() < required {
+        return Err(DecodeError::UnexpectedEnd {
+            additional: required - body.len(),
+        });
+    }
+    let passes = body
+        .len()
+        .checked_div(n)
+        .unwrap_or(LOD_PASSES)
+        .min(LOD_PASSES);
+
+    let points = (0..n)
+        .map(|i| {
+            let mut key = 0u64;
+            let mut known = 0;
+            while known < LOD_PASSES {
+                match body.get(known * n + i) {
+                    Some(b) => key = (key << 8) | *b as u64,
+                    None => break,
+                }
+                known += 1;
+            }
+            if known == LOD_PASSES {
+                HilbertPoint(key)
+            } else {
+                clamp_to_range(cell_center(key, known))
+            }
+        })
+        .collect();
+
+    Ok(LodGeometry {
+        geometry: rebuild(&layout, points),
+        passes,
+    })
+}
+
+/// Returns the point at the center of the curve cell addressed by the `known` most
+/// significant bytes of an index.
+fn cell_center(prefix: u64, known: usize) -> HilbertPoint {
+    // Each known byte resolves 4 bits of both axes.
+    let free_bits = 32 - 4 * known as u32;
+    let (x, y): (u32, u32) = h2xy(prefix.checked_shl(2 * free_bits).unwrap_or(0), 32);
+    let mask = (!0u32).checked_shl(free_bits).unwrap_or(0);
+    let half = 1u32 << (free_bits - 1);
+    HilbertPoint(xy2h((x & mask) | half, (y & mask) | half, 32))
+}
+
+/// Moves a point into the longitude and latitude range. Cell centers can fall
+/// outside it, as only the top bits of the mantissa are known.
+fn clamp_to_range(p: HilbertPoint) -> HilbertPoint {
+    let c = decode_coord(p);
+    if (-180.0..=180.0).contains(&c.x) && (-90.0..=90.0).contains(&c.y) {
+        return p;
+    }
+    encode_coord(Coord {
+        x: c.x.clamp(-180.0, 180.0),
+        y: c.y.clamp(-90.0, 90.0),
+    })
+}
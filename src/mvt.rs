@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+
+use bincode::error::EncodeError;
+use geo_types::{Coord, Rect};
+
+use crate::tile::{lonlat_to_tile_units, tile_units_to_lonlat, MAX_ZOOM};
+use crate::{decode_coord, HilbertGeometry, HilbertPoint};
+
+/// Slippy map tile coordinate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileCoord {
+    pub z: u8,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl TileCoord {
+    pub fn new(z: u8, x: u32, y: u32) -> Self {
+        Self { z, x, y }
+    }
+}
+
+/// Feature property value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MvtValue {
+    String(String),
+    Float(f32),
+    Double(f64),
+    Int(i64),
+    Uint(u64),
+    Bool(bool),
+}
+
+/// Builder for a single Mapbox Vector Tile layer.
+///
+/// Geometries are clipped to the tile (plus a buffer), projected to Web Mercator
+/// tile coordinates and written as an MVT v2 protobuf layer.
+pub struct MvtLayer {
+    name: String,
+    tile: TileCoord,
+    extent: u32,
+    buffer: u32,
+    keys: Vec<String>,
+    key_index: HashMap<String, u32>,
+    values: Vec<Vec<u8>>,
+    value_index: HashMap<Vec<u8>, u32>,
+    features: Vec<Vec<u8>>,
+}
+
+/// Checks that `tile` exists at its zoom and that `extent` is not zero.
+fn check_tile(tile: TileCoord, extent: u32) -> Result<(), EncodeError> {
+    if tile.z > MAX_ZOOM {
+        return Err(EncodeError::OtherString(format!(
+            "zoom {} above {MAX_ZOOM}",
+            tile.z
+        )));
+    }
+    let n = 1u64 << tile.z;
+    if tile.x as u64 >= n || tile.y as u64 >= n {
+        return Err(EncodeError::OtherString(format!(
+            "tile {}/{}/{} out of range",
+            tile.z, tile.x, tile.y
+        )));
+    }
+    if extent == 0 {
+        return Err(EncodeError::Other("tile extent must be positive"));
+    }
+    Ok(())
+}
+
+impl MvtLayer {
+    /// Fails if `tile.z` is above [`MAX_ZOOM`], or `tile.x` or `tile.y` is
+    /// not below `2^z`.
+    pub fn new(name: &str, tile: TileCoord) -> Result<Self, EncodeError> {
+        let extent = 4096;
+        check_tile(tile, extent)?;
+        Ok(Self {
+            name: name.to_string(),
+            tile,
+            extent,
+            buffer: 64,
+            keys: vec![],
+            key_index: HashMap::new(),
+            values: vec![],
+            value_index: HashMap::new(),
+            features: vec![],
+        })
+    }
+
+    /// Sets the tile extent (default 4096). Fails if `extent` is zero.
+    pub fn with_extent(mut self, extent: u32) -> Result<Self, EncodeError> {
+        check_tile(self.tile, extent)?;
+        self.extent = extent;
+        Ok(self)
+    }
+
+    /// Sets the clipping buffer around the tile in extent units (default 64).
+    pub fn with_buffer(mut self, buffer: u32) -> Self {
+        self.buffer = buffer;
+        self
+    }
+
+    /// Number of features added to the layer.
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    /// Adds a feature to the layer. Returns `false` if the geometry falls outside
+    /// of the tile and was skipped.
+    pub fn add_feature(
+        &mut self,
+        id: Option<u64>,
+        geom: &HilbertGeometry,
+        properties: &[(&str, MvtValue)],
+    ) -> bool {
+        let Some(clipped) = geom.clip_to_rect(&self.clip_bounds()) else {
+            return false;
+        };
+        let Some((geom_type, commands)) = self.encode_geometry(&clipped) else {
+            return false;
+        };
+
+        let tags: Vec<u32> = properties
+            .iter()
+            .flat_map(|(k, v)| [self.key(k), self.value(v)])
+            .collect();
+
+        let mut feature = vec![];
+        if let Some(id) = id {
+            write_key(&mut feature, 1, VARINT);
+            write_varint(&mut feature, id);
+        }
+        if !tags.is_empty() {
+            write_packed(&mut feature, 2, &tags);
+        }
+        write_key(&mut feature, 3, VARINT);
+        write_varint(&mut feature, geom_type);
+        write_packed(&mut feature, 4, &commands);
+        self.features.push(feature);
+        true
+    }
+
+    /// Encodes the layer as a complete MVT tile containing only this layer.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut layer = vec![];
+        write_key(&mut layer, 15, VARINT);
+        write_varint(&mut layer, 2);
+        write_bytes(&mut layer, 1, self.name.as_bytes());
+        for feature in &self.features {
+            write_bytes(&mut layer, 2, feature);
+        }
+        for key in &self.keys {
+            write_bytes(&mut layer, 3, key.as_bytes());
+        }
+        for value in &self.values {
+            write_bytes(&mut layer, 4, value);
+        }
+        write_key(&mut layer, 5, VARINT);
+        write_varint(&mut layer, self.extent as u64);
+
+        let mut tile = vec![];
+        write_bytes(&mut tile, 3, &layer);
+        tile
+    }
+
+    fn key(&mut self, key: &str) -> u32 {
+        if let Some(i) = self.key_index.get(key) {
+            return *i;
+        }
+        let i = self.keys.len() as u32;
+        self.keys.push(key.to_string());
+        self.key_index.insert(key.to_string(), i);
+        i
+    }
+
+    fn value(&mut self, value: &MvtValue) -> u32 {
+        let encoded = encode_value(value);
+        if let Some(i) = self.value_index.get(&encoded) {
+            return *i;
+        }
+        let i = self.values.len() as u32;
+        self.values.push(encoded.clone());
+        self.value_index.insert(encoded, i);
+        i
+    }
+
+    /// Tile bounds including the buffer, in lon/lat.
+    fn clip_bounds(&self) -> Rect {
+        let b = self.buffer as f64 / self.extent as f64;
+        let (x, y) = (self.tile.x as f64, self.tile.y as f64);
+        Rect::new(
+            tile_units_to_lonlat(
+                Coord {
+                    x: x - b,
+                    y: y + 1.0 + b,
+                },
+                self.tile.z,
+            ),
+            tile_units_to_lonlat(
+                Coord {
+                    x: x + 1.0 + b,
+                    y: y - b,
+                },
+                self.tile.z,
+            ),
+        )
+    }
+
+    /// Projects a lon/lat coordinate into tile-local integer coordinates.
+    fn project(&self, c: Coord) -> (i64, i64) {
+        let w = lonlat_to_tile_units(c, self.tile.z);
+        let extent = self.extent as f64;
+        (
+            ((w.x - self.tile.x as f64) * extent).round() as i64,
+            ((w.y - self.tile.y as f64) * extent).round() as i64,
+        )
+    }
+
+    fn project_all(&self, points: &[HilbertPoint]) -> Vec<(i64, i64)> {
+        let mut out: Vec<(i64, i64)> = points
+            .iter()
+            .map(|p| self.project(decode_coord(*p)))
+            .collect();
+        out.dedup();
+        out
+    }
+
+    fn encode_geometry(&self, geom: &HilbertGeometry) -> Option<(u64, Vec<u32>)> {
+        let mut enc = CommandEncoder::default();
+        let geom_type = match geom {
+            HilbertGeometry::Point(p) => {
+                enc.points(&self.project_all(&[*p]));
+                POINT
+            }
+            HilbertGeometry::MultiPoint(ps) => {
+                enc.points(
+                    &ps.iter()
+                        .map(|p| self.project(decode_coord(*p)))
+                        .collect::<Vec<_>>(),
+                );
+                POINT
+            }
+            HilbertGeometry::LineString(ps) => {
+                enc.line(&self.project_all(ps));
+                LINESTRING
+            }
+            HilbertGeometry::MultiLineString(lines) => {
+                for line in lines {
+                    enc.line(&self.project_all(line));
+                }
+                LINESTRING
+            }
+            HilbertGeometry::Polygon(rings) => {
+                self.encode_polygon(&mut enc, rings);
+                POLYGON
+            }
+            HilbertGeometry::MultiPolygon(polys) => {
+                for rings in polys {
+                    self.encode_polygon(&mut enc, rings);
+                }
+                POLYGON
+            }
+        };
+        (!enc.commands.is_empty()).then_some((geom_type, enc.commands))
+    }
+
+    fn encode_polygon(&self, enc: &mut CommandEncoder, rings: &[Vec<HilbertPoint>]) {
+        for (i, ring) in rings.iter().enumerate() {
+            let mut ring = self.project_all(ring);
+            if ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            if ring.len() < 3 {
+                // A collapsed exterior drops the whole polygon.
+                if i == 0 {
+                    return;
+                }
+                continue;
+            }
+            // Exterior rings have positive area in tile coordinates (y down), holes negative.
+            let area = signed_area(&ring);
+            if area == 0 {
+                // Holes without an exterior are invalid, as above.
+                if i == 0 {
+                    return;
+                }
+                continue;
+            }
+            if (i == 0) != (area > 0) {
+                ring.reverse();
+            }
+            enc.ring(&ring);
+        }
+    }
+}
+
+/// Encodes geometries into an MVT tile with a single layer. Fails if
+/// `tile.z` is above [`MAX_ZOOM`], or `tile.x` or `tile.y` is not below `2^z`.
+pub fn encode_mvt_layer(
+    name: &str,
+    tile: TileCoord,
+    geoms: &[HilbertGeometry],
+) -> Result<Vec<u8>, EncodeError> {
+    let mut layer = MvtLayer::new(name, tile)?;
+    for geom in geoms {
+        layer.add_feature(None, geom, &[]);
+    }
+    Ok(layer.encode())
+}
+
+const POINT: u64 = 1;
+const LINESTRING: u64 = 2;
+const POLYGON: u64 = 3;
+
+const MOVE_TO: u32 = 1;
+const LINE_TO: u32 = 2;
+const CLOSE_PATH: u32 = 7;
+
+#[derive(Default)]
+struct CommandEncoder {
+    commands: Vec<u32>,
+    cursor: (i64, i64),
+}
+
+impl CommandEncoder {
+    fn command(&mut self, id: u32, count: usize) {
+        self.commands.push((id & 0x7) | ((count as u32) << 3));
+    }
+
+    fn params(&mut self, points: &[(i64, i64)]) {
+        for &(x, y) in points {
+            self.commands.push(zigzag(x - self.cursor.0));
+            self.commands.push(zigzag(y - self.cursor.1));
+            self.cursor = (x, y);
+        }
+    }
+
+    fn points(&mut self, points: &[(i64, i64)]) {
+        if points.is_empty() {
+            return;
+        }
+        self.command(MOVE_TO, points.len());
+        self.params(points);
+    }
+
+    fn line(&mut self, points: &[(i64, i64)]) {
+        if points.len() < 2 {
+            return;
+        }
+        self.command(MOVE_TO, 1);
+        self.params(&points[..1]);
+        self.command(LINE_TO, points.len() - 1);
+        self.params(&points[1..]);
+    }
+
+    fn ring(&mut self, points: &[(i64, i64)]) {
+        self.line(points);
+        self.command(CLOSE_PATH, 1);
+    }
+}
+
+fn signed_area(ring: &[(i64, i64)]) -> i64 {
+    let n = ring.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (ring[i], ring[(i + 1) % n]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum()
+}
+
+fn zigzag(v: i64) -> u32 {
+    ((v << 1) ^ (v >> 63)) as u32
+}
+
+const VARINT: u8 = 0;
+const FIXED64: u8 = 1;
+const BYTES: u8 = 2;
+const FIXED32: u8 = 5;
+
+fn write_varint(buf: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        buf.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+fn write_key(buf: &mut Vec<u8>, field: u32, wire_type: u8) {
+    write_varint(buf, ((field << 3) | wire_type as u32) as u64);
+}
+
+fn write_bytes(buf: &mut Vec<u8>, field: u32, data: &[u8]) {
+    write_key(buf, field, BYTES);
+    write_varint(buf, data.len() as u64);
+    buf.extend_from_slice(data);
+}
+
+fn write_packed(buf: &mut Vec<u8>, field: u32, values: &[u32]) {
+    let mut packed = vec![];
+    for v in values {
+        write_varint(&mut packed, *v as u64);
+    }
+    write_bytes(buf, field, &packed);
+}
+
+fn encode_value(value: &MvtValue) -> Vec<u8> {
+    let mut buf = vec![];
+    match value {
+        MvtValue::String(s) => write_bytes(&mut buf, 1, s.as_bytes()),
+        MvtValue::Float(f) => {
+            write_key(&mut buf, 2, FIXED32);
+            buf.extend_from_slice(&f.to_le_bytes());
+        }
+        MvtValue::Double(d) => {
+            write_key(&mut buf, 3, FIXED64);
+            buf.extend_from_slice(&d.to_le_bytes());
+        }
+        MvtValue::Int(i) => {
+            write_key(&mut buf, 4, VARINT);
+            write_varint(&mut buf, *i as u64);
+        }
+        MvtValue::Uint(u) => {
+            write_key(&mut buf, 5, VARINT);
+            write_varint(&mut buf, *u);
+        }
+        MvtValue::Bool(b) => {
+            write_key(&mut buf, 7, VARINT);
+            write_varint(&mut buf, *b as u64);
+        }
+    }
+    buf
+}
@@ -0,0 +1,118 @@
+//! OpenStreetMap PBF ingestion.
+//!
+//! Nodes with tags become points and ways become linestrings, with the OSM
+//! tags as string properties and the element id as an `osm_id` property.
+//! Way vertices are resolved from node locations kept in memory as encoded
+//! indices, 8 bytes per node, so nodes must come before the ways that use
+//! them, as they do in sorted extracts.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use geo_types::Coord;
+use osmpbf::{Element, ElementReader};
+
+use crate::collection::{HilbertFeature, PropertyValue};
+use crate::{decode_coord, encode_coord, HilbertGeometry, HilbertPoint};
+
+/// Turns a stream of OSM nodes and ways into encoded features.
+#[derive(Debug, Default)]
+pub struct OsmConverter {
+    nodes: HashMap<i64, HilbertPoint>,
+    skipped_ways: usize,
+}
+
+fn feature<'a>(
+    geometry: HilbertGeometry,
+    id: i64,
+    tags: impl Iterator<Item = (&'a str, &'a str)>,
+) -> HilbertFeature {
+    let mut feature = HilbertFeature::new(geometry).with_property("osm_id", PropertyValue::Int(id));
+    for (k, v) in tags {
+        feature = feature.with_property(k, PropertyValue::String(v.to_string()));
+    }
+    feature
+}
+
+impl OsmConverter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the location of a node. Returns a point feature if the node
+    /// has tags.
+    pub fn add_node<'a>(
+        &mut self,
+        id: i64,
+        location: Coord,
+        tags: impl Iterator<Item = (&'a str, &'a str)>,
+    ) -> Option<HilbertFeature> {
+        let point = encode_coord(location);
+        self.nodes.insert(id, point);
+        let mut tags = tags.peekable();
+        tags.peek()?;
+        Some(feature(HilbertGeometry::Point(point), id, tags))
+    }
+
+    /// Returns a linestring feature for a way, or `None` if any of its nodes
+    /// are unknown, e.g. outside the extract.
+    pub fn add_way<'a>(
+        &mut self,
+        id: i64,
+        refs: impl Iterator<Item = i64>,
+        tags: impl Iterator<Item = (&'a str, &'a str)>,
+    ) -> Option<HilbertFeature> {
+        let points: Option<Vec<HilbertPoint>> = refs.map(|r| self.nodes.get(&r).copied()).collect();
+        let Some(points) = points.filter(|ps| !ps.is_empty()) else {
+            self.skipped_ways += 1;
+            return None;
+        };
+        Some(feature(HilbertGeometry::LineString(points), id, tags))
+    }
+
+    /// Location of a node seen so far.
+    pub fn node(&self, id: i64) -> Option<Coord> {
+        self.nodes.get(&id).map(|p| decode_coord(*p))
+    }
+
+    /// Number of ways dropped because of missing nodes.
+    pub fn skipped_ways(&self) -> usize {
+        self.skipped_ways
+    }
+}
+
+/// Streams the nodes and ways of an OSM PBF file into `sink`. Relations are
+/// ignored. Returns the converter, e.g. to check
+/// [`skipped_ways`](OsmConverter::skipped_ways).
+pub fn read_osm_pbf(
+    reader: impl Read + Send,
+    mut sink: impl FnMut(HilbertFeature),
+) -> Result<OsmConverter, osmpbf::Error> {
+    let mut converter = OsmConverter::new();
+    ElementReader::new(reader).for_each(|element| {
+        let feature = match element {
+            Element::Node(n) => converter.add_node(
+                n.id(),
+                Coord {
+                    x: n.lon(),
+                    y: n.lat(),
+                },
+                n.tags(),
+            ),
+            Element::DenseNode(n) => converter.add_node(
+                n.id(),
+                Coord {
+                    x: n.lon(),
+                    y: n.lat(),
+                },
+                n.tags(),
+            ),
+            Element::Way(w) => converter.add_way(w.id(), w.refs(), w.tags()),
+            Element::Relation(_) => None,
+        };
+        if let Some(feature) = feature {
+            sink(feature);
+        }
+    })?;
+    Ok(converter)
+}
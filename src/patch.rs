@@ -0,0 +1,91 @@
+//! Compact patches between two versions of an encoded geometry.
+//!
+//! A patch keeps the vertices shared at the start and end of the flattened
+//! vertex sequences and stores the rest as differences from the old vertex at
+//! the same position. Nearby points have nearby indices, so moving a few
+//! vertices or inserting points produces a patch of a few bytes.
+
+use bincode::{error::DecodeError, Decode, Encode};
+
+use crate::layout::{flatten, rebuild, Layout};
+use crate::{HilbertGeometry, HilbertPoint};
+
+/// Difference between two versions of a geometry, created by
+/// [`HilbertGeometry::diff`] and applied with [`HilbertGeometry::apply_patch`].
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode)]
+pub struct HilbertPatch {
+    /// Layout of the newer geometry, `None` if unchanged.
+    layout: Option<Layout>,
+    /// Number of leading vertices kept from the older geometry.
+    prefix: u32,
+    /// Number of trailing vertices kept from the older geometry.
+    suffix: u32,
+    /// Replacement vertices between the prefix and suffix, as differences
+    /// from the old vertex at the same position, or from the previous
+    /// replacement vertex past the end of the old ones.
+    deltas: Vec<i64>,
+}
+
+impl HilbertPatch {
+    /// Returns `true` if the patch leaves the geometry unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.layout.is_none() && self.deltas.is_empty()
+    }
+}
+
+impl HilbertGeometry {
+    /// Computes a patch that turns `self` into `newer`.
+    pub fn diff(&self, newer: &HilbertGeometry) -> HilbertPatch {
+        let (old_layout, old) = flatten(self);
+        let (new_layout, new) = flatten(newer);
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let old_middle = &old[prefix..old.len() - suffix];
+        let mut prev = prefix.checked_sub(1).map_or(0, |i| new[i].0);
+        let deltas = new[prefix..new.len() - suffix]
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let base = old_middle.get(i).map_or(prev, |o| o.0);
+                prev = p.0;
+                p.0.wrapping_sub(base) as i64
+            })
+            .collect();
+        HilbertPatch {
+            layout: (old_layout != new_layout).then_some(new_layout),
+            prefix: prefix as u32,
+            suffix: suffix as u32,
+            deltas,
+        }
+    }
+
+    /// Applies a patch created by [`diff`](Self::diff) against this geometry.
+    ///
+    /// Fails if the patch doesn't fit the geometry's vertices.
+    pub fn apply_patch(&self, patch: &HilbertPatch) -> Result<HilbertGeometry, DecodeError> {
+        let (layout, old) = flatten(self);
+        let (prefix, suffix) = (patch.prefix as usize, patch.suffix as usize);
+        if prefix + suffix > old.len() {
+            return Err(DecodeError::Other("patch doesn't match geometry"));
+        }
+        let layout = patch.layout.as_ref().unwrap_or(&layout);
+        if prefix + patch.deltas.len() + suffix != layout.num_points() {
+            return Err(DecodeError::Other("patch doesn't match layout"));
+        }
+        let old_middle = &old[prefix..old.len() - suffix];
+        let mut points = old[..prefix].to_vec();
+        let mut prev = points.last().map_or(0, |p| p.0);
+        for (i, delta) in patch.deltas.iter().enumerate() {
+            let base = old_middle.get(i).map_or(prev, |o| o.0);
+            prev = base.wrapping_add(*delta as u64);
+            points.push(HilbertPoint(prev));
+        }
+        points.extend_from_slice(&old[old.len() - suffix..]);
+        Ok(rebuild(layout, points))
+    }
+}
@@ -0,0 +1,736 @@
+//! Self-describing payloads with a choice of vertex representation.
+//!
+//! A payload starts with a tag byte naming its [`Encoding`], followed by the
+//! encoded body. The tag has the high bit set, so payloads can be told apart
+//! from untagged bincode geometries, which start with a variant index below 6.
+//! Bits 4-6 hold the payload format version and bits 0-2 the encoding. Bit 3
+//! marks payloads with a [`PayloadMetadata`] section between the tag and the
+//! body.
+
+use std::fmt;
+
+use bincode::{
+    config,
+    error::{DecodeError, EncodeError},
+    Decode, Encode,
+};
+use geo_types::{Coord, Geometry, LineString, MultiLineString, MultiPolygon, Point, Polygon};
+use half::f16;
+
+use crate::decode::{check_finite, limited};
+use crate::kv::{make_key, point_from_key, KEY_LEN};
+use crate::layout::{flatten, Layout};
+use crate::precise::HilbertPoint128;
+use crate::{
+    decode_coord, decode_geometry, encode_coord, encode_geometry, GeometryDecodeError,
+    HilbertGeometry, HilbertPoint,
+};
+
+const TAG_MARKER: u8 = 0x80;
+const TAG_METADATA: u8 = 0x08;
+const META_RING_FLAGS: u8 = 0x01;
+const META_CRS: u8 = 0x02;
+/// Version of the payload format written by this build, stored in bits 4-6
+/// of the tag.
+pub const FORMAT_VERSION: u8 = 0;
+
+/// Steps per degree of the [`Encoding::Quantized`] representation.
+pub const QUANTIZED_SCALE: f64 = 1e7;
+
+/// Vertex representation of a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// Hilbert indices, as written by [`HilbertSerializer`](crate::HilbertSerializer).
+    Hilbert = 0,
+    /// Hilbert indices stored as differences from the previous vertex.
+    HilbertDelta = 1,
+    /// Original `f64` coordinates, lossless.
+    Raw = 2,
+    /// Coordinates rounded to steps of 1/[`QUANTIZED_SCALE`] degrees, stored
+    /// as differences from the previous vertex.
+    Quantized = 3,
+    /// Like [`Raw`](Self::Raw), preceded by the Hilbert sort key of the
+    /// geometry as 8 big-endian bytes, the same as [`make_key`]. Lets
+    /// lossless payloads be ordered along the curve via [`payload_key`].
+    RawKeyed = 4,
+    /// 128-bit [`HilbertPoint128`] indices, finer than an `f64` coordinate.
+    Hilbert128 = 5,
+    /// Coordinates as half-precision floats, 4 bytes per vertex. Accurate to
+    /// about 0.1 degrees, for heatmaps and telemetry. Coordinates beyond
+    /// about ±65504, e.g. projected ones, fail to encode.
+    Half = 6,
+}
+
+impl Encoding {
+    /// Encodings tried by [`EncodingMode::Auto`], in order of preference.
+    const AUTO: [Encoding; 4] = [
+        Encoding::Hilbert,
+        Encoding::HilbertDelta,
+        Encoding::Quantized,
+        Encoding::Raw,
+    ];
+
+    fn from_tag(tag: u8) -> Option<Encoding> {
+        Self::AUTO
+            .into_iter()
+            .chain([Encoding::RawKeyed, Encoding::Hilbert128, Encoding::Half])
+            .find(|e| *e as u8 == tag)
+    }
+}
+
+/// What the encoder changed about a polygon ring before storing it, so
+/// that [`restore_rings`] can undo it.
+///
+/// Whether a ring was closed is not recorded: `geo-types` closes polygon
+/// rings on construction, so an open ring never reaches the encoder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RingFlags {
+    /// The vertex order was reversed.
+    pub reversed: bool,
+    /// Index in the stored ring of the vertex the original ring started
+    /// at, 0 if the ring wasn't rotated.
+    pub start: u32,
+}
+
+impl RingFlags {
+    fn to_bits(self) -> u8 {
+        // Bit 1 is unused.
+        self.reversed as u8 | ((self.start != 0) as u8) << 2
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        Self {
+            reversed: bits & 1 != 0,
+            start: 0,
+        }
+    }
+
+    fn rotated(bits: u8) -> bool {
+        bits & 4 != 0
+    }
+}
+
+/// Rotates a closed ring to start at vertex `start`.
+fn rotate_ring(ring: &mut LineString, start: usize) {
+    if ring.0.len() < 2 {
+        return;
+    }
+    ring.0.pop();
+    let n = ring.0.len();
+    ring.0.rotate_left(start % n);
+    ring.0.push(ring.0[0]);
+}
+
+/// Number of polygon rings of a geometry, holes included.
+fn num_rings(geom: &Geometry) -> usize {
+    match geom {
+        Geometry::Polygon(p) => 1 + p.interiors().len(),
+        Geometry::MultiPolygon(mp) => mp.0.iter().map(|p| 1 + p.interiors().len()).sum(),
+        _ => 0,
+    }
+}
+
+/// Twice the signed area of a ring, positive if counter-clockwise.
+fn signed_area(ring: &LineString) -> f64 {
+    ring.lines()
+        .map(|l| l.start.x * l.end.y - l.end.x * l.start.y)
+        .sum()
+}
+
+/// Orients exterior rings counter-clockwise and holes clockwise, as in
+/// RFC 7946.
+///
+/// Returns the normalized geometry with the flags of every ring, in the
+/// order of their polygons, exterior ring first. Other geometry types are
+/// returned as they are, with no flags.
+pub fn orient_rings(geom: &Geometry) -> (Geometry, Vec<RingFlags>) {
+    let mut flags = vec![];
+    let mut orient = |ring: &LineString, ccw: bool| {
+        let mut ring = ring.clone();
+        let area = signed_area(&ring);
+        let reversed = area != 0.0 && (area > 0.0) != ccw;
+        if reversed {
+            ring.0.reverse();
+        }
+        flags.push(RingFlags { reversed, start: 0 });
+        ring
+    };
+    let mut polygon = |p: &Polygon| {
+        let exterior = orient(p.exterior(), true);
+        let interiors = p.interiors().iter().map(|r| orient(r, false)).collect();
+        Polygon::new(exterior, interiors)
+    };
+    let oriented = match geom {
+        Geometry::Polygon(p) => Geometry::Polygon(polygon(p)),
+        Geometry::MultiPolygon(mp) => {
+            Geometry::MultiPolygon(MultiPolygon(mp.0.iter().map(&mut polygon).collect()))
+        }
+        other => other.clone(),
+    };
+    (oriented, flags)
+}
+
+/// Like [`orient_rings`], then rotates every ring to start at the vertex
+/// with the smallest curve index, so rings tracing the same vertices from
+/// different starts are stored the same.
+pub fn canonicalize_rings(geom: &Geometry) -> (Geometry, Vec<RingFlags>) {
+    let (mut canonical, mut flags) = orient_rings(geom);
+    let mut flags_iter = flags.iter_mut();
+    let mut rotate = |ring: &mut LineString| {
+        let n = ring.0.len().saturating_sub(1);
+        let start = (0..n).min_by_key(|i| encode_coord(ring.0[*i])).unwrap_or(0);
+        rotate_ring(ring, start);
+        if let Some(f) = flags_iter.next() {
+            f.start = ((n - start) % n.max(1)) as u32;
+        }
+    };
+    let mut polygon = |p: &mut Polygon| {
+        p.exterior_mut(|r| rotate(r));
+        p.interiors_mut(|rs| rs.iter_mut().for_each(&mut rotate));
+    };
+    match &mut canonical {
+        Geometry::Polygon(p) => polygon(p),
+        Geometry::MultiPolygon(mp) => mp.0.iter_mut().for_each(polygon),
+        _ => {}
+    }
+    (canonical, flags)
+}
+
+/// Undoes [`orient_rings`] and [`canonicalize_rings`], rotating and
+/// reversing the flagged rings back to their original vertex order.
+pub fn restore_rings(geom: &Geometry, flags: &[RingFlags]) -> Geometry {
+    let mut flags = flags.iter();
+    let mut restore = |ring: &LineString| {
+        let mut ring = ring.clone();
+        let flags = flags.next().copied().unwrap_or_default();
+        rotate_ring(&mut ring, flags.start as usize);
+        if flags.reversed {
+            ring.0.reverse();
+        }
+        ring
+    };
+    let mut polygon = |p: &Polygon| {
+        let exterior = restore(p.exterior());
+        Polygon::new(exterior, p.interiors().iter().map(&mut restore).collect())
+    };
+    match geom {
+        Geometry::Polygon(p) => Geometry::Polygon(polygon(p)),
+        Geometry::MultiPolygon(mp) => {
+            Geometry::MultiPolygon(MultiPolygon(mp.0.iter().map(&mut polygon).collect()))
+        }
+        other => other.clone(),
+    }
+}
+
+/// Coordinate reference system of a payload's coordinates.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Decode, Encode)]
+pub enum Crs {
+    /// An EPSG code, e.g. 4326 for WGS 84.
+    Epsg(u32),
+    /// Any other reference, e.g. a CRS URI or WKT definition.
+    Named(String),
+}
+
+impl fmt::Display for Crs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Crs::Epsg(code) => write!(f, "EPSG:{code}"),
+            Crs::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+/// Optional data stored with a payload, see
+/// [`encode_payload_with_metadata`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PayloadMetadata {
+    /// Changes made to the polygon rings, one entry per ring, or empty.
+    pub ring_flags: Vec<RingFlags>,
+    /// Reference system of the coordinates. Payloads without one are
+    /// assumed to be longitudes and latitudes on WGS 84.
+    pub crs: Option<Crs>,
+}
+
+impl PayloadMetadata {
+    /// Writes the metadata section: a byte marking the fields present, then
+    /// the fields. Returns `None` if there is nothing to store.
+    fn encode(&self, geom: &Geometry) -> Result<Option<Vec<u8>>, EncodeError> {
+        let cfg = config::standard();
+        let flagged = self.ring_flags.iter().any(|f| *f != RingFlags::default());
+        if !flagged && self.crs.is_none() {
+            return Ok(None);
+        }
+        let mut buf = vec![0];
+        if flagged {
+            if self.ring_flags.len() != num_rings(geom) {
+                return Err(EncodeError::Other("ring flag count doesn't match geometry"));
+            }
+            buf[0] |= META_RING_FLAGS;
+            let bits: Vec<u8> = self.ring_flags.iter().map(|f| f.to_bits()).collect();
+            buf.extend(bincode::encode_to_vec(bits, cfg)?);
+            // Start offsets follow for rotated rings only.
+            let starts: Vec<u32> = self
+                .ring_flags
+                .iter()
+                .map(|f| f.start)
+                .filter(|s| *s != 0)
+                .collect();
+            if !starts.is_empty() {
+                buf.extend(bincode::encode_to_vec(starts, cfg)?);
+            }
+        }
+        if let Some(crs) = &self.crs {
+            buf[0] |= META_CRS;
+            buf.extend(bincode::encode_to_vec(crs, cfg)?);
+        }
+        Ok(Some(buf))
+    }
+
+    /// Reads a metadata section, returning it with its length in bytes.
+    fn decode(data: &[u8]) -> Result<(Self, usize), DecodeError> {
+        let cfg = decode_config();
+        let present = *data
+            .first()
+            .ok_or(DecodeError::UnexpectedEnd { additional: 1 })?;
+        if present & !(META_RING_FLAGS | META_CRS) != 0 {
+            return Err(DecodeError::Other("unknown payload metadata"));
+        }
+        let mut read = 1;
+        let mut metadata = PayloadMetadata::default();
+        if present & META_RING_FLAGS != 0 {
+            let (bits, n): (Vec<u8>, usize) = bincode::decode_from_slice(&data[read..], cfg)?;
+            read += n;
+            metadata.ring_flags = bits.iter().map(|b| RingFlags::from_bits(*b)).collect();
+            if bits.iter().any(|b| RingFlags::rotated(*b)) {
+                let (starts, n): (Vec<u32>, usize) =
+                    bincode::decode_from_slice(&data[read..], cfg)?;
+                read += n;
+                if bits.iter().filter(|b| RingFlags::rotated(**b)).count() != starts.len() {
+                    return Err(DecodeError::Other("ring start count doesn't match flags"));
+                }
+                let rotated = bits
+                    .iter()
+                    .zip(&mut metadata.ring_flags)
+                    .filter(|(b, _)| RingFlags::rotated(**b));
+                for ((_, f), start) in rotated.zip(starts) {
+                    f.start = start;
+                }
+            }
+        }
+        if present & META_CRS != 0 {
+            let (crs, n): (Crs, usize) = bincode::decode_from_slice(&data[read..], cfg)?;
+            read += n;
+            metadata.crs = Some(crs);
+        }
+        Ok((metadata, read))
+    }
+}
+
+/// How [`encode_payload`] picks an encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingMode {
+    Fixed(Encoding),
+    /// Tries [`Encoding::Hilbert`], [`Encoding::HilbertDelta`],
+    /// [`Encoding::Quantized`] and [`Encoding::Raw`] and keeps the smallest,
+    /// with no error tolerance applied. Only Raw is lossless: the Hilbert
+    /// encodings round coordinates to `f32`, about 1e-5 degrees at the
+    /// antimeridian, and Quantized to 1/[`QUANTIZED_SCALE`] degrees.
+    /// Quantized is skipped for coordinates it can't represent, and
+    /// [`Encoding::Half`] is never picked.
+    Auto,
+}
+
+/// Coordinates of a geometry in the vertex order of its [`Layout`].
+fn flat_coords(geom: &Geometry) -> Vec<Coord> {
+    let rings = |p: &Polygon| {
+        std::iter::once(p.exterior())
+            .chain(p.interiors())
+            .flat_map(|r| r.0.clone())
+            .collect::<Vec<_>>()
+    };
+    match geom {
+        Geometry::Point(p) => vec![p.0],
+        Geometry::LineString(ls) => ls.0.clone(),
+        Geometry::Polygon(p) => rings(p),
+        Geometry::MultiPoint(mp) => mp.0.iter().map(|p| p.0).collect(),
+        Geometry::MultiLineString(mls) => mls.0.iter().flat_map(|ls| ls.0.clone()).collect(),
+        Geometry::MultiPolygon(mp) => mp.0.iter().flat_map(rings).collect(),
+        _ => vec![],
+    }
+}
+
+/// Configuration for decoding payload bodies and metadata.
+fn decode_config() -> impl config::Config {
+    limited(config::standard())
+}
+
+/// Rebuilds a geometry from its layout and coordinates in layout order.
+fn rebuild_geometry(layout: &Layout, coords: Vec<Coord>) -> Result<Geometry, DecodeError> {
+    if coords.len() != layout.num_points() {
+        return Err(DecodeError::Other("vertex count doesn't match layout"));
+    }
+    if !coords.iter().all(|c| c.x.is_finite() && c.y.is_finite()) {
+        return Err(DecodeError::Other("non-finite coordinate"));
+    }
+    let mut iter = coords.into_iter();
+    let mut take = |n: &u32| LineString(iter.by_ref().take(*n as usize).collect());
+    let polygon = |rings: Vec<LineString>| {
+        let mut rings = rings.into_iter();
+        Polygon::new(
+            rings.next().unwrap_or_else(|| LineString::new(vec![])),
+            rings.collect(),
+        )
+    };
+    Ok(match layout {
+        Layout::Point => Geometry::Point(Point(take(&1).0[0])),
+        Layout::LineString(n) => Geometry::LineString(take(n)),
+        Layout::MultiPoint(n) => Geometry::MultiPoint(take(n).0.into_iter().map(Point).collect()),
+        Layout::Polygon(rings) => Geometry::Polygon(polygon(rings.iter().map(take).collect())),
+        Layout::MultiLineString(lines) => {
+            Geometry::MultiLineString(MultiLineString(lines.iter().map(take).collect()))
+        }
+        Layout::MultiPolygon(polys) => Geometry::MultiPolygon(MultiPolygon(
+            polys
+                .iter()
+                .map(|rings| polygon(rings.iter().map(&mut take).collect()))
+                .collect(),
+        )),
+    })
+}
+
+/// Whether [`Encoding::Quantized`] can store `c` without saturating. False
+/// for non-finite coordinates.
+fn quantizable(c: &Coord) -> bool {
+    let limit = i64::MAX as f64 / QUANTIZED_SCALE;
+    c.x.abs() < limit && c.y.abs() < limit
+}
+
+/// Whether [`Encoding::Half`] can store `c` without overflowing to
+/// infinity, i.e. within about ±65504. False for non-finite coordinates.
+fn half_representable(c: &Coord) -> bool {
+    f16::from_f64(c.x).is_finite() && f16::from_f64(c.y).is_finite()
+}
+
+fn encode_body(geom: &Geometry, encoding: Encoding) -> Result<Vec<u8>, EncodeError> {
+    let hg = encode_geometry(geom);
+    let cfg = config::standard();
+    match encoding {
+        Encoding::Hilbert => bincode::encode_to_vec(&hg, cfg),
+        Encoding::HilbertDelta => {
+            let (layout, points) = flatten(&hg);
+            let mut prev = 0u64;
+            let deltas: Vec<i64> = points
+                .iter()
+                .map(|p| {
+                    let delta = p.0.wrapping_sub(prev) as i64;
+                    prev = p.0;
+                    delta
+                })
+                .collect();
+            bincode::encode_to_vec((layout, deltas), cfg)
+        }
+        Encoding::Raw => {
+            let coords: Vec<[f64; 2]> = flat_coords(geom).iter().map(|c| [c.x, c.y]).collect();
+            bincode::encode_to_vec((flatten(&hg).0, coords), cfg)
+        }
+        Encoding::RawKeyed => {
+            // Empty geometries have no position and sort first.
+            let mut buf = make_key(&hg).unwrap_or_else(|| vec![0; KEY_LEN]);
+            buf.extend(encode_body(geom, Encoding::Raw)?);
+            Ok(buf)
+        }
+        Encoding::Hilbert128 => {
+            let indices: Vec<u128> = flat_coords(geom)
+                .iter()
+                .map(|c| HilbertPoint128::from_coord(*c).0)
+                .collect();
+            bincode::encode_to_vec((flatten(&hg).0, indices), cfg)
+        }
+        Encoding::Half => {
+            let coords = flat_coords(geom);
+            if !coords.iter().all(half_representable) {
+                return Err(EncodeError::Other(
+                    "coordinate out of range for half encoding",
+                ));
+            }
+            let mut buf = bincode::encode_to_vec(flatten(&hg).0, cfg)?;
+            buf.reserve(coords.len() * 4);
+            for c in &coords {
+                buf.extend(f16::from_f64(c.x).to_le_bytes());
+                buf.extend(f16::from_f64(c.y).to_le_bytes());
+            }
+            Ok(buf)
+        }
+        Encoding::Quantized => {
+            let coords = flat_coords(geom);
+            if !coords.iter().all(quantizable) {
+                return Err(EncodeError::Other(
+                    "coordinate out of range for quantized encoding",
+                ));
+            }
+            let mut prev = [0i64; 2];
+            let deltas: Vec<i64> = coords
+                .iter()
+                .flat_map(|c| {
+                    let q = [
+                        (c.x * QUANTIZED_SCALE).round() as i64,
+                        (c.y * QUANTIZED_SCALE).round() as i64,
+                    ];
+                    let delta = [q[0].wrapping_sub(prev[0]), q[1].wrapping_sub(prev[1])];
+                    prev = q;
+                    delta
+                })
+                .collect();
+            bincode::encode_to_vec((flatten(&hg).0, deltas), cfg)
+        }
+    }
+}
+
+/// Tag byte of a payload with the given encoding.
+pub(crate) fn payload_tag(encoding: Encoding) -> u8 {
+    TAG_MARKER | FORMAT_VERSION << 4 | encoding as u8
+}
+
+/// Encodes a geometry into a tagged payload.
+///
+/// Fails for geometry types without an encoded form (geometry collections,
+/// lines, rects and triangles).
+pub fn encode_payload(geom: &Geometry, mode: EncodingMode) -> Result<Vec<u8>, EncodeError> {
+    encode_payload_with_metadata(geom, mode, &PayloadMetadata::default())
+}
+
+/// Like [`encode_payload`], recording the [`RingFlags`] of every polygon
+/// ring, e.g. from [`orient_rings`], for [`decode_payload_original`].
+///
+/// Without any flag set, or with no flags given, the payload is the same
+/// as from [`encode_payload`]. Otherwise fails unless there is one entry
+/// per ring.
+pub fn encode_payload_with_ring_flags(
+    geom: &Geometry,
+    mode: EncodingMode,
+    flags: &[RingFlags],
+) -> Result<Vec<u8>, EncodeError> {
+    let metadata = PayloadMetadata {
+        ring_flags: flags.to_vec(),
+        ..Default::default()
+    };
+    encode_payload_with_metadata(geom, mode, &metadata)
+}
+
+/// Like [`encode_payload`], storing `metadata` between the tag and the
+/// body. Empty metadata takes no space.
+pub fn encode_payload_with_metadata(
+    geom: &Geometry,
+    mode: EncodingMode,
+    metadata: &PayloadMetadata,
+) -> Result<Vec<u8>, EncodeError> {
+    crate::supported(geom).map_err(EncodeError::Other)?;
+    let section = metadata.encode(geom)?;
+    let encode = |encoding: Encoding| -> Result<Vec<u8>, EncodeError> {
+        let mut buf = vec![payload_tag(encoding)];
+        if let Some(section) = &section {
+            buf[0] |= TAG_METADATA;
+            buf.extend(section);
+        }
+        buf.extend(encode_body(geom, encoding)?);
+        Ok(buf)
+    };
+    match mode {
+        EncodingMode::Fixed(encoding) => encode(encoding),
+        EncodingMode::Auto => {
+            let mut best: Option<Vec<u8>> = None;
+            for encoding in Encoding::AUTO {
+                if encoding == Encoding::Quantized && !flat_coords(geom).iter().all(quantizable) {
+                    continue;
+                }
+                let buf = encode(encoding)?;
+                if best.as_ref().is_none_or(|b| buf.len() < b.len()) {
+                    best = Some(buf);
+                }
+            }
+            Ok(best.expect("at least one encoding"))
+        }
+    }
+}
+
+/// Returns the encoding of a tagged payload.
+pub fn payload_encoding(data: &[u8]) -> Result<Encoding, DecodeError> {
+    let tag = *data
+        .first()
+        .ok_or(DecodeError::UnexpectedEnd { additional: 1 })?;
+    if tag & TAG_MARKER == 0 {
+        return Err(DecodeError::Other("not a tagged payload"));
+    }
+    if (tag >> 4) & 0x07 != FORMAT_VERSION {
+        return Err(DecodeError::Other("unsupported payload version"));
+    }
+    Encoding::from_tag(tag & 0x07).ok_or(DecodeError::Other("unknown payload encoding"))
+}
+
+/// Splits a tagged payload into its encoding, metadata and body.
+fn split_payload(data: &[u8]) -> Result<(Encoding, PayloadMetadata, &[u8]), DecodeError> {
+    let encoding = payload_encoding(data)?;
+    if data[0] & TAG_METADATA == 0 {
+        return Ok((encoding, PayloadMetadata::default(), &data[1..]));
+    }
+    let (metadata, read) = PayloadMetadata::decode(&data[1..])?;
+    Ok((encoding, metadata, &data[1 + read..]))
+}
+
+/// Returns the metadata stored in a payload, empty if there is none.
+pub fn payload_metadata(data: &[u8]) -> Result<PayloadMetadata, DecodeError> {
+    Ok(split_payload(data)?.1)
+}
+
+/// Returns the [`RingFlags`] recorded in a payload, empty if there are
+/// none.
+pub fn payload_ring_flags(data: &[u8]) -> Result<Vec<RingFlags>, DecodeError> {
+    Ok(split_payload(data)?.1.ring_flags)
+}
+
+/// Returns the [`Crs`] stored in a payload, if any.
+pub fn payload_crs(data: &[u8]) -> Result<Option<Crs>, DecodeError> {
+    Ok(split_payload(data)?.1.crs)
+}
+
+/// Returns the sort key stored in an [`Encoding::RawKeyed`] payload, without
+/// decoding the geometry. Other encodings store no key.
+pub fn payload_key(data: &[u8]) -> Result<Option<HilbertPoint>, DecodeError> {
+    let (encoding, _, body) = split_payload(data)?;
+    if encoding != Encoding::RawKeyed {
+        return Ok(None);
+    }
+    point_from_key(body)
+        .map(Some)
+        .ok_or_else(|| DecodeError::UnexpectedEnd {
+            additional: KEY_LEN - body.len(),
+        })
+}
+
+/// Returns whether `data` starts with a payload tag, as opposed to an
+/// untagged bincode geometry.
+pub fn is_tagged(data: &[u8]) -> bool {
+    data.first().is_some_and(|tag| tag & TAG_MARKER != 0)
+}
+
+/// Decodes a payload written by [`encode_payload`], or an untagged bincode
+/// geometry as written by [`HilbertSerializer::new`](crate::HilbertSerializer::new).
+///
+/// Lets data stored before switching to payloads be read alongside new
+/// payloads, and rewritten lazily instead of in one batch. Untagged
+/// geometries are decoded like [`HilbertSerializer::decode`](crate::HilbertSerializer::decode)
+/// does, keeping NaN and infinite coordinates they were written with.
+pub fn decode_payload_lenient(data: &[u8]) -> Result<Geometry, GeometryDecodeError> {
+    if is_tagged(data) {
+        return decode_payload(data);
+    }
+    let hg = HilbertGeometry::decode_bincode_detailed(data, &config::standard())?;
+    Ok(decode_geometry(&hg))
+}
+
+/// Decodes a payload written by [`encode_payload`] or
+/// [`encode_payload_with_ring_flags`]. Rings come out as they were stored;
+/// see [`decode_payload_original`] to undo [`orient_rings`].
+pub fn decode_payload(data: &[u8]) -> Result<Geometry, GeometryDecodeError> {
+    let (encoding, _, body) = split_payload(data)?;
+    let cfg = decode_config();
+    match encoding {
+        Encoding::Hilbert => {
+            let hg = HilbertGeometry::decode_bincode_detailed(body, &config::standard())?;
+            let geom = decode_geometry(&hg);
+            check_finite(&geom)?;
+            Ok(geom)
+        }
+        Encoding::HilbertDelta => {
+            let ((layout, deltas), _): ((Layout, Vec<i64>), _) =
+                bincode::decode_from_slice(body, cfg)?;
+            let mut prev = 0u64;
+            let points = deltas
+                .iter()
+                .map(|d| {
+                    prev = prev.wrapping_add(*d as u64);
+                    decode_coord(HilbertPoint(prev))
+                })
+                .collect();
+            Ok(rebuild_geometry(&layout, points)?)
+        }
+        Encoding::Raw | Encoding::RawKeyed => {
+            let body = match encoding {
+                Encoding::RawKeyed => {
+                    body.get(KEY_LEN..)
+                        .ok_or_else(|| DecodeError::UnexpectedEnd {
+                            additional: KEY_LEN - body.len(),
+                        })?
+                }
+                _ => body,
+            };
+            let ((layout, coords), _): ((Layout, Vec<[f64; 2]>), _) =
+                bincode::decode_from_slice(body, cfg)?;
+            let coords = coords.iter().map(|[x, y]| Coord { x: *x, y: *y }).collect();
+            Ok(rebuild_geometry(&layout, coords)?)
+        }
+        Encoding::Hilbert128 => {
+            let ((layout, indices), _): ((Layout, Vec<u128>), _) =
+                bincode::decode_from_slice(body, cfg)?;
+            let coords = indices
+                .iter()
+                .map(|h| HilbertPoint128(*h).to_coord())
+                .collect();
+            Ok(rebuild_geometry(&layout, coords)?)
+        }
+        Encoding::Half => {
+            let (layout, read): (Layout, usize) = bincode::decode_from_slice(body, cfg)?;
+            let values = &body[read..];
+            let expected = layout.num_points() * 4;
+            if values.len() < expected {
+                return Err(DecodeError::UnexpectedEnd {
+                    additional: expected - values.len(),
+                }
+                .into());
+            }
+            let half = |b: &[u8]| f16::from_le_bytes([b[0], b[1]]).to_f64();
+            let coords = values[..expected]
+                .chunks_exact(4)
+                .map(|v| Coord {
+                    x: half(&v[..2]),
+                    y: half(&v[2..]),
+                })
+                .collect();
+            Ok(rebuild_geometry(&layout, coords)?)
+        }
+        Encoding::Quantized => {
+            let ((layout, deltas), _): ((Layout, Vec<i64>), _) =
+                bincode::decode_from_slice(body, cfg)?;
+            if deltas.len() % 2 != 0 {
+                return Err(DecodeError::Other("odd number of quantized values").into());
+            }
+            let mut prev = [0i64; 2];
+            let coords = deltas
+                .chunks_exact(2)
+                .map(|d| {
+                    prev = [prev[0].wrapping_add(d[0]), prev[1].wrapping_add(d[1])];
+                    Coord {
+                        x: prev[0] as f64 / QUANTIZED_SCALE,
+                        y: prev[1] as f64 / QUANTIZED_SCALE,
+                    }
+                })
+                .collect();
+            Ok(rebuild_geometry(&layout, coords)?)
+        }
+    }
+}
+
+/// Decodes a payload along with its metadata.
+pub fn decode_payload_with_metadata(
+    data: &[u8],
+) -> Result<(Geometry, PayloadMetadata), GeometryDecodeError> {
+    let metadata = payload_metadata(data)?;
+    Ok((decode_payload(data)?, metadata))
+}
+
+/// Decodes a payload and restores the original vertex order of the rings
+/// flagged in it. See [`restore_rings`].
+pub fn decode_payload_original(data: &[u8]) -> Result<Geometry, GeometryDecodeError> {
+    let flags = payload_ring_flags(data)?;
+    Ok(restore_rings(&decode_payload(data)?, &flags))
+}
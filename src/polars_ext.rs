@@ -0,0 +1,81 @@
+use bincode::config;
+use geo_types::Coord;
+use polars::prelude::*;
+
+use crate::decode::limited;
+use crate::ewkb::{geometry_from_ewkb, geometry_to_ewkb};
+use crate::{decode_geometry, encode_coord, encode_geometry, supported, HilbertGeometry};
+
+fn compute_error(e: impl std::fmt::Display) -> PolarsError {
+    PolarsError::ComputeError(e.to_string().into())
+}
+
+/// Hilbert index (`UInt64`) of each coordinate in a struct column whose
+/// first two fields are longitude and latitude. Null coordinates give null.
+pub fn hilbert_key(coords: Expr) -> Expr {
+    coords.map(
+        |c| {
+            let fields = c.struct_()?.fields_as_series();
+            if fields.len() < 2 {
+                return Err(compute_error("hilbert_key expects a struct of (lon, lat)"));
+            }
+            let lon = fields[0].cast(&DataType::Float64)?;
+            let lat = fields[1].cast(&DataType::Float64)?;
+            let keys: UInt64Chunked = lon
+                .f64()?
+                .into_iter()
+                .zip(lat.f64()?)
+                .map(|(x, y)| Some(encode_coord(Coord { x: x?, y: y? }).0))
+                .collect();
+            Ok(keys.with_name(c.name().clone()).into_column())
+        },
+        |_, field| Ok(Field::new(field.name().clone(), DataType::UInt64)),
+    )
+}
+
+/// Hilbert index of each `(lon, lat)` pair of numeric columns.
+pub fn hilbert_key_lonlat(lon: Expr, lat: Expr) -> Expr {
+    hilbert_key(as_struct(vec![lon, lat]))
+}
+
+/// Encodes a binary column of (E)WKB geometries into bincode-encoded
+/// [`HilbertGeometry`] bytes.
+pub fn hilbert_encode(wkb: Expr) -> Expr {
+    wkb.map(
+        |c| {
+            map_binary(&c, |wkb| {
+                let (geom, _) = geometry_from_ewkb(wkb).map_err(compute_error)?;
+                supported(&geom).map_err(compute_error)?;
+                bincode::encode_to_vec(encode_geometry(&geom), config::standard())
+                    .map_err(compute_error)
+            })
+        },
+        |_, field| Ok(Field::new(field.name().clone(), DataType::Binary)),
+    )
+}
+
+/// Decodes a binary column of bincode-encoded [`HilbertGeometry`] bytes
+/// into WKB.
+pub fn hilbert_decode(data: Expr) -> Expr {
+    data.map(
+        |c| {
+            map_binary(&c, |data| {
+                let (geom, _): (HilbertGeometry, _) =
+                    bincode::decode_from_slice(data, limited(config::standard()))
+                        .map_err(compute_error)?;
+                Ok(geometry_to_ewkb(&decode_geometry(&geom), None))
+            })
+        },
+        |_, field| Ok(Field::new(field.name().clone(), DataType::Binary)),
+    )
+}
+
+/// Applies `f` to each non-null value of a binary column.
+fn map_binary(c: &Column, f: impl Fn(&[u8]) -> PolarsResult<Vec<u8>>) -> PolarsResult<Column> {
+    let output: BinaryChunked = c
+        .binary()?
+        .into_iter()
+        .map(|value| value.map(&f).transpose())
+        .collect::<PolarsResult<_>>()?;
+    Ok(output.with_name(c.name().clone()).into_column())
+}
@@ -0,0 +1,107 @@
+use bincode::{config, error::DecodeError};
+use geo_types::Geometry;
+use sqlx::{
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef},
+    Decode, Encode, Postgres, Type,
+};
+
+use crate::decode::limited;
+use crate::ewkb::{geometry_from_ewkb, geometry_from_hex_ewkb, geometry_to_ewkb};
+use crate::{decode_geometry, encode_geometry, supported, HilbertGeometry};
+
+/// Stored as `BYTEA` containing the bincode encoding.
+impl Type<Postgres> for HilbertGeometry {
+    fn type_info() -> PgTypeInfo {
+        <Vec<u8> as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <Vec<u8> as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for HilbertGeometry {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let data = bincode::encode_to_vec(self, config::standard())?;
+        <Vec<u8> as Encode<Postgres>>::encode(data, buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for HilbertGeometry {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let data = <Vec<u8> as Decode<Postgres>>::decode(value)?;
+        let (geom, _) = bincode::decode_from_slice(&data, limited(config::standard()))?;
+        Ok(geom)
+    }
+}
+
+/// A PostGIS `geometry` value, transferred as EWKB and Hilbert-encoded on
+/// the client side.
+///
+/// ```ignore
+/// let row: (PostgisGeometry,) = sqlx::query_as("SELECT geom FROM roads LIMIT 1")
+///     .fetch_one(&pool)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostgisGeometry {
+    pub geometry: HilbertGeometry,
+    pub srid: Option<i32>,
+}
+
+impl PostgisGeometry {
+    pub fn new(geometry: HilbertGeometry, srid: Option<i32>) -> Self {
+        Self { geometry, srid }
+    }
+
+    /// Reads a geometry from (E)WKB. Geometry collections are rejected, as
+    /// they have no Hilbert-encoded equivalent.
+    pub fn from_ewkb(data: &[u8]) -> Result<Self, DecodeError> {
+        let (geom, srid) = geometry_from_ewkb(data)?;
+        Self::from_geometry(&geom, srid)
+    }
+
+    fn from_geometry(geom: &Geometry, srid: Option<i32>) -> Result<Self, DecodeError> {
+        supported(geom).map_err(DecodeError::Other)?;
+        Ok(Self::new(encode_geometry(geom), srid))
+    }
+
+    /// Writes the geometry as EWKB, including the SRID if set.
+    pub fn to_ewkb(&self) -> Vec<u8> {
+        geometry_to_ewkb(&decode_geometry(&self.geometry), self.srid)
+    }
+}
+
+impl From<PostgisGeometry> for HilbertGeometry {
+    fn from(geom: PostgisGeometry) -> Self {
+        geom.geometry
+    }
+}
+
+impl Type<Postgres> for PostgisGeometry {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("geometry")
+    }
+}
+
+impl Encode<'_, Postgres> for PostgisGeometry {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend(self.to_ewkb());
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PostgisGeometry {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => Ok(Self::from_ewkb(value.as_bytes()?)?),
+            // The text output of `geometry` is hex-encoded EWKB.
+            PgValueFormat::Text => {
+                let (geom, srid) = geometry_from_hex_ewkb(value.as_str()?)?;
+                Ok(Self::from_geometry(&geom, srid)?)
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! High-precision Hilbert indices.
+//!
+//! [`HilbertPoint`] packs the `f32` bits of each axis into a 64-bit index,
+//! which limits precision to about a metre far from the origin. A
+//! [`HilbertPoint128`] instead maps longitude and latitude to 64-bit fixed
+//! point over their full ranges and interleaves them into a 128-bit index,
+//! so the index is finer than an `f64` coordinate anywhere on the globe.
+//!
+//! The two curves differ in orientation, so 64-bit and 128-bit indices don't
+//! sort alike; convert between them through coordinates.
+
+use bincode::{Decode, Encode};
+use geo_types::Coord;
+
+use crate::{decode_coord, encode_coord, HilbertPoint};
+
+/// 2^64 as `f64`.
+const AXIS_SCALE: f64 = 18_446_744_073_709_551_616.0;
+
+/// Represents a 128-bit Hilbert-encoded point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Decode, Encode)]
+pub struct HilbertPoint128(pub u128);
+
+impl HilbertPoint128 {
+    /// Encodes a lon/lat coordinate. Coordinates outside `[-180, 180]` and
+    /// `[-90, 90]` are clamped.
+    pub fn from_coord(c: Coord) -> HilbertPoint128 {
+        // Float to int casts saturate, which clamps out of range values.
+        let x = ((c.x + 180.0) / 360.0 * AXIS_SCALE) as u64;
+        let y = ((c.y + 90.0) / 180.0 * AXIS_SCALE) as u64;
+        HilbertPoint128(xy2h(x, y))
+    }
+
+    /// Decodes the point back into a lon/lat coordinate.
+    pub fn to_coord(self) -> Coord {
+        let (x, y) = h2xy(self.0);
+        Coord {
+            x: x as f64 / AXIS_SCALE * 360.0 - 180.0,
+            y: y as f64 / AXIS_SCALE * 180.0 - 90.0,
+        }
+    }
+}
+
+impl From<HilbertPoint> for HilbertPoint128 {
+    fn from(p: HilbertPoint) -> Self {
+        HilbertPoint128::from_coord(decode_coord(p))
+    }
+}
+
+/// Lossy: rounds the coordinate to the precision of a 64-bit index.
+impl From<HilbertPoint128> for HilbertPoint {
+    fn from(p: HilbertPoint128) -> Self {
+        encode_coord(p.to_coord())
+    }
+}
+
+/// Maps a cell of the 2^64 x 2^64 grid to its distance along the curve.
+fn xy2h(mut x: u64, mut y: u64) -> u128 {
+    let mut h = 0u128;
+    for bit in (0..64).rev() {
+        let s = 1u64 << bit;
+        let rx = x & s != 0;
+        let ry = y & s != 0;
+        h += ((3 * rx as u128) ^ ry as u128) << (2 * bit);
+        if !ry {
+            if rx {
+                x = !x;
+                y = !y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+    }
+    h
+}
+
+/// Inverse of [`xy2h`].
+fn h2xy(h: u128) -> (u64, u64) {
+    let (mut x, mut y) = (0u64, 0u64);
+    for bit in 0..64 {
+        let s = 1u64 << bit;
+        let quadrant = (h >> (2 * bit)) as u64 & 3;
+        let rx = quadrant >> 1;
+        let ry = (quadrant ^ rx) & 1;
+        if ry == 0 {
+            if rx == 1 {
+                // Reflect within the `s` x `s` sub-square; `x < s` here.
+                x = s.wrapping_sub(1).wrapping_sub(x);
+                y = s.wrapping_sub(1).wrapping_sub(y);
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        x += s * rx;
+        y += s * ry;
+    }
+    (x, y)
+}
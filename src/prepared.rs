@@ -0,0 +1,109 @@
+//! Polygons prepared for fast repeated point-in-polygon tests.
+
+use std::ops::Range;
+
+use geo_types::{Geometry, Line};
+
+use crate::covering::{
+    cover_edges, edges_bbox, polygon_contains, polygon_edges, Covering, RangeKind,
+};
+use crate::{decode_coord, decode_geometry, HilbertGeometry, HilbertPoint};
+
+/// Range budget of the covering built by [`PreparedPolygon::new`].
+pub const PREPARED_MAX_RANGES: usize = 1024;
+
+/// A polygon or multipolygon with a cached curve covering and an edge index,
+/// for geofencing and other workloads testing many points against the same
+/// shape.
+///
+/// Most points are answered by a binary search over the covering alone.
+/// Only points in ranges crossed by the boundary are tested exactly, against
+/// the edges of one horizontal band of the polygon. Points exactly on the
+/// boundary may be reported either way.
+#[derive(Debug, Clone)]
+pub struct PreparedPolygon {
+    covering: Covering,
+    edges: Vec<Line>,
+    /// Edges overlapping each horizontal band, as ranges into `band_edges`.
+    bands: Vec<Range<usize>>,
+    band_edges: Vec<u32>,
+    min_y: f64,
+    band_height: f64,
+}
+
+impl PreparedPolygon {
+    /// Prepares an encoded polygon or multipolygon. Returns `None` for other
+    /// geometry types.
+    pub fn new(geom: &HilbertGeometry) -> Option<Self> {
+        Self::with_max_ranges(geom, PREPARED_MAX_RANGES)
+    }
+
+    /// Like [`new`](Self::new), with a covering of about `max_ranges`
+    /// ranges. More ranges take longer to prepare and answer more points
+    /// without an exact test.
+    pub fn with_max_ranges(geom: &HilbertGeometry, max_ranges: usize) -> Option<Self> {
+        let edges: Vec<Line> = match decode_geometry(geom) {
+            Geometry::Polygon(p) => polygon_edges(&p),
+            Geometry::MultiPolygon(mp) => mp.0.iter().flat_map(polygon_edges).collect(),
+            _ => return None,
+        };
+        let covering = cover_edges(&edges, max_ranges);
+        let (min_y, max_y) = edges_bbox(&edges).map_or((0.0, 0.0), |b| (b.min().y, b.max().y));
+        let n = (edges.len() as f64).sqrt().ceil().max(1.0) as usize;
+        let band_height = (max_y - min_y) / n as f64;
+
+        let mut per_band = vec![vec![]; n];
+        for (i, e) in edges.iter().enumerate() {
+            let (lo, hi) = (e.start.y.min(e.end.y), e.start.y.max(e.end.y));
+            let band = |y: f64| band_of(y, min_y, band_height, n);
+            for edges in &mut per_band[band(lo)..=band(hi)] {
+                edges.push(i as u32);
+            }
+        }
+        let mut bands = Vec::with_capacity(n);
+        let mut band_edges = vec![];
+        for edges in per_band {
+            bands.push(band_edges.len()..band_edges.len() + edges.len());
+            band_edges.extend(edges);
+        }
+        Some(Self {
+            covering,
+            edges,
+            bands,
+            band_edges,
+            min_y,
+            band_height,
+        })
+    }
+
+    pub fn covering(&self) -> &Covering {
+        &self.covering
+    }
+
+    /// Returns whether the encoded point lies inside the polygon.
+    pub fn contains(&self, p: HilbertPoint) -> bool {
+        match self.covering.kind_of(p.0) {
+            Some(RangeKind::Inner) => true,
+            None => false,
+            Some(RangeKind::Boundary) => self.contains_exact(p),
+        }
+    }
+
+    fn contains_exact(&self, p: HilbertPoint) -> bool {
+        let c = decode_coord(p);
+        let band = band_of(c.y, self.min_y, self.band_height, self.bands.len());
+        let edges = self.band_edges[self.bands[band].clone()]
+            .iter()
+            .map(|i| &self.edges[*i as usize]);
+        polygon_contains(edges, c)
+    }
+}
+
+/// Index of the band containing `y`, clamped to the bands.
+fn band_of(y: f64, min_y: f64, band_height: f64, n: usize) -> usize {
+    if band_height > 0.0 {
+        (((y - min_y) / band_height).max(0.0) as usize).min(n - 1)
+    } else {
+        0
+    }
+}
@@ -0,0 +1,74 @@
+//! Protocol Buffers messages for encoded geometries, for use in tonic
+//! services.
+//!
+//! [`GeometryMessage`] is the Rust type of the `hilbert_geometry.Geometry`
+//! message in `proto/hilbert_geometry.proto`. Import that file in your own
+//! `.proto` files and map the message to this type when generating code, so
+//! generated structs hold it directly:
+//!
+//! ```ignore
+//! tonic_prost_build::configure()
+//!     .extern_path(
+//!         ".hilbert_geometry.Geometry",
+//!         "::hilbert_geometry::proto::GeometryMessage",
+//!     )
+//!     .compile_protos(&["proto/service.proto"], &["proto"])?;
+//! ```
+
+use bincode::config;
+use geo_types::Geometry;
+
+use crate::{GeometryDecodeError, HilbertGeometry, HilbertSerializer};
+
+/// A geometry in HWKB: [`HilbertGeometry`] encoded with bincode's standard
+/// configuration, as written by [`HilbertSerializer::new`].
+#[derive(Clone, PartialEq, Eq, Hash, prost::Message)]
+pub struct GeometryMessage {
+    #[prost(bytes = "vec", tag = "1")]
+    pub hwkb: Vec<u8>,
+}
+
+impl From<&HilbertGeometry> for GeometryMessage {
+    fn from(geom: &HilbertGeometry) -> Self {
+        Self {
+            hwkb: bincode::encode_to_vec(geom, config::standard())
+                .expect("encoding to a Vec doesn't fail"),
+        }
+    }
+}
+
+impl From<HilbertGeometry> for GeometryMessage {
+    fn from(geom: HilbertGeometry) -> Self {
+        Self::from(&geom)
+    }
+}
+
+impl From<&Geometry> for GeometryMessage {
+    fn from(geom: &Geometry) -> Self {
+        Self::from(HilbertGeometry::from(geom))
+    }
+}
+
+impl TryFrom<&GeometryMessage> for HilbertGeometry {
+    type Error = GeometryDecodeError;
+
+    fn try_from(msg: &GeometryMessage) -> Result<Self, GeometryDecodeError> {
+        HilbertGeometry::decode_bincode_detailed(&msg.hwkb, &config::standard())
+    }
+}
+
+impl TryFrom<GeometryMessage> for HilbertGeometry {
+    type Error = GeometryDecodeError;
+
+    fn try_from(msg: GeometryMessage) -> Result<Self, GeometryDecodeError> {
+        HilbertGeometry::try_from(&msg)
+    }
+}
+
+impl TryFrom<&GeometryMessage> for Geometry {
+    type Error = GeometryDecodeError;
+
+    fn try_from(msg: &GeometryMessage) -> Result<Self, GeometryDecodeError> {
+        HilbertSerializer::new().decode_detailed(&msg.hwkb)
+    }
+}
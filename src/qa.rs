@@ -0,0 +1,63 @@
+//! Round-trip error annotation for data validation.
+//!
+//! Encoding rounds coordinates to `f32`. [`encode_geometry_annotated`]
+//! encodes a geometry and reports its worst vertex in the same pass, so QA
+//! jobs can flag features over a tolerance without decoding them again.
+
+use geo_types::{Coord, Geometry, Polygon};
+
+use crate::{decode_coord, encode_geometry, HilbertGeometry};
+
+/// The vertex with the largest round-trip error in a geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct VertexError {
+    /// Index of the vertex in encoded order, counting ring closing vertices,
+    /// as in [`HilbertGeometry::num_coords`].
+    pub index: usize,
+    /// Distance in degrees between the input and the decoded vertex.
+    pub error: f64,
+}
+
+impl VertexError {
+    /// Returns true if the error is larger than `tolerance` degrees.
+    pub fn exceeds(&self, tolerance: f64) -> bool {
+        self.error > tolerance
+    }
+}
+
+/// Encodes a geometry like [`encode_geometry`] and returns the vertex with
+/// the largest round-trip error alongside it. The first vertex wins ties;
+/// geometries without vertices report index 0 with no error.
+pub fn encode_geometry_annotated(geom: &Geometry) -> (HilbertGeometry, VertexError) {
+    let encoded = encode_geometry(geom);
+    let mut worst = VertexError::default();
+    for (index, (c, p)) in coords(geom).zip(encoded.points()).enumerate() {
+        let decoded = decode_coord(*p);
+        let error = (decoded.x - c.x).hypot(decoded.y - c.y);
+        if error > worst.error {
+            worst = VertexError { index, error };
+        }
+    }
+    (encoded, worst)
+}
+
+/// Input coordinates in the order [`HilbertGeometry::points`] visits the
+/// encoded vertices.
+fn coords(geom: &Geometry) -> Box<dyn Iterator<Item = Coord> + '_> {
+    fn rings(p: &Polygon) -> impl Iterator<Item = Coord> + '_ {
+        std::iter::once(p.exterior())
+            .chain(p.interiors())
+            .flat_map(|r| r.0.iter().copied())
+    }
+    match geom {
+        Geometry::Point(p) => Box::new(std::iter::once(p.0)),
+        Geometry::LineString(ls) => Box::new(ls.0.iter().copied()),
+        Geometry::Polygon(p) => Box::new(rings(p)),
+        Geometry::MultiPoint(mp) => Box::new(mp.0.iter().map(|p| p.0)),
+        Geometry::MultiLineString(mls) => {
+            Box::new(mls.0.iter().flat_map(|ls| ls.0.iter().copied()))
+        }
+        Geometry::MultiPolygon(mp) => Box::new(mp.0.iter().flat_map(rings)),
+        _ => Box::new(std::iter::empty()),
+    }
+}
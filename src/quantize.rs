@@ -0,0 +1,58 @@
+//! Rounding of coordinates to a fixed number of decimal places.
+//!
+//! Coordinates that differ only in their last bits, e.g. after parsing
+//! the same data with different libraries, can map to different curve
+//! indices. Quantizing before encoding makes equal inputs at the chosen
+//! precision encode to identical bytes, which dedup and hashing rely on.
+
+use geo_types::{Coord, Geometry};
+
+use crate::map_geometry_coords;
+
+/// How values are rounded to the nearest step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rounding {
+    /// Round half away from zero.
+    #[default]
+    Round,
+    /// Round toward zero.
+    Truncate,
+    /// Round half to even (banker's rounding).
+    HalfEven,
+}
+
+/// Powers of ten that are exact in `f64`. Looked up rather than computed
+/// with `powi`, whose result may differ between platforms.
+const POWERS_OF_TEN: [f64; 23] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+    1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
+/// Rounds `v` to `places` decimal places. More than 22 places are treated
+/// as 22, far beyond the precision of an `f64`.
+///
+/// Only IEEE 754 basic operations are used, so the result is the same on
+/// every platform.
+pub fn quantize(v: f64, places: u32, rounding: Rounding) -> f64 {
+    let scale = POWERS_OF_TEN[(places as usize).min(POWERS_OF_TEN.len() - 1)];
+    let scaled = v * scale;
+    let rounded = match rounding {
+        Rounding::Round => scaled.round(),
+        Rounding::Truncate => scaled.trunc(),
+        Rounding::HalfEven => scaled.round_ties_even(),
+    };
+    rounded / scale
+}
+
+/// Rounds both axes of a coordinate to `places` decimal places.
+pub fn quantize_coord(c: Coord, places: u32, rounding: Rounding) -> Coord {
+    Coord {
+        x: quantize(c.x, places, rounding),
+        y: quantize(c.y, places, rounding),
+    }
+}
+
+/// Rounds every coordinate of a geometry to `places` decimal places.
+pub fn quantize_geometry(geom: &Geometry, places: u32, rounding: Rounding) -> Geometry {
+    map_geometry_coords(geom, &|c| quantize_coord(c, places, rounding))
+}
@@ -0,0 +1,109 @@
+//! Redis sorted-set scores for encoded points.
+//!
+//! A sorted set scored by [`key_to_score`] works as a spatial index like
+//! Redis' own GEO commands: [`radius_score_ranges`] gives the
+//! `ZRANGEBYSCORE` ranges holding the members near a point, which are then
+//! filtered by distance.
+//!
+//! Scores are `f64`, exact for integers below `2^53`, so a score keeps the
+//! top [`SCORE_BITS`] bits of an index and points within the same block of
+//! `2^11` indices share a score. A block spans up to 64 `f32` steps per
+//! axis, so its size grows with the coordinate: about 6e-5 degrees for
+//! values between 8 and 16, 1e-3 degrees beyond 128. Store exact positions
+//! alongside, e.g. in the member name, to filter and decode members.
+
+use std::ops::RangeInclusive;
+
+use geo_types::{coord, Rect};
+
+use crate::covering::cover_rect;
+use crate::geo_ops::EARTH_RADIUS;
+use crate::{decode_coord, HilbertPoint};
+
+/// Number of index bits kept in a score.
+pub const SCORE_BITS: u32 = 53;
+
+const SCORE_SHIFT: u32 = 64 - SCORE_BITS;
+
+/// Returns the sorted-set score of `p`, an integer below `2^53`. Scores
+/// sort in curve order.
+pub fn key_to_score(p: HilbertPoint) -> f64 {
+    (p.0 >> SCORE_SHIFT) as f64
+}
+
+/// Indices whose score is `score`, `None` if no index has that score.
+pub fn score_to_range(score: f64) -> Option<RangeInclusive<u64>> {
+    if !(0.0..(1u64 << SCORE_BITS) as f64).contains(&score) || score.fract() != 0.0 {
+        return None;
+    }
+    let start = (score as u64) << SCORE_SHIFT;
+    Some(start..=start | ((1 << SCORE_SHIFT) - 1))
+}
+
+/// Returns a point with score `score`, in the middle of the indices sharing
+/// it. `None` if no index has that score.
+pub fn score_to_key(score: f64) -> Option<HilbertPoint> {
+    let range = score_to_range(score)?;
+    Some(HilbertPoint(range.start() | 1 << (SCORE_SHIFT - 1)))
+}
+
+/// Converts sorted index ranges, e.g. from [`cover_rect`], to inclusive
+/// score ranges for `ZRANGEBYSCORE`. Ranges that touch once the low bits
+/// are dropped are merged.
+pub fn score_ranges(ranges: &[RangeInclusive<u64>]) -> Vec<RangeInclusive<f64>> {
+    let mut merged: Vec<RangeInclusive<u64>> = vec![];
+    for r in ranges {
+        let (start, end) = (r.start() >> SCORE_SHIFT, r.end() >> SCORE_SHIFT);
+        match merged.last_mut() {
+            Some(last) if start <= last.end() + 1 => {
+                *last = *last.start()..=end.max(*last.end());
+            }
+            _ => merged.push(start..=end),
+        }
+    }
+    merged
+        .into_iter()
+        .map(|r| *r.start() as f64..=*r.end() as f64)
+        .collect()
+}
+
+/// Returns score ranges holding every point within `radius` metres of
+/// `center`, using at most roughly `max_ranges` ranges per rectangle
+/// searched.
+///
+/// The ranges cover the bounding box of the circle, split at the
+/// antimeridian, so members must be filtered by
+/// [`haversine_distance`](crate::geo_ops::haversine_distance) afterwards.
+pub fn radius_score_ranges(
+    center: HilbertPoint,
+    radius: f64,
+    max_ranges: usize,
+) -> Vec<RangeInclusive<f64>> {
+    let c = decode_coord(center);
+    let dlat = (radius / EARTH_RADIUS).to_degrees();
+    let (min_y, max_y) = ((c.y - dlat).max(-90.0), (c.y + dlat).min(90.0));
+    let max_abs_lat = min_y.abs().max(max_y.abs());
+    let dlon = if max_abs_lat < 90.0 {
+        dlat / max_abs_lat.to_radians().cos()
+    } else {
+        180.0
+    };
+    let spans = if dlon >= 180.0 {
+        vec![(-180.0, 180.0)]
+    } else if c.x - dlon < -180.0 {
+        vec![(-180.0, c.x + dlon), (c.x - dlon + 360.0, 180.0)]
+    } else if c.x + dlon > 180.0 {
+        vec![(-180.0, c.x + dlon - 360.0), (c.x - dlon, 180.0)]
+    } else {
+        vec![(c.x - dlon, c.x + dlon)]
+    };
+    let mut ranges: Vec<RangeInclusive<u64>> = spans
+        .into_iter()
+        .flat_map(|(min_x, max_x)| {
+            let rect = Rect::new(coord! { x: min_x, y: min_y }, coord! { x: max_x, y: max_y });
+            cover_rect(&rect, max_ranges)
+        })
+        .collect();
+    ranges.sort_by_key(|r| *r.start());
+    score_ranges(&ranges)
+}
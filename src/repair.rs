@@ -0,0 +1,60 @@
+//! Repair of invalid polygons before encoding.
+//!
+//! Real-world data often has self-intersecting rings, holes wound the same
+//! way as their shell or holes poking out of it. Such polygons encode fine
+//! but break consumers that expect valid geometries. Like `buffer(0)` in
+//! GEOS, [`repair_geometry`] rebuilds the area the rings enclose with a
+//! robust boolean overlay.
+
+use geo::orient::{Direction, Orient};
+use geo::{unary_union, BooleanOps};
+use geo_types::{Geometry, GeometryCollection, LineString, MultiPolygon, Polygon};
+
+/// Rebuilds polygons and multipolygons from the area enclosed by their
+/// rings, by the even-odd rule. Other geometries are returned as they are.
+///
+/// - Unclosed rings are closed.
+/// - Bow-ties are split at their crossings into separate polygons.
+/// - Ring orientation is ignored on input. The result has
+///   counter-clockwise shells and clockwise holes.
+/// - Parts of a multipolygon that overlap are merged.
+/// - Rings with no area are dropped.
+///
+/// A polygon that falls apart into several becomes a multipolygon, one
+/// with no area left an empty polygon.
+pub fn repair_geometry(geom: &Geometry) -> Geometry {
+    match geom {
+        Geometry::Polygon(p) => {
+            let mut repaired = repair_polygon(p);
+            match repaired.0.len() {
+                0 => Geometry::Polygon(Polygon::new(LineString::new(vec![]), vec![])),
+                1 => Geometry::Polygon(repaired.0.remove(0)),
+                _ => Geometry::MultiPolygon(repaired),
+            }
+        }
+        Geometry::MultiPolygon(mp) => {
+            let parts: Vec<MultiPolygon> = mp.0.iter().map(repair_polygon).collect();
+            Geometry::MultiPolygon(unary_union(&parts).orient(Direction::Default))
+        }
+        Geometry::GeometryCollection(gc) => Geometry::GeometryCollection(GeometryCollection(
+            gc.0.iter().map(repair_geometry).collect(),
+        )),
+        other => other.clone(),
+    }
+}
+
+fn repair_polygon(p: &Polygon) -> MultiPolygon {
+    let close = |ring: &LineString| {
+        let mut ring = ring.clone();
+        ring.close();
+        ring
+    };
+    let closed = Polygon::new(
+        close(p.exterior()),
+        p.interiors().iter().map(close).collect(),
+    );
+    // A union with nothing overlays the rings with the even-odd rule.
+    closed
+        .union(&MultiPolygon::new(vec![]))
+        .orient(Direction::Default)
+}
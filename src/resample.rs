@@ -0,0 +1,97 @@
+use geo_types::{Coord, Geometry, LineString, Polygon};
+
+use crate::{decode_coord, encode_coord, HilbertPoint};
+
+/// Resamples an encoded linestring to `n` vertices evenly spaced along its
+/// length, keeping the first and last vertex. Lengths are measured in
+/// degrees.
+///
+/// A linestring with no length repeats its first vertex. Empty input or
+/// `n == 0` gives an empty result.
+pub fn resample_linestring(ls: &[HilbertPoint], n: usize) -> Vec<HilbertPoint> {
+    let coords: Vec<Coord> = ls.iter().map(|p| decode_coord(*p)).collect();
+    let (Some(first), Some(last)) = (ls.first(), ls.last()) else {
+        return vec![];
+    };
+    if n == 0 {
+        return vec![];
+    }
+    if n == 1 {
+        return vec![*first];
+    }
+
+    let lengths: Vec<f64> = coords
+        .windows(2)
+        .map(|w| (w[1].x - w[0].x).hypot(w[1].y - w[0].y))
+        .collect();
+    let total: f64 = lengths.iter().sum();
+    if total == 0.0 {
+        return vec![*first; n];
+    }
+
+    let mut out = Vec::with_capacity(n);
+    out.push(*first);
+    let (mut segment, mut start) = (0, 0.0);
+    for i in 1..n - 1 {
+        let target = total * i as f64 / (n - 1) as f64;
+        while segment < lengths.len() - 1 && start + lengths[segment] < target {
+            start += lengths[segment];
+            segment += 1;
+        }
+        let t = if lengths[segment] > 0.0 {
+            ((target - start) / lengths[segment]).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let (a, b) = (coords[segment], coords[segment + 1]);
+        out.push(encode_coord(Coord {
+            x: a.x + (b.x - a.x) * t,
+            y: a.y + (b.y - a.y) * t,
+        }));
+    }
+    out.push(*last);
+    out
+}
+
+/// Inserts evenly spaced vertices into every segment longer than
+/// `max_segment_deg`, so that segments cross fewer curve cells. Existing
+/// vertices are kept. Points and non-positive limits are left unchanged.
+pub fn densify(geom: &Geometry, max_segment_deg: f64) -> Geometry {
+    if max_segment_deg <= 0.0 || max_segment_deg.is_nan() {
+        return geom.clone();
+    }
+    let line = |ls: &LineString| densify_line(ls, max_segment_deg);
+    let poly =
+        |p: &Polygon| Polygon::new(line(p.exterior()), p.interiors().iter().map(line).collect());
+    match geom {
+        Geometry::LineString(ls) => Geometry::LineString(line(ls)),
+        Geometry::Polygon(p) => Geometry::Polygon(poly(p)),
+        Geometry::MultiLineString(mls) => {
+            Geometry::MultiLineString(mls.0.iter().map(line).collect())
+        }
+        Geometry::MultiPolygon(mp) => Geometry::MultiPolygon(mp.0.iter().map(poly).collect()),
+        Geometry::GeometryCollection(gc) => {
+            Geometry::GeometryCollection(gc.0.iter().map(|g| densify(g, max_segment_deg)).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+fn densify_line(ls: &LineString, max_segment_deg: f64) -> LineString {
+    let mut out = Vec::with_capacity(ls.0.len());
+    for w in ls.0.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        let steps = ((b.x - a.x).hypot(b.y - a.y) / max_segment_deg)
+            .ceil()
+            .max(1.0) as usize;
+        out.extend((0..steps).map(|i| {
+            let t = i as f64 / steps as f64;
+            Coord {
+                x: a.x + (b.x - a.x) * t,
+                y: a.y + (b.y - a.y) * t,
+            }
+        }));
+    }
+    out.extend(ls.0.last());
+    LineString(out)
+}
@@ -0,0 +1,155 @@
+//! Snapping points to a road network, a building block for map matching
+//! without a routing engine.
+
+use geo_types::{Coord, Rect};
+
+use crate::search::find_in_rect;
+use crate::{decode_coord, encode_coord, HilbertGeometry, HilbertPoint};
+
+/// The road segment closest to a point, see [`RoadNetwork::nearest_segment`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentMatch {
+    /// Index of the road in the input of [`RoadNetwork::new`].
+    pub road: usize,
+    /// Index of the segment's first vertex within the road, counting the
+    /// vertices of all parts of a multilinestring.
+    pub segment: usize,
+    /// Closest point on the segment.
+    pub snapped: Coord,
+    /// Distance to the segment, in degrees.
+    pub distance: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    road: u32,
+    index: u32,
+    start: Coord,
+    end: Coord,
+}
+
+/// Road segments indexed by the Hilbert index of their midpoints.
+///
+/// A lookup scans the midpoints within the search radius plus half the
+/// longest segment, so a few very long segments make every lookup slower.
+/// Densify such roads first, see [`resample::densify`](crate::resample::densify).
+#[derive(Debug, Clone, Default)]
+pub struct RoadNetwork {
+    /// Midpoint indices, sorted.
+    keys: Vec<HilbertPoint>,
+    /// Segments in the order of `keys`.
+    segments: Vec<Segment>,
+    max_half_len: f64,
+}
+
+impl RoadNetwork {
+    /// Indexes the segments of encoded linestrings and multilinestrings.
+    /// Other geometry types are skipped but keep their road index.
+    pub fn new<'a>(roads: impl IntoIterator<Item = &'a HilbertGeometry>) -> Self {
+        let mut segments = vec![];
+        for (road, geom) in roads.into_iter().enumerate() {
+            let parts = match geom {
+                HilbertGeometry::LineString(ps) => std::slice::from_ref(ps),
+                HilbertGeometry::MultiLineString(parts) => parts.as_slice(),
+                _ => continue,
+            };
+            // Index of the first vertex of the part within the road.
+            let mut offset = 0;
+            for part in parts {
+                let coords: Vec<Coord> = part.iter().map(|p| decode_coord(*p)).collect();
+                for (i, w) in coords.windows(2).enumerate() {
+                    segments.push(Segment {
+                        road: road as u32,
+                        index: (offset + i) as u32,
+                        start: w[0],
+                        end: w[1],
+                    });
+                }
+                offset += part.len();
+            }
+        }
+        let midpoint = |s: &Segment| Coord {
+            x: (s.start.x + s.end.x) / 2.0,
+            y: (s.start.y + s.end.y) / 2.0,
+        };
+        let mut keyed: Vec<(HilbertPoint, Segment)> = segments
+            .into_iter()
+            .map(|s| (encode_coord(midpoint(&s)), s))
+            .collect();
+        keyed.sort_by_key(|(k, _)| *k);
+        let max_half_len = keyed
+            .iter()
+            .map(|(_, s)| distance(s.start, s.end) / 2.0)
+            .fold(0.0, f64::max);
+        let (keys, segments) = keyed.into_iter().unzip();
+        Self {
+            keys,
+            segments,
+            max_half_len,
+        }
+    }
+
+    /// Number of indexed segments.
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Returns the segment closest to `point` within `radius` degrees, if
+    /// any. Ties go to the segment with the lower road and segment index.
+    pub fn nearest_segment(&self, point: HilbertPoint, radius: f64) -> Option<SegmentMatch> {
+        let p = decode_coord(point);
+        // Slack for the midpoints being stored at f32 precision.
+        let slack = (p.x.abs() + p.y.abs() + 1.0) * f32::EPSILON as f64 * 4.0;
+        let reach = radius + self.max_half_len + slack;
+        let rect = Rect::new(
+            Coord {
+                x: p.x - reach,
+                y: p.y - reach,
+            },
+            Coord {
+                x: p.x + reach,
+                y: p.y + reach,
+            },
+        );
+        find_in_rect(&self.keys, &rect)
+            .into_iter()
+            .map(|i| {
+                let s = &self.segments[i];
+                let snapped = closest_point(s.start, s.end, p);
+                SegmentMatch {
+                    road: s.road as usize,
+                    segment: s.index as usize,
+                    snapped,
+                    distance: distance(p, snapped),
+                }
+            })
+            .filter(|m| m.distance <= radius)
+            .min_by(|a, b| {
+                a.distance
+                    .total_cmp(&b.distance)
+                    .then((a.road, a.segment).cmp(&(b.road, b.segment)))
+            })
+    }
+}
+
+fn distance(a: Coord, b: Coord) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+/// Closest point to `p` on the segment from `a` to `b`.
+fn closest_point(a: Coord, b: Coord, p: Coord) -> Coord {
+    let d = b - a;
+    let len2 = d.x * d.x + d.y * d.y;
+    if len2 == 0.0 {
+        return a;
+    }
+    let t = (((p.x - a.x) * d.x + (p.y - a.y) * d.y) / len2).clamp(0.0, 1.0);
+    Coord {
+        x: a.x + t * d.x,
+        y: a.y + t * d.y,
+    }
+}
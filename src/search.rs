@@ -0,0 +1,62 @@
+//! Binary search over sorted slices of encoded points.
+//!
+//! Flat indexes of points sorted by Hilbert index support range lookups with
+//! plain binary search. These helpers take care of the bound handling, and of
+//! the coordinate ordering for rectangle queries: the curve runs over the bit
+//! patterns of `f32` coordinates, where negative values sort by magnitude, so
+//! comparing coordinates as floats gives wrong results near the axes.
+
+use std::ops::{Bound, Range, RangeBounds};
+
+use geo_types::Rect;
+
+use crate::covering::{cover_rect, DEFAULT_MAX_RANGES};
+use crate::{decode_coord, HilbertPoint};
+
+/// Returns the position of the first point not less than `p`.
+pub fn lower_bound(sorted: &[HilbertPoint], p: HilbertPoint) -> usize {
+    sorted.partition_point(|q| *q < p)
+}
+
+/// Returns the position of the first point greater than `p`.
+pub fn upper_bound(sorted: &[HilbertPoint], p: HilbertPoint) -> usize {
+    sorted.partition_point(|q| *q <= p)
+}
+
+/// Returns the positions of the points within `interval`, e.g.
+/// `a..b`, `a..=b` or `a..`.
+pub fn find_range(
+    sorted: &[HilbertPoint],
+    interval: impl RangeBounds<HilbertPoint>,
+) -> Range<usize> {
+    let start = match interval.start_bound() {
+        Bound::Included(p) => lower_bound(sorted, *p),
+        Bound::Excluded(p) => upper_bound(sorted, *p),
+        Bound::Unbounded => 0,
+    };
+    let end = match interval.end_bound() {
+        Bound::Included(p) => upper_bound(sorted, *p),
+        Bound::Excluded(p) => lower_bound(sorted, *p),
+        Bound::Unbounded => sorted.len(),
+    };
+    start..end.max(start)
+}
+
+/// Returns the positions of the points inside `rect`, in ascending order.
+///
+/// Candidates are looked up by the ranges of [`cover_rect`] and then checked
+/// against `rect` in `f32`, so points encoded from coordinates on the
+/// boundary are included.
+pub fn find_in_rect(sorted: &[HilbertPoint], rect: &Rect) -> Vec<usize> {
+    let (min, max) = (rect.min(), rect.max());
+    let inside = |p: &HilbertPoint| {
+        let c = decode_coord(*p);
+        let (x, y) = (c.x as f32, c.y as f32);
+        x >= min.x as f32 && x <= max.x as f32 && y >= min.y as f32 && y <= max.y as f32
+    };
+    cover_rect(rect, DEFAULT_MAX_RANGES)
+        .into_iter()
+        .flat_map(|r| find_range(sorted, HilbertPoint(*r.start())..=HilbertPoint(*r.end())))
+        .filter(|i| inside(&sorted[*i]))
+        .collect()
+}
@@ -0,0 +1,151 @@
+//! Seekable encoding for long linestrings.
+//!
+//! Vertices are stored as differences from the previous vertex, grouped into
+//! blocks that start with an absolute index (a sync point). A table of block
+//! offsets at the start of the data lets a reader decode any vertex by
+//! scanning at most one block.
+
+use std::ops::Range;
+
+use bincode::{
+    config::{self, Configuration},
+    error::{DecodeError, EncodeError},
+};
+
+use crate::decode::limited;
+use crate::HilbertPoint;
+
+/// Encodes a linestring with a sync point every `interval` vertices.
+///
+/// Smaller intervals make access faster and the output larger. Intervals
+/// longer than the linestring are shortened to its length. Fails if
+/// `interval` is zero.
+pub fn encode_seekable(points: &[HilbertPoint], interval: usize) -> Result<Vec<u8>, EncodeError> {
+    if interval == 0 {
+        return Err(EncodeError::Other("sync interval must be positive"));
+    }
+    let interval = match points.len() {
+        0 => interval,
+        len => interval.min(len),
+    };
+    let cfg = config::standard();
+    let mut body = Vec::new();
+    let mut sync = Vec::with_capacity(points.len().div_ceil(interval));
+    for block in points.chunks(interval) {
+        sync.push(body.len() as u64);
+        bincode::encode_into_std_write(block[0].0, &mut body, cfg)?;
+        for w in block.windows(2) {
+            let delta = w[1].0.wrapping_sub(w[0].0) as i64;
+            bincode::encode_into_std_write(delta, &mut body, cfg)?;
+        }
+    }
+    let mut buf = bincode::encode_to_vec((points.len() as u64, interval as u64, sync), cfg)?;
+    buf.extend(body);
+    Ok(buf)
+}
+
+/// Reads vertices of a linestring written by [`encode_seekable`] without
+/// decoding the whole linestring.
+pub struct SeekableLineString<'a> {
+    body: &'a [u8],
+    config: Configuration,
+    len: usize,
+    interval: usize,
+    sync: Vec<u64>,
+}
+
+impl<'a> SeekableLineString<'a> {
+    /// Decodes the header and sync table of `data`. Fails if the header
+    /// claims more vertices than the data can hold.
+    pub fn new(data: &'a [u8]) -> Result<Self, DecodeError> {
+        let config = config::standard();
+        let ((len, interval, sync), read): ((u64, u64, Vec<u64>), usize) =
+            bincode::decode_from_slice(data, limited(config))?;
+        let (len, interval) = (len as usize, interval as usize);
+        let body = &data[read..];
+        // Every vertex takes at least one byte.
+        if interval == 0
+            || len > body.len()
+            || (len > 0 && interval > len)
+            || sync.len() != len.div_ceil(interval)
+            || sync.windows(2).any(|w| w[0] > w[1])
+            || sync.last().is_some_and(|o| *o as usize >= body.len())
+        {
+            return Err(DecodeError::Other("invalid seekable linestring header"));
+        }
+        Ok(Self {
+            body,
+            config,
+            len,
+            interval,
+            sync,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Decodes vertex `i`, or `None` if out of bounds.
+    pub fn vertex_at(&self, i: usize) -> Option<Result<HilbertPoint, DecodeError>> {
+        if i >= self.len {
+            return None;
+        }
+        Some(self.slice(i..i + 1)?.map(|ps| ps[0]))
+    }
+
+    /// Decodes the vertices in `range`, or `None` if it is out of bounds.
+    pub fn slice(&self, range: Range<usize>) -> Option<Result<Vec<HilbertPoint>, DecodeError>> {
+        if range.start > range.end || range.end > self.len {
+            return None;
+        }
+        let mut out = Vec::with_capacity(range.len());
+        let mut block = range.start / self.interval;
+        while out.len() < range.len() {
+            let first = block * self.interval;
+            let skip = range.start.saturating_sub(first);
+            let take = (range.len() - out.len()).min(self.interval - skip);
+            if let Err(e) = self.read_block(block, skip, take, &mut out) {
+                return Some(Err(e));
+            }
+            block += 1;
+        }
+        Some(Ok(out))
+    }
+
+    /// Decodes the whole linestring.
+    pub fn to_vec(&self) -> Result<Vec<HilbertPoint>, DecodeError> {
+        self.slice(0..self.len).unwrap_or(Ok(vec![]))
+    }
+
+    /// Appends `take` vertices of `block` to `out`, skipping its first `skip`.
+    fn read_block(
+        &self,
+        block: usize,
+        skip: usize,
+        take: usize,
+        out: &mut Vec<HilbertPoint>,
+    ) -> Result<(), DecodeError> {
+        let mut pos = self.sync[block] as usize;
+        let (first, read): (u64, usize) =
+            bincode::decode_from_slice(&self.body[pos..], self.config)?;
+        pos += read;
+        let mut current = first;
+        for i in 0..skip + take {
+            if i > 0 {
+                let (delta, read): (i64, usize) =
+                    bincode::decode_from_slice(&self.body[pos..], self.config)?;
+                pos += read;
+                current = current.wrapping_add(delta as u64);
+            }
+            if i >= skip {
+                out.push(HilbertPoint(current));
+            }
+        }
+        Ok(())
+    }
+}
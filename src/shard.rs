@@ -0,0 +1,63 @@
+//! Spatially coherent partitioning by curve order.
+//!
+//! Shards are contiguous stretches of the curve, so nearby features tend to
+//! land on the same shard. Boundaries are positions along the curve between
+//! 0 and 1, which keeps them independent of the index width and easy to
+//! store in cluster configuration.
+
+use crate::cell::HilbertCell;
+use crate::kv::key_point;
+use crate::{HilbertGeometry, HilbertPoint};
+
+/// Position of `key` along the curve, between 0 and 1.
+pub fn curve_position(key: HilbertPoint) -> f64 {
+    key.0 as f64 / 2f64.powi(64)
+}
+
+/// Returns the shard of `key` given the sorted inner `boundaries` of the
+/// shards: shard `i` holds positions from `boundaries[i - 1]` (inclusive) up
+/// to `boundaries[i]`, so there are `boundaries.len() + 1` shards.
+pub fn shard_for(key: HilbertPoint, boundaries: &[f64]) -> usize {
+    let position = curve_position(key);
+    boundaries.partition_point(|b| *b <= position)
+}
+
+/// Computes boundaries for [`shard_for`] that split `keys` into `n_shards`
+/// shards of about equal size.
+///
+/// Keys at the same curve position always share a shard, so heavily
+/// repeated keys can leave some shards empty. Returns no boundaries for
+/// fewer than two shards or no keys.
+pub fn compute_balanced_boundaries(keys: &[HilbertPoint], n_shards: usize) -> Vec<f64> {
+    if n_shards < 2 || keys.is_empty() {
+        return vec![];
+    }
+    let mut positions: Vec<f64> = keys.iter().map(|k| curve_position(*k)).collect();
+    positions.sort_unstable_by(f64::total_cmp);
+    (1..n_shards)
+        .map(|i| positions[i * positions.len() / n_shards])
+        .collect()
+}
+
+/// Returns the partition of `geom` among `n_partitions`, e.g. Kafka
+/// partitions, so events in the same curve cell at `level` go to the same
+/// partition and can be processed by one stateful consumer.
+///
+/// Geometries are placed by their point or the center of their bounding
+/// box, as in [`make_key`](crate::kv::make_key). Each partition holds an
+/// equal contiguous stretch of the cells along the curve, so neighbouring
+/// cells mostly share a partition. Use [`compute_balanced_boundaries`] and
+/// [`shard_for`] instead where data is unevenly spread. Empty geometries have
+/// no partition.
+///
+/// # Panics
+///
+/// If `n_partitions` is 0 or `level` is above
+/// [`MAX_LEVEL`](crate::cell::MAX_LEVEL).
+pub fn partition_for(geom: &HilbertGeometry, n_partitions: usize, level: u32) -> Option<usize> {
+    assert!(n_partitions > 0, "no partitions");
+    let cell = HilbertCell::containing(key_point(geom)?, level);
+    // There are 4^level cells at a level.
+    let partition = (u128::from(cell.key) * n_partitions as u128) >> (2 * level);
+    Some(partition as usize)
+}
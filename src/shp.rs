@@ -0,0 +1,231 @@
+//! Shapefile (`.shp` + `.dbf`) import and export for collections.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use geo_types::Geometry;
+use shapefile::dbase::{FieldName, FieldValue, Record, TableWriterBuilder};
+
+use crate::collection::{FeatureMetadata, HilbertCollection, HilbertFeature, PropertyValue};
+use crate::{decode_geometry, encode_geometry, HilbertGeometry};
+
+/// Width of character fields written to `.dbf` files.
+const CHARACTER_FIELD_LEN: u8 = 254;
+
+/// Error returned by [`read_shapefile`] and [`write_shapefile`].
+#[derive(Debug)]
+pub enum ShapefileError {
+    Shapefile(shapefile::Error),
+    /// A shape or geometry that has no counterpart in the other format.
+    Geometry(String),
+    /// A property that can't be stored as a `.dbf` field.
+    Field(String),
+}
+
+impl fmt::Display for ShapefileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShapefileError::Shapefile(e) => write!(f, "shapefile error: {e}"),
+            ShapefileError::Geometry(e) => write!(f, "unsupported geometry: {e}"),
+            ShapefileError::Field(e) => write!(f, "unsupported field: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ShapefileError {}
+
+impl From<shapefile::Error> for ShapefileError {
+    fn from(e: shapefile::Error) -> Self {
+        ShapefileError::Shapefile(e)
+    }
+}
+
+fn from_field(value: FieldValue) -> PropertyValue {
+    let number = |n: f64| {
+        if n.fract() == 0.0 && n.abs() < 2f64.powi(53) {
+            PropertyValue::Int(n as i64)
+        } else {
+            PropertyValue::Double(n)
+        }
+    };
+    match value {
+        FieldValue::Character(Some(s)) | FieldValue::Memo(s) => PropertyValue::String(s),
+        FieldValue::Numeric(Some(n)) => number(n),
+        FieldValue::Float(Some(n)) => PropertyValue::Double(n as f64),
+        FieldValue::Double(n) | FieldValue::Currency(n) => PropertyValue::Double(n),
+        FieldValue::Integer(i) => PropertyValue::Int(i as i64),
+        FieldValue::Logical(Some(b)) => PropertyValue::Bool(b),
+        FieldValue::Date(Some(d)) => {
+            PropertyValue::String(format!("{}-{:02}-{:02}", d.year(), d.month(), d.day()))
+        }
+        FieldValue::Character(None)
+        | FieldValue::Numeric(None)
+        | FieldValue::Float(None)
+        | FieldValue::Logical(None)
+        | FieldValue::Date(None) => PropertyValue::Null,
+        other => PropertyValue::String(format!("{other:?}")),
+    }
+}
+
+/// Reads a shapefile and its `.dbf` attributes into a collection. Null shapes
+/// are skipped; Z and M values are dropped.
+pub fn read_shapefile(path: impl AsRef<Path>) -> Result<HilbertCollection, ShapefileError> {
+    let mut reader = shapefile::Reader::from_path(path)?;
+    let mut collection = HilbertCollection::default();
+    for item in reader.iter_shapes_and_records() {
+        let (shape, record) = item?;
+        if let shapefile::Shape::NullShape = shape {
+            continue;
+        }
+        let geom = Geometry::<f64>::try_from(shape)
+            .map_err(|e| ShapefileError::Geometry(e.to_string()))?;
+        let mut properties: Vec<(String, PropertyValue)> = HashMap::from(record)
+            .into_iter()
+            .map(|(k, v)| (k, from_field(v)))
+            .collect();
+        properties.sort_by(|a, b| a.0.cmp(&b.0));
+        collection.push(HilbertFeature {
+            geometry: encode_geometry(&geom),
+            properties,
+            metadata: FeatureMetadata::default(),
+        });
+    }
+    Ok(collection)
+}
+
+/// Field type of a property column, widened as values are seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Logical,
+    Integer,
+    Numeric,
+    Character,
+}
+
+fn column_of(value: &PropertyValue) -> Option<Column> {
+    match value {
+        PropertyValue::Null => None,
+        PropertyValue::Bool(_) => Some(Column::Logical),
+        PropertyValue::Int(_) => Some(Column::Integer),
+        PropertyValue::Double(_) => Some(Column::Numeric),
+        PropertyValue::String(_) => Some(Column::Character),
+    }
+}
+
+fn to_field(value: Option<&PropertyValue>, column: Column) -> FieldValue {
+    match (column, value) {
+        (Column::Logical, Some(PropertyValue::Bool(b))) => FieldValue::Logical(Some(*b)),
+        (Column::Logical, _) => FieldValue::Logical(None),
+        (Column::Integer | Column::Numeric, Some(PropertyValue::Int(i))) => {
+            FieldValue::Numeric(Some(*i as f64))
+        }
+        (Column::Numeric, Some(PropertyValue::Double(d))) => FieldValue::Numeric(Some(*d)),
+        (Column::Integer | Column::Numeric, _) => FieldValue::Numeric(None),
+        (Column::Character, None | Some(PropertyValue::Null)) => FieldValue::Character(None),
+        (Column::Character, Some(PropertyValue::String(s))) => {
+            FieldValue::Character(Some(s.clone()))
+        }
+        (Column::Character, Some(PropertyValue::Bool(b))) => {
+            FieldValue::Character(Some(b.to_string()))
+        }
+        (Column::Character, Some(PropertyValue::Int(i))) => {
+            FieldValue::Character(Some(i.to_string()))
+        }
+        (Column::Character, Some(PropertyValue::Double(d))) => {
+            FieldValue::Character(Some(d.to_string()))
+        }
+    }
+}
+
+/// Writes a collection as a shapefile with a `.dbf` attribute table.
+///
+/// A shapefile holds a single shape type, so all features must be points,
+/// multipoints, (multi)linestrings or (multi)polygons. Columns holding mixed
+/// value types are written as text. Field names are limited to 10 characters.
+pub fn write_shapefile(
+    collection: &HilbertCollection,
+    path: impl AsRef<Path>,
+) -> Result<(), ShapefileError> {
+    // Dimension of the shape type, 0 for single points.
+    let shape_kind = |g: &HilbertGeometry| match g {
+        HilbertGeometry::Point(_) => 0,
+        HilbertGeometry::MultiPoint(_) => 1,
+        HilbertGeometry::LineString(_) | HilbertGeometry::MultiLineString(_) => 2,
+        HilbertGeometry::Polygon(_) | HilbertGeometry::MultiPolygon(_) => 3,
+    };
+    if let Some(first) = collection.features.first() {
+        let kind = shape_kind(&first.geometry);
+        if let Some(other) = collection
+            .features
+            .iter()
+            .find(|f| shape_kind(&f.geometry) != kind)
+        {
+            return Err(ShapefileError::Geometry(format!(
+                "cannot mix {} and {} in one shapefile",
+                first.geometry.kind(),
+                other.geometry.kind()
+            )));
+        }
+    }
+
+    let mut columns: Vec<(String, Column)> = vec![];
+    for (key, value) in collection.features.iter().flat_map(|f| &f.properties) {
+        let Some(column) = column_of(value) else {
+            continue;
+        };
+        match columns.iter_mut().find(|(k, _)| k == key) {
+            Some((_, existing)) if *existing == column => {}
+            Some((_, existing)) => {
+                *existing = match (*existing, column) {
+                    (Column::Integer, Column::Numeric) | (Column::Numeric, Column::Integer) => {
+                        Column::Numeric
+                    }
+                    _ => Column::Character,
+                }
+            }
+            None => columns.push((key.clone(), column)),
+        }
+    }
+    let mut table = TableWriterBuilder::new();
+    for (key, column) in &columns {
+        let name = FieldName::try_from(key.as_str())
+            .map_err(|_| ShapefileError::Field(format!("invalid field name {key:?}")))?;
+        table = match column {
+            Column::Logical => table.add_logical_field(name),
+            Column::Integer => table.add_numeric_field(name, 20, 0),
+            Column::Numeric => table.add_numeric_field(name, 24, 15),
+            Column::Character => table.add_character_field(name, CHARACTER_FIELD_LEN),
+        };
+    }
+
+    let mut writer = shapefile::Writer::from_path(path, table)?;
+    for f in &collection.features {
+        let mut record = Record::default();
+        for (key, column) in &columns {
+            record.insert(key.clone(), to_field(f.property(key), *column));
+        }
+        match decode_geometry(&f.geometry) {
+            Geometry::Point(p) => {
+                writer.write_shape_and_record(&shapefile::Point::from(p), &record)?
+            }
+            Geometry::MultiPoint(mp) => {
+                writer.write_shape_and_record(&shapefile::Multipoint::from(mp), &record)?
+            }
+            Geometry::LineString(ls) => {
+                writer.write_shape_and_record(&shapefile::Polyline::from(ls), &record)?
+            }
+            Geometry::MultiLineString(mls) => {
+                writer.write_shape_and_record(&shapefile::Polyline::from(mls), &record)?
+            }
+            Geometry::Polygon(p) => {
+                writer.write_shape_and_record(&shapefile::Polygon::from(p), &record)?
+            }
+            Geometry::MultiPolygon(mp) => {
+                writer.write_shape_and_record(&shapefile::Polygon::from(mp), &record)?
+            }
+            _ => unreachable!("decoded geometries are never collections"),
+        }
+    }
+    Ok(())
+}
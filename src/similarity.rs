@@ -0,0 +1,122 @@
+//! Trajectory similarity between encoded linestrings, e.g. to find
+//! duplicate GPS traces or to compare a trace with candidate road paths.
+
+use geo_types::{Coord, Rect};
+
+use crate::covering::{cover_rect, DEFAULT_MAX_RANGES};
+use crate::{decode_coord, HilbertGeometry};
+
+fn distance(a: Coord, b: Coord) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+fn vertices(geom: &HilbertGeometry) -> Vec<Coord> {
+    geom.points().map(|p| decode_coord(*p)).collect()
+}
+
+/// Discrete Fréchet distance between the vertex sequences of `a` and `b`,
+/// in degrees: the shortest leash that lets two walkers traverse both
+/// sequences forward, vertex by vertex. Lower means more similar, 0 for
+/// identical traces. `None` if either geometry has no vertices.
+///
+/// Takes O(n * m) time and O(m) memory. Identical encodings are detected
+/// without decoding.
+pub fn similarity(a: &HilbertGeometry, b: &HilbertGeometry) -> Option<f64> {
+    if a == b {
+        return (a.num_coords() > 0).then_some(0.0);
+    }
+    frechet(&vertices(a), &vertices(b))
+}
+
+/// Returns whether the [`similarity`] of `a` and `b` is at most
+/// `max_distance`.
+///
+/// Identical encodings are accepted by comparing their curve indices, and
+/// cheap checks run before the full computation: the distance between the
+/// endpoints, the gap between the bounding boxes, and whether the curve
+/// indices of each trace fall within a covering of the other's bounding box
+/// grown by `max_distance`. Most unrelated traces are rejected by these.
+pub fn within_distance(a: &HilbertGeometry, b: &HilbertGeometry, max_distance: f64) -> bool {
+    let ends = |g: &HilbertGeometry| Some((*g.points().next()?, *g.points().last()?));
+    let (Some((a_first, a_last)), Some((b_first, b_last))) = (ends(a), ends(b)) else {
+        return false;
+    };
+    if a == b {
+        return max_distance >= 0.0;
+    }
+    // Both walkers start together and end together.
+    let endpoints = distance(decode_coord(a_first), decode_coord(b_first))
+        .max(distance(decode_coord(a_last), decode_coord(b_last)));
+    if endpoints > max_distance {
+        return false;
+    }
+    let (Some(a_box), Some(b_box)) = (a.bounding_rect(), b.bounding_rect()) else {
+        return false;
+    };
+    if rect_gap(&a_box, &b_box) > max_distance {
+        return false;
+    }
+    // Every vertex lies within `max_distance` of a vertex of the other trace,
+    // so its index must be covered by the ranges of the grown bounding box.
+    if max_distance.is_finite()
+        && !(keys_covered(b, &grow(&a_box, max_distance))
+            && keys_covered(a, &grow(&b_box, max_distance)))
+    {
+        return false;
+    }
+    frechet(&vertices(a), &vertices(b)).is_some_and(|d| d <= max_distance)
+}
+
+/// Grows a rectangle by `d` on every side.
+fn grow(rect: &Rect, d: f64) -> Rect {
+    Rect::new(
+        Coord {
+            x: rect.min().x - d,
+            y: rect.min().y - d,
+        },
+        Coord {
+            x: rect.max().x + d,
+            y: rect.max().y + d,
+        },
+    )
+}
+
+/// Returns whether every curve index of `geom` falls within the ranges
+/// covering `rect`.
+fn keys_covered(geom: &HilbertGeometry, rect: &Rect) -> bool {
+    let ranges = cover_rect(rect, DEFAULT_MAX_RANGES);
+    geom.points().all(|p| {
+        let i = ranges.partition_point(|r| *r.end() < p.0);
+        ranges.get(i).is_some_and(|r| r.contains(&p.0))
+    })
+}
+
+/// Distance between the closest points of two rectangles.
+fn rect_gap(a: &Rect, b: &Rect) -> f64 {
+    let dx = (b.min().x - a.max().x).max(a.min().x - b.max().x).max(0.0);
+    let dy = (b.min().y - a.max().y).max(a.min().y - b.max().y).max(0.0);
+    dx.hypot(dy)
+}
+
+fn frechet(a: &[Coord], b: &[Coord]) -> Option<f64> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    // Row i holds the distance of the best coupling of a[..=i] and b[..=j].
+    let mut row = vec![0.0; b.len()];
+    for (i, p) in a.iter().enumerate() {
+        let mut diagonal = 0.0f64;
+        for (j, q) in b.iter().enumerate() {
+            let d = distance(*p, *q);
+            let above = row[j];
+            row[j] = match (i, j) {
+                (0, 0) => d,
+                (0, _) => row[j - 1].max(d),
+                (_, 0) => above.max(d),
+                _ => diagonal.min(above).min(row[j - 1]).max(d),
+            };
+            diagonal = above;
+        }
+    }
+    row.last().copied()
+}
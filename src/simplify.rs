@@ -0,0 +1,84 @@
+use geo_types::Coord;
+
+use crate::{decode_coord, HilbertGeometry, HilbertPoint};
+
+/// Distance from `p` to the segment from `a` to `b`.
+fn segment_distance(p: Coord, a: Coord, b: Coord) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len2 = dx * dx + dy * dy;
+    let t = if len2 == 0.0 {
+        0.0
+    } else {
+        (((p.x - a.x) * dx + (p.y - a.y) * dy) / len2).clamp(0.0, 1.0)
+    };
+    (p.x - a.x - t * dx).hypot(p.y - a.y - t * dy)
+}
+
+/// Douglas–Peucker over one vertex sequence, keeping the original indices
+/// of the vertices that remain.
+fn simplify_points(points: &[HilbertPoint], tolerance: f64) -> Vec<HilbertPoint> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let coords: Vec<Coord> = points.iter().map(|p| decode_coord(*p)).collect();
+    let mut keep = vec![false; coords.len()];
+    keep[0] = true;
+    keep[coords.len() - 1] = true;
+    let mut stack = vec![(0, coords.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let (index, max) = (first + 1..last)
+            .map(|i| (i, segment_distance(coords[i], coords[first], coords[last])))
+            .fold((0, -1.0), |best, d| if d.1 > best.1 { d } else { best });
+        if max > tolerance {
+            keep[index] = true;
+            stack.extend([(first, index), (index, last)]);
+        }
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(p, keep)| keep.then_some(*p))
+        .collect()
+}
+
+/// Simplifies a closed ring, keeping it as is if it would collapse below
+/// four vertices.
+fn simplify_ring(ring: &[HilbertPoint], tolerance: f64) -> Vec<HilbertPoint> {
+    let simplified = simplify_points(ring, tolerance);
+    if simplified.len() < 4 {
+        ring.to_vec()
+    } else {
+        simplified
+    }
+}
+
+impl HilbertGeometry {
+    /// Simplifies linestrings and rings with the Douglas–Peucker algorithm,
+    /// dropping vertices closer than `tolerance` degrees to the simplified
+    /// line, e.g. to derive coarser zoom levels from stored data.
+    ///
+    /// Parts are decoded one at a time, and the remaining vertices keep
+    /// their stored indices, so nothing is re-encoded and no precision is
+    /// lost. Rings that would collapse below four vertices are kept as they
+    /// are. Points and multipoints are returned unchanged.
+    pub fn simplified(&self, tolerance: f64) -> HilbertGeometry {
+        let line = |ps: &Vec<HilbertPoint>| simplify_points(ps, tolerance);
+        let rings = |rings: &Vec<Vec<HilbertPoint>>| {
+            rings
+                .iter()
+                .map(|r| simplify_ring(r, tolerance))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            HilbertGeometry::Point(_) | HilbertGeometry::MultiPoint(_) => self.clone(),
+            HilbertGeometry::LineString(ps) => HilbertGeometry::LineString(line(ps)),
+            HilbertGeometry::Polygon(rs) => HilbertGeometry::Polygon(rings(rs)),
+            HilbertGeometry::MultiLineString(lines) => {
+                HilbertGeometry::MultiLineString(lines.iter().map(line).collect())
+            }
+            HilbertGeometry::MultiPolygon(polys) => {
+                HilbertGeometry::MultiPolygon(polys.iter().map(rings).collect())
+            }
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use std::ops::RangeInclusive;
+
+use bincode::{Decode, Encode};
+use geo_types::{Coord, LineString, Rect};
+
+use crate::covering::cover_rect;
+use crate::HilbertPoint;
+
+/// Bits used per dimension. Three dimensions fit into a 63-bit key.
+const BITS: u32 = 21;
+const MAX_CELL: f64 = ((1u32 << BITS) - 1) as f64;
+
+/// Represents a point encoded on a 3D (lon, lat, time) Hilbert curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Decode, Encode)]
+pub struct SpatioTemporalPoint(pub u64);
+
+/// Time range mapped onto the time axis of the curve.
+///
+/// Timestamps are plain `f64` values in any consistent unit (e.g. seconds since
+/// Unix epoch). Values outside of the range are clamped to its bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Decode, Encode)]
+pub struct TimeRange {
+    start: f64,
+    end: f64,
+}
+
+impl TimeRange {
+    /// # Panics
+    ///
+    /// If `end` is not after `start`.
+    pub fn new(start: f64, end: f64) -> Self {
+        assert!(end > start, "time range end must be after start");
+        Self { start, end }
+    }
+
+    pub fn start(&self) -> f64 {
+        self.start
+    }
+
+    pub fn end(&self) -> f64 {
+        self.end
+    }
+}
+
+/// Encodes coordinates with timestamps on a 3D Hilbert curve, so that keys of
+/// nearby positions at nearby times end up close to each other.
+#[derive(Debug, Clone, Copy)]
+pub struct SpatioTemporalEncoder {
+    range: TimeRange,
+}
+
+impl SpatioTemporalEncoder {
+    pub fn new(range: TimeRange) -> Self {
+        Self { range }
+    }
+
+    pub fn range(&self) -> TimeRange {
+        self.range
+    }
+
+    /// Encodes a lon/lat coordinate and a timestamp into a curve key.
+    pub fn encode(&self, coord: Coord<f64>, t: f64) -> SpatioTemporalPoint {
+        let mut axes = [
+            quantize(coord.x, -180.0, 180.0),
+            quantize(coord.y, -90.0, 90.0),
+            quantize(t, self.range.start, self.range.end),
+        ];
+        axes_to_transpose(&mut axes);
+        SpatioTemporalPoint(interleave(&axes))
+    }
+
+    /// Decodes a curve key back into a coordinate and timestamp.
+    pub fn decode(&self, p: SpatioTemporalPoint) -> (Coord<f64>, f64) {
+        let mut axes = deinterleave(p.0);
+        transpose_to_axes(&mut axes);
+        let coord = Coord {
+            x: dequantize(axes[0], -180.0, 180.0),
+            y: dequantize(axes[1], -90.0, 90.0),
+        };
+        (coord, dequantize(axes[2], self.range.start, self.range.end))
+    }
+
+    /// Encodes a trajectory, pairing each vertex with its timestamp.
+    ///
+    /// Panics if the number of timestamps does not match the number of vertices.
+    pub fn encode_trajectory(&self, ls: &LineString, times: &[f64]) -> Vec<SpatioTemporalPoint> {
+        assert_eq!(ls.0.len(), times.len(), "one timestamp per vertex required");
+        ls.coords()
+            .zip(times)
+            .map(|(c, t)| self.encode(*c, *t))
+            .collect()
+    }
+
+    /// Decodes a trajectory into a linestring and its per-vertex timestamps.
+    pub fn decode_trajectory(&self, points: &[SpatioTemporalPoint]) -> (LineString, Vec<f64>) {
+        let (coords, times) = points.iter().map(|p| self.decode(*p)).unzip();
+        (LineString(coords), times)
+    }
+}
+
+/// Builds `u64` keys of a time bucket followed by the top bits of a Hilbert
+/// index, for stores that mostly query recent data by area.
+///
+/// The top `time_bits` bits hold the bucket of the timestamp, counted from
+/// `origin` in steps of `bucket_len`, and the remaining bits the most
+/// significant bits of the index. Keys of one bucket sort in curve order, so
+/// "this area, last hour" with hour buckets is a few ranges in each of two
+/// buckets. Unlike [`SpatioTemporalEncoder`], time has no fixed range: more
+/// time bits allow more buckets at the cost of spatial precision.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeSlicedKeyBuilder {
+    origin: f64,
+    bucket_len: f64,
+    time_bits: u32,
+}
+
+impl TimeSlicedKeyBuilder {
+    /// # Panics
+    ///
+    /// If `bucket_len` is not positive or `time_bits` is not between 1 and 63.
+    pub fn new(origin: f64, bucket_len: f64, time_bits: u32) -> Self {
+        assert!(bucket_len > 0.0, "bucket length must be positive");
+        assert!(
+            (1..64).contains(&time_bits),
+            "time bits must be between 1 and 63"
+        );
+        Self {
+            origin,
+            bucket_len,
+            time_bits,
+        }
+    }
+
+    /// Number of index bits kept in a key.
+    pub fn spatial_bits(&self) -> u32 {
+        64 - self.time_bits
+    }
+
+    /// Bucket of timestamp `t`. Timestamps before `origin` fall in bucket 0
+    /// and those past the last bucket in the last one.
+    pub fn bucket(&self, t: f64) -> u64 {
+        let max = (1u64 << self.time_bits) - 1;
+        let bucket = ((t - self.origin) / self.bucket_len).floor();
+        if bucket >= max as f64 {
+            max
+        } else {
+            bucket.max(0.0) as u64
+        }
+    }
+
+    /// Start time of `bucket`.
+    pub fn bucket_start(&self, bucket: u64) -> f64 {
+        self.origin + bucket as f64 * self.bucket_len
+    }
+
+    pub fn key(&self, p: HilbertPoint, t: f64) -> u64 {
+        self.bucket(t) << self.spatial_bits() | p.0 >> self.time_bits
+    }
+
+    /// Splits a key into its bucket and the indices it may hold.
+    pub fn split(&self, key: u64) -> (u64, RangeInclusive<u64>) {
+        let start = key << self.time_bits;
+        (
+            key >> self.spatial_bits(),
+            start..=start | ((1 << self.time_bits) - 1),
+        )
+    }
+
+    /// Returns sorted key ranges holding every point inside `rect` with a
+    /// timestamp from `start` to `end`, covering `rect` with at most roughly
+    /// `max_ranges` index ranges per bucket.
+    ///
+    /// Ranges hold whole buckets and may include points outside of `rect`,
+    /// so results should be filtered by position and time afterwards.
+    pub fn scan_ranges(
+        &self,
+        rect: &Rect,
+        start: f64,
+        end: f64,
+        max_ranges: usize,
+    ) -> Vec<RangeInclusive<u64>> {
+        let spatial = cover_rect(rect, max_ranges);
+        let mut ranges: Vec<RangeInclusive<u64>> = vec![];
+        for bucket in self.bucket(start)..=self.bucket(end) {
+            let prefix = bucket << self.spatial_bits();
+            for r in &spatial {
+                let (lo, hi) = (
+                    prefix | r.start() >> self.time_bits,
+                    prefix | r.end() >> self.time_bits,
+                );
+                match ranges.last_mut() {
+                    Some(last) if lo <= last.end().saturating_add(1) => {
+                        *last = *last.start()..=hi.max(*last.end());
+                    }
+                    _ => ranges.push(lo..=hi),
+                }
+            }
+        }
+        ranges
+    }
+}
+
+fn quantize(v: f64, min: f64, max: f64) -> u32 {
+    // Decoded time ranges skip the check in `TimeRange::new`.
+    if max.partial_cmp(&min) != Some(std::cmp::Ordering::Greater) {
+        return 0;
+    }
+    let n = ((v - min) / (max - min)).clamp(0.0, 1.0);
+    (n * MAX_CELL).round() as u32
+}
+
+fn dequantize(v: u32, min: f64, max: f64) -> f64 {
+    min + (v as f64 / MAX_CELL) * (max - min)
+}
+
+/// Converts axes into the transposed Hilbert index (Skilling, 2004).
+fn axes_to_transpose(x: &mut [u32; 3]) {
+    let m = 1u32 << (BITS - 1);
+    let mut q = m;
+    while q > 1 {
+        let p = q - 1;
+        for i in 0..x.len() {
+            if x[i] & q != 0 {
+                x[0] ^= p;
+            } else {
+                let t = (x[0] ^ x[i]) & p;
+                x[0] ^= t;
+                x[i] ^= t;
+            }
+        }
+        q >>= 1;
+    }
+    for i in 1..x.len() {
+        x[i] ^= x[i - 1];
+    }
+    let mut t = 0;
+    let mut q = m;
+    while q > 1 {
+        if x[2] & q != 0 {
+            t ^= q - 1;
+        }
+        q >>= 1;
+    }
+    x.iter_mut().for_each(|v| *v ^= t);
+}
+
+/// Converts a transposed Hilbert index back into axes (Skilling, 2004).
+fn transpose_to_axes(x: &mut [u32; 3]) {
+    let t = x[2] >> 1;
+    for i in (1..x.len()).rev() {
+        x[i] ^= x[i - 1];
+    }
+    x[0] ^= t;
+    let mut q = 2;
+    while q != 1 << BITS {
+        let p = q - 1;
+        for i in (0..x.len()).rev() {
+            if x[i] & q != 0 {
+                x[0] ^= p;
+            } else {
+                let t = (x[0] ^ x[i]) & p;
+                x[0] ^= t;
+                x[i] ^= t;
+            }
+        }
+        q <<= 1;
+    }
+}
+
+fn interleave(x: &[u32; 3]) -> u64 {
+    let mut h = 0u64;
+    for b in (0..BITS).rev() {
+        for v in x {
+            h = (h << 1) | ((v >> b) & 1) as u64;
+        }
+    }
+    h
+}
+
+fn deinterleave(h: u64) -> [u32; 3] {
+    let mut x = [0u32; 3];
+    for b in 0..BITS {
+        for (i, v) in x.iter_mut().enumerate() {
+            let shift = b * 3 + (2 - i as u32);
+            *v |= (((h >> shift) & 1) as u32) << b;
+        }
+    }
+    x
+}
@@ -0,0 +1,255 @@
+//! Helpers for testing pipelines built on the codec.
+//!
+//! [`assert_roundtrip`] checks the guarantees the encoding gives: the
+//! structure of a geometry is kept as is, coordinates are preserved within
+//! `f32` precision, and the bincode form decodes to the same value. With the
+//! `proptest` feature, [`strategies`] provides generators for arbitrary
+//! encodable geometries.
+
+use bincode::config;
+use geo_types::{Coord, Geometry, LineString, Polygon};
+
+use crate::{decode_geometry, encode_geometry, HilbertGeometry};
+
+/// Largest per-axis error of a lon/lat coordinate after a round trip, in
+/// degrees. Half of the `f32` spacing at 180.
+pub const LONLAT_EPSILON: f64 = 7.63e-6;
+
+fn rings(poly: &Polygon) -> Vec<&LineString> {
+    std::iter::once(poly.exterior())
+        .chain(poly.interiors())
+        .collect()
+}
+
+/// Coordinates of a geometry grouped by part, with a tag naming the part
+/// structure. Two geometries with equal shapes differ only in coordinates.
+fn parts(geom: &Geometry) -> (&'static str, Vec<Vec<Coord>>) {
+    match geom {
+        Geometry::Point(p) => ("Point", vec![vec![p.0]]),
+        Geometry::LineString(ls) => ("LineString", vec![ls.0.clone()]),
+        Geometry::Polygon(p) => ("Polygon", rings(p).iter().map(|r| r.0.clone()).collect()),
+        Geometry::MultiPoint(mp) => ("MultiPoint", vec![mp.0.iter().map(|p| p.0).collect()]),
+        Geometry::MultiLineString(mls) => (
+            "MultiLineString",
+            mls.0.iter().map(|ls| ls.0.clone()).collect(),
+        ),
+        Geometry::MultiPolygon(mp) => {
+            let mut parts = vec![];
+            for p in &mp.0 {
+                // Polygon boundaries are marked with an empty part.
+                parts.push(vec![]);
+                parts.extend(rings(p).iter().map(|r| r.0.clone()));
+            }
+            ("MultiPolygon", parts)
+        }
+        _ => panic!("geometry type can't be encoded: {geom:?}"),
+    }
+}
+
+/// Encodes and decodes `geom`, returning the largest per-axis coordinate
+/// error. Panics if the decoded geometry has a different structure.
+pub fn roundtrip_error(geom: &Geometry) -> f64 {
+    let decoded = decode_geometry(&encode_geometry(geom));
+    let (kind, expected) = parts(geom);
+    let (decoded_kind, actual) = parts(&decoded);
+    assert_eq!(kind, decoded_kind, "geometry type changed");
+    assert_eq!(
+        expected.iter().map(Vec::len).collect::<Vec<_>>(),
+        actual.iter().map(Vec::len).collect::<Vec<_>>(),
+        "part sizes changed"
+    );
+    expected
+        .iter()
+        .flatten()
+        .zip(actual.iter().flatten())
+        .map(|(a, b)| (a.x - b.x).abs().max((a.y - b.y).abs()))
+        .fold(0.0, f64::max)
+}
+
+/// Asserts that `geom` survives encoding, bincode serialization and decoding
+/// with every coordinate within `epsilon` of the input on both axes.
+///
+/// Panics with a description of the first violated guarantee. Use
+/// [`LONLAT_EPSILON`] for lon/lat data.
+pub fn assert_roundtrip(geom: &Geometry, epsilon: f64) {
+    let encoded = encode_geometry(geom);
+    let bytes = bincode::encode_to_vec(&encoded, config::standard()).unwrap();
+    let (decoded, read): (HilbertGeometry, _) =
+        bincode::decode_from_slice(&bytes, config::standard()).unwrap();
+    assert_eq!(read, bytes.len(), "bincode data not fully consumed");
+    assert_eq!(decoded, encoded, "bincode round trip changed the geometry");
+
+    let error = roundtrip_error(geom);
+    assert!(
+        error <= epsilon,
+        "coordinate error {error:e} exceeds {epsilon:e} for {geom:?}"
+    );
+    // Decoded coordinates are exact f32 values, so encoding is idempotent.
+    assert_eq!(
+        encode_geometry(&decode_geometry(&encoded)),
+        encoded,
+        "re-encoding changed the geometry"
+    );
+}
+
+/// `proptest` strategies generating encodable lon/lat geometries.
+#[cfg(feature = "proptest")]
+pub mod strategies {
+    use geo_types::{
+        Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+    };
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    /// Coordinates within lon/lat bounds.
+    pub fn coord() -> impl Strategy<Value = Coord> {
+        (-180.0..=180.0, -90.0..=90.0).prop_map(|(x, y)| Coord { x, y })
+    }
+
+    pub fn point() -> impl Strategy<Value = Point> {
+        coord().prop_map(Point)
+    }
+
+    /// Linestrings of up to `max_len` coordinates.
+    pub fn line_string(max_len: usize) -> impl Strategy<Value = LineString> {
+        vec(coord(), 0..=max_len).prop_map(LineString)
+    }
+
+    /// Closed rings of 4 to `max_len + 1` coordinates.
+    pub fn ring(max_len: usize) -> impl Strategy<Value = LineString> {
+        vec(coord(), 3..=max_len.max(3)).prop_map(|coords| {
+            let mut ring = LineString(coords);
+            ring.close();
+            ring
+        })
+    }
+
+    /// Polygons with up to `max_holes` interior rings. Rings are not
+    /// guaranteed to be valid, which the codec doesn't require.
+    pub fn polygon(max_len: usize, max_holes: usize) -> impl Strategy<Value = Polygon> {
+        (ring(max_len), vec(ring(max_len), 0..=max_holes))
+            .prop_map(|(exterior, interiors)| Polygon::new(exterior, interiors))
+    }
+
+    /// Any geometry type supported by the codec.
+    pub fn geometry() -> impl Strategy<Value = Geometry> {
+        prop_oneof![
+            point().prop_map(Geometry::Point),
+            line_string(16).prop_map(Geometry::LineString),
+            polygon(16, 2).prop_map(Geometry::Polygon),
+            vec(point(), 0..8).prop_map(|p| Geometry::MultiPoint(MultiPoint(p))),
+            vec(line_string(8), 0..4).prop_map(|ls| Geometry::MultiLineString(MultiLineString(ls))),
+            vec(polygon(8, 1), 0..4).prop_map(|p| Geometry::MultiPolygon(MultiPolygon(p))),
+        ]
+    }
+}
+
+/// Fuzz targets for pipelines embedding the codec, and the `arbitrary`
+/// implementation they build on.
+///
+/// [`HilbertGeometry`] implements [`Arbitrary`](arbitrary::Arbitrary) with
+/// indices of lon/lat coordinates and closed rings, so generated geometries
+/// always decode and survive [`assert_roundtrip`]. The targets only panic on
+/// a bug. With `cargo fuzz`:
+///
+/// ```ignore
+/// fuzz_target!(|data: &[u8]| hilbert_geometry::testing::fuzz::decode(data));
+/// ```
+#[cfg(feature = "arbitrary")]
+pub mod fuzz {
+    use arbitrary::{Arbitrary, Result, Unstructured};
+    use bincode::config;
+    use geo_types::Coord;
+
+    use super::assert_roundtrip;
+    use crate::payload::{
+        decode_payload, decode_payload_lenient, decode_payload_original, encode_payload,
+        payload_metadata, Encoding, EncodingMode,
+    };
+    use crate::{decode_geometry, encode_coord, HilbertGeometry, HilbertPoint, HilbertSerializer};
+
+    fn point(u: &mut Unstructured) -> Result<HilbertPoint> {
+        // Microdegree steps, which keep the whole range reachable from a
+        // few bytes.
+        let x = u.int_in_range(-180_000_000..=180_000_000)? as f64 * 1e-6;
+        let y = u.int_in_range(-90_000_000..=90_000_000)? as f64 * 1e-6;
+        Ok(encode_coord(Coord { x, y }))
+    }
+
+    fn points(u: &mut Unstructured) -> Result<Vec<HilbertPoint>> {
+        let len = u.arbitrary_len::<[u32; 2]>()?;
+        (0..len).map(|_| point(u)).collect()
+    }
+
+    fn ring(u: &mut Unstructured) -> Result<Vec<HilbertPoint>> {
+        let mut ring = (0..3).map(|_| point(u)).collect::<Result<Vec<_>>>()?;
+        ring.extend(points(u)?);
+        ring.push(ring[0]);
+        Ok(ring)
+    }
+
+    fn polygon(u: &mut Unstructured) -> Result<Vec<Vec<HilbertPoint>>> {
+        let holes = u.int_in_range(0..=3)?;
+        (0..=holes).map(|_| ring(u)).collect()
+    }
+
+    fn parts<T>(u: &mut Unstructured, part: fn(&mut Unstructured) -> Result<T>) -> Result<Vec<T>> {
+        let len = u.int_in_range(0..=4)?;
+        (0..len).map(|_| part(u)).collect()
+    }
+
+    impl<'a> Arbitrary<'a> for HilbertGeometry {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            Ok(match u.int_in_range(0..=5)? {
+                0 => HilbertGeometry::Point(point(u)?),
+                1 => HilbertGeometry::LineString(points(u)?),
+                2 => HilbertGeometry::Polygon(polygon(u)?),
+                3 => HilbertGeometry::MultiPoint(points(u)?),
+                4 => HilbertGeometry::MultiLineString(parts(u, points)?),
+                _ => HilbertGeometry::MultiPolygon(parts(u, polygon)?),
+            })
+        }
+    }
+
+    /// Feeds `data` to every decoder. Errors are expected; panics, hangs
+    /// and runaway allocations are bugs. Geometries that do decode must
+    /// re-encode to the same value.
+    pub fn decode(data: &[u8]) {
+        let cfg = config::standard();
+        if let Ok(hg) = HilbertGeometry::decode_bincode_detailed(data, &cfg) {
+            let bytes = hg.clone().encode_bincode(&cfg).unwrap();
+            assert_eq!(HilbertGeometry::decode_bincode(&bytes, &cfg).unwrap(), hg);
+            decode_geometry(&hg);
+        }
+        let _ = HilbertSerializer::new().decode(data);
+        let _ = decode_payload(data);
+        let _ = decode_payload_lenient(data);
+        let _ = decode_payload_original(data);
+        let _ = payload_metadata(data);
+    }
+
+    /// Builds a geometry from `data` and checks it through
+    /// [`assert_roundtrip`] and every payload encoding, the lossless ones
+    /// exactly.
+    pub fn roundtrip(data: &[u8]) -> Result<()> {
+        let hg = HilbertGeometry::arbitrary(&mut Unstructured::new(data))?;
+        let geom = decode_geometry(&hg);
+        // Coordinates are exact `f32` values, so nothing may move.
+        assert_roundtrip(&geom, 0.0);
+        for encoding in [
+            Encoding::Hilbert,
+            Encoding::HilbertDelta,
+            Encoding::Raw,
+            Encoding::RawKeyed,
+        ] {
+            let payload = encode_payload(&geom, EncodingMode::Fixed(encoding)).unwrap();
+            assert_eq!(decode_payload(&payload).unwrap(), geom, "{encoding:?}");
+        }
+        // Lossy encodings only need to decode.
+        for encoding in [Encoding::Quantized, Encoding::Hilbert128, Encoding::Half] {
+            let payload = encode_payload(&geom, EncodingMode::Fixed(encoding)).unwrap();
+            decode_payload(&payload).unwrap();
+        }
+        Ok(())
+    }
+}
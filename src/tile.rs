@@ -0,0 +1,234 @@
+//! Slippy map (XYZ) tile helpers for encoded geometries, and a compact
+//! encoding for geometries inside a single curve tile.
+
+use std::f64::consts::PI;
+
+use bincode::{
+    config,
+    error::{DecodeError, EncodeError},
+};
+use geo_types::{Coord, Geometry};
+
+use crate::cell::{HilbertCell, MAX_LEVEL};
+use crate::decode::limited;
+use crate::layout::{flatten, rebuild, Layout};
+use crate::quantize::{quantize_geometry, Rounding};
+use crate::{
+    decode_coord, decode_geometry, encode_geometry, map_geometry_coords, HilbertGeometry,
+    HilbertPoint,
+};
+
+/// Latitude limit of the Web Mercator projection.
+const MAX_LATITUDE: f64 = 85.051_128_78;
+
+/// Deepest zoom accepted by the tile helpers, so tile columns and rows fit in
+/// a `u32`.
+pub const MAX_ZOOM: u8 = 32;
+
+/// Number of tiles along each axis at `zoom`.
+fn tiles_per_side(zoom: u8) -> f64 {
+    assert!(zoom <= MAX_ZOOM, "zoom {zoom} above {MAX_ZOOM}");
+    (1u64 << zoom) as f64
+}
+
+/// Projects a lon/lat coordinate to Web Mercator world coordinates in tile
+/// units at `zoom`: the map spans `[0, 2^zoom]` on both axes, y pointing south.
+/// Panics if `zoom` is above [`MAX_ZOOM`].
+pub fn lonlat_to_tile_units(c: Coord, zoom: u8) -> Coord {
+    let n = tiles_per_side(zoom);
+    let lat = c.y.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+    Coord {
+        x: (c.x + 180.0) / 360.0 * n,
+        y: (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / PI) / 2.0 * n,
+    }
+}
+
+/// Inverse of [`lonlat_to_tile_units`]. Panics if `zoom` is above [`MAX_ZOOM`].
+pub fn tile_units_to_lonlat(c: Coord, zoom: u8) -> Coord {
+    let n = tiles_per_side(zoom);
+    Coord {
+        x: c.x / n * 360.0 - 180.0,
+        y: (PI * (1.0 - 2.0 * c.y / n)).sinh().atan().to_degrees(),
+    }
+}
+
+/// Snaps every coordinate to the pixel grid of XYZ tiles at `zoom`, with
+/// `extent` pixels per tile side (256 for raster tiles, 4096 for vector tiles).
+///
+/// Coordinates from different sources that fall on the same pixel become
+/// identical, so they encode to the same indices. Latitudes are clamped to
+/// the Web Mercator range. Panics if `zoom` is above [`MAX_ZOOM`].
+pub fn snap_to_tile_grid(geom: &Geometry, zoom: u8, extent: u32) -> Geometry {
+    let extent = extent as f64;
+    map_geometry_coords(geom, &|c| {
+        let px = lonlat_to_tile_units(c, zoom);
+        tile_units_to_lonlat(
+            Coord {
+                x: (px.x * extent).round() / extent,
+                y: (px.y * extent).round() / extent,
+            },
+            zoom,
+        )
+    })
+}
+
+/// Tile column and row containing a lon/lat coordinate at `zoom`.
+fn tile_of(c: Coord, zoom: u8) -> (u32, u32) {
+    let t = lonlat_to_tile_units(c, zoom);
+    let max = tiles_per_side(zoom) - 1.0;
+    (
+        t.x.floor().clamp(0.0, max) as u32,
+        t.y.floor().clamp(0.0, max) as u32,
+    )
+}
+
+impl HilbertPoint {
+    /// Returns the `(x, y, z)` slippy tile containing the point at zoom `z`,
+    /// or `None` if `z` is above [`MAX_ZOOM`].
+    pub fn tile(self, z: u8) -> Option<(u32, u32, u8)> {
+        if z > MAX_ZOOM {
+            return None;
+        }
+        let (x, y) = tile_of(decode_coord(self), z);
+        Some((x, y, z))
+    }
+}
+
+/// Returns the `(x, y, z)` tiles at zoom `z` touched by the bounding box of
+/// `geom`, row by row. Tiles are produced lazily, as large boxes at deep zooms
+/// touch billions of them. Empty geometries touch no tiles. Returns `None` if
+/// `z` is above [`MAX_ZOOM`].
+pub fn tile_range(
+    geom: &HilbertGeometry,
+    z: u8,
+) -> Option<impl Iterator<Item = (u32, u32, u8)> + use<>> {
+    if z > MAX_ZOOM {
+        return None;
+    }
+    // Tile rows grow southwards.
+    let bounds = geom.bounding_rect().map(|rect| {
+        let (x0, y0) = tile_of(
+            Coord {
+                x: rect.min().x,
+                y: rect.max().y,
+            },
+            z,
+        );
+        let (x1, y1) = tile_of(
+            Coord {
+                x: rect.max().x,
+                y: rect.min().y,
+            },
+            z,
+        );
+        (x0, y0, x1, y1)
+    });
+    Some(bounds.into_iter().flat_map(move |(x0, y0, x1, y1)| {
+        (y0..=y1).flat_map(move |y| (x0..=x1).map(move |x| (x, y, z)))
+    }))
+}
+
+/// Pixels per tile side assumed by [`generalize`], as in vector tiles.
+pub const GENERALIZE_EXTENT: u32 = 4096;
+
+/// Returns a copy of `hg` for each of `zooms`, in the given order, suited to
+/// tiles at that zoom.
+///
+/// Each copy is simplified with a tolerance of one pixel of a
+/// [`GENERALIZE_EXTENT`] tile, measured in degrees of longitude, and then
+/// rounded to the fewest decimal places that still resolve a quarter pixel.
+/// Coarse zooms thus keep few vertices on a coarse grid, so equal outlines
+/// encode to equal indices. Fails if a zoom is above [`MAX_ZOOM`].
+pub fn generalize(
+    hg: &HilbertGeometry,
+    zooms: &[u8],
+) -> Result<Vec<(u8, HilbertGeometry)>, EncodeError> {
+    if let Some(zoom) = zooms.iter().find(|z| **z > MAX_ZOOM) {
+        return Err(EncodeError::OtherString(format!(
+            "zoom {zoom} above {MAX_ZOOM}"
+        )));
+    }
+    Ok(zooms
+        .iter()
+        .map(|&zoom| {
+            let pixel = 360.0 / (tiles_per_side(zoom) * GENERALIZE_EXTENT as f64);
+            let places = (-(pixel / 4.0).log10()).ceil().max(0.0) as u32;
+            let simplified = decode_geometry(&hg.simplified(pixel));
+            let quantized = quantize_geometry(&simplified, places, Rounding::Round);
+            (zoom, encode_geometry(&quantized))
+        })
+        .collect())
+}
+
+/// Number of low index bits below a curve tile at `level`.
+fn tile_shift(level: u32) -> u32 {
+    2 * (MAX_LEVEL - level)
+}
+
+/// Returns the curve tile at `level` containing `p`: the key of its
+/// [`HilbertCell`], i.e. the top `2 * level` bits of its index. Curve tiles
+/// are unrelated to XYZ tiles.
+///
+/// # Panics
+///
+/// If `level` is above [`MAX_LEVEL`].
+pub fn curve_tile(p: HilbertPoint, level: u32) -> u64 {
+    HilbertCell::containing(p, level).key
+}
+
+/// Encodes a geometry whose vertices all lie in one curve tile at `level`,
+/// storing each vertex as a fixed-width offset from the tile's first index.
+///
+/// Each vertex takes `(64 - 2 * level) / 8` bytes rounded up, e.g. 4 bytes at
+/// level 16. Fails if the vertices span several tiles or `level` is above
+/// [`MAX_LEVEL`].
+pub fn encode_tile_local(hg: &HilbertGeometry, level: u32) -> Result<Vec<u8>, EncodeError> {
+    if level > MAX_LEVEL {
+        return Err(EncodeError::Other("curve tile level must be at most 32"));
+    }
+    let (layout, points) = flatten(hg);
+    let tile = points.first().map_or(0, |p| curve_tile(*p, level));
+    if points.iter().any(|p| curve_tile(*p, level) != tile) {
+        return Err(EncodeError::Other("geometry spans multiple curve tiles"));
+    }
+    let shift = tile_shift(level);
+    let width = shift.div_ceil(8) as usize;
+    let mut buf = bincode::encode_to_vec((&layout, level as u8, tile), config::standard())?;
+    buf.reserve(points.len() * width);
+    for p in &points {
+        buf.extend_from_slice(&p.0.to_le_bytes()[..width]);
+    }
+    Ok(buf)
+}
+
+/// Decodes a geometry written by [`encode_tile_local`].
+pub fn decode_tile_local(data: &[u8]) -> Result<HilbertGeometry, DecodeError> {
+    let ((layout, level, tile), read): ((Layout, u8, u64), usize) =
+        bincode::decode_from_slice(data, limited(config::standard()))?;
+    let level = level as u32;
+    if level > MAX_LEVEL {
+        return Err(DecodeError::Other("invalid curve tile level"));
+    }
+    // There are 4^level tiles at a level.
+    if u128::from(tile) >= 1u128 << (2 * level) {
+        return Err(DecodeError::Other("invalid curve tile"));
+    }
+    let shift = tile_shift(level);
+    let width = shift.div_ceil(8) as usize;
+    let body = &data[read..];
+    let expected = layout.num_points() * width;
+    if body.len() < expected {
+        return Err(DecodeError::UnexpectedEnd {
+            additional: expected - body.len(),
+        });
+    }
+    let origin = *HilbertCell { level, key: tile }.range().start();
+    let points = (0..layout.num_points())
+        .map(|i| {
+            let mut bytes = [0u8; 8];
+            bytes[..width].copy_from_slice(&body[i * width..(i + 1) * width]);
+            HilbertPoint(origin | u64::from_le_bytes(bytes))
+        })
+        .collect();
+    Ok(rebuild(&layout, points))
+}
@@ -0,0 +1,271 @@
+use std::collections::{HashMap, HashSet};
+
+use bincode::{
+    config::Configuration,
+    de::Decoder,
+    error::{DecodeError, EncodeError},
+    Decode, Encode,
+};
+
+use crate::decode::limited;
+use crate::{HilbertGeometry, HilbertPoint};
+
+/// Reference to a shared arc. Negative values (`!index`) refer to the arc in
+/// reverse direction, following the TopoJSON convention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Encode)]
+pub struct ArcRef(pub i32);
+
+impl ArcRef {
+    pub fn index(&self) -> usize {
+        if self.0 < 0 {
+            !self.0 as usize
+        } else {
+            self.0 as usize
+        }
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        self.0 < 0
+    }
+}
+
+/// Geometry stored in a topology. Polygon rings are stored as arc references.
+#[derive(Debug, Clone, Decode, Encode)]
+pub enum TopoGeometry {
+    Polygon(Vec<Vec<ArcRef>>),
+    MultiPolygon(Vec<Vec<Vec<ArcRef>>>),
+    Other(HilbertGeometry),
+}
+
+/// Topology-aware container for polygon datasets.
+///
+/// Boundaries shared between polygons (identical runs of Hilbert indices) are
+/// split into arcs and stored once. Rings are reconstructed on decode, starting
+/// from a junction vertex, so the ring start may differ from the source.
+#[derive(Debug, Clone, Encode)]
+pub struct HilbertTopology {
+    arcs: Vec<Vec<HilbertPoint>>,
+    geometries: Vec<TopoGeometry>,
+}
+
+// Arc references are checked when decoding, so reconstructing rings can index
+// the arcs directly.
+impl<Context> Decode<Context> for HilbertTopology {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let arcs: Vec<Vec<HilbertPoint>> = Decode::decode(decoder)?;
+        let geometries: Vec<TopoGeometry> = Decode::decode(decoder)?;
+        let valid = |r: &ArcRef| r.index() < arcs.len();
+        let all_valid = geometries.iter().all(|g| match g {
+            TopoGeometry::Polygon(rings) => rings.iter().flatten().all(valid),
+            TopoGeometry::MultiPolygon(polys) => polys.iter().flatten().flatten().all(valid),
+            TopoGeometry::Other(_) => true,
+        });
+        if !all_valid {
+            return Err(DecodeError::Other("arc reference out of range"));
+        }
+        Ok(Self { arcs, geometries })
+    }
+}
+
+bincode::impl_borrow_decode!(HilbertTopology);
+
+#[derive(Clone, Copy)]
+enum Neighbors {
+    Pair(u64, u64),
+    Junction,
+}
+
+impl HilbertTopology {
+    /// Builds a topology from a list of encoded geometries. Fails if there
+    /// are more arcs than an [`ArcRef`] can refer to.
+    pub fn build(geoms: &[HilbertGeometry]) -> Result<Self, EncodeError> {
+        let rings = geoms.iter().flat_map(|g| match g {
+            HilbertGeometry::Polygon(rings) => rings.iter().collect::<Vec<_>>(),
+            HilbertGeometry::MultiPolygon(polys) => polys.iter().flatten().collect(),
+            _ => vec![],
+        });
+
+        // A vertex is a junction if it is seen with different neighbors.
+        let mut neighbors: HashMap<u64, Neighbors> = HashMap::new();
+        for ring in rings {
+            let ring = open_ring(ring);
+            let n = ring.len();
+            for i in 0..n {
+                let (a, b) = (ring[(i + n - 1) % n].0, ring[(i + 1) % n].0);
+                let pair = Neighbors::Pair(a.min(b), a.max(b));
+                neighbors
+                    .entry(ring[i].0)
+                    .and_modify(|e| match (*e, pair) {
+                        (Neighbors::Pair(a, b), Neighbors::Pair(c, d)) if (a, b) == (c, d) => {}
+                        _ => *e = Neighbors::Junction,
+                    })
+                    .or_insert(pair);
+            }
+        }
+
+        let mut builder = ArcBuilder {
+            junctions: neighbors
+                .into_iter()
+                .filter_map(|(k, v)| matches!(v, Neighbors::Junction).then_some(k))
+                .collect(),
+            arcs: vec![],
+            index: HashMap::new(),
+        };
+        let geometries = geoms
+            .iter()
+            .map(|g| {
+                Ok(match g {
+                    HilbertGeometry::Polygon(rings) => TopoGeometry::Polygon(builder.rings(rings)?),
+                    HilbertGeometry::MultiPolygon(polys) => TopoGeometry::MultiPolygon(
+                        polys
+                            .iter()
+                            .map(|rings| builder.rings(rings))
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    other => TopoGeometry::Other(other.clone()),
+                })
+            })
+            .collect::<Result<_, EncodeError>>()?;
+
+        Ok(Self {
+            arcs: builder.arcs,
+            geometries,
+        })
+    }
+
+    /// Shared arcs referenced by the stored geometries.
+    pub fn arcs(&self) -> &[Vec<HilbertPoint>] {
+        &self.arcs
+    }
+
+    /// Stored geometries, with polygon rings as arc references.
+    pub fn topo_geometries(&self) -> &[TopoGeometry] {
+        &self.geometries
+    }
+
+    pub fn len(&self) -> usize {
+        self.geometries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.geometries.is_empty()
+    }
+
+    /// Reconstructs the geometry at index `i`.
+    pub fn geometry(&self, i: usize) -> Option<HilbertGeometry> {
+        self.geometries.get(i).map(|g| self.reconstruct(g))
+    }
+
+    /// Reconstructs all geometries in order.
+    pub fn geometries(&self) -> impl Iterator<Item = HilbertGeometry> + '_ {
+        self.geometries.iter().map(|g| self.reconstruct(g))
+    }
+
+    fn reconstruct(&self, geom: &TopoGeometry) -> HilbertGeometry {
+        let ring = |refs: &Vec<ArcRef>| self.ring(refs);
+        match geom {
+            TopoGeometry::Polygon(rings) => {
+                HilbertGeometry::Polygon(rings.iter().map(ring).collect())
+            }
+            TopoGeometry::MultiPolygon(polys) => HilbertGeometry::MultiPolygon(
+                polys.iter().map(|p| p.iter().map(ring).collect()).collect(),
+            ),
+            TopoGeometry::Other(g) => g.clone(),
+        }
+    }
+
+    fn ring(&self, refs: &[ArcRef]) -> Vec<HilbertPoint> {
+        let mut ring: Vec<HilbertPoint> = vec![];
+        for r in refs {
+            let arc = &self.arcs[r.index()];
+            let skip = usize::from(!ring.is_empty());
+            if r.is_reversed() {
+                ring.extend(arc.iter().rev().skip(skip));
+            } else {
+                ring.extend(arc.iter().skip(skip));
+            }
+        }
+        ring
+    }
+
+    pub fn encode_bincode(&self, config: &Configuration) -> Result<Vec<u8>, EncodeError> {
+        bincode::encode_to_vec(self, *config)
+    }
+
+    /// Decodes a topology, rejecting arc references past the end of
+    /// [`arcs`](Self::arcs).
+    pub fn decode_bincode(
+        data: &[u8],
+        config: &Configuration,
+    ) -> Result<HilbertTopology, DecodeError> {
+        let (decoded, _): (HilbertTopology, usize) =
+            bincode::decode_from_slice(data, limited(*config))?;
+        Ok(decoded)
+    }
+}
+
+/// Returns the ring without its closing vertex.
+fn open_ring(ring: &[HilbertPoint]) -> &[HilbertPoint] {
+    match ring {
+        [first, .., last] if first.0 == last.0 => &ring[..ring.len() - 1],
+        _ => ring,
+    }
+}
+
+struct ArcBuilder {
+    junctions: HashSet<u64>,
+    arcs: Vec<Vec<HilbertPoint>>,
+    index: HashMap<Vec<u64>, usize>,
+}
+
+impl ArcBuilder {
+    fn rings(&mut self, rings: &[Vec<HilbertPoint>]) -> Result<Vec<Vec<ArcRef>>, EncodeError> {
+        rings.iter().map(|r| self.ring(r)).collect()
+    }
+
+    /// Cuts a ring into arcs at junctions and returns references to them.
+    fn ring(&mut self, ring: &[HilbertPoint]) -> Result<Vec<ArcRef>, EncodeError> {
+        let open = open_ring(ring);
+        if open.is_empty() {
+            return Ok(vec![]);
+        }
+        let start = match open.iter().position(|p| self.junctions.contains(&p.0)) {
+            Some(i) => i,
+            // Without junctions the whole ring is one arc; rotate it to a
+            // canonical start so identical rings are shared.
+            None => (0..open.len()).min_by_key(|i| open[*i].0).unwrap_or(0),
+        };
+        let mut rotated: Vec<HilbertPoint> = open[start..]
+            .iter()
+            .chain(&open[..start])
+            .copied()
+            .collect();
+        rotated.push(rotated[0]);
+
+        let mut refs = vec![];
+        let mut from = 0;
+        for i in 1..rotated.len() {
+            if i == rotated.len() - 1 || self.junctions.contains(&rotated[i].0) {
+                refs.push(self.arc(&rotated[from..=i])?);
+                from = i;
+            }
+        }
+        Ok(refs)
+    }
+
+    fn arc(&mut self, arc: &[HilbertPoint]) -> Result<ArcRef, EncodeError> {
+        let key: Vec<u64> = arc.iter().map(|p| p.0).collect();
+        if let Some(i) = self.index.get(&key) {
+            return Ok(ArcRef(*i as i32));
+        }
+        let reversed: Vec<u64> = key.iter().rev().copied().collect();
+        if let Some(i) = self.index.get(&reversed) {
+            return Ok(ArcRef(!(*i as i32)));
+        }
+        let i = self.arcs.len();
+        let r = i32::try_from(i).map_err(|_| EncodeError::Other("too many arcs"))?;
+        self.arcs.push(arc.to_vec());
+        self.index.insert(key, i);
+        Ok(ArcRef(r))
+    }
+}
@@ -0,0 +1,91 @@
+//! GPX and KML ingestion.
+
+use std::fmt;
+use std::io::Read;
+
+use geo_types::Geometry;
+
+use crate::{encode_geometry, HilbertGeometry};
+
+/// Error returned by the trace readers.
+#[derive(Debug)]
+pub enum TraceError {
+    #[cfg(feature = "gpx")]
+    Gpx(gpx::errors::GpxError),
+    #[cfg(feature = "kml")]
+    Kml(kml::Error),
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "gpx")]
+            TraceError::Gpx(e) => write!(f, "GPX error: {e}"),
+            #[cfg(feature = "kml")]
+            TraceError::Kml(e) => write!(f, "KML error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+#[cfg(feature = "gpx")]
+impl From<gpx::errors::GpxError> for TraceError {
+    fn from(e: gpx::errors::GpxError) -> Self {
+        TraceError::Gpx(e)
+    }
+}
+
+#[cfg(feature = "kml")]
+impl From<kml::Error> for TraceError {
+    fn from(e: kml::Error) -> Self {
+        TraceError::Kml(e)
+    }
+}
+
+/// Reads the tracks, routes and waypoints of a GPX document: one linestring
+/// per track segment and route, followed by one point per waypoint.
+/// Segments and routes without points are skipped.
+#[cfg(feature = "gpx")]
+pub fn read_gpx(reader: impl Read) -> Result<Vec<HilbertGeometry>, TraceError> {
+    let gpx = gpx::read(reader)?;
+    let lines = gpx
+        .tracks
+        .iter()
+        .flat_map(|t| &t.segments)
+        .map(|s| s.linestring())
+        .chain(gpx.routes.iter().map(|r| r.linestring()))
+        .filter(|ls| !ls.0.is_empty())
+        .map(|ls| encode_geometry(&Geometry::LineString(ls)));
+    let points = gpx
+        .waypoints
+        .iter()
+        .map(|w| encode_geometry(&Geometry::Point(w.point())));
+    Ok(lines.chain(points).collect())
+}
+
+/// Reads the placemark geometries of a KML document. Members of
+/// `MultiGeometry` elements are returned individually.
+#[cfg(feature = "kml")]
+pub fn read_kml(reader: impl Read) -> Result<Vec<HilbertGeometry>, TraceError> {
+    let kml = kml::KmlReader::<_, f64>::from_reader(std::io::BufReader::new(reader)).read()?;
+    let mut out = vec![];
+    for geom in geo_types::GeometryCollection::<f64>::try_from(kml)? {
+        push_geometry(geom, &mut out);
+    }
+    Ok(out)
+}
+
+#[cfg(feature = "kml")]
+fn push_geometry(geom: Geometry, out: &mut Vec<HilbertGeometry>) {
+    match geom {
+        Geometry::GeometryCollection(gc) => gc.into_iter().for_each(|g| push_geometry(g, out)),
+        Geometry::Point(_)
+        | Geometry::LineString(_)
+        | Geometry::Polygon(_)
+        | Geometry::MultiPoint(_)
+        | Geometry::MultiLineString(_)
+        | Geometry::MultiPolygon(_) => out.push(encode_geometry(&geom)),
+        Geometry::Line(_) | Geometry::Rect(_) | Geometry::Triangle(_) => {}
+    }
+}
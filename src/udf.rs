@@ -0,0 +1,182 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use bincode::config;
+use datafusion::arrow::array::{AsArray, BinaryArray, UInt64Array};
+use datafusion::arrow::datatypes::{DataType, Float64Type};
+use datafusion::common::{DataFusionError, Result};
+use datafusion::logical_expr::{
+    ColumnarValue, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, Volatility,
+};
+use datafusion::prelude::SessionContext;
+use geo_types::Coord;
+
+use crate::decode::limited;
+use crate::ewkb::{geometry_from_ewkb, geometry_to_ewkb};
+use crate::{decode_geometry, encode_coord, encode_geometry, supported, HilbertGeometry};
+
+/// Returns the scalar UDFs provided by this crate:
+///
+/// - `hilbert_encode(wkb BINARY) -> BINARY`: encodes (E)WKB into bincode bytes.
+/// - `hilbert_decode(bytes BINARY) -> BINARY`: decodes bincode bytes into WKB.
+/// - `hilbert_key(lon DOUBLE, lat DOUBLE) -> UBIGINT`: Hilbert index of a coordinate.
+///
+/// Null inputs produce null outputs.
+pub fn udfs() -> Vec<ScalarUDF> {
+    vec![
+        ScalarUDF::new_from_impl(HilbertEncode::new()),
+        ScalarUDF::new_from_impl(HilbertDecode::new()),
+        ScalarUDF::new_from_impl(HilbertKey::new()),
+    ]
+}
+
+/// Registers all UDFs from [`udfs`] with a session.
+pub fn register_udfs(ctx: &SessionContext) {
+    for udf in udfs() {
+        ctx.register_udf(udf);
+    }
+}
+
+fn execution_error(e: impl std::fmt::Display) -> DataFusionError {
+    DataFusionError::Execution(e.to_string())
+}
+
+/// Applies `f` to each non-null value of a binary column.
+fn map_binary(
+    args: &[ColumnarValue],
+    f: impl Fn(&[u8]) -> Result<Vec<u8>>,
+) -> Result<ColumnarValue> {
+    let arrays = ColumnarValue::values_to_arrays(args)?;
+    let output = arrays[0]
+        .as_binary::<i32>()
+        .iter()
+        .map(|value| value.map(&f).transpose())
+        .collect::<Result<BinaryArray>>()?;
+    Ok(ColumnarValue::Array(Arc::new(output)))
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct HilbertEncode {
+    signature: Signature,
+}
+
+impl HilbertEncode {
+    fn new() -> Self {
+        Self {
+            signature: Signature::exact(vec![DataType::Binary], Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for HilbertEncode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "hilbert_encode"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        map_binary(&args.args, |wkb| {
+            let (geom, _) = geometry_from_ewkb(wkb).map_err(execution_error)?;
+            supported(&geom).map_err(execution_error)?;
+            bincode::encode_to_vec(encode_geometry(&geom), config::standard())
+                .map_err(execution_error)
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct HilbertDecode {
+    signature: Signature,
+}
+
+impl HilbertDecode {
+    fn new() -> Self {
+        Self {
+            signature: Signature::exact(vec![DataType::Binary], Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for HilbertDecode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "hilbert_decode"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        map_binary(&args.args, |data| {
+            let (geom, _): (HilbertGeometry, _) =
+                bincode::decode_from_slice(data, limited(config::standard()))
+                    .map_err(execution_error)?;
+            Ok(geometry_to_ewkb(&decode_geometry(&geom), None))
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct HilbertKey {
+    signature: Signature,
+}
+
+impl HilbertKey {
+    fn new() -> Self {
+        Self {
+            signature: Signature::exact(
+                vec![DataType::Float64, DataType::Float64],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for HilbertKey {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "hilbert_key"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::UInt64)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let arrays = ColumnarValue::values_to_arrays(&args.args)?;
+        let lon = arrays[0].as_primitive::<Float64Type>();
+        let lat = arrays[1].as_primitive::<Float64Type>();
+        let output: UInt64Array = lon
+            .iter()
+            .zip(lat.iter())
+            .map(|(x, y)| Some(encode_coord(Coord { x: x?, y: y? }).0))
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(output)))
+    }
+}
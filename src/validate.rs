@@ -0,0 +1,273 @@
+//! Structural checks of geometries before encoding.
+//!
+//! The codec stores whatever it is given. These checks catch invalid input
+//! at encode time, with the offending parts named, instead of leaving it to
+//! consumers downstream.
+
+use std::fmt;
+
+use geo_types::{Coord, Geometry, Line, LineString, Polygon};
+
+/// Which checks [`validate`] runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationOptions {
+    /// Interior rings must lie inside the exterior ring and must not
+    /// overlap each other. Touching at single points is allowed.
+    pub validate_holes: bool,
+    /// Linestrings and rings must not intersect themselves. Only
+    /// consecutive segments may share a point, as may the first and last
+    /// segments of a closed path.
+    pub validate_simple: bool,
+}
+
+/// A problem found by [`validate`]. Polygons and linestrings are numbered
+/// within their multi geometry, holes within their polygon, from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// A hole reaches outside the exterior ring.
+    HoleOutsideShell { polygon: usize, hole: usize },
+    /// Two holes overlap, or one lies inside the other.
+    HolesOverlap {
+        polygon: usize,
+        first: usize,
+        second: usize,
+    },
+    /// Two segments of a path intersect. `ring` is 0 for linestrings and
+    /// exterior rings and `1 + i` for hole `i`; segment `i` runs from
+    /// vertex `i` to vertex `i + 1`.
+    SelfIntersection {
+        part: usize,
+        ring: usize,
+        first: usize,
+        second: usize,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::HoleOutsideShell { polygon, hole } => {
+                write!(f, "hole {hole} of polygon {polygon} is outside the shell")
+            }
+            ValidationError::HolesOverlap {
+                polygon,
+                first,
+                second,
+            } => write!(f, "holes {first} and {second} of polygon {polygon} overlap"),
+            ValidationError::SelfIntersection {
+                part,
+                ring,
+                first,
+                second,
+            } => write!(
+                f,
+                "segments {first} and {second} of ring {ring} of part {part} intersect"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Runs the checks enabled in `options` and returns all problems found.
+pub fn validate(geom: &Geometry, options: &ValidationOptions) -> Vec<ValidationError> {
+    let polygons: Vec<&Polygon> = match geom {
+        Geometry::Polygon(p) => vec![p],
+        Geometry::MultiPolygon(mp) => mp.0.iter().collect(),
+        _ => vec![],
+    };
+    let mut errors = vec![];
+    if options.validate_holes {
+        for (i, p) in polygons.iter().enumerate() {
+            validate_holes(i, p, &mut errors);
+        }
+    }
+    if options.validate_simple {
+        let paths: Vec<(usize, usize, &LineString)> = match geom {
+            Geometry::LineString(ls) => vec![(0, 0, ls)],
+            Geometry::MultiLineString(mls) => {
+                mls.0.iter().enumerate().map(|(i, ls)| (i, 0, ls)).collect()
+            }
+            _ => polygons
+                .iter()
+                .enumerate()
+                .flat_map(|(i, p)| {
+                    std::iter::once(p.exterior())
+                        .chain(p.interiors())
+                        .enumerate()
+                        .map(move |(ring, ls)| (i, ring, ls))
+                })
+                .collect(),
+        };
+        for (part, ring, ls) in paths {
+            if let Some((first, second)) = self_intersection(ls) {
+                errors.push(ValidationError::SelfIntersection {
+                    part,
+                    ring,
+                    first,
+                    second,
+                });
+            }
+        }
+    }
+    errors
+}
+
+fn validate_holes(polygon: usize, p: &Polygon, errors: &mut Vec<ValidationError>) {
+    let shell = p.exterior();
+    let holes = p.interiors();
+    for (i, hole) in holes.iter().enumerate() {
+        let outside = hole
+            .0
+            .iter()
+            .any(|c| locate(shell, *c) == Location::Outside);
+        if outside || rings_cross(shell, hole) {
+            errors.push(ValidationError::HoleOutsideShell { polygon, hole: i });
+        }
+    }
+    for (i, a) in holes.iter().enumerate() {
+        for (j, b) in holes.iter().enumerate().skip(i + 1) {
+            if rings_cross(a, b) || inside_other(a, b) || inside_other(b, a) {
+                errors.push(ValidationError::HolesOverlap {
+                    polygon,
+                    first: i,
+                    second: j,
+                });
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Sign of the turn from `a` to `b` to `c`: 1 for left, -1 for right and
+/// 0 for collinear points.
+fn orientation(a: Coord, b: Coord, c: Coord) -> f64 {
+    let cross = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+    if cross == 0.0 {
+        0.0
+    } else {
+        cross.signum()
+    }
+}
+
+fn on_segment(a: Coord, b: Coord, c: Coord) -> bool {
+    orientation(a, b, c) == 0.0
+        && c.x >= a.x.min(b.x)
+        && c.x <= a.x.max(b.x)
+        && c.y >= a.y.min(b.y)
+        && c.y <= a.y.max(b.y)
+}
+
+/// Locates `c` relative to a closed ring, by the even-odd rule.
+fn locate(ring: &LineString, c: Coord) -> Location {
+    let mut inside = false;
+    for line in ring.lines() {
+        let (a, b) = (line.start, line.end);
+        if on_segment(a, b, c) {
+            return Location::Boundary;
+        }
+        if (a.y > c.y) != (b.y > c.y) && c.x < a.x + (c.y - a.y) * (b.x - a.x) / (b.y - a.y) {
+            inside = !inside;
+        }
+    }
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// Whether an edge of `a` properly crosses an edge of `b`, i.e. they
+/// intersect in a single point interior to both.
+fn rings_cross(a: &LineString, b: &LineString) -> bool {
+    a.lines().any(|e| {
+        b.lines().any(|f| {
+            orientation(e.start, e.end, f.start) * orientation(e.start, e.end, f.end) < 0.0
+                && orientation(f.start, f.end, e.start) * orientation(f.start, f.end, e.end) < 0.0
+        })
+    })
+}
+
+/// Whether ring `a` lies inside ring `b`, judged by its vertices not on
+/// the boundary of `b`. Identical rings count as inside.
+fn inside_other(a: &LineString, b: &LineString) -> bool {
+    let mut locations = a.0.iter().map(|c| locate(b, *c));
+    match locations.find(|l| *l != Location::Boundary) {
+        Some(l) => l == Location::Inside,
+        None => !a.0.is_empty(),
+    }
+}
+
+/// Whether segments `e` and `f` share at least one point.
+fn segments_intersect(e: Line, f: Line) -> bool {
+    let d1 = orientation(e.start, e.end, f.start);
+    let d2 = orientation(e.start, e.end, f.end);
+    let d3 = orientation(f.start, f.end, e.start);
+    let d4 = orientation(f.start, f.end, e.end);
+    (d1 * d2 < 0.0 && d3 * d4 < 0.0)
+        || on_segment(e.start, e.end, f.start)
+        || on_segment(e.start, e.end, f.end)
+        || on_segment(f.start, f.end, e.start)
+        || on_segment(f.start, f.end, e.end)
+}
+
+/// Whether consecutive segments `e` and `f`, with `e.end == f.start`,
+/// overlap beyond their shared vertex, i.e. the path doubles back.
+fn segments_overlap(e: Line, f: Line) -> bool {
+    orientation(e.start, e.end, f.end) == 0.0
+        && (on_segment(e.start, e.end, f.end) || on_segment(f.start, f.end, e.start))
+}
+
+/// Finds the first pair of intersecting segments of a path, by a sweep
+/// over the segments ordered by their smallest x. Zero-length segments
+/// from repeated vertices are skipped.
+fn self_intersection(ls: &LineString) -> Option<(usize, usize)> {
+    let segments: Vec<(usize, Line)> = ls
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| l.start != l.end)
+        .collect();
+    let n = segments.len();
+    let closed = ls.is_closed() && n > 2;
+    // Positions in `segments` of consecutive segments, which share a vertex.
+    let adjacent = |i: usize, j: usize| {
+        let (i, j) = (i.min(j), i.max(j));
+        j == i + 1 || (closed && i == 0 && j == n - 1)
+    };
+
+    let mut order: Vec<usize> = (0..n).collect();
+    let min_x = |i: usize| segments[i].1.start.x.min(segments[i].1.end.x);
+    let max_x = |i: usize| segments[i].1.start.x.max(segments[i].1.end.x);
+    order.sort_by(|a, b| min_x(*a).total_cmp(&min_x(*b)));
+
+    let mut found: Option<(usize, usize)> = None;
+    let mut active: Vec<usize> = vec![];
+    for i in order {
+        active.retain(|j| max_x(*j) >= min_x(i));
+        for &j in &active {
+            let (a, b) = (i.min(j), i.max(j));
+            let (e, f) = (segments[a].1, segments[b].1);
+            let hit = if adjacent(a, b) {
+                if b == a + 1 {
+                    segments_overlap(e, f)
+                } else {
+                    segments_overlap(f, e)
+                }
+            } else {
+                segments_intersect(e, f)
+            };
+            if hit {
+                let pair = (segments[a].0, segments[b].0);
+                found = Some(found.map_or(pair, |f| f.min(pair)));
+            }
+        }
+        active.push(i);
+    }
+    found
+}
@@ -0,0 +1,98 @@
+//! Traversal of encoded geometries without building intermediate collections.
+
+use geo_types::Coord;
+
+use crate::{decode_coord, HilbertGeometry, HilbertPoint};
+
+/// Callbacks for [`HilbertGeometry::visit`]. Only [`coord`](Self::coord) is
+/// required.
+///
+/// The structure is reported as follows:
+///
+/// - `Point`, `LineString`: coordinates only.
+/// - `Polygon`: a ring per exterior and interior ring.
+/// - `MultiPoint`, `MultiLineString`: a part per member.
+/// - `MultiPolygon`: a part per polygon, each with its rings.
+pub trait CoordVisitor {
+    fn begin_geometry(&mut self, _kind: &'static str) {}
+    fn end_geometry(&mut self) {}
+    fn begin_part(&mut self, _index: usize) {}
+    fn end_part(&mut self, _index: usize) {}
+    fn begin_ring(&mut self, _index: usize) {}
+    fn end_ring(&mut self, _index: usize) {}
+    /// Called with each decoded vertex, in encoded order.
+    fn coord(&mut self, coord: Coord);
+}
+
+impl<V: CoordVisitor + ?Sized> CoordVisitor for &mut V {
+    fn begin_geometry(&mut self, kind: &'static str) {
+        (**self).begin_geometry(kind)
+    }
+    fn end_geometry(&mut self) {
+        (**self).end_geometry()
+    }
+    fn begin_part(&mut self, index: usize) {
+        (**self).begin_part(index)
+    }
+    fn end_part(&mut self, index: usize) {
+        (**self).end_part(index)
+    }
+    fn begin_ring(&mut self, index: usize) {
+        (**self).begin_ring(index)
+    }
+    fn end_ring(&mut self, index: usize) {
+        (**self).end_ring(index)
+    }
+    fn coord(&mut self, coord: Coord) {
+        (**self).coord(coord)
+    }
+}
+
+fn visit_coords(visitor: &mut impl CoordVisitor, points: &[HilbertPoint]) {
+    for p in points {
+        visitor.coord(decode_coord(*p));
+    }
+}
+
+fn visit_rings(visitor: &mut impl CoordVisitor, rings: &[Vec<HilbertPoint>]) {
+    for (i, ring) in rings.iter().enumerate() {
+        visitor.begin_ring(i);
+        visit_coords(visitor, ring);
+        visitor.end_ring(i);
+    }
+}
+
+impl HilbertGeometry {
+    /// Walks the geometry, passing decoded coordinates to `visitor`. Pass
+    /// `&mut visitor` to inspect its state afterwards.
+    pub fn visit(&self, mut visitor: impl CoordVisitor) {
+        visitor.begin_geometry(self.kind());
+        match self {
+            HilbertGeometry::Point(p) => visitor.coord(decode_coord(*p)),
+            HilbertGeometry::LineString(ps) => visit_coords(&mut visitor, ps),
+            HilbertGeometry::Polygon(rings) => visit_rings(&mut visitor, rings),
+            HilbertGeometry::MultiPoint(ps) => {
+                for (i, p) in ps.iter().enumerate() {
+                    visitor.begin_part(i);
+                    visitor.coord(decode_coord(*p));
+                    visitor.end_part(i);
+                }
+            }
+            HilbertGeometry::MultiLineString(lines) => {
+                for (i, line) in lines.iter().enumerate() {
+                    visitor.begin_part(i);
+                    visit_coords(&mut visitor, line);
+                    visitor.end_part(i);
+                }
+            }
+            HilbertGeometry::MultiPolygon(polys) => {
+                for (i, rings) in polys.iter().enumerate() {
+                    visitor.begin_part(i);
+                    visit_rings(&mut visitor, rings);
+                    visitor.end_part(i);
+                }
+            }
+        }
+        visitor.end_geometry();
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, polygon, Geometry};
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_approx_eq_roundtrip() {
+        let ls = Geometry::LineString(line_string![
+            (x: 24.93456789, y: 60.16991234),
+            (x: -0.12761234, y: 51.50721234)
+        ]);
+        let decoded = decode_geometry(&encode_geometry(&ls));
+        assert!(geometries_approx_eq(&ls, &decoded, 1e-5));
+        assert!(!geometries_approx_eq(&ls, &decoded, 0.0));
+
+        let other = Geometry::LineString(line_string![
+            (x: 24.93456789, y: 60.16991234),
+            (x: -0.12761234, y: 51.50821234)
+        ]);
+        assert!(!geometries_approx_eq(&ls, &other, 1e-5));
+        assert!(!geometries_approx_eq(
+            &ls,
+            &Geometry::Point(point! { x: 24.93456789, y: 60.16991234 }),
+            1.0
+        ));
+    }
+
+    #[test]
+    fn test_approx_eq_ring_rotation() {
+        let a = Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 1.0),
+            (x: 0.0, y: 0.0)
+        ]);
+        let rotated = Geometry::Polygon(polygon![
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 1.0),
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0)
+        ]);
+        let reversed = Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0),
+            (x: 0.0, y: 1.0),
+            (x: 1.0, y: 1.0),
+            (x: 1.0, y: 0.0),
+            (x: 0.0, y: 0.0)
+        ]);
+        assert!(geometries_approx_eq(&a, &rotated, 0.0));
+        assert!(!geometries_approx_eq(&a, &reversed, 0.0));
+
+        let (ha, hr) = (encode_geometry(&a), encode_geometry(&rotated));
+        assert_ne!(ha, hr);
+        assert!(ha.approx_eq(&hr, 1e-9));
+    }
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "tokio")]
+
+#[cfg(test)]
+mod tests {
+    use bincode::config;
+    use geo_types::{line_string, point, Geometry};
+    use hilbert_geometry::asyncio::*;
+    use hilbert_geometry::collection::*;
+    use hilbert_geometry::*;
+    use std::io::Cursor;
+
+    #[tokio::test]
+    async fn test_async_geometry_roundtrip() {
+        let config = config::standard();
+        let geoms = [
+            encode_geometry(&Geometry::Point(point!(x: 1.0, y: 2.0))),
+            encode_geometry(&Geometry::LineString(line_string![
+                (x: 1.0, y: 1.0),
+                (x: 5.0, y: 5.0)
+            ])),
+        ];
+        let mut buf = vec![];
+        for geom in &geoms {
+            encode_to_async_writer(&mut buf, geom, &config)
+                .await
+                .unwrap();
+        }
+        let mut reader = buf.as_slice();
+        for geom in &geoms {
+            let decoded = decode_from_async_reader(&mut reader, &config)
+                .await
+                .unwrap();
+            assert_eq!(&decoded, geom);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_collection_reader() {
+        let features: Vec<HilbertFeature> = (0..5)
+            .map(|i| {
+                HilbertFeature::new(encode_geometry(&Geometry::Point(
+                    point!(x: i as f64, y: 0.0),
+                )))
+            })
+            .collect();
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        for feature in &features {
+            writer.write(feature).unwrap();
+        }
+        let data = writer.finish().unwrap();
+
+        let mut reader = AsyncCollectionReader::new(Cursor::new(data)).await.unwrap();
+        assert_eq!(reader.len(), 5);
+        assert_eq!(reader.get(3).await.unwrap().unwrap(), features[3]);
+        assert!(reader.get(5).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_async_corrupt_input() {
+        let config = config::standard();
+        let frame = u32::MAX.to_le_bytes();
+        assert!(decode_from_async_reader(&mut frame.as_slice(), &config)
+            .await
+            .is_err());
+
+        let feature =
+            HilbertFeature::new(encode_geometry(&Geometry::Point(point!(x: 1.0, y: 2.0))));
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        writer.write(&feature).unwrap();
+        writer.write(&feature).unwrap();
+        let data = writer.finish().unwrap();
+
+        let mut bad_magic = data.clone();
+        bad_magic[0] = 0;
+        assert!(AsyncCollectionReader::new(Cursor::new(bad_magic))
+            .await
+            .is_err());
+
+        // Swap the two offsets in the footer.
+        let footer = CollectionFooter::read(&data).unwrap();
+        let mut swapped = footer.clone();
+        swapped.offsets.reverse();
+        let encoded = |f: &CollectionFooter| bincode::encode_to_vec(f, config).unwrap();
+        let (old, new) = (encoded(&footer), encoded(&swapped));
+        let start = data.len() - TRAILER_LEN - old.len();
+        let mut bad_offsets = data.clone();
+        bad_offsets[start..start + new.len()].copy_from_slice(&new);
+        assert!(CollectionReader::new(bad_offsets.clone()).is_err());
+        assert!(AsyncCollectionReader::new(Cursor::new(bad_offsets))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "benchmark")]
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use geo_types::{
+        line_string, point, polygon, Geometry, GeometryCollection, MultiPoint, MultiPolygon, Rect,
+    };
+    use hilbert_geometry::benchmark::*;
+    use hilbert_geometry::geometries_approx_eq;
+
+    fn sample() -> Vec<Geometry> {
+        let poly = polygon!(
+            exterior: [
+                (x: 24.0, y: 60.0),
+                (x: 24.5, y: 60.0),
+                (x: 24.5, y: 60.5),
+                (x: 24.0, y: 60.0),
+            ],
+            interiors: [[
+                (x: 24.1, y: 60.05),
+                (x: 24.4, y: 60.05),
+                (x: 24.4, y: 60.35),
+                (x: 24.1, y: 60.05),
+            ]],
+        );
+        vec![
+            point!(x: 24.9384, y: 60.1699).into(),
+            line_string![(x: -0.1276, y: 51.5072), (x: 2.3522, y: 48.8566)].into(),
+            poly.clone().into(),
+            MultiPoint::from(vec![(1.0, 2.0), (-3.25, 4.5)]).into(),
+            MultiPolygon(vec![poly.clone(), poly]).into(),
+        ]
+    }
+
+    #[test]
+    fn test_format_roundtrip() {
+        for format in Format::ALL {
+            for geom in sample() {
+                let decoded = format.decode(&format.encode(&geom).unwrap()).unwrap();
+                assert!(
+                    geometries_approx_eq(&geom, &decoded, 1e-5),
+                    "{}",
+                    format.name()
+                );
+            }
+            assert_eq!(format.decode(&[]), None);
+        }
+        let geom = point!(x: 24.9384, y: 60.1699).into();
+        assert_eq!(
+            Format::Wkb.decode(&Format::Wkb.encode(&geom).unwrap()),
+            Some(geom)
+        );
+        let rect = Rect::new((0.0, 0.0), (1.0, 1.0)).into();
+        assert!(Format::ALL.iter().all(|f| f.encode(&rect).is_none()));
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut geoms = sample();
+        geoms.push(Geometry::GeometryCollection(GeometryCollection(vec![])));
+        let comparison = compare_for(&geoms, Duration::ZERO);
+        assert_eq!((comparison.geometries, comparison.skipped), (5, 1));
+        let formats: Vec<_> = comparison.formats.iter().map(|s| s.format).collect();
+        assert_eq!(formats, Format::ALL);
+        let bytes = |f| comparison.get(f).unwrap().bytes;
+        assert!(bytes(Format::Twkb) < bytes(Format::Wkb));
+        assert!(bytes(Format::Hwkb) < bytes(Format::Wkb));
+        assert!(bytes(Format::Wkb) < bytes(Format::GeoJson));
+
+        let table = comparison.to_string();
+        assert_eq!(table.lines().count(), 5);
+        assert!(table.lines().nth(2).unwrap().starts_with("WKB"));
+        assert!(table.contains("100.0%"));
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{point, Geometry};
+    use hilbert_geometry::cache::*;
+    use hilbert_geometry::*;
+
+    fn payload(x: f64) -> Vec<u8> {
+        HilbertSerializer::new()
+            .encode(&Geometry::Point(point!(x: x, y: 60.0)))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_decoding_cache() {
+        let cache = DecodingCache::new(HilbertSerializer::new(), 2);
+        assert!(cache.is_empty());
+        let (a, b, c) = (payload(1.0), payload(2.0), payload(3.0));
+
+        let first = cache.decode(&a).unwrap();
+        assert_eq!(*first, Geometry::Point(point!(x: 1.0, y: 60.0)));
+        assert!(std::sync::Arc::ptr_eq(&first, &cache.decode(&a).unwrap()));
+        cache.decode(&b).unwrap();
+        // `a` was used more recently than `b`, so `b` is evicted.
+        cache.decode(&a).unwrap();
+        cache.decode(&c).unwrap();
+        assert_eq!(cache.len(), 2);
+        cache.decode(&a).unwrap();
+        cache.decode(&b).unwrap();
+        assert_eq!(cache.stats(), CacheStats { hits: 3, misses: 4 });
+
+        assert!(cache.decode(&[0xff]).is_err());
+        assert_eq!(cache.len(), 2);
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_decoding_cache_threads() {
+        let cache = DecodingCache::new(HilbertSerializer::new(), 16);
+        let payloads: Vec<Vec<u8>> = (0..8).map(|i| payload(i as f64)).collect();
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for p in payloads.iter().cycle().take(100) {
+                        cache.decode(p).unwrap();
+                    }
+                });
+            }
+        });
+        let stats = cache.stats();
+        assert_eq!(stats.hits + stats.misses, 400);
+        assert!(stats.misses >= 8 && stats.misses <= 32);
+        assert_eq!(cache.len(), 8);
+    }
+}
@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use hilbert_geometry::cell::*;
+    use hilbert_geometry::HilbertPoint;
+
+    #[test]
+    fn test_cell_hierarchy() {
+        let point = HilbertPoint(0xdead_beef_0123_4567);
+        assert_eq!(HilbertCell::containing(point, 0), HilbertCell::ROOT);
+        assert_eq!(HilbertCell::ROOT.range(), 0..=u64::MAX);
+        assert_eq!(HilbertCell::ROOT.parent(), None);
+
+        let cell = HilbertCell::containing(point, 4);
+        assert_eq!(
+            cell,
+            HilbertCell {
+                level: 4,
+                key: 0xde
+            }
+        );
+        assert_eq!(cell.range(), 0xde00_0000_0000_0000..=0xdeff_ffff_ffff_ffff);
+        assert!(cell.contains(point));
+        assert!(!cell.contains(HilbertPoint(0xdf00_0000_0000_0000)));
+
+        let children = cell.children().unwrap();
+        assert_eq!(children[0].range().start(), cell.range().start());
+        assert_eq!(children[3].range().end(), cell.range().end());
+        assert!(children.iter().all(|c| c.parent() == Some(cell)));
+        assert_eq!(HilbertCell::containing(point, 5), children[2]);
+
+        let leaf = HilbertCell::containing(point, MAX_LEVEL);
+        assert_eq!(leaf.range(), point.0..=point.0);
+        assert_eq!(leaf.children(), None);
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use geo_types::{line_string, point, Geometry};
+    use hilbert_geometry::changelog::*;
+    use hilbert_geometry::collection::*;
+    use hilbert_geometry::*;
+
+    fn events() -> Vec<ChangeEvent> {
+        let a = encode_geometry(&Geometry::Point(point! { x: 24.5, y: 60.25 }));
+        let line = encode_geometry(&Geometry::LineString(line_string![
+            (x: 0.0, y: 0.0),
+            (x: 1.5, y: 2.5),
+            (x: 3.0, y: 2.5)
+        ]));
+        let moved = encode_geometry(&Geometry::LineString(line_string![
+            (x: 0.0, y: 0.0),
+            (x: 1.5, y: 2.75),
+            (x: 3.0, y: 2.5)
+        ]));
+        vec![
+            ChangeEvent::Insert {
+                id: 1,
+                feature: HilbertFeature::new(a.clone()),
+            },
+            ChangeEvent::Insert {
+                id: 2,
+                feature: HilbertFeature::new(line.clone())
+                    .with_property("name", PropertyValue::String("road".into())),
+            },
+            ChangeEvent::Update {
+                id: 2,
+                change: FeatureChange::Patch(line.diff(&moved)),
+            },
+            ChangeEvent::Update {
+                id: 1,
+                change: FeatureChange::Replace(HilbertFeature::new(a).with_metadata(
+                    FeatureMetadata {
+                        timestamp: Some(10),
+                        ..Default::default()
+                    },
+                )),
+            },
+            ChangeEvent::Delete { id: 1 },
+        ]
+    }
+
+    #[test]
+    fn test_changelog_replay() {
+        let mut writer = ChangeLogWriter::new(vec![]).unwrap();
+        for (i, event) in events().into_iter().enumerate() {
+            assert_eq!(writer.append(event).unwrap(), i as u64);
+        }
+        let log = writer.finish().unwrap();
+
+        let mut replica = BTreeMap::new();
+        let records: Vec<ChangeRecord> = ChangeLogReader::new(&log)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            records.iter().map(|r| r.seq).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            records.iter().map(|r| r.event.clone()).collect::<Vec<_>>(),
+            events()
+        );
+        for record in &records {
+            record.event.apply(&mut replica).unwrap();
+        }
+        assert_eq!(replica.keys().copied().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(
+            decode_geometry(&replica[&2].geometry),
+            Geometry::LineString(line_string![
+                (x: 0.0, y: 0.0),
+                (x: 1.5, y: 2.75),
+                (x: 3.0, y: 2.5)
+            ])
+        );
+        assert!(replica[&2].property("name").is_some());
+
+        assert!(matches!(
+            ChangeEvent::Delete { id: 1 }.apply(&mut replica),
+            Err(ApplyError::Missing(1))
+        ));
+        assert!(matches!(
+            records[1].event.apply(&mut replica),
+            Err(ApplyError::Exists(2))
+        ));
+    }
+
+    #[test]
+    fn test_changelog_tail() {
+        let mut writer = ChangeLogWriter::new(vec![]).unwrap();
+        let events = events();
+        writer.append(events[0].clone()).unwrap();
+        writer.append(events[1].clone()).unwrap();
+        let full = writer.finish().unwrap();
+
+        // The second record is still being written.
+        let partial = &full[..full.len() - 5];
+        let mut reader = ChangeLogReader::new(partial).unwrap();
+        assert_eq!(reader.next().unwrap().unwrap().seq, 0);
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+
+        let mut reader = ChangeLogReader::resume(&full, reader.position());
+        assert_eq!(reader.next().unwrap().unwrap().event, events[1]);
+        assert!(reader.next().is_none());
+
+        // Appending continues the sequence.
+        let mut writer = ChangeLogWriter::append_to(full, 2);
+        writer.append(events[2].clone()).unwrap();
+        let log = writer.finish().unwrap();
+        let last = ChangeLogReader::new(&log).unwrap().last().unwrap().unwrap();
+        assert_eq!(last.seq, 2);
+
+        assert!(ChangeLogReader::new(b"HGC1").is_err());
+    }
+}
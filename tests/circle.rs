@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{polygon, Geometry, MultiPoint, Point};
+    use hilbert_geometry::{decode_geometry, encode_geometry, HilbertGeometry};
+
+    fn circle(geom: Geometry) -> (HilbertGeometry, (f64, f64), f64) {
+        let encoded = encode_geometry(&geom);
+        let (center, radius) = encoded.bounding_circle().unwrap();
+        let Geometry::Point(c) = decode_geometry(&HilbertGeometry::Point(center)) else {
+            unreachable!()
+        };
+        (encoded, (c.x(), c.y()), radius)
+    }
+
+    fn multipoint(coords: &[(f64, f64)]) -> Geometry {
+        Geometry::MultiPoint(MultiPoint(coords.iter().map(|&c| Point::from(c)).collect()))
+    }
+
+    fn assert_encloses(encoded: &HilbertGeometry, center: (f64, f64), radius: f64) {
+        for p in encoded.points() {
+            let Geometry::Point(p) = decode_geometry(&HilbertGeometry::Point(*p)) else {
+                unreachable!()
+            };
+            assert!((p.x() - center.0).hypot(p.y() - center.1) <= radius + 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_bounding_circle() {
+        let square = Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0),
+            (x: 2.0, y: 0.0),
+            (x: 2.0, y: 2.0),
+            (x: 0.0, y: 2.0),
+        ]);
+        let (encoded, center, radius) = circle(square);
+        assert!((center.0 - 1.0).abs() < 1e-6 && (center.1 - 1.0).abs() < 1e-6);
+        assert!((radius - 2f64.sqrt()).abs() < 1e-6);
+        assert_encloses(&encoded, center, radius);
+
+        // Obtuse triangle: the longest side is a diameter.
+        let (encoded, center, radius) = circle(multipoint(&[(0.0, 0.0), (4.0, 0.0), (2.0, 0.5)]));
+        assert!((center.0 - 2.0).abs() < 1e-6 && center.1.abs() < 1e-6);
+        assert!((radius - 2.0).abs() < 1e-6);
+        assert_encloses(&encoded, center, radius);
+
+        let (encoded, center, radius) = circle(multipoint(&[
+            (1.0, 1.0),
+            (3.0, 3.0),
+            (2.0, 2.0),
+            (0.0, 0.0),
+        ]));
+        assert!((radius - 4.5f64.sqrt()).abs() < 1e-6);
+        assert_encloses(&encoded, center, radius);
+
+        let (_, center, radius) = circle(Geometry::Point(Point::new(24.94, 60.17)));
+        assert!((center.0 - 24.94).abs() < 1e-5 && (center.1 - 60.17).abs() < 1e-5);
+        assert_eq!(radius, 0.0);
+    }
+
+    #[test]
+    fn test_bounding_circle_many_points() {
+        let coords: Vec<(f64, f64)> = (0..500)
+            .map(|i| {
+                let t = i as f64 * 0.37;
+                (t.cos() * (i % 17) as f64, t.sin() * (i % 13) as f64)
+            })
+            .collect();
+        let (encoded, center, radius) = circle(multipoint(&coords));
+        assert_encloses(&encoded, center, radius);
+        assert!(radius <= 17.0);
+    }
+}
@@ -0,0 +1,178 @@
+#![cfg(feature = "cli")]
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    use hilbert_geometry::collection::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("hilbertgeom-{}-{name}", std::process::id()))
+    }
+
+    fn hilbertgeom(args: &[&str]) -> String {
+        let output = Command::new(env!("CARGO_BIN_EXE_hilbertgeom"))
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn test_cli_convert_roundtrip() {
+        let input = temp_path("input.geojson");
+        let hgc = temp_path("output.hgc");
+        let wkt = temp_path("output.wkt");
+        std::fs::write(
+            &input,
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {"name": "a", "lanes": 2},
+                 "geometry": {"type": "LineString", "coordinates": [[24.94, 60.17], [24.95, 60.18]]}},
+                {"type": "Feature", "properties": null,
+                 "geometry": {"type": "Point", "coordinates": [-3.7, 40.42]}}
+            ]}"#,
+        )
+        .unwrap();
+
+        let (input_arg, hgc_arg, wkt_arg) = (
+            input.to_str().unwrap(),
+            hgc.to_str().unwrap(),
+            wkt.to_str().unwrap(),
+        );
+        hilbertgeom(&["convert", input_arg, hgc_arg, "--compress"]);
+        hilbertgeom(&["convert", hgc_arg, wkt_arg, "--precision", "2"]);
+
+        let wkt_out = std::fs::read_to_string(&wkt).unwrap();
+        assert_eq!(
+            wkt_out.lines().collect::<Vec<_>>(),
+            ["LINESTRING(24.94 60.17,24.95 60.18)", "POINT(-3.7 40.42)"]
+        );
+
+        hilbertgeom(&["convert", hgc_arg, hgc_arg, "--sort"]);
+        let data = std::fs::read(&hgc).unwrap();
+        let reader = CollectionReader::new(data).unwrap();
+        assert_eq!(reader.len(), 2);
+        let line = reader
+            .iter()
+            .map(|f| f.unwrap())
+            .find(|f| f.property("name").is_some())
+            .unwrap();
+        assert_eq!(line.property("lanes"), Some(&PropertyValue::Int(2)));
+
+        for path in [input, hgc, wkt] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_cli_rounding() {
+        let input = temp_path("rounding.wkt");
+        let output = temp_path("rounded.wkt");
+        std::fs::write(&input, "POINT(1.25 -2.75)\n").unwrap();
+        let (input_arg, output_arg) = (input.to_str().unwrap(), output.to_str().unwrap());
+        for (rounding, expected) in [
+            ("round", "POINT(1.3 -2.8)"),
+            ("truncate", "POINT(1.2 -2.7)"),
+            ("half-even", "POINT(1.2 -2.8)"),
+            ("none", "POINT(1.25 -2.75)"),
+        ] {
+            hilbertgeom(&[
+                "convert",
+                input_arg,
+                output_arg,
+                "--precision",
+                "1",
+                "--rounding",
+                rounding,
+            ]);
+            assert_eq!(std::fs::read_to_string(&output).unwrap().trim(), expected);
+        }
+        for path in [input, output] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_cli_sort_and_stats() {
+        let input = temp_path("sort.wkt");
+        let sorted = temp_path("sorted.wkt");
+        let lines = [
+            "POINT(139.69123456 35.69)",
+            "POINT(-3.7 40.42)",
+            "LINESTRING(24.94 60.17,24.95 60.18)",
+        ];
+        std::fs::write(&input, lines.join("\n")).unwrap();
+        let (input_arg, sorted_arg) = (input.to_str().unwrap(), sorted.to_str().unwrap());
+
+        hilbertgeom(&["sort", input_arg, sorted_arg]);
+        let mut out: Vec<String> = std::fs::read_to_string(&sorted)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+        assert_eq!(out.len(), 3);
+        out.sort();
+        let mut expected: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        expected.sort();
+        assert_eq!(out, expected);
+
+        let stats = hilbertgeom(&["stats", input_arg, "--summary"]);
+        assert!(stats.contains("geometries: 3"));
+        assert!(stats.contains("points: 4"));
+        assert!(stats.contains("max error"));
+
+        for path in [input, sorted] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_cli_convert_stream() {
+        let input = temp_path("stream.geojson");
+        let hgc = temp_path("stream.hgc");
+        let features: Vec<String> = (0..500)
+            .map(|i| {
+                format!(
+                    r#"{{"type": "Feature", "properties": {{"id": {i}}},
+                     "geometry": {{"type": "Point", "coordinates": [{}, 60.1]}}}}"#,
+                    24.0 + i as f64 * 0.001
+                )
+            })
+            .chain([r#"{"type": "Feature", "properties": null, "geometry": null}"#.into()])
+            .collect();
+        std::fs::write(
+            &input,
+            format!(
+                r#"{{"type": "FeatureCollection", "features": [{}]}}"#,
+                features.join(",")
+            ),
+        )
+        .unwrap();
+
+        let (input_arg, hgc_arg) = (input.to_str().unwrap(), hgc.to_str().unwrap());
+        hilbertgeom(&["convert", input_arg, hgc_arg, "--stream", "--compress"]);
+        let data = std::fs::read(&hgc).unwrap();
+        let mut decoded = vec![];
+        flate2::read::GzDecoder::new(data.as_slice())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        let reader = CollectionReader::new(decoded).unwrap();
+        assert_eq!(reader.len(), 500);
+        let last = reader.get(499).unwrap().unwrap();
+        assert_eq!(last.property("id"), Some(&PropertyValue::Int(499)));
+
+        let status = Command::new(env!("CARGO_BIN_EXE_hilbertgeom"))
+            .args(["convert", hgc_arg, input_arg, "--stream"])
+            .output()
+            .unwrap()
+            .status;
+        assert!(!status.success());
+
+        for path in [input, hgc] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
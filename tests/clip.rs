@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, polygon, Geometry, Rect};
+    use hilbert_geometry::*;
+
+    fn unit_rect() -> Rect {
+        Rect::new((0.0, 0.0), (1.0, 1.0))
+    }
+
+    #[test]
+    fn test_clip_polygon() {
+        let poly = Geometry::Polygon(polygon![
+            (x: -1.0, y: -1.0),
+            (x: 0.5, y: -1.0),
+            (x: 0.5, y: 0.5),
+            (x: -1.0, y: 0.5),
+            (x: -1.0, y: -1.0)
+        ]);
+        let clipped = encode_geometry(&poly).clip_to_rect(&unit_rect()).unwrap();
+        let Geometry::Polygon(clipped) = decode_geometry(&clipped) else {
+            panic!("expected polygon");
+        };
+        let mut coords: Vec<(f64, f64)> = clipped.exterior().coords().map(|c| (c.x, c.y)).collect();
+        coords.pop();
+        coords.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(coords, vec![(0.0, 0.0), (0.0, 0.5), (0.5, 0.0), (0.5, 0.5)]);
+    }
+
+    #[test]
+    fn test_clip_linestring() {
+        let ls = Geometry::LineString(line_string![
+            (x: 0.5, y: 0.5),
+            (x: 0.5, y: 2.0),
+            (x: 0.75, y: 0.5)
+        ]);
+        let clipped = encode_geometry(&ls).clip_to_rect(&unit_rect()).unwrap();
+        assert!(matches!(clipped, HilbertGeometry::MultiLineString(ref parts) if parts.len() == 2));
+
+        let outside = encode_geometry(&Geometry::Point(point!(x: 2.0, y: 2.0)));
+        assert!(outside.clip_to_rect(&unit_rect()).is_none());
+    }
+}
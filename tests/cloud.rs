@@ -0,0 +1,71 @@
+#![cfg(feature = "object_store")]
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use geo_types::{point, Geometry};
+    use hilbert_geometry::cloud::*;
+    use hilbert_geometry::collection::*;
+    use hilbert_geometry::*;
+    use object_store::{memory::InMemory, path::Path, ObjectStore};
+
+    #[tokio::test]
+    async fn test_object_store_reader() {
+        let features: Vec<HilbertFeature> = (0..5)
+            .map(|i| {
+                HilbertFeature::new(encode_geometry(&Geometry::Point(
+                    point!(x: i as f64, y: 0.0),
+                )))
+            })
+            .collect();
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        for feature in &features {
+            writer.write(feature).unwrap();
+        }
+        let store = Arc::new(InMemory::new());
+        let path = Path::from("data/points.hgc");
+        store
+            .put(&path, writer.finish().unwrap().into())
+            .await
+            .unwrap();
+
+        let reader = ObjectStoreCollectionReader::open(store, path)
+            .await
+            .unwrap();
+        assert_eq!(reader.len(), 5);
+        assert_eq!(reader.get(2).await.unwrap().unwrap(), features[2]);
+
+        let bbox = BoundingBox {
+            min_x: 2.5,
+            min_y: -1.0,
+            max_x: 10.0,
+            max_y: 1.0,
+        };
+        assert_eq!(reader.query(&bbox).await.unwrap(), features[3..]);
+    }
+
+    #[tokio::test]
+    async fn test_object_store_reader_corrupt() {
+        let feature =
+            HilbertFeature::new(encode_geometry(&Geometry::Point(point!(x: 1.0, y: 2.0))));
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        writer.write(&feature).unwrap();
+        let mut data = writer.finish().unwrap();
+        let config = bincode::config::standard();
+        let footer = CollectionFooter::read(&data).unwrap();
+        let start =
+            data.len() - TRAILER_LEN - bincode::encode_to_vec(&footer, config).unwrap().len();
+        // The footer starts with the offset count and the first offset. Point
+        // the only feature past the footer.
+        assert_eq!(data[start..start + 2], [1, 4]);
+        data[start + 1] = 250;
+
+        let store = Arc::new(InMemory::new());
+        let path = Path::from("data/corrupt.hgc");
+        store.put(&path, data.into()).await.unwrap();
+        assert!(ObjectStoreCollectionReader::open(store, path)
+            .await
+            .is_err());
+    }
+}
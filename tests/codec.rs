@@ -0,0 +1,57 @@
+#![cfg(feature = "codec")]
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use geo_types::{line_string, point, Geometry};
+    use hilbert_geometry::codec::*;
+    use hilbert_geometry::HilbertSerializer;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    #[test]
+    fn test_framed_codec() {
+        let geoms = vec![
+            Geometry::Point(point! { x: 24.5, y: 60.25 }),
+            Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.5, y: 2.5)]),
+        ];
+        let mut codec = FramedCodec::new();
+        let mut buf = BytesMut::new();
+        for geom in &geoms {
+            codec.encode(geom, &mut buf).unwrap();
+        }
+        assert_eq!(
+            &buf[..],
+            &HilbertSerializer::new().encode_many(&geoms).unwrap()[..]
+        );
+
+        // Bytes arrive one at a time.
+        let mut src = BytesMut::new();
+        let mut decoded = vec![];
+        for b in buf {
+            src.extend_from_slice(&[b]);
+            while let Some(geom) = codec.decode(&mut src).unwrap() {
+                decoded.push(geom);
+            }
+        }
+        assert_eq!(decoded, geoms);
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn test_frame_too_large() {
+        let mut codec = FramedCodec::new().max_frame_len(8);
+        let line = Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.5, y: 2.5)]);
+        let mut buf = BytesMut::new();
+        assert!(matches!(
+            codec.encode(line, &mut buf),
+            Err(CodecError::FrameTooLarge(_))
+        ));
+        assert!(buf.is_empty());
+
+        let mut src = BytesMut::from(&u32::MAX.to_le_bytes()[..]);
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(CodecError::FrameTooLarge(len)) if len == u32::MAX as usize
+        ));
+    }
+}
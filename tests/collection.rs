@@ -0,0 +1,400 @@
+#[cfg(test)]
+mod tests {
+    use bincode::config;
+    use geo_types::{line_string, polygon, Geometry};
+    use hilbert_geometry::collection::*;
+    use hilbert_geometry::*;
+
+    fn sample() -> HilbertCollection {
+        let square = encode_geometry(&Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 1.0),
+            (x: 0.0, y: 0.0)
+        ]));
+        let line = encode_geometry(&Geometry::LineString(line_string![
+            (x: 1.0, y: 1.0),
+            (x: 5.0, y: 5.0)
+        ]));
+        (0..10)
+            .map(|i| HilbertFeature::new(square.clone()).with_property("id", PropertyValue::Int(i)))
+            .chain([HilbertFeature::new(line)])
+            .collect()
+    }
+
+    #[test]
+    fn test_collection_roundtrip() {
+        let config = config::standard();
+        let collection = sample();
+        let encoded = collection.encode_bincode(&config).unwrap();
+        let decoded = HilbertCollection::decode_bincode(&encoded, &config).unwrap();
+        assert_eq!(collection, decoded);
+        assert_eq!(
+            decoded.features[3].property("id"),
+            Some(&PropertyValue::Int(3))
+        );
+    }
+
+    #[test]
+    fn test_collection_deduplication() {
+        let config = config::standard();
+        let collection = sample();
+        let plain = collection.encode_bincode(&config).unwrap();
+        let dedup = collection.encode_bincode_deduplicated(&config).unwrap();
+        assert!(dedup.len() < plain.len());
+        assert_eq!(
+            HilbertCollection::decode_bincode(&dedup, &config).unwrap(),
+            collection
+        );
+
+        // One shared part referenced by a multilinestring far more often
+        // than decoding may expand to, behind an empty metadata header.
+        let no_properties: Vec<(String, u8)> = vec![];
+        let bomb = (
+            vec![vec![0u64; 1 << 16]],
+            vec![(0u8, 4u32, vec![(1u32, 0u32); 1000], no_properties)],
+        );
+        let data = bincode::encode_to_vec(bomb, config).unwrap();
+        assert!(HilbertCollection::decode_bincode(&data, &config).is_err());
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let collection: HilbertCollection = [
+            Geometry::LineString(line_string![(x: 24.94, y: 60.17), (x: 24.95, y: 60.18)]),
+            Geometry::LineString(line_string![(x: 25.0, y: 61.0), (x: 25.1, y: 61.1)]),
+            Geometry::LineString(line_string![(x: 24.94, y: 60.17), (x: 24.95, y: 60.18)]),
+            Geometry::LineString(line_string![(x: 24.94002, y: 60.17), (x: 24.95, y: 60.18002)]),
+            Geometry::LineString(line_string![(x: 25.1, y: 61.1), (x: 25.0, y: 61.0)]),
+        ]
+        .iter()
+        .map(|g| HilbertFeature::new(encode_geometry(g)))
+        .collect();
+
+        assert_eq!(
+            collection.find_duplicates(0.0),
+            vec![DuplicateGroup {
+                features: vec![0, 2],
+                exact: true
+            }]
+        );
+        assert_eq!(
+            collection.find_duplicates(1e-4),
+            vec![DuplicateGroup {
+                features: vec![0, 2, 3],
+                exact: false
+            }]
+        );
+        assert!(sample().find_duplicates(1e-5)[0].exact);
+
+        // The same ring from another start vertex, and reversed.
+        let collection: HilbertCollection = [
+            polygon![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 1.0, y: 1.0), (x: 0.0, y: 1.0)],
+            polygon![(x: 1.0, y: 1.0), (x: 0.0, y: 1.0), (x: 0.0, y: 0.0), (x: 1.0, y: 0.0)],
+            polygon![(x: 1.0, y: 0.0), (x: 0.0, y: 0.0), (x: 0.0, y: 1.0), (x: 1.0, y: 1.0)],
+        ]
+        .into_iter()
+        .map(|p| HilbertFeature::new(encode_geometry(&Geometry::Polygon(p))))
+        .collect();
+        assert_eq!(
+            collection.find_duplicates(0.0),
+            vec![DuplicateGroup {
+                features: vec![0, 1, 2],
+                exact: true
+            }]
+        );
+    }
+
+    #[test]
+    fn test_columnar_collection() {
+        let config = config::standard();
+        let collection = sample();
+        let columnar = ColumnarCollection::from(&collection);
+        assert_eq!(columnar.len(), 11);
+        assert_eq!(columnar.offsets().len(), 12);
+        assert_eq!(&columnar.offsets()[..3], &[0, 5, 10]);
+        assert_eq!(columnar.indices().len(), 52);
+        assert_eq!(columnar.decode_coords().len(), 52);
+        assert_eq!(columnar.feature(10).unwrap(), collection.features[10]);
+        assert!(columnar.feature(11).is_none());
+        assert_eq!(HilbertCollection::from(&columnar), collection);
+
+        let encoded = columnar.encode_bincode(&config).unwrap();
+        let decoded = ColumnarCollection::decode_bincode(&encoded, &config).unwrap();
+        assert_eq!(decoded, columnar);
+        assert!(
+            ColumnarCollection::decode_bincode(&encoded[..encoded.len() - 1], &config).is_err()
+        );
+    }
+
+    #[test]
+    fn test_collection_writer_footer() {
+        let collection = sample();
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        for feature in &collection.features {
+            writer.write(feature).unwrap();
+        }
+        let data = writer.finish().unwrap();
+
+        let footer = CollectionFooter::read(&data).unwrap();
+        assert_eq!(footer.len(), collection.len());
+        let bbox = footer.bbox.unwrap();
+        assert_eq!((bbox.min_x, bbox.max_x), (0.0, 5.0));
+
+        let offset = footer.offsets[10] as usize;
+        let (last, _): (HilbertFeature, usize) =
+            bincode::decode_from_slice(&data[offset..], config::standard()).unwrap();
+        assert_eq!(last, collection.features[10]);
+    }
+
+    #[test]
+    fn test_collection_stats() {
+        let collection = sample();
+        let stats = CollectionStats::compute(&collection.features);
+        assert_eq!((stats.features, stats.vertices), (11, 52));
+        assert_eq!(
+            stats.types["Polygon"],
+            TypeStats {
+                features: 10,
+                vertices: 50
+            }
+        );
+        assert_eq!(stats.types["LineString"].features, 1);
+        assert_eq!(stats.bbox.unwrap().max_x, 5.0);
+        assert_eq!(stats.key_quantiles.len(), KEY_QUANTILES + 1);
+        assert!(stats.key_quantiles.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(
+            stats.bytes as usize,
+            collection
+                .features
+                .iter()
+                .map(|f| bincode::encode_to_vec(f, config::standard()).unwrap().len())
+                .sum::<usize>()
+        );
+        assert_eq!(
+            CollectionStats::compute([]).key_quantiles,
+            Vec::<u64>::new()
+        );
+
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        for feature in &collection.features {
+            writer.write(feature).unwrap();
+        }
+        let reader = CollectionReader::new(writer.finish().unwrap()).unwrap();
+        assert_eq!(reader.footer().stats.as_ref(), Some(&stats));
+
+        // Footers without statistics, read without looking ahead.
+        let footer = CollectionFooter {
+            stats: None,
+            ..reader.footer().clone()
+        };
+        let encoded = bincode::encode_to_vec(&footer, config::standard()).unwrap();
+        let decoded: CollectionFooter =
+            bincode::decode_from_std_read(&mut &encoded[..], config::standard()).unwrap();
+        assert_eq!(decoded, footer);
+        let encoded = bincode::encode_to_vec(reader.footer(), config::standard()).unwrap();
+        let decoded: CollectionFooter =
+            bincode::decode_from_std_read(&mut &encoded[..], config::standard()).unwrap();
+        assert_eq!(decoded.stats, Some(stats));
+    }
+
+    #[test]
+    fn test_collection_reader() {
+        let collection = sample();
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        for feature in &collection.features {
+            writer.write(feature).unwrap();
+        }
+        let reader = CollectionReader::new(writer.finish().unwrap()).unwrap();
+        assert_eq!(reader.len(), collection.len());
+        assert_eq!(reader.get(10).unwrap().unwrap(), collection.features[10]);
+        assert!(reader.get(11).is_none());
+        assert_eq!(
+            reader.range(2..4).collect::<Result<Vec<_>, _>>().unwrap(),
+            collection.features[2..4]
+        );
+
+        let query = BoundingBox {
+            min_x: 2.0,
+            min_y: 2.0,
+            max_x: 3.0,
+            max_y: 3.0,
+        };
+        assert_eq!(reader.query(&query).collect::<Vec<_>>(), vec![10]);
+    }
+
+    #[test]
+    fn test_corrupt_collection() {
+        let config = config::standard();
+        let encoded = sample().encode_bincode(&config).unwrap();
+        for len in 0..encoded.len() {
+            assert!(HilbertCollection::decode_bincode(&encoded[..len], &config).is_err());
+        }
+
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        for feature in &sample().features {
+            writer.write(feature).unwrap();
+        }
+        let mut data = writer.finish().unwrap();
+        let offset = CollectionFooter::read(&data).unwrap().offsets[0] as usize;
+        // A linestring claiming u64::MAX vertices, after the metadata flags.
+        data[offset + 1..offset + 11]
+            .copy_from_slice(&[1, 253, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+        let reader = CollectionReader::new(data).unwrap();
+        assert!(reader.get(0).unwrap().is_err());
+        assert!(reader.get(1).unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_feature_metadata() {
+        let mut collection = sample();
+        let plain = bincode::encode_to_vec(&collection.features[0], config::standard()).unwrap();
+        let metadata = FeatureMetadata {
+            timestamp: Some(-1_000),
+            id: Some(42),
+            ttl: Some(500),
+        };
+        collection.features[0] = collection.features[0].clone().with_metadata(metadata);
+        collection.features[1].metadata.id = Some(7);
+
+        // Features without metadata only store the flags byte of the header.
+        let encoded = bincode::encode_to_vec(&collection.features[0], config::standard()).unwrap();
+        assert_eq!(encoded.len(), plain.len() + FEATURE_HEADER_LEN - 1);
+        assert_eq!(plain[0], 0);
+        assert_eq!(encoded[FEATURE_HEADER_LEN..], plain[1..]);
+        assert_eq!(read_metadata(&encoded).unwrap(), metadata);
+        assert_eq!(read_metadata(&plain).unwrap(), FeatureMetadata::default());
+        assert!(read_metadata(&encoded[..10]).is_err());
+        assert!(read_metadata(&[8]).is_err());
+        // Readers that can't look ahead decode features with and without a header.
+        let std_read = |data: &[u8]| -> Result<HilbertFeature, _> {
+            bincode::decode_from_std_read(&mut &data[..], config::standard())
+        };
+        assert_eq!(
+            std_read(&plain).unwrap().metadata,
+            FeatureMetadata::default()
+        );
+        assert_eq!(std_read(&encoded).unwrap(), collection.features[0]);
+        assert_eq!(metadata.expires_at(), Some(-500));
+        assert!(metadata.is_expired(-500) && !metadata.is_expired(-501));
+
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        for feature in &collection.features {
+            writer.write(feature).unwrap();
+        }
+        let reader = CollectionReader::new(writer.finish().unwrap()).unwrap();
+        assert_eq!(reader.metadata(0).unwrap().unwrap(), metadata);
+        assert_eq!(reader.metadata(1).unwrap().unwrap().id, Some(7));
+        assert!(reader.metadata(2).unwrap().unwrap().is_empty());
+        assert_eq!(
+            reader.iter().collect::<Result<Vec<_>, _>>().unwrap(),
+            collection.features
+        );
+
+        let config = config::standard();
+        let encoded = collection.encode_bincode_deduplicated(&config).unwrap();
+        let decoded = HilbertCollection::decode_bincode(&encoded, &config).unwrap();
+        assert_eq!(decoded, collection);
+    }
+
+    #[test]
+    fn test_migrate() {
+        let collection = sample();
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        for feature in &collection.features {
+            writer.write(feature).unwrap();
+        }
+        let mut data = writer.finish().unwrap();
+        let reader = CollectionReader::new(data.clone()).unwrap();
+
+        let options = MigrateOptions::new().serializer(HilbertSerializer::new().densify(1.0));
+        let (migrated, report) = migrate(&reader, vec![], &options).unwrap();
+        assert_eq!(report.written, 11);
+        let migrated = CollectionReader::new(migrated).unwrap();
+        let first = migrated.get(0).unwrap().unwrap();
+        assert_eq!(first, collection.features[0]);
+        let HilbertGeometry::LineString(line) = migrated.get(10).unwrap().unwrap().geometry else {
+            panic!("expected a linestring")
+        };
+        assert_eq!(line.len(), 7);
+
+        // Payload encodings set the precision of the stored vertices.
+        let options =
+            MigrateOptions::new().encoding(payload::EncodingMode::Fixed(payload::Encoding::Half));
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        let point = geo_types::Point::new(24.9384, 60.1699);
+        writer
+            .write(&HilbertFeature::new(encode_geometry(&Geometry::Point(
+                point,
+            ))))
+            .unwrap();
+        let points = CollectionReader::new(writer.finish().unwrap()).unwrap();
+        let (migrated, _) = migrate(&points, vec![], &options).unwrap();
+        let migrated = CollectionReader::new(migrated).unwrap();
+        let Geometry::Point(half) = decode_geometry(&migrated.get(0).unwrap().unwrap().geometry)
+        else {
+            panic!("expected a point")
+        };
+        assert_ne!(half, point);
+        assert!((half.x() - point.x()).abs() < 0.02 && (half.y() - point.y()).abs() < 0.04);
+
+        // Collections can't keep a CRS.
+        let options = MigrateOptions::new().serializer(
+            HilbertSerializer::new()
+                .encoding(payload::EncodingMode::Fixed(payload::Encoding::Raw))
+                .crs(payload::Crs::Epsg(4326)),
+        );
+        let err = migrate(&reader, vec![], &options).unwrap_err();
+        assert!(matches!(err, MigrateError::Encode(_)));
+
+        // Corrupt the variant of the first feature, after its metadata flags.
+        data[COLLECTION_MAGIC.len() + 1] = 0xff;
+        let reader = CollectionReader::new(data).unwrap();
+        let err = migrate(&reader, vec![], &MigrateOptions::new()).unwrap_err();
+        assert!(matches!(err, MigrateError::Decode { index: 0, .. }));
+        let (migrated, report) =
+            migrate(&reader, vec![], &MigrateOptions::new().skip_invalid()).unwrap();
+        assert_eq!((report.written, report.skipped), (10, 1));
+        assert_eq!(CollectionReader::new(migrated).unwrap().len(), 10);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_collection_reader_par_iter() {
+        use rayon::prelude::*;
+
+        let collection: HilbertCollection = (0..5000)
+            .map(|i| {
+                let point = geo_types::Point::new(i as f64 * 0.01, 60.0);
+                HilbertFeature::new(encode_geometry(&Geometry::Point(point)))
+                    .with_property("id", PropertyValue::Int(i))
+            })
+            .collect();
+        let mut writer = HilbertCollectionWriter::new(vec![]).unwrap();
+        for feature in &collection.features {
+            writer.write(feature).unwrap();
+        }
+        let reader = CollectionReader::new(writer.finish().unwrap()).unwrap();
+        let features: Vec<HilbertFeature> = reader.par_iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(features, collection.features);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_collection_reader_mmap() {
+        let collection = sample();
+        let path = std::env::temp_dir().join("hilbert_geometry_mmap_test.hgc");
+        let mut writer =
+            HilbertCollectionWriter::new(std::fs::File::create(&path).unwrap()).unwrap();
+        for feature in &collection.features {
+            writer.write(feature).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let reader = CollectionReader::open_mmap(&path).unwrap();
+        let features = reader.iter().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(features, collection.features);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use hilbert_geometry::compat::*;
+    use hilbert_geometry::payload::FORMAT_VERSION;
+
+    #[test]
+    fn test_verify_compatibility() {
+        verify_compatibility().unwrap();
+        assert!(test_vectors().iter().any(|v| v.version == FORMAT_VERSION));
+    }
+
+    #[test]
+    fn test_verify_vector_reports_mismatch() {
+        let mut v = test_vectors().remove(0);
+        let mut bytes = v.bytes.to_vec();
+        *bytes.last_mut().unwrap() ^= 1;
+        v.bytes = bytes.leak();
+        let err = verify_vector(&v).unwrap_err();
+        assert_eq!(err.vector, "bincode point");
+        assert!(matches!(err.kind, CompatErrorKind::GeometryMismatch(_)));
+
+        let mut v = test_vectors().remove(0);
+        v.bytes = &v.bytes[..4];
+        let err = verify_vector(&v).unwrap_err();
+        assert!(matches!(err.kind, CompatErrorKind::Decode(_)));
+        assert!(err.to_string().starts_with("test vector \"bincode point\""));
+    }
+}
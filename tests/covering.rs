@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, polygon, Geometry, LineString, Point, Polygon, Rect};
+    use hilbert_geometry::covering::*;
+    use hilbert_geometry::*;
+
+    fn index(x: f64, y: f64) -> u64 {
+        match encode_geometry(&Geometry::Point(Point::new(x, y))) {
+            HilbertGeometry::Point(p) => p.0,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_cover_rect_contains_points() {
+        let rect = Rect::new(coord! { x: -3.5, y: 58.0 }, coord! { x: 12.25, y: 61.5 });
+        for max_ranges in [1, 8, 64] {
+            let ranges = cover_rect(&rect, max_ranges);
+            assert!(!ranges.is_empty());
+            assert!(ranges.windows(2).all(|w| w[0].end() < w[1].start()));
+            for i in 0..=20 {
+                for j in 0..=20 {
+                    let x = -3.5 + 15.75 * i as f64 / 20.0;
+                    let y = 58.0 + 3.5 * j as f64 / 20.0;
+                    let h = index(x, y);
+                    assert!(ranges.iter().any(|r| r.contains(&h)), "{x} {y}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cover_rect_excludes_far_points() {
+        let rect = Rect::new(coord! { x: 24.0, y: 60.0 }, coord! { x: 25.0, y: 61.0 });
+        let ranges = cover_rect(&rect, DEFAULT_MAX_RANGES);
+        for (x, y) in [(-24.5, 60.5), (24.5, -60.5), (100.0, 10.0), (0.0, 0.0)] {
+            let h = index(x, y);
+            assert!(!ranges.iter().any(|r| r.contains(&h)), "{x} {y}");
+        }
+    }
+
+    #[test]
+    fn test_cover_polygon() {
+        let polygon = polygon!(
+            exterior: [(x: 24.0, y: 60.0), (x: 26.0, y: 60.0), (x: 26.0, y: 62.0), (x: 24.0, y: 62.0)],
+            interiors: [[(x: 24.8, y: 60.8), (x: 25.2, y: 60.8), (x: 25.2, y: 61.2), (x: 24.8, y: 61.2)]],
+        );
+        let covering = cover_polygon(&polygon, 256);
+        assert!(!covering.inner.is_empty());
+        assert!(!covering.boundary.is_empty());
+        let within = |ranges: &[std::ops::RangeInclusive<u64>], i: u64| {
+            ranges.iter().any(|r| r.contains(&i))
+        };
+        for ix in 0..=60 {
+            for iy in 0..=60 {
+                let (x, y) = (23.5 + ix as f64 * 0.05, 59.5 + iy as f64 * 0.05);
+                let in_square = (24.0..=26.0).contains(&x) && (60.0..=62.0).contains(&y);
+                let in_hole = x > 24.8 && x < 25.2 && y > 60.8 && y < 61.2;
+                let i = index(x, y);
+                if within(&covering.inner, i) {
+                    assert!(in_square && !in_hole, "({x}, {y}) is not inside");
+                }
+                if in_square && !in_hole {
+                    assert!(within(&covering.inner, i) || within(&covering.boundary, i));
+                }
+            }
+        }
+
+        let (inner, boundary) = kv::polygon_scan_ranges(&polygon, 256);
+        assert_eq!(inner.len(), covering.inner.len());
+        assert_eq!(boundary.len(), covering.boundary.len());
+        assert_eq!(cover_polygon(&polygon![], 16), Covering::default());
+    }
+
+    #[test]
+    fn test_cover_rect_with_interior() {
+        let rect = Rect::new(coord! { x: 24.0, y: 60.0 }, coord! { x: 26.0, y: 62.0 });
+        let covering = cover_rect_with_interior(&rect, 128);
+        assert_eq!(covering.exterior(), cover_rect(&rect, 128));
+        assert_eq!(covering.interior(), covering.inner.as_slice());
+        assert_eq!(covering.kind_of(index(25.0, 61.0)), Some(RangeKind::Inner));
+        assert!(!covering.boundary.is_empty());
+        assert_eq!(covering.kind_of(index(-25.0, 61.0)), None);
+        for ix in 0..=40 {
+            for iy in 0..=40 {
+                let (x, y) = (23.0 + ix as f64 * 0.1, 59.0 + iy as f64 * 0.1);
+                let inside = (24.0..=26.0).contains(&x) && (60.0..=62.0).contains(&y);
+                match covering.kind_of(index(x, y)) {
+                    Some(RangeKind::Inner) => assert!(inside),
+                    None => assert!(!inside),
+                    Some(RangeKind::Boundary) => {}
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_traversal() {
+        let rect = Rect::new(coord! { x: -3.5, y: 58.0 }, coord! { x: 12.25, y: 61.5 });
+        let poly = polygon![
+            (x: -3.5, y: 58.0),
+            (x: 12.25, y: 58.0),
+            (x: 12.25, y: 61.5),
+            (x: -3.5, y: 61.5),
+        ];
+        for traversal in [traverse_rect(&rect), traverse_polygon(&poly)] {
+            let cells: Vec<_> = traversal.take_while(|(c, _)| c.level <= 20).collect();
+            // Coarse to fine, in curve order within each level.
+            assert!(cells.windows(2).all(|w| {
+                let (a, b) = (w[0].0, w[1].0);
+                a.level < b.level || (a.level == b.level && a.key < b.key)
+            }));
+            let covers = |index: u64, level: u32| {
+                cells.iter().any(|(c, kind)| {
+                    c.contains(HilbertPoint(index))
+                        && (c.level == level || *kind == RangeKind::Inner && c.level < level)
+                })
+            };
+            let inside = [index(-3.5, 58.0), index(0.0, 60.0), index(12.25, 61.5)];
+            for level in 0..=20 {
+                assert!(inside.iter().all(|&i| covers(i, level)));
+            }
+            assert!(!covers(index(30.0, 60.0), 20));
+            assert!(!covers(index(0.0, -60.0), 20));
+        }
+        let empty = Polygon::new(LineString::new(vec![]), vec![]);
+        assert_eq!(traverse_polygon(&empty).count(), 0);
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, point, Geometry, Rect};
+    use hilbert_geometry::crs::*;
+    use hilbert_geometry::payload::*;
+    use hilbert_geometry::HilbertSerializer;
+
+    fn tm35fin() -> Normalization {
+        Normalization::from_bounds(Rect::new(
+            coord! { x: 0.0, y: 6_000_000.0 },
+            coord! { x: 1_000_000.0, y: 8_000_000.0 },
+        ))
+    }
+
+    fn error(a: &Geometry, b: &Geometry) -> f64 {
+        let (Geometry::Point(a), Geometry::Point(b)) = (a, b) else {
+            panic!("expected points")
+        };
+        (a.x() - b.x()).abs().max((a.y() - b.y()).abs())
+    }
+
+    #[test]
+    fn test_normalization() {
+        let n = tm35fin();
+        let c = coord! { x: 385_000.0, y: 6_672_000.0 };
+        assert_eq!(n.normalize(c), coord! { x: 0.385, y: 0.336 });
+        assert_eq!(n.denormalize(n.normalize(c)), c);
+
+        let swap = Normalization::new(|c| coord! { x: c.y, y: c.x }, |c| coord! { x: c.y, y: c.x });
+        let registry = CrsRegistry::new();
+        let crs = Crs::Named("swapped".to_string());
+        assert!(registry.get(&crs).is_none());
+        assert!(registry.register(crs.clone(), swap).is_none());
+        assert_eq!(
+            registry
+                .get(&crs)
+                .unwrap()
+                .normalize(coord! { x: 1.0, y: 2.0 }),
+            coord! { x: 2.0, y: 1.0 }
+        );
+        assert!(registry.register(crs.clone(), tm35fin()).is_some());
+        assert!(registry.unregister(&crs).is_some());
+        assert!(registry.get(&crs).is_none());
+    }
+
+    #[test]
+    fn test_serializer_normalization() {
+        let geom = Geometry::Point(point!(x: 385_123.456, y: 6_672_345.678));
+        let hilbert = EncodingMode::Fixed(Encoding::Hilbert);
+        // Not registered: coordinates are encoded as they are.
+        let crs = Crs::Epsg(3067);
+        let plain = HilbertSerializer::new().encoding(hilbert).crs(crs.clone());
+        let decoded = plain.decode(&plain.encode(&geom).unwrap()).unwrap();
+        assert!(error(&geom, &decoded) > 0.1);
+
+        CrsRegistry::global().register(crs.clone(), tm35fin());
+        let data = plain.encode(&geom).unwrap();
+        let (decoded, stored) = plain.decode_with_crs(&data).unwrap();
+        assert!(error(&geom, &decoded) < 0.05);
+        assert_eq!(stored, Some(crs.clone()));
+        // The payload holds normalized coordinates.
+        assert!(
+            error(
+                &decode_payload(&data).unwrap(),
+                &point!(x: 0.385, y: 0.336).into()
+            ) < 1e-3
+        );
+        // Any serializer reading the payload denormalizes it.
+        let reader = HilbertSerializer::new().encoding(hilbert);
+        assert!(error(&geom, &reader.decode(&data).unwrap()) < 0.05);
+        CrsRegistry::global().unregister(&crs);
+    }
+}
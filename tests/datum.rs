@@ -0,0 +1,60 @@
+#![cfg(feature = "datum")]
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, Coord, Geometry};
+    use hilbert_geometry::datum::*;
+    use hilbert_geometry::payload::{Encoding, EncodingMode};
+    use hilbert_geometry::HilbertSerializer;
+
+    /// Approximate ground distance in metres between nearby positions.
+    fn metres(a: Coord, b: Coord) -> f64 {
+        let dy = (a.y - b.y) * 111_320.0;
+        let dx = (a.x - b.x) * 111_320.0 * a.y.to_radians().cos();
+        dx.hypot(dy)
+    }
+
+    #[test]
+    fn test_datum_shift() {
+        let helsinki = coord! { x: 24.9384, y: 60.1699 };
+        let etrs89 = DatumShift::new(Datum::Etrs89, 2024.0);
+        let shifted = etrs89.from_wgs84(helsinki);
+        // Europe has drifted north-east since the datum was fixed.
+        assert!((0.7..1.0).contains(&metres(helsinki, shifted)));
+        assert!(shifted.x < helsinki.x && shifted.y < helsinki.y);
+        let at_1989 = DatumShift::new(Datum::Etrs89, 1989.0).from_wgs84(helsinki);
+        assert!(metres(helsinki, at_1989) < 0.1);
+
+        let kansas = coord! { x: -98.5795, y: 39.8283 };
+        let nad83 = DatumShift::new(Datum::Nad83, 2024.0);
+        assert!((1.0..2.0).contains(&metres(kansas, nad83.from_wgs84(kansas))));
+
+        for (shift, c) in [(etrs89, helsinki), (nad83, kansas)] {
+            let back = shift.to_wgs84(shift.from_wgs84(c));
+            assert!((back.x - c.x).abs() < 1e-10 && (back.y - c.y).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_serializer_datum() {
+        let shift = DatumShift::new(Datum::Etrs89, 2024.0);
+        let geom = Geometry::LineString(vec![(24.9384, 60.1699), (25.0, 60.2)].into());
+        // Raw payloads are lossless, leaving only the shift to compare.
+        let plain = HilbertSerializer::new().encoding(EncodingMode::Fixed(Encoding::Raw));
+        let serializer = HilbertSerializer::new()
+            .encoding(EncodingMode::Fixed(Encoding::Raw))
+            .datum(shift);
+        let data = serializer.encode(&geom).unwrap();
+        let (Geometry::LineString(decoded), Geometry::LineString(stored), Geometry::LineString(ls)) = (
+            serializer.decode(&data).unwrap(),
+            plain.decode(&data).unwrap(),
+            &geom,
+        ) else {
+            panic!("expected linestrings")
+        };
+        for ((d, s), c) in decoded.0.iter().zip(&stored.0).zip(&ls.0) {
+            assert!(metres(*d, *c) < 1e-6);
+            assert!(metres(*s, shift.to_wgs84(*c)) < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use bincode::config;
+    use bincode::error::DecodeError;
+    use geo_types::{line_string, polygon, Geometry, MultiLineString, MultiPolygon};
+    use hilbert_geometry::*;
+
+    fn encode(geom: &Geometry) -> Vec<u8> {
+        HilbertSerializer::new().encode(geom).unwrap()
+    }
+
+    #[test]
+    fn test_decode_matches_derived() {
+        let poly = polygon![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 0.0)
+        ];
+        let geom = Geometry::MultiPolygon(MultiPolygon(vec![poly.clone(), poly]));
+        let data = encode(&geom);
+        let decoded = HilbertGeometry::decode_bincode(&data, &config::standard()).unwrap();
+        let (derived, _): (HilbertGeometry, _) =
+            bincode::decode_from_slice(&data, config::standard()).unwrap();
+        assert_eq!(decoded, derived);
+    }
+
+    #[test]
+    fn test_truncated_vertex() {
+        let geom = Geometry::Polygon(polygon![
+            (x: 10.0, y: 60.0),
+            (x: 11.0, y: 60.0),
+            (x: 11.0, y: 61.0),
+            (x: 10.0, y: 60.0)
+        ]);
+        let data = encode(&geom);
+        let err = HilbertSerializer::new()
+            .decode_detailed(&data[..data.len() - 1])
+            .unwrap_err();
+        assert_eq!(err.kind, Some("Polygon"));
+        assert_eq!((err.part, err.ring, err.vertex), (None, Some(0), Some(3)));
+        assert!(err.expected_bytes().unwrap() > 0);
+        assert!(err
+            .to_string()
+            .starts_with("failed to decode Polygon ring 0, vertex 3 at byte"));
+        // The plain decoders keep returning bincode errors.
+        assert!(matches!(
+            HilbertSerializer::new().decode(&data[..data.len() - 1]),
+            Err(DecodeError::UnexpectedEnd { .. })
+        ));
+    }
+
+    #[test]
+    fn test_corrupted_length() {
+        let geom = Geometry::LineString(line_string![(x: 1.0, y: 1.0), (x: 5.0, y: 5.0)]);
+        let mut data = encode(&geom);
+        // Claim 200 vertices.
+        data[1] = 200;
+        let err = HilbertGeometry::decode_bincode_detailed(&data, &config::standard()).unwrap_err();
+        assert_eq!(err.kind, Some("LineString"));
+        assert_eq!(err.vertex, None);
+        assert_eq!(err.offset, 2);
+        assert!(err.expected_bytes().unwrap() >= 200 - (data.len() - 2));
+    }
+
+    #[test]
+    fn test_invalid_tag() {
+        let err =
+            HilbertGeometry::decode_bincode_detailed(&[9, 0], &config::standard()).unwrap_err();
+        assert_eq!(err.kind, None);
+        assert!(matches!(
+            err.source,
+            DecodeError::UnexpectedVariant { found: 9, .. }
+        ));
+
+        let err: DecodeError = HilbertGeometry::decode_bincode_detailed(&[], &config::standard())
+            .unwrap_err()
+            .into();
+        assert!(matches!(err, DecodeError::OtherString(_)));
+    }
+
+    #[test]
+    fn test_non_finite_coordinates() {
+        let serializer = HilbertSerializer::new();
+        let geom = Geometry::MultiLineString(MultiLineString(vec![
+            line_string![(x: 1.0, y: 1.0)],
+            line_string![(x: 1.0, y: 1.0), (x: f64::INFINITY, y: 5.0)],
+        ]));
+        let data = serializer.encode(&geom).unwrap();
+        let err = serializer.decode_detailed(&data).unwrap_err();
+        assert_eq!(err.kind, Some("MultiLineString"));
+        assert_eq!((err.part, err.ring, err.vertex), (Some(1), None, Some(1)));
+        assert!(err
+            .to_string()
+            .starts_with("failed to decode MultiLineString part 1, vertex 1"));
+        // The encoding itself is structurally valid, and plain decoding
+        // keeps the coordinates it was written with.
+        assert!(serializer.decode(&data).is_ok());
+        let hg = HilbertGeometry::decode_bincode(&encode(&geom), &config::standard());
+        assert!(hg.is_ok());
+    }
+}
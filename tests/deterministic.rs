@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, polygon, Geometry};
+    use hilbert_geometry::quantize::{quantize, Rounding};
+    use hilbert_geometry::HilbertSerializer;
+
+    // Golden payloads. A change to any of these breaks compatibility with
+    // payloads already written by every platform.
+    const POINT: &[u8] = &[0, 253, 38, 4, 248, 142, 191, 69, 13, 32];
+    const LINESTRING: &[u8] = &[
+        1, 3, 253, 211, 114, 148, 170, 163, 205, 162, 202, 253, 80, 6, 240, 82, 160, 205, 162, 202,
+        253, 141, 160, 61, 81, 156, 205, 162, 202,
+    ];
+    const POLYGON: &[u8] = &[
+        2, 2, 5, 0, 253, 0, 0, 0, 0, 0, 172, 3, 16, 253, 0, 0, 0, 0, 0, 8, 2, 32, 253, 170, 170,
+        170, 170, 170, 14, 171, 58, 0, 4, 253, 0, 0, 0, 0, 0, 8, 0, 32, 253, 0, 0, 0, 0, 0, 89, 0,
+        32, 253, 0, 0, 0, 0, 0, 170, 0, 32, 253, 0, 0, 0, 0, 0, 8, 0, 32,
+    ];
+
+    fn golden() -> Vec<(Geometry, &'static [u8])> {
+        vec![
+            (point!(x: 24.9384, y: 60.1699).into(), POINT),
+            (
+                line_string![
+                    (x: -73.985656, y: 40.748433),
+                    (x: -73.968285, y: 40.785091),
+                    (x: -74.044502, y: 40.689247),
+                ]
+                .into(),
+                LINESTRING,
+            ),
+            (
+                polygon!(
+                    exterior: [(x: 0.0, y: 0.0), (x: 10.0, y: 0.0), (x: 10.0, y: 10.0), (x: 0.0, y: 10.0)],
+                    interiors: [[(x: 2.5, y: 2.5), (x: 7.5, y: 2.5), (x: 7.5, y: 7.5)]],
+                )
+                .into(),
+                POLYGON,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_deterministic_golden_bytes() {
+        let serializer = HilbertSerializer::deterministic();
+        for (geom, bytes) in golden() {
+            assert_eq!(serializer.encode(&geom).unwrap(), bytes, "{geom:?}");
+            serializer.decode(bytes).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_deterministic_ignores_last_bits() {
+        let serializer = HilbertSerializer::deterministic();
+        let noisy: Geometry = point!(x: 24.938400000000005, y: 60.16989999999999).into();
+        assert_eq!(serializer.encode(&noisy).unwrap(), POINT);
+    }
+
+    #[test]
+    fn test_quantize_exact_scale() {
+        assert_eq!(quantize(0.1234567891, 7, Rounding::Round), 0.1234568);
+        assert_eq!(quantize(-73.98565649, 7, Rounding::Round), -73.9856565);
+        // Beyond the table the value is left as it is.
+        assert_eq!(quantize(1.5, 40, Rounding::Round), 1.5);
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, polygon, Geometry, GeometryCollection};
+    use hilbert_geometry::ewkb::*;
+
+    #[test]
+    fn test_ewkb_point_with_srid() {
+        // SELECT ST_AsEWKB('SRID=4326;POINT(1 2)')
+        let data = [
+            0x01, 0x01, 0x00, 0x00, 0x20, 0xE6, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0xF0, 0x3F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40,
+        ];
+        let (geom, srid) = geometry_from_ewkb(&data).unwrap();
+        assert_eq!(geom, Geometry::Point(point!(x: 1.0, y: 2.0)));
+        assert_eq!(srid, Some(4326));
+        assert_eq!(geometry_to_ewkb(&geom, srid), data);
+    }
+
+    #[test]
+    fn test_ewkb_roundtrip() {
+        let geoms = vec![
+            Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.5, y: -2.0)]),
+            Geometry::Polygon(polygon![
+                (x: 0.0, y: 0.0),
+                (x: 4.0, y: 0.0),
+                (x: 4.0, y: 4.0),
+                (x: 0.0, y: 0.0),
+            ]),
+            Geometry::GeometryCollection(GeometryCollection(vec![
+                Geometry::Point(point!(x: 3.0, y: 4.0)),
+                Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)]),
+            ])),
+        ];
+        for geom in geoms {
+            let data = geometry_to_ewkb(&geom, None);
+            assert_eq!(geometry_from_ewkb(&data).unwrap(), (geom, None));
+        }
+    }
+
+    #[test]
+    fn test_ewkb_drops_z() {
+        // SELECT ST_AsBinary('POINT Z (1 2 3)'), big endian ISO WKB
+        let mut data = vec![0x00];
+        data.extend(1001u32.to_be_bytes());
+        for v in [1.0f64, 2.0, 3.0] {
+            data.extend(v.to_be_bytes());
+        }
+        let (geom, srid) = geometry_from_ewkb(&data).unwrap();
+        assert_eq!(geom, Geometry::Point(point!(x: 1.0, y: 2.0)));
+        assert_eq!(srid, None);
+    }
+
+    #[test]
+    fn test_ewkb_truncated() {
+        let data = geometry_to_ewkb(&Geometry::Point(point!(x: 1.0, y: 2.0)), None);
+        assert!(geometry_from_ewkb(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_ewkb_nesting_limit() {
+        // Geometry collections holding one geometry collection each.
+        let level = [1, 7, 0, 0, 0, 1, 0, 0, 0];
+        let data = level.repeat(100_000);
+        assert!(geometry_from_ewkb(&data).is_err());
+
+        let mut nested = Geometry::Point(point!(x: 1.0, y: 2.0));
+        for _ in 0..10 {
+            nested = Geometry::GeometryCollection(GeometryCollection(vec![nested]));
+        }
+        let data = geometry_to_ewkb(&nested, None);
+        assert_eq!(geometry_from_ewkb(&data).unwrap().0, nested);
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use bincode::error::DecodeError;
+    use geo_types::{line_string, point, polygon, Geometry};
+    use hilbert_geometry::frame::*;
+    use hilbert_geometry::payload::{Encoding, EncodingMode};
+    use hilbert_geometry::*;
+
+    fn geometries() -> Vec<Geometry> {
+        vec![
+            Geometry::Point(point! { x: 24.5, y: 60.25 }),
+            Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.5, y: 2.5)]),
+            Geometry::Polygon(polygon![
+                (x: 10.0, y: 50.0),
+                (x: 11.0, y: 50.0),
+                (x: 11.0, y: 51.0),
+                (x: 10.0, y: 50.0)
+            ]),
+        ]
+    }
+
+    #[test]
+    fn test_decode_many() {
+        let geoms = geometries();
+        for serializer in [
+            HilbertSerializer::new(),
+            HilbertSerializer::new().encoding(EncodingMode::Fixed(Encoding::HilbertDelta)),
+        ] {
+            let data = serializer.encode_many(&geoms).unwrap();
+            let decoded: Vec<Geometry> = serializer
+                .decode_many(&data)
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(decoded, geoms);
+        }
+        assert_eq!(HilbertSerializer::new().decode_many(&[]).count(), 0);
+    }
+
+    #[test]
+    fn test_decode_many_errors() {
+        let serializer = HilbertSerializer::new();
+        let geoms = geometries();
+        let data = serializer.encode_many(&geoms).unwrap();
+
+        // A truncated last frame fails once and ends the iteration.
+        let results: Vec<_> = serializer.decode_many(&data[..data.len() - 3]).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[..2].iter().all(Result::is_ok));
+        let err = results[2].as_ref().unwrap_err();
+        assert_eq!(err.expected_bytes(), Some(3));
+
+        // A corrupt frame fails alone, with the offset in the whole buffer.
+        let mut framed = vec![];
+        write_frame(&mut framed, &[0xff]).unwrap();
+        write_frame(&mut framed, &serializer.encode(&geoms[0]).unwrap()).unwrap();
+        let results: Vec<_> = serializer.decode_many(&framed).collect();
+        assert_eq!(results[0].as_ref().unwrap_err().offset, FRAME_HEADER_LEN);
+        assert_eq!(results[1].as_ref().unwrap(), &geoms[0]);
+    }
+
+    #[test]
+    fn test_read_frame() {
+        let mut buf = vec![];
+        write_frame(&mut buf, b"abc").unwrap();
+        buf.push(7);
+        assert_eq!(read_frame(&buf).unwrap(), (&b"abc"[..], &[7u8][..]));
+        assert!(matches!(
+            read_frame(&buf[..2]),
+            Err(DecodeError::UnexpectedEnd { additional: 2 })
+        ));
+    }
+}
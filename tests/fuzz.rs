@@ -0,0 +1,56 @@
+#![cfg(feature = "arbitrary")]
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use hilbert_geometry::payload::{encode_payload, Encoding, EncodingMode};
+    use hilbert_geometry::testing::fuzz;
+    use hilbert_geometry::{decode_geometry, HilbertGeometry};
+
+    /// Deterministic pseudo-random buffers of varying length.
+    fn buffers() -> impl Iterator<Item = Vec<u8>> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        (0..500).map(move |i| {
+            (0..i % 97)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u8
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_arbitrary_geometries() {
+        for data in buffers() {
+            fuzz::roundtrip(&data).unwrap();
+        }
+        let hg = HilbertGeometry::arbitrary(&mut Unstructured::new(&[2; 64])).unwrap();
+        assert!(matches!(hg, HilbertGeometry::Polygon(_)));
+    }
+
+    #[test]
+    fn test_decode_garbage() {
+        for data in buffers() {
+            fuzz::decode(&data);
+        }
+    }
+
+    #[test]
+    fn test_decode_mutated_payloads() {
+        for (i, data) in buffers().enumerate() {
+            let Ok(hg) = HilbertGeometry::arbitrary(&mut Unstructured::new(&data)) else {
+                continue;
+            };
+            let encoding = [Encoding::Hilbert, Encoding::Raw, Encoding::Half][i % 3];
+            let mut payload =
+                encode_payload(&decode_geometry(&hg), EncodingMode::Fixed(encoding)).unwrap();
+            let pos = i % payload.len();
+            payload[pos] ^= data.first().copied().unwrap_or(0xff);
+            fuzz::decode(&payload);
+            fuzz::decode(&payload[..pos]);
+        }
+    }
+}
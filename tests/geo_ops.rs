@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{point, Geometry};
+    use hilbert_geometry::geo_ops::*;
+    use hilbert_geometry::{decode_geometry, encode_geometry, HilbertGeometry, HilbertPoint};
+
+    fn encode(x: f64, y: f64) -> HilbertPoint {
+        let HilbertGeometry::Point(p) = encode_geometry(&Geometry::Point(point!(x: x, y: y)))
+        else {
+            unreachable!()
+        };
+        p
+    }
+
+    fn decode(p: HilbertPoint) -> (f64, f64) {
+        let Geometry::Point(p) = decode_geometry(&HilbertGeometry::Point(p)) else {
+            unreachable!()
+        };
+        (p.x(), p.y())
+    }
+
+    #[test]
+    fn test_distance_and_bearing() {
+        let (london, paris) = (encode(-0.1276, 51.5072), encode(2.3522, 48.8566));
+        assert!((haversine_distance(london, paris) - 343_530.3).abs() < 1.0);
+        assert_eq!(
+            haversine_distance(london, paris),
+            haversine_distance(paris, london)
+        );
+        assert_eq!(haversine_distance(london, london), 0.0);
+        assert!((bearing(london, paris) - 148.1158).abs() < 1e-3);
+        assert_eq!(bearing(london, london), 0.0);
+
+        let north = (encode(10.0, 0.0), encode(10.0, 1.0));
+        assert!(bearing(north.0, north.1).abs() < 1e-9);
+        assert!((bearing(north.1, north.0) - 180.0).abs() < 1e-9);
+        let west = encode(9.0, 0.0);
+        assert!((bearing(north.0, west) - 270.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_destination() {
+        let london = encode(-0.1276, 51.5072);
+        let paris = encode(2.3522, 48.8566);
+        let reached = destination(
+            london,
+            bearing(london, paris),
+            haversine_distance(london, paris),
+        );
+        assert!(haversine_distance(reached, paris) < 1.0);
+
+        // A quarter of the way around the equator, across the antimeridian.
+        let quarter = std::f64::consts::FRAC_PI_2 * EARTH_RADIUS;
+        let (x, y) = decode(destination(encode(135.0, 0.0), 90.0, quarter));
+        assert!((x + 135.0).abs() < 1e-5 && y.abs() < 1e-5);
+        assert_eq!(destination(london, 45.0, 0.0), london);
+    }
+}
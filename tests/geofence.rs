@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{point, polygon, Geometry, Polygon};
+    use hilbert_geometry::geofence::*;
+    use hilbert_geometry::prepared::PreparedPolygon;
+    use hilbert_geometry::{encode_geometry, HilbertGeometry, HilbertPoint};
+
+    fn key(x: f64, y: f64) -> HilbertPoint {
+        match encode_geometry(&Geometry::Point(point!(x: x, y: y))) {
+            HilbertGeometry::Point(p) => p,
+            _ => unreachable!(),
+        }
+    }
+
+    fn square(x: f64, y: f64, size: f64) -> Polygon {
+        polygon![
+            (x: x, y: y),
+            (x: x + size, y: y),
+            (x: x + size, y: y + size),
+            (x: x, y: y + size),
+        ]
+    }
+
+    fn fence(name: &str, polygon: Polygon) -> (String, PreparedPolygon) {
+        let geom = encode_geometry(&Geometry::Polygon(polygon));
+        (name.to_string(), PreparedPolygon::new(&geom).unwrap())
+    }
+
+    #[test]
+    fn test_geofence_events() {
+        let set = GeofenceSet::new([
+            fence("big", square(24.0, 60.0, 2.0)),
+            fence("small", square(24.5, 60.5, 0.2)),
+            fence("far", square(-70.0, -30.0, 1.0)),
+        ]);
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.containing(key(24.6, 60.6)), vec![0, 1]);
+        assert_eq!(set.containing(key(-69.5, -29.5)), vec![2]);
+        assert!(set.containing(key(0.0, 0.0)).is_empty());
+        assert_eq!(set.name(1), Some("small"));
+
+        let mut watcher = set.watch();
+        assert!(watcher.update(key(23.0, 60.6)).is_empty());
+        assert_eq!(
+            watcher.update(key(24.2, 60.6)),
+            vec![GeofenceEvent::Enter("big")]
+        );
+        assert_eq!(
+            watcher.update(key(24.6, 60.6)),
+            vec![GeofenceEvent::Enter("small")]
+        );
+        assert_eq!(watcher.inside().collect::<Vec<_>>(), vec!["big", "small"]);
+        assert!(watcher.update(key(24.61, 60.61)).is_empty());
+        assert_eq!(
+            watcher.update(key(-69.5, -29.5)),
+            vec![
+                GeofenceEvent::Exit("big"),
+                GeofenceEvent::Exit("small"),
+                GeofenceEvent::Enter("far")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_geofence_many_fences() {
+        let fences: Vec<_> = (0..400)
+            .map(|i| {
+                let (x, y) = ((i % 20) as f64, (i / 20) as f64);
+                fence(&format!("{i}"), square(x, y, 0.5))
+            })
+            .collect();
+        let set = GeofenceSet::new(fences);
+        for i in 0..400 {
+            let (x, y) = ((i % 20) as f64, (i / 20) as f64);
+            assert_eq!(set.containing(key(x + 0.25, y + 0.25)), vec![i]);
+            assert!(set.containing(key(x + 0.75, y + 0.75)).is_empty());
+        }
+    }
+}
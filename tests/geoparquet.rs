@@ -0,0 +1,101 @@
+#![cfg(feature = "geoparquet")]
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::{Float64Type, Int64Type};
+    use bincode::config;
+    use geo_types::{line_string, point, Geometry};
+    use hilbert_geometry::collection::*;
+    use hilbert_geometry::geoparquet::*;
+    use hilbert_geometry::kv::make_key;
+    use hilbert_geometry::*;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("hilbert-geoparquet-{}-{name}", std::process::id()))
+    }
+
+    fn sample() -> HilbertCollection {
+        let mut features: Vec<HilbertFeature> = (0..10)
+            .map(|i| {
+                let x = ((i * 7) % 10) as f64;
+                HilbertFeature::new(encode_geometry(&Geometry::Point(point!(x: x, y: 60.0))))
+                    .with_property("id", PropertyValue::Int(i))
+            })
+            .collect();
+        features.push(
+            HilbertFeature::new(encode_geometry(&Geometry::LineString(
+                line_string![(x: -1.0, y: 59.0), (x: 1.0, y: 61.0)],
+            )))
+            .with_property("id", PropertyValue::Int(10)),
+        );
+        HilbertCollection::new(features)
+    }
+
+    #[test]
+    fn test_geo_metadata() {
+        let json = GeoParquetWriter::new()
+            .crs("{\"id\":{\"authority\":\"OGC\",\"code\":\"CRS84\"}}")
+            .geo_metadata(&sample());
+        assert!(json.starts_with("{\"version\":\"1.1.0\",\"primary_column\":\"geometry\""));
+        assert!(json.contains("\"encoding\":\"hilbert-geometry\""));
+        assert!(json.contains("\"geometry_types\":[\"LineString\",\"Point\"]"));
+        assert!(json.contains("\"bbox\":[-1,59,9,61]"));
+        assert!(json.contains("\"crs\":{\"id\":{\"authority\":\"OGC\",\"code\":\"CRS84\"}}"));
+        assert!(json.contains("\"covering\":{\"bbox\":{\"xmin\":[\"bbox\",\"xmin\"]"));
+    }
+
+    #[test]
+    fn test_write_sorted_row_groups() {
+        let path = temp_path("sorted.parquet");
+        GeoParquetWriter::new()
+            .row_group_size(4)
+            .write(File::create(&path).unwrap(), &sample())
+            .unwrap();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap()).unwrap();
+        let metadata = builder.metadata();
+        assert_eq!(metadata.num_row_groups(), 3);
+        assert_eq!(metadata.file_metadata().num_rows(), 11);
+        let geo = metadata
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap()
+            .iter()
+            .find(|kv| kv.key == "geo")
+            .and_then(|kv| kv.value.clone())
+            .unwrap();
+        assert!(geo.contains("\"encoding\":\"hilbert-geometry\""));
+
+        let mut keys = vec![];
+        let mut ids = vec![];
+        for batch in builder.build().unwrap() {
+            let batch = batch.unwrap();
+            let geometries = batch.column_by_name("geometry").unwrap().as_binary::<i32>();
+            let bboxes = batch.column_by_name("bbox").unwrap().as_struct();
+            let xmin = bboxes
+                .column_by_name("xmin")
+                .unwrap()
+                .as_primitive::<Float64Type>();
+            for (i, bytes) in geometries.iter().enumerate() {
+                let (geom, _): (HilbertGeometry, _) =
+                    bincode::decode_from_slice(bytes.unwrap(), config::standard()).unwrap();
+                assert!(!bboxes.is_null(i));
+                assert_eq!(xmin.value(i), geom.bounding_rect().unwrap().min().x);
+                keys.push(make_key(&geom));
+            }
+            let id = batch
+                .column_by_name("id")
+                .unwrap()
+                .as_primitive::<Int64Type>();
+            ids.extend(id.values().iter().copied());
+        }
+        assert!(keys.windows(2).all(|w| w[0] <= w[1]));
+        ids.sort();
+        assert_eq!(ids, (0..11).collect::<Vec<_>>());
+        std::fs::remove_file(&path).ok();
+    }
+}
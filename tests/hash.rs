@@ -0,0 +1,87 @@
+#![cfg(feature = "blake3")]
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, polygon, Geometry};
+    use hilbert_geometry::collection::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_content_hash() {
+        let a = encode_geometry(&Geometry::Point(point!(x: 24.94, y: 60.17)));
+        let b = encode_geometry(&Geometry::Point(point!(x: 24.94, y: 60.17)));
+        let c = encode_geometry(&Geometry::Point(point!(x: 24.95, y: 60.17)));
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_ne!(a.content_hash(), c.content_hash());
+
+        // Same indices, different geometry type.
+        let line = encode_geometry(&Geometry::LineString(line_string![(x: 1.0, y: 2.0)]));
+        let multi = HilbertGeometry::MultiPoint(match &line {
+            HilbertGeometry::LineString(ps) => ps.clone(),
+            _ => unreachable!(),
+        });
+        assert_ne!(line.content_hash(), multi.content_hash());
+    }
+
+    #[test]
+    fn test_content_hash_ring_start() {
+        let a = encode_geometry(&Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+        ]));
+        let b = encode_geometry(&Geometry::Polygon(polygon![
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 0.0),
+        ]));
+        let reversed = encode_geometry(&Geometry::Polygon(polygon![
+            (x: 1.0, y: 1.0),
+            (x: 1.0, y: 0.0),
+            (x: 0.0, y: 0.0),
+        ]));
+        assert_ne!(a, b);
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_ne!(a.content_hash(), reversed.content_hash());
+    }
+
+    #[test]
+    fn test_assign_ids() {
+        let point = |x: f64| encode_geometry(&Geometry::Point(point!(x: x, y: 60.25)));
+        let features = vec![
+            HilbertFeature::new(point(24.5)),
+            HilbertFeature::new(point(25.5)),
+            HilbertFeature::new(point(24.5)),
+            HilbertFeature::new(point(24.5)).with_property("a", PropertyValue::Int(1)),
+        ];
+        let mut collection = HilbertCollection::new(features.clone());
+        let ids = collection.assign_ids();
+        assert_eq!(
+            collection
+                .features
+                .iter()
+                .map(|f| f.metadata.id.unwrap())
+                .collect::<Vec<_>>(),
+            ids
+        );
+        let mut unique = ids.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 4);
+        assert_eq!(
+            ids[1],
+            u64::from_le_bytes(point(25.5).content_hash()[..8].try_into().unwrap())
+        );
+
+        // Rebuilding in another order gives each feature the same id.
+        let mut reordered = HilbertCollection::new(features.into_iter().rev().collect());
+        let reordered_ids = reordered.assign_ids();
+        assert_eq!(reordered_ids[2], ids[1]);
+        assert_eq!(reordered_ids[0], ids[3]);
+        let mut duplicates = [reordered_ids[1], reordered_ids[3]];
+        duplicates.sort();
+        let mut expected = [ids[0], ids[2]];
+        expected.sort();
+        assert_eq!(duplicates, expected);
+    }
+}
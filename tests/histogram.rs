@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, point, Geometry};
+    use hilbert_geometry::cell::HilbertCell;
+    use hilbert_geometry::histogram::*;
+    use hilbert_geometry::{encode_geometry, HilbertGeometry, HilbertPoint};
+
+    #[test]
+    fn test_key_histogram_counts() {
+        let keys: Vec<_> = [0, 1, 2, 3, 5, 9].map(HilbertPoint).to_vec();
+        let buckets = key_histogram(&keys, 5);
+        let ranges: Vec<_> = buckets.iter().map(|b| b.range.clone()).collect();
+        assert_eq!(ranges, vec![0..=1, 2..=3, 4..=5, 6..=7, 8..=9]);
+        let counts: Vec<_> = buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![2, 2, 1, 0, 1]);
+        assert!(buckets.iter().all(|b| b.bbox.is_none()));
+
+        assert!(key_histogram(&[], 5).is_empty());
+        assert!(key_histogram(&keys, 0).is_empty());
+        assert!(key_histogram_with_bboxes(&keys, 0).is_empty());
+        assert_eq!(key_histogram(&keys[..1], 5).len(), 1);
+        let full = key_histogram(&[HilbertPoint(0), HilbertPoint(u64::MAX)], 2);
+        assert_eq!(full[1].range, 1 << 63..=u64::MAX);
+        assert_eq!((full[0].count, full[1].count), (1, 1));
+    }
+
+    #[test]
+    fn test_key_histogram_bboxes() {
+        let key = |x: f64, y: f64| match encode_geometry(&Geometry::Point(point!(x: x, y: y))) {
+            HilbertGeometry::Point(p) => p,
+            _ => unreachable!(),
+        };
+        let keys = vec![key(24.5, 60.25), key(24.75, 60.5), key(-70.0, -30.0)];
+        let buckets = key_histogram_with_bboxes(&keys, 1);
+        let bbox = buckets[0].bbox.unwrap();
+        assert_eq!(bbox.min(), coord! { x: -70.0, y: -30.0 });
+        assert_eq!(bbox.max(), coord! { x: 24.75, y: 60.5 });
+        let total: usize = key_histogram_with_bboxes(&keys, 16)
+            .iter()
+            .map(|b| b.count)
+            .sum();
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn test_aggregate_to_cells() {
+        let keys: Vec<_> = [
+            0x4000_0000_0000_0001,
+            0x0000_0000_0000_0005,
+            0x4fff_ffff_ffff_ffff,
+            0xc000_0000_0000_0000,
+            0x0000_0000_0000_0007,
+        ]
+        .map(HilbertPoint)
+        .to_vec();
+        let cells = aggregate_to_cells(&keys, 1);
+        assert_eq!(
+            cells,
+            vec![
+                (HilbertCell { level: 1, key: 0 }, 2),
+                (HilbertCell { level: 1, key: 1 }, 2),
+                (HilbertCell { level: 1, key: 3 }, 1),
+            ]
+        );
+        assert_eq!(aggregate_to_cells(&keys, 0), vec![(HilbertCell::ROOT, 5)]);
+        assert_eq!(aggregate_to_cells(&keys, 32).len(), 5);
+        assert!(aggregate_to_cells(&[], 10).is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{polygon, Geometry, MultiPoint, Point, Polygon};
+    use hilbert_geometry::{decode_geometry, encode_geometry, HilbertGeometry};
+
+    fn hull(coords: &[(f64, f64)]) -> Polygon {
+        let points = MultiPoint(coords.iter().map(|&c| Point::from(c)).collect());
+        let encoded = encode_geometry(&Geometry::MultiPoint(points));
+        let Geometry::Polygon(hull) = decode_geometry(&encoded.convex_hull().unwrap()) else {
+            panic!("expected a polygon")
+        };
+        hull
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let square = [
+            (0.0, 0.0),
+            (1.0, 1.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (0.5, 1.5),
+            (2.0, 1.0),
+        ];
+        assert_eq!(
+            hull(&square),
+            polygon![
+                (x: 0.0, y: 0.0),
+                (x: 2.0, y: 0.0),
+                (x: 2.0, y: 2.0),
+                (x: 0.0, y: 2.0),
+                (x: 0.0, y: 0.0),
+            ]
+        );
+
+        // Vertices of other geometry types count too.
+        let encoded = encode_geometry(&Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0),
+            (x: 4.0, y: 0.0),
+            (x: 2.0, y: 1.0),
+            (x: 4.0, y: 4.0),
+            (x: 0.0, y: 4.0),
+        ]));
+        let Geometry::Polygon(hull) = decode_geometry(&encoded.convex_hull().unwrap()) else {
+            panic!("expected a polygon")
+        };
+        assert_eq!(hull.exterior().0.len(), 5);
+    }
+
+    #[test]
+    fn test_convex_hull_degenerate() {
+        assert_eq!(
+            hull(&[(0.0, 0.0), (1.0, 1.0), (3.0, 3.0), (2.0, 2.0)]),
+            polygon![(x: 0.0, y: 0.0), (x: 3.0, y: 3.0), (x: 0.0, y: 0.0)]
+        );
+        assert_eq!(
+            hull(&[(5.0, 5.0), (5.0, 5.0)]),
+            polygon![(x: 5.0, y: 5.0), (x: 5.0, y: 5.0), (x: 5.0, y: 5.0)]
+        );
+        assert!(HilbertGeometry::MultiPoint(vec![]).convex_hull().is_none());
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, polygon, Geometry, LineString};
+    use hilbert_geometry::incremental::*;
+    use hilbert_geometry::payload::*;
+
+    fn delta_payload(geom: &Geometry) -> Vec<u8> {
+        encode_payload(geom, EncodingMode::Fixed(Encoding::HilbertDelta)).unwrap()
+    }
+
+    #[test]
+    fn test_linestring_encoder_snapshots() {
+        let fixes: Vec<_> = (0..50)
+            .map(|i| coord! { x: 24.9 + i as f64 * 1e-4, y: 60.1 - i as f64 * 1e-4 })
+            .collect();
+        let mut encoder = LineStringEncoder::new();
+        assert!(encoder.is_empty());
+        assert_eq!(
+            encoder.snapshot().unwrap(),
+            delta_payload(&Geometry::LineString(LineString::new(vec![])))
+        );
+        for (i, fix) in fixes.iter().enumerate() {
+            encoder.push(*fix).unwrap();
+            let expected = Geometry::LineString(LineString::new(fixes[..=i].to_vec()));
+            assert_eq!(encoder.snapshot().unwrap(), delta_payload(&expected));
+        }
+        assert_eq!(encoder.len(), 50);
+        let decoded = decode_payload(&encoder.snapshot().unwrap()).unwrap();
+        let Geometry::LineString(ls) = decoded else {
+            panic!("expected a linestring")
+        };
+        assert_eq!(ls.0.len(), 50);
+    }
+
+    #[test]
+    fn test_polygon_encoder() {
+        let mut encoder = PolygonEncoder::new();
+        assert_eq!(
+            encoder.snapshot().unwrap(),
+            delta_payload(&Geometry::Polygon(polygon![]))
+        );
+        for (x, y) in [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)] {
+            encoder.push(coord! { x: x, y: y }).unwrap();
+        }
+        // The open ring is closed in the snapshot only.
+        let triangle = polygon![(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0)];
+        assert_eq!(
+            encoder.snapshot().unwrap(),
+            delta_payload(&Geometry::Polygon(triangle))
+        );
+
+        encoder.push(coord! { x: 0.0, y: 4.0 }).unwrap();
+        encoder.next_ring().unwrap();
+        encoder.next_ring().unwrap();
+        for (x, y) in [(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 1.0)] {
+            encoder.push(coord! { x: x, y: y }).unwrap();
+        }
+        let expected = polygon!(
+            exterior: [(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 4.0)],
+            interiors: [[(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 2.0, y: 2.0)]],
+        );
+        let snapshot = encoder.snapshot().unwrap();
+        assert_eq!(
+            snapshot,
+            delta_payload(&Geometry::Polygon(expected.clone()))
+        );
+        assert_eq!(
+            decode_payload(&snapshot).unwrap(),
+            Geometry::Polygon(expected)
+        );
+    }
+
+    #[test]
+    fn test_recent_track_encoder() {
+        let fix = |i: usize| coord! { x: 24.9 + i as f64 * 1e-3, y: 60.1 };
+        let mut track = RecentTrackEncoder::new(5);
+        for i in 0..8 {
+            track.push(fix(i), i as f64);
+        }
+        assert_eq!(track.len(), 5);
+        assert_eq!(
+            track.times().collect::<Vec<_>>(),
+            vec![3.0, 4.0, 5.0, 6.0, 7.0]
+        );
+        let expected = Geometry::LineString((3..8).map(fix).collect());
+        assert_eq!(track.snapshot().unwrap(), delta_payload(&expected));
+        assert_eq!(
+            track.linestring(),
+            hilbert_geometry::encode_geometry(&expected)
+        );
+
+        let mut track = RecentTrackEncoder::new(100).max_age(10.0);
+        for t in [0.0, 5.0, 10.0, 12.0, 30.0] {
+            track.push(fix(t as usize), t);
+            assert!(!track.is_empty());
+        }
+        assert_eq!(track.times().collect::<Vec<_>>(), vec![30.0]);
+        track.push(fix(31), 35.0);
+        assert_eq!(track.times().collect::<Vec<_>>(), vec![30.0, 35.0]);
+    }
+}
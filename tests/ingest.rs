@@ -0,0 +1,44 @@
+#![cfg(feature = "csv")]
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{point, Geometry};
+    use hilbert_geometry::ingest::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_encode_csv_points() {
+        let mut csv = String::from("time,lat,lon\n");
+        for i in 0..100_000 {
+            csv += &format!(
+                "{i},{},{}\n",
+                60.0 + i as f64 * 1e-6,
+                24.0 + i as f64 * 1e-6
+            );
+        }
+        let points = encode_csv_points(csv.as_bytes(), "lon", "lat").unwrap();
+        assert_eq!(points.len(), 100_000);
+        let expected = encode_geometry(&Geometry::Point(
+            point!(x: 24.0 + 0.07777, y: 60.0 + 0.07777),
+        ));
+        assert_eq!(HilbertGeometry::Point(points[77_770]), expected);
+    }
+
+    #[test]
+    fn test_encode_csv_points_errors() {
+        let csv = "lon,lat\n1.0,2.0\n3.0,oops\n";
+        match encode_csv_points(csv.as_bytes(), "lon", "lat") {
+            Err(CsvError::InvalidCoordinate { record, value }) => {
+                assert_eq!((record, value.as_str()), (2, "oops"))
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(matches!(
+            encode_csv_points(csv.as_bytes(), "x", "lat"),
+            Err(CsvError::MissingColumn(c)) if c == "x"
+        ));
+        assert!(encode_csv_points("lon,lat\n".as_bytes(), "lon", "lat")
+            .unwrap()
+            .is_empty());
+    }
+}
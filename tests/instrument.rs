@@ -0,0 +1,77 @@
+#![cfg(feature = "tracing")]
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use geo_types::{line_string, Geometry};
+    use hilbert_geometry::*;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Records the numeric fields of events and the names of spans.
+    #[derive(Clone, Default)]
+    struct Recorder {
+        fields: Arc<Mutex<Vec<(String, u64)>>>,
+        spans: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl Visit for Recorder {
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            self.fields
+                .lock()
+                .unwrap()
+                .push((field.name().to_string(), value));
+        }
+        fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span.metadata().name());
+            Id::from_u64(spans.len() as u64)
+        }
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+        fn enter(&self, _span: &Id) {}
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[test]
+    fn test_serializer_events() {
+        let recorder = Recorder::default();
+        let geom = Geometry::LineString(line_string![(x: 1.0, y: 2.0), (x: 3.0, y: 4.0)]);
+        let serializer = HilbertSerializer::new();
+        let bytes = tracing::subscriber::with_default(recorder.clone(), || {
+            let bytes = serializer.encode(&geom).unwrap();
+            serializer.decode(&bytes).unwrap();
+            assert!(serializer.decode(&bytes[..3]).is_err());
+            bytes
+        });
+
+        assert_eq!(
+            *recorder.spans.lock().unwrap(),
+            vec!["hilbert_encode", "hilbert_decode", "hilbert_decode"]
+        );
+        let fields = recorder.fields.lock().unwrap();
+        let n = bytes.len() as u64;
+        assert_eq!(
+            *fields,
+            vec![
+                ("monotonic_counter.hilbert_bytes_encoded".to_string(), n),
+                ("monotonic_counter.hilbert_vertices_encoded".to_string(), 2),
+                ("monotonic_counter.hilbert_bytes_decoded".to_string(), n),
+                ("monotonic_counter.hilbert_vertices_decoded".to_string(), 2),
+                ("monotonic_counter.hilbert_decode_errors".to_string(), 1),
+            ]
+        );
+    }
+}
@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use geo_types::{line_string, point, polygon, Geometry};
+    use geo_types::{
+        line_string, point, polygon, Coord, Geometry, MultiLineString, MultiPolygon, Point,
+    };
     use hilbert_geometry::*;
-    use wkb;
 
     #[test]
     fn test_point_encoding() {
@@ -15,6 +16,13 @@ mod tests {
         let encoded = encode_geometry(&pt);
         let decoded = decode_geometry(&encoded);
         assert_eq!(pt, decoded);
+
+        assert_eq!(try_encode_geometry(&pt).unwrap(), encoded);
+        let line = Geometry::Line(geo_types::Line::new(
+            Coord { x: 0.0, y: 0.0 },
+            Coord { x: 1.0, y: 1.0 },
+        ));
+        assert!(try_encode_geometry(&line).is_err());
     }
 
     #[test]
@@ -96,4 +104,288 @@ mod tests {
         );
         assert_eq!(poly, decoded);
     }
+
+    #[test]
+    fn test_sort_multipoints() {
+        let points = |coords: &[(f64, f64)]| {
+            Geometry::MultiPoint(coords.iter().map(|&c| Point::from(c)).collect())
+        };
+        let a = points(&[
+            (24.5, 60.25),
+            (-70.5, -33.375),
+            (24.5, 60.25),
+            (2.25, 48.75),
+        ]);
+        let b = points(&[(2.25, 48.75), (24.5, 60.25), (-70.5, -33.375)]);
+        let serializer = HilbertSerializer::new().sort_multipoints();
+        let encoded = serializer.encode(&a).unwrap();
+        assert_eq!(encoded, serializer.encode(&b).unwrap());
+
+        let Geometry::MultiPoint(decoded) = serializer.decode(&encoded).unwrap() else {
+            panic!("expected a multipoint")
+        };
+        assert_eq!(decoded.0.len(), 3);
+        let HilbertGeometry::MultiPoint(indices) = encode_geometry(&decoded.into()) else {
+            unreachable!()
+        };
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+
+        // Off by default, and other geometry types are left alone.
+        assert_eq!(
+            HilbertSerializer::new()
+                .decode(&HilbertSerializer::new().encode(&a).unwrap())
+                .unwrap(),
+            a
+        );
+        let ls = Geometry::LineString(line_string![(x: 5.0, y: 5.0), (x: 1.0, y: 1.0)]);
+        assert_eq!(
+            serializer.decode(&serializer.encode(&ls).unwrap()).unwrap(),
+            ls
+        );
+    }
+
+    #[test]
+    fn test_introspection() {
+        let poly = polygon![
+            exterior: [
+                (x: 0.0, y: 0.0),
+                (x: 4.0, y: 0.0),
+                (x: 4.0, y: 4.0),
+                (x: 0.0, y: 0.0)
+            ],
+            interiors: [[
+                (x: 1.0, y: 1.0),
+                (x: 2.0, y: 1.0),
+                (x: 2.0, y: 2.0),
+                (x: 1.0, y: 1.0)
+            ]]
+        ];
+        let geom = encode_geometry(&Geometry::MultiPolygon(MultiPolygon(vec![
+            poly.clone(),
+            poly,
+        ])));
+        assert_eq!(geom.kind(), "MultiPolygon");
+        assert_eq!(geom.num_coords(), 16);
+        assert_eq!(geom.num_rings(), 4);
+        assert_eq!(geom.num_geometries(), 2);
+        assert!(!geom.is_empty());
+
+        let ls = encode_geometry(&Geometry::LineString(line_string![]));
+        assert_eq!(ls.kind(), "LineString");
+        assert_eq!(
+            (ls.num_coords(), ls.num_rings(), ls.num_geometries()),
+            (0, 0, 1)
+        );
+        assert!(ls.is_empty());
+    }
+
+    #[test]
+    fn test_map_coords() {
+        let poly = Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 0.0)
+        ]);
+        let moved = encode_geometry(&poly).map_coords(|c| Coord {
+            x: c.x * 2.0 + 10.0,
+            y: c.y - 5.0,
+        });
+        let expected = Geometry::Polygon(polygon![
+            (x: 10.0, y: -5.0),
+            (x: 12.0, y: -5.0),
+            (x: 12.0, y: -4.0),
+            (x: 10.0, y: -5.0)
+        ]);
+        assert_eq!(moved, encode_geometry(&expected));
+    }
+
+    #[test]
+    fn test_translate_and_scale() {
+        let poly = polygon![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 0.0)
+        ];
+        let mut encoded = encode_geometry(&MultiPolygon(vec![poly.clone(), poly]).into());
+        encoded.translate(10.0, -5.0);
+        let moved = polygon![
+            (x: 10.0, y: -5.0),
+            (x: 11.0, y: -5.0),
+            (x: 11.0, y: -4.0),
+            (x: 10.0, y: -5.0)
+        ];
+        assert_eq!(
+            encoded,
+            encode_geometry(&MultiPolygon(vec![moved.clone(), moved]).into())
+        );
+
+        encoded.scale_about(Coord { x: 10.0, y: -5.0 }, 4.0);
+        let scaled = polygon![
+            (x: 10.0, y: -5.0),
+            (x: 14.0, y: -5.0),
+            (x: 14.0, y: -1.0),
+            (x: 10.0, y: -5.0)
+        ];
+        assert_eq!(
+            encoded,
+            encode_geometry(&MultiPolygon(vec![scaled.clone(), scaled]).into())
+        );
+
+        let mut point = encode_geometry(&Geometry::Point(point!(x: 2.0, y: 2.0)));
+        point.scale_about(Coord { x: 1.0, y: 1.0 }, -0.5);
+        assert_eq!(
+            point,
+            encode_geometry(&Geometry::Point(point!(x: 0.5, y: 0.5)))
+        );
+    }
+
+    #[test]
+    fn test_curve_interpolation() {
+        let (a, b) = (HilbertPoint(10), HilbertPoint(u64::MAX));
+        assert_eq!(a.lerp_along_curve(b, 0.0), a);
+        assert_eq!(a.lerp_along_curve(b, 1.0), b);
+        assert_eq!(b.lerp_along_curve(a, 1.5), a);
+        assert_eq!(a.midpoint(b), HilbertPoint(u64::MAX / 2 + 5));
+        assert_eq!(HilbertPoint(3).midpoint(HilbertPoint(7)), HilbertPoint(5));
+
+        let samples: Vec<_> = (0..=10)
+            .map(|i| a.lerp_along_curve(b, i as f64 / 10.0))
+            .collect();
+        assert!(samples.windows(2).all(|w| w[0] < w[1]));
+        let mid = a.lerp_along_curve(b, 0.5);
+        assert!(mid.0.abs_diff(a.midpoint(b).0) <= 1);
+    }
+
+    #[test]
+    fn test_merge() {
+        let point = |x: f64| encode_geometry(&Geometry::Point(point!(x: x, y: 1.0)));
+        let merged = HilbertGeometry::merge(vec![point(1.0), point(2.0)]).unwrap();
+        let HilbertGeometry::MultiPoint(ps) = &merged else {
+            panic!("expected a multipoint")
+        };
+        assert_eq!(ps.len(), 2);
+        let merged = HilbertGeometry::merge(vec![merged, point(3.0)]).unwrap();
+        assert_eq!(merged.num_geometries(), 3);
+
+        let line = encode_geometry(&Geometry::LineString(line_string![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 1.0)
+        ]));
+        let lines = HilbertGeometry::merge(vec![line.clone(), line.clone()]).unwrap();
+        assert_eq!(lines.kind(), "MultiLineString");
+        assert_eq!(lines.num_geometries(), 2);
+        assert_eq!(lines.num_coords(), 4);
+
+        let square = encode_geometry(&Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 0.0)
+        ]));
+        let polys = HilbertGeometry::merge(vec![square.clone()]).unwrap();
+        assert_eq!(polys.kind(), "MultiPolygon");
+        assert_eq!(
+            decode_geometry(&polys),
+            Geometry::MultiPolygon(MultiPolygon(vec![decode_geometry(&square)
+                .try_into()
+                .unwrap()]))
+        );
+
+        assert!(HilbertGeometry::merge(vec![]).is_err());
+        assert!(HilbertGeometry::merge(vec![point(1.0), line]).is_err());
+        assert!(HilbertGeometry::merge(vec![square, point(1.0)]).is_err());
+    }
+
+    #[test]
+    fn test_into_parts() {
+        let multi = encode_geometry(&Geometry::MultiLineString(MultiLineString(vec![
+            line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)],
+            line_string![(x: 2.0, y: 2.0), (x: 3.0, y: 3.0), (x: 4.0, y: 4.0)],
+        ])));
+        let parts = multi.clone().into_parts();
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|p| p.kind() == "LineString"));
+        assert_eq!(parts[1].num_coords(), 3);
+        assert_eq!(HilbertGeometry::merge(parts).unwrap(), multi);
+
+        let points = encode_geometry(&Geometry::MultiPoint(
+            vec![point!(x: 1.0, y: 2.0), point!(x: 3.0, y: 4.0)].into(),
+        ));
+        assert_eq!(
+            points.into_parts()[1],
+            encode_geometry(&Geometry::Point(point!(x: 3.0, y: 4.0)))
+        );
+
+        let point = encode_geometry(&Geometry::Point(point!(x: 1.0, y: 2.0)));
+        assert_eq!(point.clone().into_parts(), vec![point]);
+        assert!(HilbertGeometry::MultiPolygon(vec![])
+            .into_parts()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_reverse() {
+        let ls = Geometry::LineString(
+            line_string![(x: 1.0, y: 2.0), (x: 3.0, y: 4.0), (x: 5.0, y: 6.0)],
+        );
+        let mut encoded = encode_geometry(&ls);
+        encoded.reverse();
+        assert_eq!(
+            decode_geometry(&encoded),
+            Geometry::LineString(
+                line_string![(x: 5.0, y: 6.0), (x: 3.0, y: 4.0), (x: 1.0, y: 2.0)]
+            )
+        );
+
+        let poly = polygon!(
+            exterior: [(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 4.0)],
+            interiors: [[(x: 1.0, y: 1.0), (x: 1.0, y: 2.0), (x: 2.0, y: 2.0)]],
+        );
+        let mut encoded = encode_geometry(&MultiPolygon(vec![poly.clone()]).into());
+        encoded.reverse();
+        let Geometry::MultiPolygon(reversed) = decode_geometry(&encoded) else {
+            panic!("expected a multipolygon")
+        };
+        let mut expected = poly.exterior().0.clone();
+        expected.reverse();
+        assert_eq!(reversed.0[0].exterior().0, expected);
+        encoded.reverse();
+        assert_eq!(decode_geometry(&encoded), MultiPolygon(vec![poly]).into());
+
+        let mut point = encode_geometry(&Geometry::Point(point!(x: 1.0, y: 2.0)));
+        let before = point.clone();
+        point.reverse();
+        assert_eq!(point, before);
+    }
+
+    #[test]
+    fn test_flip_ring_roles() {
+        let hole = polygon![(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 2.0, y: 2.0)];
+        let shell = polygon![(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0)];
+        let misordered = polygon!(
+            exterior: [(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 2.0, y: 2.0)],
+            interiors: [[(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0)]],
+        );
+        let mut encoded = encode_geometry(&misordered.into());
+        assert!(encoded.flip_ring_roles(0, 1));
+        let Geometry::Polygon(fixed) = decode_geometry(&encoded) else {
+            panic!("expected a polygon")
+        };
+        assert_eq!(fixed.exterior(), shell.exterior());
+        assert_eq!(&fixed.interiors()[0], hole.exterior());
+
+        assert!(!encoded.flip_ring_roles(0, 2));
+        assert!(!encoded.flip_ring_roles(1, 1));
+        let mut multi = HilbertGeometry::merge(vec![encoded.clone(), encoded.clone()]).unwrap();
+        assert!(multi.flip_ring_roles(1, 1));
+        assert!(!multi.flip_ring_roles(2, 0));
+        let HilbertGeometry::MultiPolygon(polys) = &multi else {
+            unreachable!()
+        };
+        assert_eq!(polys[1][0], polys[0][1]);
+        let mut ls = encode_geometry(&Geometry::LineString(line_string![(x: 1.0, y: 2.0)]));
+        assert!(!ls.flip_ring_roles(0, 0));
+    }
 }
@@ -0,0 +1,98 @@
+#![cfg(feature = "arrow")]
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Array, AsArray, Float64Array};
+    use arrow::datatypes::{DataType, Float64Type, Int64Type};
+    use arrow::ipc::reader::StreamReader;
+    use bincode::config;
+    use geo_types::{point, Geometry};
+    use hilbert_geometry::collection::*;
+    use hilbert_geometry::ipc::*;
+    use hilbert_geometry::*;
+
+    fn features() -> Vec<HilbertFeature> {
+        (0..4)
+            .map(|i| {
+                let feature = HilbertFeature::new(encode_geometry(&Geometry::Point(
+                    point!(x: i as f64, y: 60.0),
+                )))
+                .with_property("id", PropertyValue::Int(i))
+                .with_property(
+                    "speed",
+                    if i % 2 == 0 {
+                        PropertyValue::Int(i * 10)
+                    } else {
+                        PropertyValue::Double(i as f64 + 0.5)
+                    },
+                );
+                if i == 2 {
+                    feature.with_property("note", PropertyValue::String("stop".into()))
+                } else {
+                    feature
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_feature_schema() {
+        let schema = feature_schema(&features());
+        let columns: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().as_str(), f.data_type().clone()))
+            .collect();
+        assert_eq!(
+            columns,
+            [
+                (GEOMETRY_COLUMN, DataType::Binary),
+                ("id", DataType::Int64),
+                ("speed", DataType::Float64),
+                ("note", DataType::Utf8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_features_to_record_batch() {
+        let features = features();
+        let schema = Arc::new(feature_schema(&features));
+        let batch = features_to_record_batch(&features, schema).unwrap();
+        assert_eq!(batch.num_rows(), 4);
+        let geometry = batch.column(0).as_binary::<i32>().value(3);
+        let (decoded, _): (HilbertGeometry, _) =
+            bincode::decode_from_slice(geometry, config::standard()).unwrap();
+        assert_eq!(decoded, features[3].geometry);
+        assert_eq!(batch.column(1).as_primitive::<Int64Type>().value(2), 2);
+        assert_eq!(
+            batch.column(2).as_primitive::<Float64Type>(),
+            &Float64Array::from(vec![0.0, 1.5, 20.0, 3.5])
+        );
+        let note = batch.column(3).as_string::<i32>();
+        assert!(note.is_null(0));
+        assert_eq!(note.value(2), "stop");
+
+        let other = vec![HilbertFeature::new(features[0].geometry.clone())
+            .with_property("id", PropertyValue::String("a".into()))];
+        let schema = Arc::new(feature_schema(&features));
+        assert!(features_to_record_batch(&other, schema).is_err());
+    }
+
+    #[test]
+    fn test_feature_stream_writer() {
+        let features = features();
+        let schema = Arc::new(feature_schema(&features));
+        let mut writer = FeatureStreamWriter::try_new(vec![], schema.clone()).unwrap();
+        writer.write(&features[..2]).unwrap();
+        writer.write(&features[2..]).unwrap();
+        let data = writer.finish().unwrap();
+
+        let reader = StreamReader::try_new(data.as_slice(), None).unwrap();
+        assert_eq!(reader.schema(), schema);
+        let rows: usize = reader.map(|b| b.unwrap().num_rows()).sum();
+        assert_eq!(rows, 4);
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use geo_types::{coord, line_string, Geometry, Point, Rect};
+    use hilbert_geometry::kv::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_kv_scan() {
+        let mut store = BTreeMap::new();
+        for i in 0..40 {
+            for j in 0..40 {
+                let (x, y) = (-10.0 + i as f64 * 0.5, 40.0 + j as f64 * 0.5);
+                let geom = encode_geometry(&Geometry::Point(Point::new(x, y)));
+                let key = make_key_with_id(&geom, &[i, j]).unwrap();
+                store.insert(key, (x, y));
+            }
+        }
+
+        let rect = Rect::new(coord! { x: -2.2, y: 45.1 }, coord! { x: 3.0, y: 47.0 });
+        let mut found = vec![];
+        for (start, end) in scan_ranges(&rect) {
+            let scan: Vec<_> = if end.is_empty() {
+                store.range(start..).collect()
+            } else {
+                store.range(start..end).collect()
+            };
+            found.extend(scan.into_iter().map(|(_, v)| *v).filter(|(x, y)| {
+                *x >= rect.min().x && *x <= rect.max().x && *y >= rect.min().y && *y <= rect.max().y
+            }));
+        }
+        found.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut expected: Vec<_> = store
+            .values()
+            .copied()
+            .filter(|(x, y)| {
+                *x >= rect.min().x && *x <= rect.max().x && *y >= rect.min().y && *y <= rect.max().y
+            })
+            .collect();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(found, expected);
+        assert_eq!(found.len(), 11 * 4);
+    }
+
+    #[test]
+    fn test_make_key() {
+        let point = encode_geometry(&Geometry::Point(Point::new(1.0, 2.0)));
+        let key = make_key(&point).unwrap();
+        assert_eq!(key.len(), KEY_LEN);
+        assert_eq!(
+            Some(&point),
+            point_from_key(&key).map(HilbertGeometry::Point).as_ref()
+        );
+
+        let line = encode_geometry(&Geometry::LineString(line_string![
+            (x: 0.0, y: 0.0),
+            (x: 2.0, y: 4.0),
+        ]));
+        assert_eq!(make_key(&line), make_key(&point));
+        assert_eq!(make_key(&HilbertGeometry::LineString(vec![])), None);
+    }
+
+    #[test]
+    fn test_partition_scan() {
+        let level = 12;
+        let mut table: BTreeMap<String, BTreeMap<String, (f64, f64)>> = BTreeMap::new();
+        for i in 0..40 {
+            for j in 0..40 {
+                let (x, y) = (-10.0 + i as f64 * 0.5, 40.0 + j as f64 * 0.5);
+                let HilbertGeometry::Point(p) = encode_geometry(&Geometry::Point(Point::new(x, y)))
+                else {
+                    unreachable!()
+                };
+                let (partition, sort) = partition_and_sort_key(p, level);
+                assert_eq!(sort.len(), 16);
+                assert!(partition.starts_with("12/") && partition.len() == 9);
+                table.entry(partition).or_default().insert(sort, (x, y));
+            }
+        }
+        assert!(table.len() > 1);
+
+        let rect = Rect::new(coord! { x: -2.2, y: 45.1 }, coord! { x: 3.0, y: 47.0 });
+        let mut found = vec![];
+        for r in partition_scan_ranges(&rect, level, 16) {
+            assert!(r.start <= r.end);
+            if let Some(items) = table.get(&r.partition) {
+                found.extend(items.range(r.start..=r.end).map(|(_, v)| *v));
+            }
+        }
+        found.retain(|(x, y)| {
+            *x >= rect.min().x && *x <= rect.max().x && *y >= rect.min().y && *y <= rect.max().y
+        });
+        found.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut expected: Vec<_> = table
+            .values()
+            .flat_map(|items| items.values().copied())
+            .filter(|(x, y)| *x >= -2.2 && *x <= 3.0 && *y >= 45.1 && *y <= 47.0)
+            .collect();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(found, expected);
+        assert_eq!(found.len(), 11 * 4);
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, line_string, point, Geometry};
+    use hilbert_geometry::encode_geometry;
+
+    #[test]
+    fn test_point_at_fraction() {
+        let ls = encode_geometry(&Geometry::LineString(line_string![
+            (x: 0.0, y: 0.0), (x: 2.0, y: 0.0), (x: 2.0, y: 2.0)
+        ]));
+        assert_eq!(ls.point_at_fraction(0.0), Some(coord! { x: 0.0, y: 0.0 }));
+        assert_eq!(ls.point_at_fraction(0.25), Some(coord! { x: 1.0, y: 0.0 }));
+        assert_eq!(ls.point_at_fraction(0.5), Some(coord! { x: 2.0, y: 0.0 }));
+        assert_eq!(ls.point_at_fraction(0.75), Some(coord! { x: 2.0, y: 1.0 }));
+        assert_eq!(ls.point_at_fraction(2.0), Some(coord! { x: 2.0, y: 2.0 }));
+
+        let still = encode_geometry(&Geometry::LineString(line_string![
+            (x: 1.0, y: 1.0), (x: 1.0, y: 1.0)
+        ]));
+        assert_eq!(
+            still.point_at_fraction(0.5),
+            Some(coord! { x: 1.0, y: 1.0 })
+        );
+        let empty = encode_geometry(&Geometry::LineString(line_string![]));
+        assert_eq!(empty.point_at_fraction(0.5), None);
+        let pt = encode_geometry(&Geometry::Point(point!(x: 1.0, y: 1.0)));
+        assert_eq!(pt.point_at_fraction(0.5), None);
+    }
+
+    #[test]
+    fn test_fraction_at_point() {
+        let ls = encode_geometry(&Geometry::LineString(line_string![
+            (x: 0.0, y: 0.0), (x: 2.0, y: 0.0), (x: 2.0, y: 2.0)
+        ]));
+        assert_eq!(ls.fraction_at_point(coord! { x: 1.0, y: 0.5 }), Some(0.25));
+        assert_eq!(ls.fraction_at_point(coord! { x: 3.0, y: 1.0 }), Some(0.75));
+        assert_eq!(ls.fraction_at_point(coord! { x: -1.0, y: -1.0 }), Some(0.0));
+        for t in [0.1, 0.4, 0.9] {
+            let p = ls.point_at_fraction(t).unwrap();
+            assert!((ls.fraction_at_point(p).unwrap() - t).abs() < 1e-12);
+        }
+        let pt = encode_geometry(&Geometry::Point(point!(x: 1.0, y: 1.0)));
+        assert_eq!(pt.fraction_at_point(coord! { x: 1.0, y: 1.0 }), None);
+    }
+}
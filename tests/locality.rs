@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{Geometry, Point};
+    use hilbert_geometry::locality::*;
+    use hilbert_geometry::{encode_geometry, HilbertGeometry};
+
+    fn grid(side: usize) -> Vec<HilbertGeometry> {
+        (0..side * side)
+            .map(|i| {
+                let p = Point::new((i % side) as f64 * 0.01, (i / side) as f64 * 0.01);
+                encode_geometry(&Geometry::Point(p))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_locality_report() {
+        let geoms = grid(32);
+        let hilbert = locality_report(&geoms).unwrap();
+        assert_eq!(hilbert.features, 1024);
+        assert!((hilbert.random_index_gap - 1025.0 / 3.0).abs() < 1e-9);
+        assert!(hilbert.clustering_ratio < 0.1, "{hilbert:?}");
+        assert!((hilbert.mean_neighbor_distance - 0.01).abs() < 1e-6);
+        assert!(hilbert.mean_step_distance < 0.02);
+
+        // Row-major order keeps one neighbour of each point next to it.
+        let row_major: Vec<u64> = (0..1024).collect();
+        let rows = locality_report_with_keys(&geoms, &row_major).unwrap();
+        assert!(rows.mean_step_distance > hilbert.mean_step_distance);
+
+        // A scrambled order is about as bad as a random one.
+        let scrambled: Vec<u64> = (0..1024u64)
+            .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .collect();
+        let random = locality_report_with_keys(&geoms, &scrambled).unwrap();
+        assert!(random.clustering_ratio > 0.7, "{random:?}");
+        assert!(random.clustering_ratio > 5.0 * hilbert.clustering_ratio);
+        assert_eq!(
+            random.mean_neighbor_distance,
+            hilbert.mean_neighbor_distance
+        );
+    }
+
+    #[test]
+    fn test_locality_report_too_few() {
+        assert!(locality_report(&[]).is_none());
+        let geoms = grid(1);
+        assert!(locality_report(&geoms).is_none());
+        let with_empty = vec![geoms[0].clone(), HilbertGeometry::MultiPoint(vec![])];
+        assert!(locality_report(&with_empty).is_none());
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, polygon, Geometry};
+    use hilbert_geometry::lod::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_lod_progressive_decode() {
+        let poly = Geometry::Polygon(polygon![
+            (x: 10.0, y: 50.0),
+            (x: 11.0, y: 50.0),
+            (x: 11.0, y: 51.0),
+            (x: 10.0, y: 51.0),
+            (x: 10.0, y: 50.0)
+        ]);
+        let encoded = encode_lod(&encode_geometry(&poly)).unwrap();
+
+        let full = decode_lod(&encoded).unwrap();
+        assert_eq!(full.passes, LOD_PASSES);
+        assert_eq!(decode_geometry(&full.geometry), poly);
+
+        // Dropping the last passes still yields a geometry close to the original.
+        let coarse = decode_lod(&encoded[..encoded.len() - 5 * 2]).unwrap();
+        assert_eq!(coarse.passes, LOD_PASSES - 2);
+        let Geometry::Polygon(coarse) = decode_geometry(&coarse.geometry) else {
+            panic!("expected polygon");
+        };
+        let Geometry::Polygon(poly) = poly else {
+            unreachable!()
+        };
+        for (a, b) in poly.exterior().coords().zip(coarse.exterior().coords()) {
+            assert!((a.x - b.x).abs() < 0.01 && (a.y - b.y).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_lod_minimum_passes() {
+        let line = line_string![(x: 60.0, y: 60.0), (x: 179.9, y: -89.9)];
+        let encoded = encode_lod(&encode_geometry(&Geometry::LineString(line.clone()))).unwrap();
+        let layout_len = encoded.len() - 2 * LOD_PASSES;
+
+        // One pass only holds part of the exponent.
+        assert!(decode_lod(&encoded[..layout_len + 2]).is_err());
+
+        let coarse = decode_lod(&encoded[..layout_len + 2 * MIN_LOD_PASSES]).unwrap();
+        assert_eq!(coarse.passes, MIN_LOD_PASSES);
+        let Geometry::LineString(coarse) = decode_geometry(&coarse.geometry) else {
+            panic!("expected linestring");
+        };
+        for (a, b) in line.coords().zip(coarse.coords()) {
+            assert!((-180.0..=180.0).contains(&b.x) && (-90.0..=90.0).contains(&b.y));
+            assert!((a.x - b.x).abs() < a.x.abs() / 8.0 && (a.y - b.y).abs() < a.y.abs() / 8.0);
+        }
+
+        // A layout claiming u32::MAX vertices with no passes.
+        assert!(decode_lod(&[1, 252, 0xff, 0xff, 0xff, 0xff]).is_err());
+    }
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "mvt")]
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{point, polygon, Geometry};
+    use hilbert_geometry::mvt::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_mvt_point() {
+        let pt = encode_geometry(&Geometry::Point(point!(x: 0.0, y: 0.0)));
+        let tile = encode_mvt_layer("points", TileCoord::new(0, 0, 0), &[pt]).unwrap();
+        // MoveTo(1) to the tile center (2048, 2048), zigzag-encoded as varints.
+        let geometry = [0x22, 0x05, 0x09, 0x80, 0x20, 0x80, 0x20];
+        assert!(tile.windows(geometry.len()).any(|w| w == geometry));
+    }
+
+    #[test]
+    fn test_mvt_rejects_deep_zoom() {
+        assert!(MvtLayer::new("points", TileCoord::new(tile::MAX_ZOOM, 0, 0)).is_ok());
+        assert!(MvtLayer::new("points", TileCoord::new(tile::MAX_ZOOM + 1, 0, 0)).is_err());
+        assert!(encode_mvt_layer("points", TileCoord::new(64, 0, 0), &[]).is_err());
+    }
+
+    #[test]
+    fn test_mvt_rejects_invalid_tile() {
+        assert!(MvtLayer::new("points", TileCoord::new(2, 3, 3)).is_ok());
+        assert!(MvtLayer::new("points", TileCoord::new(2, 4, 0)).is_err());
+        assert!(MvtLayer::new("points", TileCoord::new(2, 0, 4)).is_err());
+        assert!(encode_mvt_layer("points", TileCoord::new(0, 1, 0), &[]).is_err());
+
+        let layer = MvtLayer::new("points", TileCoord::new(0, 0, 0)).unwrap();
+        assert!(layer.with_extent(0).is_err());
+        let layer = MvtLayer::new("points", TileCoord::new(0, 0, 0)).unwrap();
+        assert!(layer.with_extent(256).is_ok());
+    }
+
+    #[test]
+    fn test_mvt_skips_features_outside_tile() {
+        let poly = encode_geometry(&Geometry::Polygon(polygon![
+            (x: 10.0, y: 10.0),
+            (x: 20.0, y: 10.0),
+            (x: 20.0, y: 20.0),
+            (x: 10.0, y: 20.0),
+            (x: 10.0, y: 10.0)
+        ]));
+        let mut layer = MvtLayer::new("polygons", TileCoord::new(1, 1, 0)).unwrap();
+        assert!(layer.add_feature(Some(1), &poly, &[("name", MvtValue::String("a".into()))]));
+
+        let mut layer = MvtLayer::new("polygons", TileCoord::new(1, 0, 1)).unwrap();
+        assert!(!layer.add_feature(Some(1), &poly, &[]));
+        assert!(layer.is_empty());
+    }
+
+    #[test]
+    fn test_mvt_drops_polygon_with_flat_exterior() {
+        let poly = encode_geometry(&Geometry::Polygon(polygon!(
+            exterior: [(x: 0.0, y: 0.0), (x: 40.0, y: 0.0), (x: 80.0, y: 0.0), (x: 0.0, y: 0.0)],
+            interiors: [[(x: 10.0, y: 10.0), (x: 20.0, y: 10.0), (x: 20.0, y: 20.0), (x: 10.0, y: 10.0)]],
+        )));
+        let mut layer = MvtLayer::new("polygons", TileCoord::new(0, 0, 0)).unwrap();
+        assert!(!layer.add_feature(None, &poly, &[]));
+    }
+}
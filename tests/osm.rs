@@ -0,0 +1,46 @@
+#![cfg(feature = "osmpbf")]
+
+#[cfg(test)]
+mod tests {
+    use geo_types::coord;
+    use hilbert_geometry::collection::PropertyValue;
+    use hilbert_geometry::osm::*;
+
+    #[test]
+    fn test_osm_converter() {
+        let mut osm = OsmConverter::new();
+        assert!(osm
+            .add_node(1, coord! { x: 24.94, y: 60.17 }, std::iter::empty())
+            .is_none());
+        let cafe = osm
+            .add_node(
+                2,
+                coord! { x: 24.95, y: 60.18 },
+                [("amenity", "cafe")].into_iter(),
+            )
+            .unwrap();
+        assert_eq!(cafe.geometry.kind(), "Point");
+        assert_eq!(cafe.property("osm_id"), Some(&PropertyValue::Int(2)));
+        assert_eq!(
+            cafe.property("amenity"),
+            Some(&PropertyValue::String("cafe".into()))
+        );
+        let node = osm.node(1).unwrap();
+        assert!((node.x - 24.94).abs() < 1e-5 && (node.y - 60.17).abs() < 1e-5);
+
+        let road = osm
+            .add_way(10, [1, 2].into_iter(), [("highway", "primary")].into_iter())
+            .unwrap();
+        assert_eq!(road.geometry.kind(), "LineString");
+        assert_eq!(road.geometry.num_coords(), 2);
+        assert_eq!(road.property("osm_id"), Some(&PropertyValue::Int(10)));
+
+        assert!(osm
+            .add_way(11, [1, 3].into_iter(), std::iter::empty())
+            .is_none());
+        assert!(osm
+            .add_way(12, std::iter::empty(), std::iter::empty())
+            .is_none());
+        assert_eq!(osm.skipped_ways(), 2);
+    }
+}
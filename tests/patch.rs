@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use bincode::config;
+    use geo_types::{line_string, polygon, Geometry};
+    use hilbert_geometry::*;
+
+    fn track() -> Geometry {
+        Geometry::LineString(
+            (0..100)
+                .map(|i| (24.9 + i as f64 * 1e-4, 60.1 + i as f64 * 1e-4))
+                .collect(),
+        )
+    }
+
+    fn edit(geom: &Geometry, f: impl Fn(&mut Vec<geo_types::Coord>)) -> Geometry {
+        let Geometry::LineString(mut ls) = geom.clone() else {
+            unreachable!()
+        };
+        f(&mut ls.0);
+        Geometry::LineString(ls)
+    }
+
+    fn check(old: &Geometry, new: &Geometry) -> usize {
+        let (old, new) = (encode_geometry(old), encode_geometry(new));
+        let patch = old.diff(&new);
+        assert_eq!(old.apply_patch(&patch).unwrap(), new);
+        bincode::encode_to_vec(&patch, config::standard())
+            .unwrap()
+            .len()
+    }
+
+    #[test]
+    fn test_patch_small_edits() {
+        let old = track();
+        let full = bincode::encode_to_vec(encode_geometry(&old), config::standard())
+            .unwrap()
+            .len();
+
+        let moved = edit(&old, |cs| cs[40].x += 1e-5);
+        assert!(check(&old, &moved) < 20);
+
+        let inserted = edit(&old, |cs| cs.insert(70, (24.9071, 60.1071).into()));
+        assert!(check(&old, &inserted) < 30);
+
+        let removed = edit(&old, |cs| {
+            cs.remove(0);
+        });
+        assert!(check(&old, &removed) < 10);
+
+        let appended = edit(&old, |cs| cs.push((24.92, 60.12).into()));
+        assert!(check(&old, &appended) < full / 10);
+
+        let hg = encode_geometry(&old);
+        assert!(hg.diff(&hg).is_empty());
+        assert!(!hg.diff(&encode_geometry(&moved)).is_empty());
+    }
+
+    #[test]
+    fn test_patch_changes_type() {
+        let square = Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 0.0)
+        ]);
+        let line = Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0)]);
+        check(&square, &line);
+        check(&line, &square);
+        check(&line, &Geometry::Point((5.0, 5.0).into()));
+    }
+
+    #[test]
+    fn test_patch_mismatch() {
+        let old = encode_geometry(&track());
+        let new = encode_geometry(&edit(&track(), |cs| cs[10].y += 1.0));
+        let patch = old.diff(&new);
+        let other = encode_geometry(&Geometry::Point((1.0, 1.0).into()));
+        assert!(other.apply_patch(&patch).is_err());
+    }
+}
@@ -0,0 +1,299 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, polygon, Geometry, GeometryCollection};
+    use hilbert_geometry::payload::*;
+    use hilbert_geometry::*;
+
+    fn track() -> Geometry {
+        Geometry::LineString(
+            (0..200)
+                .map(|i| (24.9 + i as f64 * 1e-4, 60.1 + (i as f64 * 0.1).sin() * 1e-3))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_payload_roundtrip() {
+        let poly = Geometry::Polygon(polygon![
+            (x: 24.123456789, y: 60.1),
+            (x: 24.2, y: 60.1),
+            (x: 24.2, y: 60.2),
+            (x: 24.123456789, y: 60.1)
+        ]);
+        for encoding in [
+            Encoding::Hilbert,
+            Encoding::HilbertDelta,
+            Encoding::Raw,
+            Encoding::Quantized,
+        ] {
+            for geom in [&poly, &track()] {
+                let data = encode_payload(geom, EncodingMode::Fixed(encoding)).unwrap();
+                assert_eq!(payload_encoding(&data).unwrap(), encoding);
+                let decoded = decode_payload(&data).unwrap();
+                match encoding {
+                    Encoding::Raw => assert_eq!(&decoded, geom),
+                    Encoding::Quantized => assert!(geometries_approx_eq(geom, &decoded, 1e-7)),
+                    _ => assert!(geometries_approx_eq(geom, &decoded, 1e-5)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_payload_auto() {
+        let geom = track();
+        let auto = encode_payload(&geom, EncodingMode::Auto).unwrap();
+        for encoding in [
+            Encoding::Hilbert,
+            Encoding::HilbertDelta,
+            Encoding::Raw,
+            Encoding::Quantized,
+        ] {
+            let fixed = encode_payload(&geom, EncodingMode::Fixed(encoding)).unwrap();
+            assert!(auto.len() <= fixed.len());
+        }
+        // Small coordinate steps favour one of the delta encodings.
+        assert_ne!(payload_encoding(&auto).unwrap(), Encoding::Raw);
+
+        let serializer = HilbertSerializer::new().encoding(EncodingMode::Auto);
+        let decoded = serializer
+            .decode(&serializer.encode(&geom).unwrap())
+            .unwrap();
+        assert!(geometries_approx_eq(&geom, &decoded, 1e-5));
+    }
+
+    #[test]
+    fn test_payload_errors() {
+        let pt = Geometry::Point(point! { x: 1.0, y: 2.0 });
+        let plain = HilbertSerializer::new().encode(&pt).unwrap();
+        assert!(payload_encoding(&plain).is_err());
+        assert!(decode_payload(&[]).is_err());
+
+        let data = encode_payload(
+            &Geometry::LineString(line_string![(x: 1.0, y: 2.0), (x: 3.0, y: 4.0)]),
+            EncodingMode::Fixed(Encoding::Raw),
+        )
+        .unwrap();
+        assert!(decode_payload(&data[..data.len() - 1]).is_err());
+
+        let gc = Geometry::GeometryCollection(GeometryCollection(vec![pt]));
+        assert!(encode_payload(&gc, EncodingMode::Auto).is_err());
+    }
+
+    #[test]
+    fn test_payload_adversarial() {
+        let pt = Geometry::Point(point! { x: 1.0, y: 2.0 });
+        let tag = encode_payload(&pt, EncodingMode::Fixed(Encoding::Raw)).unwrap()[0];
+        // A point layout followed by a coordinate count of 2^60.
+        let mut data = vec![tag, 0, 0xfd];
+        data.extend((1u64 << 60).to_le_bytes());
+        assert!(decode_payload(&data).is_err());
+
+        let nan = Geometry::Point(point! { x: f64::NAN, y: 2.0 });
+        for encoding in [Encoding::Hilbert, Encoding::HilbertDelta, Encoding::Raw] {
+            let data = encode_payload(&nan, EncodingMode::Fixed(encoding)).unwrap();
+            assert!(decode_payload(&data).is_err(), "{encoding:?}");
+        }
+        let quantized = EncodingMode::Fixed(Encoding::Quantized);
+        assert!(encode_payload(&nan, quantized).is_err());
+
+        // Beyond the range of quantized steps, Auto falls back to another encoding.
+        let far = Geometry::LineString(line_string![(x: 1e13, y: 0.0), (x: -1e13, y: 0.0)]);
+        assert!(encode_payload(&far, quantized).is_err());
+        let auto = encode_payload(&far, EncodingMode::Auto).unwrap();
+        assert_ne!(payload_encoding(&auto).unwrap(), Encoding::Quantized);
+        let near = Geometry::LineString(line_string![(x: 9e11, y: 0.0), (x: -9e11, y: 0.0)]);
+        let data = encode_payload(&near, quantized).unwrap();
+        assert_eq!(decode_payload(&data).unwrap(), near);
+    }
+
+    #[test]
+    fn test_payload_raw_keyed() {
+        let geom = Geometry::LineString(line_string![
+            (x: 24.123456789012, y: 60.1),
+            (x: 24.2, y: 60.2000000001)
+        ]);
+        let data = encode_payload(&geom, EncodingMode::Fixed(Encoding::RawKeyed)).unwrap();
+        assert_eq!(decode_payload(&data).unwrap(), geom);
+
+        let key = kv::make_key(&encode_geometry(&geom)).unwrap();
+        assert_eq!(payload_key(&data).unwrap(), kv::point_from_key(&key));
+        assert_eq!(&data[1..9], key.as_slice());
+
+        let raw = encode_payload(&geom, EncodingMode::Fixed(Encoding::Raw)).unwrap();
+        assert_eq!(payload_key(&raw).unwrap(), None);
+        assert!(payload_key(&data[..5]).is_err());
+        assert!(decode_payload(&data[..5]).is_err());
+    }
+
+    #[test]
+    fn test_payload_half() {
+        let cloud = Geometry::MultiPoint(
+            (0..100)
+                .map(|i| point!(x: 24.0 + i as f64 * 0.37, y: 60.0 - i as f64 * 0.21))
+                .collect(),
+        );
+        let data = encode_payload(&cloud, EncodingMode::Fixed(Encoding::Half)).unwrap();
+        let hilbert = encode_payload(&cloud, EncodingMode::Fixed(Encoding::Hilbert)).unwrap();
+        assert!(data.len() * 2 < hilbert.len());
+        let decoded = decode_payload(&data).unwrap();
+        assert!(geometries_approx_eq(&decoded, &cloud, 0.1));
+        assert!(!geometries_approx_eq(&decoded, &cloud, 1e-4));
+        assert!(decode_payload(&data[..data.len() - 1]).is_err());
+
+        // Projected coordinates overflow half floats.
+        let projected = Geometry::Point(point!(x: 385_000.0, y: 6_672_000.0));
+        assert!(encode_payload(&projected, EncodingMode::Fixed(Encoding::Half)).is_err());
+    }
+
+    #[test]
+    fn test_payload_lenient() {
+        let geom = track();
+        let legacy = HilbertSerializer::new().encode(&geom).unwrap();
+        let tagged = encode_payload(&geom, EncodingMode::Fixed(Encoding::Raw)).unwrap();
+        assert!(!is_tagged(&legacy));
+        assert!(is_tagged(&tagged));
+        assert!(!is_tagged(&[]));
+        assert_eq!(
+            decode_payload_lenient(&legacy).unwrap(),
+            HilbertSerializer::new().decode(&legacy).unwrap()
+        );
+        assert_eq!(decode_payload_lenient(&tagged).unwrap(), geom);
+        assert!(decode_payload_lenient(&legacy[..legacy.len() - 1]).is_err());
+
+        let serializer = HilbertSerializer::new().encoding(EncodingMode::Fixed(Encoding::Raw));
+        assert!(serializer.decode(&legacy).is_err());
+        let serializer = serializer.lenient();
+        assert!(serializer.decode(&legacy).is_ok());
+        assert_eq!(serializer.decode(&tagged).unwrap(), geom);
+    }
+
+    #[test]
+    fn test_payload_ring_flags() {
+        // Clockwise shell, counter-clockwise first hole, clockwise second.
+        let poly = Geometry::Polygon(polygon!(
+            exterior: [(x: 0.0, y: 0.0), (x: 0.0, y: 8.0), (x: 8.0, y: 8.0), (x: 8.0, y: 0.0)],
+            interiors: [
+                [(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 2.0, y: 2.0)],
+                [(x: 4.0, y: 4.0), (x: 5.0, y: 5.0), (x: 5.0, y: 4.0)],
+            ],
+        ));
+        let (oriented, flags) = orient_rings(&poly);
+        let reversed = RingFlags {
+            reversed: true,
+            ..Default::default()
+        };
+        assert_eq!(flags, vec![reversed, reversed, RingFlags::default()]);
+        assert_eq!(orient_rings(&oriented).1, vec![RingFlags::default(); 3]);
+        assert_eq!(restore_rings(&oriented, &flags), poly);
+
+        let raw = EncodingMode::Fixed(Encoding::RawKeyed);
+        let data = encode_payload_with_ring_flags(&oriented, raw, &flags).unwrap();
+        assert_eq!(payload_encoding(&data).unwrap(), Encoding::RawKeyed);
+        assert_eq!(payload_ring_flags(&data).unwrap(), flags);
+        assert_eq!(
+            payload_key(&data).unwrap(),
+            payload_key(&encode_payload(&oriented, raw).unwrap()).unwrap()
+        );
+        assert_eq!(decode_payload(&data).unwrap(), oriented);
+        assert_eq!(decode_payload_original(&data).unwrap(), poly);
+        assert!(encode_payload_with_ring_flags(&oriented, raw, &flags[..2]).is_err());
+
+        // Without changes the payload carries no flags.
+        let unchanged = encode_payload_with_ring_flags(&oriented, raw, &[RingFlags::default(); 3]);
+        assert_eq!(unchanged.unwrap(), encode_payload(&oriented, raw).unwrap());
+        assert!(payload_ring_flags(&encode_payload(&oriented, raw).unwrap())
+            .unwrap()
+            .is_empty());
+
+        let serializer = HilbertSerializer::new()
+            .encoding(EncodingMode::Fixed(Encoding::Raw))
+            .orient_rings();
+        let data = serializer.encode(&poly).unwrap();
+        assert_eq!(serializer.decode(&data).unwrap(), oriented);
+        assert_eq!(decode_payload_original(&data).unwrap(), poly);
+    }
+
+    #[test]
+    fn test_payload_ring_rotation() {
+        let poly = Geometry::Polygon(polygon!(
+            exterior: [(x: 8.0, y: 8.0), (x: 0.0, y: 8.0), (x: 0.0, y: 0.0), (x: 8.0, y: 0.0)],
+            interiors: [[(x: 2.0, y: 2.0), (x: 1.0, y: 2.0), (x: 1.0, y: 1.0)]],
+        ));
+        let (canonical, flags) = canonicalize_rings(&poly);
+        let HilbertGeometry::Polygon(rings) = encode_geometry(&canonical) else {
+            panic!("expected a polygon")
+        };
+        // Each ring starts at its smallest index, keeping its orientation.
+        for ring in rings {
+            assert_eq!(ring[0], *ring.iter().min().unwrap());
+        }
+        assert_eq!(orient_rings(&canonical).1, vec![RingFlags::default(); 2]);
+        assert_eq!(
+            canonicalize_rings(&canonical).1,
+            vec![RingFlags::default(); 2]
+        );
+        assert!(flags.iter().any(|f| f.start != 0));
+        assert_eq!(restore_rings(&canonical, &flags), poly);
+
+        let serializer = HilbertSerializer::new()
+            .encoding(EncodingMode::Fixed(Encoding::Raw))
+            .canonicalize_rings();
+        let data = serializer.encode(&poly).unwrap();
+        assert_eq!(payload_ring_flags(&data).unwrap(), flags);
+        assert_eq!(serializer.decode(&data).unwrap(), canonical);
+        assert_eq!(decode_payload_original(&data).unwrap(), poly);
+    }
+
+    #[test]
+    fn test_payload_crs() {
+        let geom = Geometry::Point(point!(x: 385000.0, y: 6672000.0));
+        let raw = EncodingMode::Fixed(Encoding::Raw);
+        let metadata = PayloadMetadata {
+            crs: Some(Crs::Epsg(3067)),
+            ..Default::default()
+        };
+        let data = encode_payload_with_metadata(&geom, raw, &metadata).unwrap();
+        assert_eq!(payload_encoding(&data).unwrap(), Encoding::Raw);
+        assert_eq!(payload_crs(&data).unwrap(), Some(Crs::Epsg(3067)));
+        assert_eq!(
+            decode_payload_with_metadata(&data).unwrap(),
+            (geom.clone(), metadata)
+        );
+        assert_eq!(
+            payload_crs(&encode_payload(&geom, raw).unwrap()).unwrap(),
+            None
+        );
+        assert!(decode_payload(&data[..3]).is_err());
+
+        // CRS and ring flags together.
+        let poly = Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0), (x: 0.0, y: 1.0), (x: 1.0, y: 1.0), (x: 0.0, y: 0.0)
+        ]);
+        let crs = Crs::Named("urn:ogc:def:crs:OGC:1.3:CRS84".to_string());
+        assert_eq!(crs.to_string(), "urn:ogc:def:crs:OGC:1.3:CRS84");
+        assert_eq!(Crs::Epsg(4326).to_string(), "EPSG:4326");
+        let serializer = HilbertSerializer::new()
+            .encoding(EncodingMode::Auto)
+            .orient_rings()
+            .crs(crs.clone());
+        let data = serializer.encode(&poly).unwrap();
+        let metadata = payload_metadata(&data).unwrap();
+        assert_eq!(metadata.crs, Some(crs.clone()));
+        assert_eq!(metadata.ring_flags.len(), 1);
+        assert!(geometries_approx_eq(
+            &decode_payload_original(&data).unwrap(),
+            &poly,
+            1e-5
+        ));
+        assert_eq!(
+            serializer.decode_with_crs(&data).unwrap().1,
+            Some(crs.clone())
+        );
+
+        let legacy = HilbertSerializer::new().encode(&geom).unwrap();
+        let lenient = HilbertSerializer::new().encoding(raw).lenient();
+        assert_eq!(lenient.decode_with_crs(&legacy).unwrap().1, None);
+        assert!(HilbertSerializer::new().crs(crs).encode(&geom).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "polars")]
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{point, Geometry};
+    use hilbert_geometry::ewkb::{geometry_from_ewkb, geometry_to_ewkb};
+    use hilbert_geometry::polars_ext::*;
+    use hilbert_geometry::*;
+    use polars::prelude::*;
+
+    fn index(x: f64, y: f64) -> u64 {
+        match encode_geometry(&Geometry::Point(point!(x: x, y: y))) {
+            HilbertGeometry::Point(p) => p.0,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_polars_hilbert_key_sort() {
+        let df = df! {
+            "lon" => [24.94, -3.7, 139.69],
+            "lat" => [60.17, 40.42, 35.69],
+        }
+        .unwrap();
+        let out = df
+            .lazy()
+            .with_column(hilbert_key_lonlat(col("lon"), col("lat")).alias("key"))
+            .sort(["key"], Default::default())
+            .collect()
+            .unwrap();
+
+        let mut expected = vec![
+            index(24.94, 60.17),
+            index(-3.7, 40.42),
+            index(139.69, 35.69),
+        ];
+        expected.sort();
+        let keys: Vec<u64> = out
+            .column("key")
+            .unwrap()
+            .u64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn test_polars_encode_decode() {
+        let geom = Geometry::Point(point!(x: 24.94, y: 60.17));
+        let wkb = BinaryChunked::from_iter([Some(geometry_to_ewkb(&geom, None)), None])
+            .with_name("geom".into());
+        let df = DataFrame::new(vec![wkb.into_column()]).unwrap();
+
+        let out = df
+            .lazy()
+            .select([
+                hilbert_encode(col("geom")).alias("encoded"),
+                hilbert_decode(hilbert_encode(col("geom"))).alias("decoded"),
+            ])
+            .collect()
+            .unwrap();
+
+        let encoded = out.column("encoded").unwrap().binary().unwrap();
+        let expected = HilbertSerializer::new().encode(&geom).unwrap();
+        assert_eq!(encoded.get(0), Some(expected.as_slice()));
+        assert_eq!(encoded.get(1), None);
+
+        let decoded = out.column("decoded").unwrap().binary().unwrap();
+        let (decoded, _) = geometry_from_ewkb(decoded.get(0).unwrap()).unwrap();
+        assert_eq!(decoded, HilbertSerializer::new().decode(&expected).unwrap());
+    }
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "sqlx-postgres")]
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{point, Geometry, GeometryCollection};
+    use hilbert_geometry::ewkb::geometry_to_ewkb;
+    use hilbert_geometry::postgres::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_postgis_geometry_ewkb() {
+        let geom = encode_geometry(&Geometry::Point(point!(x: 24.94, y: 60.17)));
+        let pg = PostgisGeometry::new(geom.clone(), Some(4326));
+        let decoded = PostgisGeometry::from_ewkb(&pg.to_ewkb()).unwrap();
+        assert_eq!(decoded, pg);
+        assert_eq!(HilbertGeometry::from(decoded), geom);
+    }
+
+    #[test]
+    fn test_postgis_geometry_rejects_collection() {
+        let data = geometry_to_ewkb(
+            &Geometry::GeometryCollection(GeometryCollection(vec![])),
+            None,
+        );
+        assert!(PostgisGeometry::from_ewkb(&data).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, line_string, Geometry};
+    use hilbert_geometry::payload::*;
+    use hilbert_geometry::precise::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_point128_roundtrip() {
+        for c in [
+            coord! { x: 24.123456789012345, y: 60.98765432101234 },
+            coord! { x: -179.999999999, y: -89.999999999 },
+            coord! { x: 0.0, y: 0.0 },
+            coord! { x: 179.123, y: 0.5 },
+        ] {
+            let back = HilbertPoint128::from_coord(c).to_coord();
+            assert!((back.x - c.x).abs() < 1e-12, "{c:?} -> {back:?}");
+            assert!((back.y - c.y).abs() < 1e-12, "{c:?} -> {back:?}");
+        }
+        assert_eq!(
+            HilbertPoint128::from_coord(coord! { x: -180.0, y: -90.0 }),
+            HilbertPoint128(0)
+        );
+        assert_eq!(
+            HilbertPoint128::from_coord(coord! { x: 500.0, y: 0.0 }),
+            HilbertPoint128::from_coord(coord! { x: 180.0, y: 0.0 })
+        );
+    }
+
+    #[test]
+    fn test_point128_curve_is_continuous() {
+        // Consecutive indices map to adjacent grid cells.
+        let step = (360.0 / 2f64.powi(64), 180.0 / 2f64.powi(64));
+        for start in [0u128, 1 << 70, u128::MAX / 3] {
+            let a = HilbertPoint128(start).to_coord();
+            let b = HilbertPoint128(start + 1).to_coord();
+            assert!((b.x - a.x).abs() <= step.0 * 1.5 + 1e-12);
+            assert!((b.y - a.y).abs() <= step.1 * 1.5 + 1e-12);
+        }
+        for h in [0u128, 7, 1 << 100, u128::MAX] {
+            let p = HilbertPoint128(h);
+            assert_eq!(HilbertPoint128::from_coord(p.to_coord()).0 >> 20, h >> 20);
+        }
+    }
+
+    #[test]
+    fn test_point128_conversion() {
+        let p = encode_geometry(&Geometry::Point((24.9384, 60.1699).into()));
+        let HilbertGeometry::Point(p) = p else {
+            unreachable!()
+        };
+        let wide = HilbertPoint128::from(p);
+        assert_eq!(HilbertPoint::from(wide), p);
+    }
+
+    #[test]
+    fn test_payload_hilbert128() {
+        let geom = Geometry::LineString(line_string![
+            (x: 24.123456789012, y: 60.1),
+            (x: 24.2, y: 60.2000000001)
+        ]);
+        let data = encode_payload(&geom, EncodingMode::Fixed(Encoding::Hilbert128)).unwrap();
+        assert_eq!(payload_encoding(&data).unwrap(), Encoding::Hilbert128);
+        assert!(geometries_approx_eq(
+            &decode_payload(&data).unwrap(),
+            &geom,
+            1e-12
+        ));
+        assert!(!geometries_approx_eq(
+            &decode_payload(
+                &encode_payload(&geom, EncodingMode::Fixed(Encoding::Hilbert)).unwrap()
+            )
+            .unwrap(),
+            &geom,
+            1e-9
+        ));
+    }
+}
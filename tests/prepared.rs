@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, polygon, Geometry, MultiPolygon};
+    use hilbert_geometry::prepared::*;
+    use hilbert_geometry::{encode_geometry, HilbertGeometry, HilbertPoint};
+
+    fn key(x: f64, y: f64) -> HilbertPoint {
+        match encode_geometry(&Geometry::Point(point!(x: x, y: y))) {
+            HilbertGeometry::Point(p) => p,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_prepared_polygon_contains() {
+        // A triangle with a square hole, plus a separate square.
+        let triangle = polygon!(
+            exterior: [(x: 24.0, y: 60.0), (x: 26.0, y: 60.0), (x: 25.0, y: 62.0)],
+            interiors: [[(x: 24.9, y: 60.4), (x: 25.1, y: 60.4), (x: 25.1, y: 60.6), (x: 24.9, y: 60.6)]],
+        );
+        let square = polygon![(x: 27.0, y: 60.0), (x: 28.0, y: 60.0), (x: 28.0, y: 61.0), (x: 27.0, y: 61.0)];
+        let geom = encode_geometry(&Geometry::MultiPolygon(MultiPolygon(vec![
+            triangle, square,
+        ])));
+        let prepared = PreparedPolygon::new(&geom).unwrap();
+        assert!(!prepared.covering().inner.is_empty());
+
+        let inside = |x: f64, y: f64| {
+            let in_triangle = y > 60.0 && (x - 25.0).abs() < (62.0 - y) / 2.0;
+            let in_hole = x > 24.9 && x < 25.1 && y > 60.4 && y < 60.6;
+            let in_square = x > 27.0 && x < 28.0 && y > 60.0 && y < 61.0;
+            in_triangle && !in_hole || in_square
+        };
+        for ix in 0..100 {
+            for iy in 0..60 {
+                let (x, y) = (23.513 + ix as f64 * 0.0497, 59.507 + iy as f64 * 0.0491);
+                assert_eq!(prepared.contains(key(x, y)), inside(x, y), "({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_prepared_polygon_types() {
+        let line = encode_geometry(&Geometry::LineString(
+            line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)],
+        ));
+        assert!(PreparedPolygon::new(&line).is_none());
+        let empty = PreparedPolygon::new(&encode_geometry(&Geometry::Polygon(polygon![]))).unwrap();
+        assert!(!empty.contains(key(0.0, 0.0)));
+        let coarse = encode_geometry(&Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 4.0)
+        ]));
+        let coarse = PreparedPolygon::with_max_ranges(&coarse, 1).unwrap();
+        assert!(coarse.contains(key(1.5, 2.5)));
+        assert!(!coarse.contains(key(4.5, 2.5)));
+    }
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "prost")]
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{polygon, Geometry};
+    use hilbert_geometry::proto::GeometryMessage;
+    use hilbert_geometry::*;
+    use prost::Message;
+
+    /// A message embedding a geometry, as generated with `extern_path`.
+    #[derive(Clone, PartialEq, Message)]
+    struct Update {
+        #[prost(string, tag = "1")]
+        id: String,
+        #[prost(message, optional, tag = "2")]
+        geometry: Option<GeometryMessage>,
+    }
+
+    #[test]
+    fn test_geometry_message() {
+        let poly = Geometry::Polygon(polygon![
+            (x: 10.0, y: 50.0),
+            (x: 11.0, y: 50.0),
+            (x: 11.0, y: 51.0),
+            (x: 10.0, y: 50.0)
+        ]);
+        let hg = encode_geometry(&poly);
+        let msg = GeometryMessage::from(&hg);
+        assert_eq!(msg.hwkb, HilbertSerializer::new().encode(&poly).unwrap());
+
+        let update = Update {
+            id: "a".into(),
+            geometry: Some(msg),
+        };
+        let decoded = Update::decode(&update.encode_to_vec()[..]).unwrap();
+        let msg = decoded.geometry.unwrap();
+        assert_eq!(HilbertGeometry::try_from(&msg).unwrap(), hg);
+        assert_eq!(Geometry::try_from(&msg).unwrap(), poly);
+
+        let corrupt = GeometryMessage { hwkb: vec![2, 1] };
+        assert!(HilbertGeometry::try_from(corrupt).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, polygon, Geometry};
+    use hilbert_geometry::qa::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_annotated_matches_plain_encoding() {
+        let geom = Geometry::LineString(line_string![
+            (x: 24.9384, y: 60.1699),
+            (x: 24.9412, y: 60.1712),
+        ]);
+        let (encoded, _) = encode_geometry_annotated(&geom);
+        assert_eq!(encoded, encode_geometry(&geom));
+    }
+
+    #[test]
+    fn test_worst_vertex() {
+        // Exterior ring of exact f32 values, then a hole with one inexact vertex.
+        let geom = Geometry::Polygon(polygon!(
+            exterior: [(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 0.0)],
+            interiors: [[(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 2.0, y: 2.123456789), (x: 1.0, y: 1.0)]],
+        ));
+        let (_, worst) = encode_geometry_annotated(&geom);
+        let y = 2.123456789;
+        // Four exterior vertices including the closing one, then the hole.
+        assert_eq!(worst.index, 6);
+        assert_eq!(worst.error, (y as f32 as f64 - y).abs());
+        assert!(worst.exceeds(1e-9));
+        assert!(!worst.exceeds(1e-6));
+    }
+
+    #[test]
+    fn test_exact_vertices_have_no_error() {
+        let (_, worst) = encode_geometry_annotated(&Geometry::Point(point!(x: 0.5, y: -1.25)));
+        assert_eq!(worst, VertexError::default());
+        let (_, worst) = encode_geometry_annotated(&Geometry::LineString(line_string![]));
+        assert_eq!(
+            worst,
+            VertexError {
+                index: 0,
+                error: 0.0
+            }
+        );
+    }
+}
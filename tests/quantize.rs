@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, line_string, point, Geometry};
+    use hilbert_geometry::quantize::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_quantize_strategies() {
+        assert_eq!(quantize(1.25, 1, Rounding::Round), 1.3);
+        assert_eq!(quantize(-1.25, 1, Rounding::Round), -1.3);
+        assert_eq!(quantize(1.25, 1, Rounding::Truncate), 1.2);
+        assert_eq!(quantize(-1.29, 1, Rounding::Truncate), -1.2);
+        assert_eq!(quantize(1.25, 1, Rounding::HalfEven), 1.2);
+        assert_eq!(quantize(1.75, 1, Rounding::HalfEven), 1.8);
+        assert_eq!(
+            quantize_coord(coord! { x: 24.123456, y: 60.987654 }, 3, Rounding::Round),
+            coord! { x: 24.123, y: 60.988 }
+        );
+    }
+
+    #[test]
+    fn test_serializer_quantize() {
+        // Equal at 4 decimal places, but further apart than the f32 resolution.
+        let a = Geometry::Point(point!(x: 24.94126, y: 60.17));
+        let b = Geometry::Point(point!(x: 24.94134, y: 60.17));
+        let plain = HilbertSerializer::new();
+        assert_ne!(plain.encode(&a).unwrap(), plain.encode(&b).unwrap());
+
+        let quantized = HilbertSerializer::new().quantize(4, Rounding::Round);
+        assert_eq!(quantized.encode(&a).unwrap(), quantized.encode(&b).unwrap());
+
+        let line = Geometry::LineString(line_string![(x: 1.26, y: 2.0), (x: 3.0, y: -4.04)]);
+        let encoded = HilbertSerializer::new()
+            .quantize(1, Rounding::Truncate)
+            .encode(&line)
+            .unwrap();
+        let expected = Geometry::LineString(line_string![(x: 1.2, y: 2.0), (x: 3.0, y: -4.0)]);
+        assert_eq!(encoded, plain.encode(&expected).unwrap());
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{point, Geometry};
+    use hilbert_geometry::geo_ops::haversine_distance;
+    use hilbert_geometry::redis::*;
+    use hilbert_geometry::*;
+
+    fn key(x: f64, y: f64) -> HilbertPoint {
+        match encode_geometry(&Geometry::Point(point! { x: x, y: y })) {
+            HilbertGeometry::Point(p) => p,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_scores() {
+        let p = key(24.9384, 60.1699);
+        let score = key_to_score(p);
+        assert_eq!(score.fract(), 0.0);
+        assert!(score < 2f64.powi(53));
+        assert!(score_to_range(score).unwrap().contains(&p.0));
+        assert_eq!(key_to_score(score_to_key(score).unwrap()), score);
+        assert!(key_to_score(key(24.94, 60.17)) != score);
+
+        assert_eq!(score_to_range(0.5), None);
+        assert_eq!(score_to_range(-1.0), None);
+        assert_eq!(score_to_range(2f64.powi(53)), None);
+        assert_eq!(key_to_score(HilbertPoint(u64::MAX)), 2f64.powi(53) - 1.0);
+    }
+
+    #[test]
+    fn test_radius_score_ranges() {
+        for (cx, cy) in [(24.9384, 60.1699), (179.99, -16.5), (-0.001, 0.001)] {
+            let center = key(cx, cy);
+            let radius = 2_000.0;
+            let ranges = radius_score_ranges(center, radius, 16);
+            assert!(ranges.windows(2).all(|w| w[0].end() < w[1].start()));
+            for i in -40..=40 {
+                for j in -40..=40 {
+                    let x = cx + i as f64 * 0.001;
+                    let x = if x > 180.0 { x - 360.0 } else { x };
+                    let p = key(x, cy + j as f64 * 0.001);
+                    if haversine_distance(center, p) <= radius {
+                        let score = key_to_score(p);
+                        assert!(ranges.iter().any(|r| r.contains(&score)), "{x} {cy}");
+                    }
+                }
+            }
+        }
+    }
+}
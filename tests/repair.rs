@@ -0,0 +1,97 @@
+#![cfg(feature = "repair")]
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{polygon, Geometry, GeometryCollection, LineString, MultiPolygon, Polygon};
+    use hilbert_geometry::repair::*;
+    use hilbert_geometry::validate::*;
+    use hilbert_geometry::HilbertSerializer;
+
+    const ALL: ValidationOptions = ValidationOptions {
+        validate_holes: true,
+        validate_simple: true,
+    };
+
+    /// Twice the signed area, positive for counter-clockwise rings.
+    fn signed_area(ring: &LineString) -> f64 {
+        ring.lines()
+            .map(|l| l.start.x * l.end.y - l.end.x * l.start.y)
+            .sum()
+    }
+
+    fn bowtie() -> Polygon {
+        polygon![
+            (x: 0.0, y: 0.0),
+            (x: 2.0, y: 2.0),
+            (x: 2.0, y: 0.0),
+            (x: 0.0, y: 2.0),
+        ]
+    }
+
+    #[test]
+    fn test_repair_bowtie() {
+        let Geometry::MultiPolygon(mp) = repair_geometry(&Geometry::Polygon(bowtie())) else {
+            panic!("expected a multipolygon")
+        };
+        assert_eq!(mp.0.len(), 2);
+        for p in &mp.0 {
+            assert_eq!(p.exterior().0.len(), 4);
+            assert_eq!(signed_area(p.exterior()), 2.0);
+        }
+        assert!(validate(&Geometry::MultiPolygon(mp), &ALL).is_empty());
+    }
+
+    #[test]
+    fn test_repair_hole_polarity() {
+        // The hole is wound counter-clockwise, like the shell.
+        let p = polygon!(
+            exterior: [(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 4.0)],
+            interiors: [[(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 2.0, y: 2.0), (x: 1.0, y: 2.0)]],
+        );
+        let Geometry::Polygon(repaired) = repair_geometry(&Geometry::Polygon(p)) else {
+            panic!("expected a polygon")
+        };
+        assert_eq!(signed_area(repaired.exterior()), 32.0);
+        assert_eq!(repaired.interiors().len(), 1);
+        assert_eq!(signed_area(&repaired.interiors()[0]), -2.0);
+
+        // Overlapping parts are merged, degenerate ones dropped.
+        let mp = MultiPolygon(vec![
+            polygon![(x: 0.0, y: 0.0), (x: 2.0, y: 0.0), (x: 2.0, y: 2.0), (x: 0.0, y: 2.0)],
+            polygon![(x: 1.0, y: 0.0), (x: 3.0, y: 0.0), (x: 3.0, y: 2.0), (x: 1.0, y: 2.0)],
+            polygon![(x: 5.0, y: 5.0), (x: 6.0, y: 6.0), (x: 7.0, y: 7.0)],
+        ]);
+        let Geometry::MultiPolygon(merged) = repair_geometry(&Geometry::MultiPolygon(mp)) else {
+            panic!("expected a multipolygon")
+        };
+        assert_eq!(merged.0.len(), 1);
+        assert_eq!(signed_area(merged.0[0].exterior()), 12.0);
+    }
+
+    #[test]
+    fn test_serializer_repair() {
+        let geom = Geometry::Polygon(bowtie());
+        assert!(HilbertSerializer::new()
+            .validate(ALL)
+            .encode(&geom)
+            .is_err());
+        let serializer = HilbertSerializer::new().repair().validate(ALL);
+        let decoded = serializer
+            .decode(&serializer.encode(&geom).unwrap())
+            .unwrap();
+        let Geometry::MultiPolygon(mp) = decoded else {
+            panic!("expected a multipolygon")
+        };
+        assert_eq!(mp.0.len(), 2);
+
+        let point = Geometry::Point((1.0, 2.0).into());
+        assert_eq!(repair_geometry(&point), point);
+    }
+
+    #[test]
+    fn test_serializer_repair_collection() {
+        let gc =
+            Geometry::GeometryCollection(GeometryCollection(vec![Geometry::Polygon(bowtie())]));
+        assert!(HilbertSerializer::new().repair().encode(&gc).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, Geometry};
+    use hilbert_geometry::resample::*;
+    use hilbert_geometry::*;
+
+    fn encode_line(geom: Geometry) -> Vec<HilbertPoint> {
+        match encode_geometry(&geom) {
+            HilbertGeometry::LineString(ps) => ps,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_resample_linestring() {
+        let ls = encode_line(Geometry::LineString(line_string![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 0.5),
+            (x: 1.0, y: 3.0)
+        ]));
+        let resampled = resample_linestring(&ls, 5);
+        let expected = encode_line(Geometry::LineString(line_string![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+            (x: 1.0, y: 2.0),
+            (x: 1.0, y: 3.0)
+        ]));
+        assert_eq!(resampled, expected);
+
+        assert_eq!(resample_linestring(&ls, 2), vec![ls[0], ls[3]]);
+        assert_eq!(resample_linestring(&ls, 1), vec![ls[0]]);
+        assert!(resample_linestring(&ls, 0).is_empty());
+        assert!(resample_linestring(&[], 4).is_empty());
+        assert_eq!(resample_linestring(&ls[..1], 3), vec![ls[0]; 3]);
+    }
+
+    #[test]
+    fn test_densify() {
+        let ls = Geometry::LineString(line_string![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 0.25)
+        ]);
+        let dense = densify(&ls, 0.3);
+        let expected = Geometry::LineString(line_string![
+            (x: 0.0, y: 0.0),
+            (x: 0.25, y: 0.0),
+            (x: 0.5, y: 0.0),
+            (x: 0.75, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 0.25)
+        ]);
+        assert_eq!(dense, expected);
+        assert_eq!(densify(&ls, 0.0), ls);
+
+        let serializer = HilbertSerializer::new().densify(0.3);
+        let decoded = serializer.decode(&serializer.encode(&ls).unwrap()).unwrap();
+        assert_eq!(decoded, expected);
+    }
+}
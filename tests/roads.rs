@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, line_string, point, Geometry, MultiLineString};
+    use hilbert_geometry::roads::*;
+    use hilbert_geometry::{encode_geometry, HilbertGeometry, HilbertPoint};
+
+    fn key(x: f64, y: f64) -> HilbertPoint {
+        match encode_geometry(&Geometry::Point(point!(x: x, y: y))) {
+            HilbertGeometry::Point(p) => p,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_nearest_segment() {
+        let roads = [
+            encode_geometry(&Geometry::LineString(line_string![
+                (x: 24.0, y: 60.0), (x: 24.01, y: 60.0), (x: 24.02, y: 60.0)
+            ])),
+            encode_geometry(&Geometry::Point(point!(x: 24.0, y: 60.0))),
+            encode_geometry(&Geometry::MultiLineString(MultiLineString(vec![
+                line_string![(x: 24.0, y: 60.1), (x: 24.02, y: 60.1)],
+                line_string![(x: 24.015, y: 60.005), (x: 24.015, y: 60.05)],
+            ]))),
+        ];
+        let network = RoadNetwork::new(&roads);
+        assert_eq!(network.len(), 4);
+
+        let m = network.nearest_segment(key(24.012, 60.001), 0.01).unwrap();
+        assert_eq!((m.road, m.segment), (0, 1));
+        assert!((m.distance - 0.001).abs() < 1e-5);
+        assert!((m.snapped.x - 24.012).abs() < 1e-5);
+        assert!((m.snapped.y - 60.0).abs() < 1e-5);
+
+        // Closer to the side street of the second road.
+        let m = network.nearest_segment(key(24.0155, 60.02), 0.01).unwrap();
+        assert_eq!((m.road, m.segment), (2, 2));
+        assert!(network.nearest_segment(key(24.05, 60.05), 0.01).is_none());
+        assert!(RoadNetwork::default()
+            .nearest_segment(key(24.0, 60.0), 1.0)
+            .is_none());
+    }
+
+    #[test]
+    fn test_nearest_segment_long_segment() {
+        // The midpoint is far from the query point, the segment is not.
+        let road = encode_geometry(&Geometry::LineString(line_string![
+            (x: 20.0, y: 60.0), (x: 30.0, y: 60.0)
+        ]));
+        let network = RoadNetwork::new([&road]);
+        let m = network.nearest_segment(key(20.5, 60.001), 0.01).unwrap();
+        assert_eq!(m.snapped, coord! { x: 20.5, y: 60.0 });
+    }
+
+    #[test]
+    fn test_nearest_segment_empty_part() {
+        let road = HilbertGeometry::MultiLineString(vec![
+            vec![key(24.0, 60.0), key(24.01, 60.0)],
+            vec![],
+            vec![key(24.0, 60.1), key(24.01, 60.1), key(24.02, 60.1)],
+        ]);
+        let network = RoadNetwork::new([&road]);
+        // The second segment of the last part starts at vertex 3.
+        let m = network.nearest_segment(key(24.015, 60.1), 0.01).unwrap();
+        assert_eq!((m.road, m.segment), (0, 3));
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, Geometry, Point, Rect};
+    use hilbert_geometry::search::*;
+    use hilbert_geometry::*;
+
+    fn encode_point(x: f64, y: f64) -> HilbertPoint {
+        match encode_geometry(&Geometry::Point(Point::new(x, y))) {
+            HilbertGeometry::Point(p) => p,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_find_range() {
+        let sorted: Vec<HilbertPoint> = [1, 3, 3, 3, 7, 9].map(HilbertPoint).to_vec();
+        let p = HilbertPoint;
+        assert_eq!(lower_bound(&sorted, p(3)), 1);
+        assert_eq!(upper_bound(&sorted, p(3)), 4);
+        assert_eq!(find_range(&sorted, p(3)..=p(7)), 1..5);
+        assert_eq!(find_range(&sorted, p(3)..p(7)), 1..4);
+        assert_eq!(
+            find_range(
+                &sorted,
+                (std::ops::Bound::Excluded(p(3)), std::ops::Bound::Unbounded)
+            ),
+            4..6
+        );
+        assert_eq!(find_range(&sorted, ..p(2)), 0..1);
+        assert_eq!(find_range(&sorted, p(4)..p(6)), 4..4);
+        assert_eq!(find_range(&sorted, p(8)..p(2)), 5..5);
+        assert_eq!(find_range(&[], ..), 0..0);
+    }
+
+    #[test]
+    fn test_find_in_rect_across_axes() {
+        let mut sorted: Vec<HilbertPoint> = (-8..=8)
+            .flat_map(|i| (-8..=8).map(move |j| encode_point(i as f64 * 0.25, j as f64 * 0.25)))
+            .collect();
+        sorted.sort();
+
+        let rect = Rect::new(coord! { x: -1.0, y: -1.5 }, coord! { x: 0.5, y: 0.0 });
+        let found: Vec<HilbertPoint> = find_in_rect(&sorted, &rect)
+            .into_iter()
+            .map(|i| sorted[i])
+            .collect();
+        let expected: Vec<HilbertPoint> = sorted
+            .iter()
+            .copied()
+            .filter(|p| {
+                let c = Geometry::from(HilbertGeometry::Point(*p));
+                let Geometry::Point(c) = c else {
+                    unreachable!()
+                };
+                c.x() >= -1.0 && c.x() <= 0.5 && c.y() >= -1.5 && c.y() <= 0.0
+            })
+            .collect();
+        assert_eq!(expected.len(), 7 * 7);
+        assert_eq!(found, expected);
+    }
+}
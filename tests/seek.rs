@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use hilbert_geometry::seek::*;
+    use hilbert_geometry::*;
+
+    fn track(n: u64) -> Vec<HilbertPoint> {
+        (0..n)
+            .map(|i| HilbertPoint(1_000_000_000 + i * 37 - (i % 5) * 11))
+            .collect()
+    }
+
+    #[test]
+    fn test_seekable_access() {
+        let points = track(1000);
+        let data = encode_seekable(&points, 64).unwrap();
+        let ls = SeekableLineString::new(&data).unwrap();
+        assert_eq!(ls.len(), 1000);
+        assert_eq!(ls.to_vec().unwrap(), points);
+        for i in [0, 1, 63, 64, 65, 500, 999] {
+            assert_eq!(ls.vertex_at(i).unwrap().unwrap(), points[i]);
+        }
+        assert!(ls.vertex_at(1000).is_none());
+        assert_eq!(ls.slice(60..200).unwrap().unwrap(), &points[60..200]);
+        assert_eq!(ls.slice(990..1000).unwrap().unwrap(), &points[990..]);
+        assert!(ls.slice(5..5).unwrap().unwrap().is_empty());
+        assert!(ls.slice(990..1001).is_none());
+    }
+
+    #[test]
+    fn test_seekable_edge_cases() {
+        assert!(encode_seekable(&track(3), 0).is_err());
+
+        let data = encode_seekable(&[], 16).unwrap();
+        let ls = SeekableLineString::new(&data).unwrap();
+        assert!(ls.is_empty());
+        assert!(ls.to_vec().unwrap().is_empty());
+
+        let points = track(10);
+        let data = encode_seekable(&points, 1).unwrap();
+        let ls = SeekableLineString::new(&data).unwrap();
+        assert_eq!(ls.slice(3..7).unwrap().unwrap(), &points[3..7]);
+
+        let data = encode_seekable(&points, 4).unwrap();
+        assert!(SeekableLineString::new(&data[..3]).is_err());
+        let truncated = SeekableLineString::new(&data[..data.len() - 1]).unwrap();
+        assert!(truncated.slice(8..10).unwrap().is_err());
+
+        // An interval longer than the linestring is shortened.
+        let data = encode_seekable(&points, 100).unwrap();
+        assert_eq!(
+            SeekableLineString::new(&data).unwrap().to_vec().unwrap(),
+            points
+        );
+    }
+
+    #[test]
+    fn test_seekable_malformed_header() {
+        let cfg = bincode::config::standard();
+        let header = |len: u64, interval: u64, sync: Vec<u64>| {
+            let mut data = bincode::encode_to_vec((len, interval, sync), cfg).unwrap();
+            data.extend([0; 8]);
+            data
+        };
+        assert!(SeekableLineString::new(&header(u64::MAX, u64::MAX, vec![0])).is_err());
+        assert!(SeekableLineString::new(&header(9, 1, vec![0; 9])).is_err());
+        assert!(SeekableLineString::new(&header(2, 4, vec![0])).is_err());
+        assert!(SeekableLineString::new(&header(2, 2, vec![0])).is_ok());
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, Geometry, LineString};
+    use hilbert_geometry::shard::*;
+    use hilbert_geometry::{encode_geometry, HilbertGeometry, HilbertPoint};
+
+    #[test]
+    fn test_shard_for() {
+        let boundaries = [0.25, 0.5];
+        assert_eq!(shard_for(HilbertPoint(0), &boundaries), 0);
+        assert_eq!(shard_for(HilbertPoint(1 << 62), &boundaries), 1);
+        assert_eq!(shard_for(HilbertPoint(3 << 62), &boundaries), 2);
+        assert_eq!(shard_for(HilbertPoint(u64::MAX), &boundaries), 2);
+        assert_eq!(shard_for(HilbertPoint(u64::MAX), &[]), 0);
+    }
+
+    #[test]
+    fn test_compute_balanced_boundaries() {
+        // Skewed keys: most of them near the start of the curve.
+        let keys: Vec<_> = (0..1000u64)
+            .map(|i| HilbertPoint(i * i * i * 1_000_000))
+            .collect();
+        let boundaries = compute_balanced_boundaries(&keys, 4);
+        assert_eq!(boundaries.len(), 3);
+        assert!(boundaries.windows(2).all(|w| w[0] < w[1]));
+        let mut sizes = [0; 4];
+        for key in &keys {
+            sizes[shard_for(*key, &boundaries)] += 1;
+        }
+        assert_eq!(sizes, [250; 4]);
+
+        assert!(compute_balanced_boundaries(&keys, 1).is_empty());
+        assert!(compute_balanced_boundaries(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_partition_for() {
+        let n = 12;
+        let a = encode_geometry(&Geometry::Point(point! { x: 24.9384, y: 60.1699 }));
+        let b = encode_geometry(&Geometry::Point(point! { x: 24.9385, y: 60.1698 }));
+        assert_eq!(partition_for(&a, n, 16), partition_for(&b, n, 16));
+        let line = encode_geometry(&Geometry::LineString(line_string![
+            (x: 24.9383, y: 60.1699),
+            (x: 24.9385, y: 60.1699)
+        ]));
+        assert_eq!(partition_for(&line, n, 16), partition_for(&a, n, 16));
+
+        // Partitions are contiguous stretches of the curve.
+        let mut points: Vec<HilbertGeometry> = (0..36)
+            .flat_map(|i| {
+                (0..18).map(move |j| {
+                    let x = -175.0 + i as f64 * 10.0;
+                    let y = -85.0 + j as f64 * 10.0;
+                    encode_geometry(&Geometry::Point(point! { x: x, y: y }))
+                })
+            })
+            .collect();
+        points.sort_by_key(|p| match p {
+            HilbertGeometry::Point(p) => p.0,
+            _ => unreachable!(),
+        });
+        let partitions: Vec<usize> = points
+            .iter()
+            .map(|p| partition_for(p, n, 16).unwrap())
+            .collect();
+        assert!(partitions.windows(2).all(|w| w[0] <= w[1]));
+        assert!(partitions[0] < partitions[partitions.len() - 1]);
+        assert_eq!(partition_for(&a, 1, 0), Some(0));
+        assert_eq!(partition_for(&a, n, 0), Some(0));
+
+        let empty = encode_geometry(&Geometry::LineString(LineString::new(vec![])));
+        assert_eq!(partition_for(&empty, n, 16), None);
+    }
+}
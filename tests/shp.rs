@@ -0,0 +1,98 @@
+#![cfg(feature = "shapefile")]
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, Geometry, MultiLineString};
+    use hilbert_geometry::collection::*;
+    use hilbert_geometry::shp::*;
+    use hilbert_geometry::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("hilbert-shp-{}-{name}", std::process::id()))
+    }
+
+    fn remove_shapefile(path: &std::path::Path) {
+        for ext in ["shp", "shx", "dbf"] {
+            std::fs::remove_file(path.with_extension(ext)).ok();
+        }
+    }
+
+    #[test]
+    fn test_shapefile_roundtrip() {
+        let roads: HilbertCollection = [
+            Geometry::LineString(line_string![(x: 24.94, y: 60.17), (x: 24.95, y: 60.18)]),
+            Geometry::MultiLineString(MultiLineString(vec![
+                line_string![(x: 25.0, y: 60.0), (x: 25.1, y: 60.1)],
+                line_string![(x: 25.2, y: 60.2), (x: 25.3, y: 60.3)],
+            ])),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, g)| {
+            HilbertFeature::new(encode_geometry(g))
+                .with_property("lanes", PropertyValue::Int(i as i64 + 1))
+                .with_property("name", PropertyValue::String(format!("road {i}")))
+        })
+        .collect();
+        let path = temp_path("roads.shp");
+        write_shapefile(&roads, &path).unwrap();
+        let read = read_shapefile(&path).unwrap();
+        assert_eq!(read, roads);
+        remove_shapefile(&path);
+    }
+
+    #[test]
+    fn test_shapefile_mixed_columns() {
+        let points: HilbertCollection = [
+            PropertyValue::Int(1),
+            PropertyValue::Double(2.5),
+            PropertyValue::Null,
+        ]
+        .into_iter()
+        .map(|v| {
+            HilbertFeature::new(encode_geometry(&Geometry::Point(point!(x: 1.0, y: 2.0))))
+                .with_property("value", v)
+        })
+        .collect();
+        let path = temp_path("points.shp");
+        write_shapefile(&points, &path).unwrap();
+        let values: Vec<_> = read_shapefile(&path)
+            .unwrap()
+            .features
+            .iter()
+            .map(|f| f.property("value").cloned())
+            .collect();
+        assert_eq!(
+            values,
+            [
+                Some(PropertyValue::Int(1)),
+                Some(PropertyValue::Double(2.5)),
+                Some(PropertyValue::Null)
+            ]
+        );
+        remove_shapefile(&path);
+    }
+
+    #[test]
+    fn test_shapefile_rejects_mixed_shapes() {
+        let mixed: HilbertCollection = [
+            Geometry::Point(point!(x: 1.0, y: 2.0)),
+            Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)]),
+        ]
+        .iter()
+        .map(|g| HilbertFeature::new(encode_geometry(g)))
+        .collect();
+        let err = write_shapefile(&mixed, temp_path("mixed.shp")).unwrap_err();
+        assert!(matches!(err, ShapefileError::Geometry(_)));
+
+        let long_name: HilbertCollection =
+            [
+                HilbertFeature::new(encode_geometry(&Geometry::Point(point!(x: 1.0, y: 2.0))))
+                    .with_property("much_too_long_name", PropertyValue::Bool(true)),
+            ]
+            .into_iter()
+            .collect();
+        let err = write_shapefile(&long_name, temp_path("long.shp")).unwrap_err();
+        assert!(matches!(err, ShapefileError::Field(_)));
+    }
+}
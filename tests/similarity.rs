@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, Geometry, LineString};
+    use hilbert_geometry::similarity::*;
+    use hilbert_geometry::{encode_geometry, HilbertGeometry};
+
+    fn encode(ls: LineString) -> HilbertGeometry {
+        encode_geometry(&Geometry::LineString(ls))
+    }
+
+    #[test]
+    fn test_similarity() {
+        let a = encode(line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 2.0, y: 0.0)]);
+        let b = encode(line_string![(x: 0.0, y: 0.5), (x: 1.0, y: 0.5), (x: 2.0, y: 0.5)]);
+        assert_eq!(similarity(&a, &a), Some(0.0));
+        assert_eq!(similarity(&a, &b), Some(0.5));
+        // Different sampling of the same path.
+        let c = encode(line_string![
+            (x: 0.0, y: 0.0), (x: 0.5, y: 0.0), (x: 1.0, y: 0.0), (x: 1.5, y: 0.0), (x: 2.0, y: 0.0)
+        ]);
+        assert_eq!(similarity(&a, &c), Some(0.5));
+        // The same path walked backwards is far away.
+        let reversed = encode(line_string![(x: 2.0, y: 0.0), (x: 1.0, y: 0.0), (x: 0.0, y: 0.0)]);
+        assert_eq!(similarity(&a, &reversed), Some(2.0));
+        assert_eq!(similarity(&a, &encode(line_string![])), None);
+    }
+
+    #[test]
+    fn test_within_distance() {
+        let a = encode(line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 2.0, y: 0.0)]);
+        let b = encode(line_string![(x: 0.0, y: 0.5), (x: 1.0, y: 0.5), (x: 2.0, y: 0.5)]);
+        assert!(within_distance(&a, &a, 0.0));
+        assert!(within_distance(&a, &b, 0.5));
+        assert!(!within_distance(&a, &b, 0.4));
+        // Same endpoints, but a detour in the middle.
+        let detour = encode(line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 3.0), (x: 2.0, y: 0.0)]);
+        assert!(!within_distance(&a, &detour, 1.0));
+        assert!(within_distance(&a, &detour, 3.0));
+        let far = encode(line_string![(x: 50.0, y: 50.0), (x: 51.0, y: 50.0)]);
+        assert!(!within_distance(&a, &far, 10.0));
+        assert!(!within_distance(&a, &encode(line_string![]), 10.0));
+        assert!(within_distance(&a, &detour, f64::INFINITY));
+    }
+}
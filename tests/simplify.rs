@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, polygon, Geometry, MultiLineString};
+    use hilbert_geometry::{decode_geometry, encode_geometry};
+
+    #[test]
+    fn test_simplified_linestring() {
+        let ls = line_string![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.1),
+            (x: 2.0, y: -0.1),
+            (x: 3.0, y: 5.0),
+            (x: 4.0, y: 6.0),
+            (x: 5.0, y: 7.0),
+            (x: 6.0, y: 8.1),
+            (x: 7.0, y: 9.0),
+        ];
+        let encoded = encode_geometry(&Geometry::LineString(ls.clone()));
+        let expected = line_string![
+            (x: 0.0, y: 0.0),
+            (x: 2.0, y: -0.1),
+            (x: 3.0, y: 5.0),
+            (x: 7.0, y: 9.0),
+        ];
+        assert_eq!(
+            encoded.simplified(1.0),
+            encode_geometry(&Geometry::LineString(expected))
+        );
+        // Only the vertex on the line from (3, 5) to (5, 7) goes.
+        assert_eq!(encoded.simplified(0.01).num_coords(), 7);
+
+        let multi = encode_geometry(&Geometry::MultiLineString(MultiLineString(vec![
+            ls.clone(),
+            line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)],
+        ])));
+        let Geometry::MultiLineString(simplified) = decode_geometry(&multi.simplified(100.0))
+        else {
+            panic!("expected a multilinestring")
+        };
+        assert_eq!(simplified.0[0].0.len(), 2);
+        assert_eq!(simplified.0[1].0.len(), 2);
+    }
+
+    #[test]
+    fn test_simplified_polygon() {
+        let poly = polygon!(
+            exterior: [
+                (x: 0.0, y: 0.0),
+                (x: 5.0, y: 0.01),
+                (x: 10.0, y: 0.0),
+                (x: 10.0, y: 10.0),
+                (x: 0.0, y: 10.0),
+            ],
+            interiors: [[(x: 4.0, y: 4.0), (x: 4.1, y: 4.0), (x: 4.1, y: 4.1)]],
+        );
+        let encoded = encode_geometry(&Geometry::Polygon(poly));
+        let Geometry::Polygon(simplified) = decode_geometry(&encoded.simplified(0.5)) else {
+            panic!("expected a polygon")
+        };
+        assert_eq!(
+            simplified.exterior(),
+            &line_string![
+                (x: 0.0, y: 0.0),
+                (x: 10.0, y: 0.0),
+                (x: 10.0, y: 10.0),
+                (x: 0.0, y: 10.0),
+                (x: 0.0, y: 0.0),
+            ]
+        );
+        // The hole would collapse and is kept whole.
+        assert_eq!(simplified.interiors()[0].0.len(), 4);
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, line_string, Geometry, Point, Rect};
+    use hilbert_geometry::spatiotemporal::*;
+    use hilbert_geometry::{encode_geometry, HilbertGeometry};
+
+    #[test]
+    fn test_spatiotemporal_roundtrip() {
+        let encoder = SpatioTemporalEncoder::new(TimeRange::new(0.0, 86_400.0));
+        let ls = line_string![
+            (x: 24.94, y: 60.17),
+            (x: 24.95, y: 60.18),
+            (x: -73.98, y: 40.75)
+        ];
+        let times = [100.0, 160.0, 43_200.0];
+        let encoded = encoder.encode_trajectory(&ls, &times);
+        let (decoded, decoded_times) = encoder.decode_trajectory(&encoded);
+
+        for (a, b) in ls.coords().zip(decoded.coords()) {
+            assert!((a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3);
+        }
+        for (a, b) in times.iter().zip(&decoded_times) {
+            assert!((a - b).abs() < 0.1);
+        }
+
+        // An empty range read back from storage maps every time to its start.
+        let bytes = bincode::encode_to_vec((5.0f64, 5.0f64), bincode::config::standard()).unwrap();
+        let (empty, _): (TimeRange, _) =
+            bincode::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+        let (_, t) = SpatioTemporalEncoder::new(empty)
+            .decode(SpatioTemporalEncoder::new(empty).encode(ls.0[0], 7.0));
+        assert_eq!(t, 5.0);
+    }
+
+    #[test]
+    fn test_spatiotemporal_locality() {
+        let encoder = SpatioTemporalEncoder::new(TimeRange::new(0.0, 3_600.0));
+        let pos = coord! { x: 10.0, y: 10.0 };
+        let a = encoder.encode(pos, 1_000.0).0;
+        let b = encoder.encode(pos, 1_001.0).0;
+        let c = encoder.encode(pos, 3_000.0).0;
+        assert!(a.abs_diff(b) < a.abs_diff(c));
+    }
+
+    #[test]
+    fn test_time_sliced_keys() {
+        // Hour buckets over a day of events.
+        let builder = TimeSlicedKeyBuilder::new(0.0, 3_600.0, 16);
+        assert_eq!(builder.spatial_bits(), 48);
+        assert_eq!(builder.bucket(-5.0), 0);
+        assert_eq!(builder.bucket(7_300.0), 2);
+        assert_eq!(builder.bucket(1e12), (1 << 16) - 1);
+        assert_eq!(builder.bucket_start(2), 7_200.0);
+
+        let mut events = vec![];
+        for i in 0..24 {
+            for j in 0..20 {
+                let (x, y) = (20.0 + j as f64 * 0.5, 60.0 + (i % 4) as f64 * 0.5);
+                let HilbertGeometry::Point(p) = encode_geometry(&Geometry::Point(Point::new(x, y)))
+                else {
+                    unreachable!()
+                };
+                let t = i as f64 * 3_600.0 + j as f64 * 100.0;
+                let key = builder.key(p, t);
+                let (bucket, indices) = builder.split(key);
+                assert_eq!(bucket, builder.bucket(t));
+                assert!(indices.contains(&p.0));
+                events.push((key, x, y, t));
+            }
+        }
+        events.sort_by_key(|e| e.0);
+
+        let rect = Rect::new(coord! { x: 22.0, y: 60.0 }, coord! { x: 24.0, y: 61.0 });
+        let (start, end) = (20.0 * 3_600.0, 22.0 * 3_600.0);
+        let ranges = builder.scan_ranges(&rect, start, end, 8);
+        assert!(ranges.windows(2).all(|w| w[0].end() < w[1].start()));
+        let mut found: Vec<_> = ranges
+            .iter()
+            .flat_map(|r| {
+                let lo = events.partition_point(|e| e.0 < *r.start());
+                let hi = events.partition_point(|e| e.0 <= *r.end());
+                events[lo..hi].iter()
+            })
+            .filter(|(_, x, y, t)| {
+                (22.0..=24.0).contains(x) && (60.0..=61.0).contains(y) && (start..=end).contains(t)
+            })
+            .collect();
+        found.sort_by_key(|e| e.0);
+        let mut expected: Vec<_> = events
+            .iter()
+            .filter(|(_, x, y, t)| {
+                (22.0..=24.0).contains(x) && (60.0..=61.0).contains(y) && (start..=end).contains(t)
+            })
+            .collect();
+        expected.sort_by_key(|e| e.0);
+        assert_eq!(found, expected);
+        assert_eq!(found.len(), 10);
+    }
+}
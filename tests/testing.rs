@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, polygon, Geometry, MultiPoint};
+    use hilbert_geometry::testing::*;
+
+    #[test]
+    fn test_assert_roundtrip() {
+        let geoms = [
+            Geometry::Point(point! { x: 179.999999, y: -89.999999 }),
+            Geometry::LineString(line_string![(x: 24.9384, y: 60.1699), (x: -0.1276, y: 51.5072)]),
+            Geometry::Polygon(polygon![
+                (x: 0.0, y: 0.0),
+                (x: 1.0, y: 0.0),
+                (x: 1.0, y: 1.0),
+                (x: 0.0, y: 0.0)
+            ]),
+            Geometry::MultiPoint(MultiPoint(vec![])),
+        ];
+        for geom in &geoms {
+            assert_roundtrip(geom, LONLAT_EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_error() {
+        let exact = Geometry::Point(point! { x: 0.5, y: -0.25 });
+        assert_eq!(roundtrip_error(&exact), 0.0);
+
+        let geom = Geometry::Point(point! { x: 179.123456789, y: 0.1 });
+        let error = roundtrip_error(&geom);
+        assert!(error > 0.0 && error <= LONLAT_EPSILON);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds")]
+    fn test_assert_roundtrip_epsilon() {
+        let geom = Geometry::Point(point! { x: 179.123456789, y: 0.1 });
+        assert_roundtrip(&geom, 1e-9);
+    }
+
+    #[cfg(feature = "proptest")]
+    mod prop {
+        use hilbert_geometry::testing::strategies::geometry;
+        use hilbert_geometry::testing::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn test_arbitrary_roundtrip(geom in geometry()) {
+                assert_roundtrip(&geom, LONLAT_EPSILON);
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{coord, line_string, point, Geometry};
+    use hilbert_geometry::tile::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_tile_units() {
+        let c = lonlat_to_tile_units(coord! { x: 0.0, y: 0.0 }, 1);
+        assert_eq!(c, coord! { x: 1.0, y: 1.0 });
+        let back = tile_units_to_lonlat(coord! { x: 3.0, y: 1.0 }, 2);
+        assert!((back.x - 90.0).abs() < 1e-12 && (back.y - 66.51326044311186).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_snap_to_tile_grid() {
+        let (zoom, extent) = (10, 256);
+        let a = Geometry::Point(point! { x: 24.93841, y: 60.16993 });
+        let b = Geometry::Point(point! { x: 24.93842, y: 60.16992 });
+        let (sa, sb) = (
+            snap_to_tile_grid(&a, zoom, extent),
+            snap_to_tile_grid(&b, zoom, extent),
+        );
+        assert_eq!(sa, sb);
+        assert_eq!(snap_to_tile_grid(&sa, zoom, extent), sa);
+
+        // Snapped coordinates decode onto whole pixels.
+        let serializer = HilbertSerializer::new().snap_to_tile_grid(zoom, extent);
+        let decoded = serializer.decode(&serializer.encode(&a).unwrap()).unwrap();
+        let Geometry::Point(p) = decoded else {
+            unreachable!()
+        };
+        let px = lonlat_to_tile_units(p.0, zoom);
+        for v in [px.x * 256.0, px.y * 256.0] {
+            assert!((v - v.round()).abs() < 0.01);
+        }
+
+        let ls = Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)]);
+        let Geometry::LineString(snapped) = snap_to_tile_grid(&ls, 0, 4096) else {
+            unreachable!()
+        };
+        assert_eq!(snapped.0.len(), 2);
+        assert_eq!(snapped.0[0], coord! { x: 0.0, y: 0.0 });
+
+        let serializer = HilbertSerializer::new().snap_to_tile_grid(MAX_ZOOM + 1, extent);
+        assert!(serializer.encode(&a).is_err());
+    }
+
+    #[test]
+    fn test_tiles() {
+        let pt = encode_geometry(&Geometry::Point(point! { x: 24.93841, y: 60.16993 }));
+        let HilbertGeometry::Point(p) = pt else {
+            unreachable!()
+        };
+        assert_eq!(p.tile(0), Some((0, 0, 0)));
+        assert_eq!(p.tile(10), Some((582, 296, 10)));
+        assert_eq!(
+            tile_range(&pt, 10).unwrap().collect::<Vec<_>>(),
+            [(582, 296, 10)]
+        );
+
+        let ls = encode_geometry(&Geometry::LineString(line_string![
+            (x: -10.0, y: 10.0),
+            (x: 10.0, y: -10.0)
+        ]));
+        assert_eq!(
+            tile_range(&ls, 1).unwrap().collect::<Vec<_>>(),
+            [(0, 0, 1), (1, 0, 1), (0, 1, 1), (1, 1, 1)]
+        );
+        assert_eq!(
+            tile_range(&HilbertGeometry::LineString(vec![]), 3)
+                .unwrap()
+                .count(),
+            0
+        );
+        let world = encode_geometry(&Geometry::LineString(line_string![
+            (x: -179.0, y: -80.0),
+            (x: 179.0, y: 80.0)
+        ]));
+        let mut tiles = tile_range(&world, 20).unwrap();
+        assert_eq!(tiles.next(), Some((2912, 117712, 20)));
+        let (x, y, _) = p.tile(MAX_ZOOM).unwrap();
+        assert_eq!((x >> 22, y >> 22), (582, 296));
+        assert_eq!(p.tile(MAX_ZOOM + 1), None);
+        assert!(tile_range(&pt, MAX_ZOOM + 1).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "above")]
+    fn test_tiles_zoom_limit() {
+        lonlat_to_tile_units(coord! { x: 0.0, y: 0.0 }, 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "above")]
+    fn test_curve_tile_level_limit() {
+        curve_tile(HilbertPoint(0), cell::MAX_LEVEL + 1);
+    }
+
+    #[test]
+    fn test_tile_local_encoding() {
+        let ls = encode_geometry(&Geometry::LineString(line_string![
+            (x: 24.9384, y: 60.1699),
+            (x: 24.9390, y: 60.1702),
+            (x: 24.9401, y: 60.1710)
+        ]));
+        // Deepest level whose tile holds all vertices.
+        let level = (0..=32)
+            .rev()
+            .find(|l| encode_tile_local(&ls, *l).is_ok())
+            .unwrap();
+        let data = encode_tile_local(&ls, level).unwrap();
+        assert_eq!(decode_tile_local(&data).unwrap(), ls);
+        let full = ls
+            .clone()
+            .encode_bincode(&bincode::config::standard())
+            .unwrap();
+        assert!(data.len() < full.len());
+
+        assert_eq!(
+            decode_tile_local(&encode_tile_local(&ls, 0).unwrap()).unwrap(),
+            ls
+        );
+        assert!(encode_tile_local(&ls, 32).is_err());
+        assert!(decode_tile_local(&data[..data.len() - 1]).is_err());
+
+        // Tiles past the last one at their level: the tile is the byte
+        // before the 8-byte offset at levels 0 and 1.
+        for (level, tile) in [(0, 1), (1, 4)] {
+            let mut data = encode_tile_local(&ls, level).unwrap();
+            let at = data.len() - ls.num_coords() * 8 - 1;
+            assert_eq!(data[at - 1], level as u8);
+            data[at] = tile;
+            assert!(decode_tile_local(&data).is_err());
+        }
+
+        let pt = encode_geometry(&Geometry::Point(point! { x: 1.0, y: 2.0 }));
+        assert_eq!(
+            decode_tile_local(&encode_tile_local(&pt, 32).unwrap()).unwrap(),
+            pt
+        );
+    }
+
+    #[test]
+    fn test_generalize() {
+        // A zigzag of about 5 m amplitude, 100 m between vertices.
+        let line = Geometry::LineString(
+            (0..200)
+                .map(|i| {
+                    let x = 24.0 + i as f64 * 0.001;
+                    coord! { x: x, y: 60.0 + 0.00005 * (i % 2) as f64 }
+                })
+                .collect(),
+        );
+        let hg = encode_geometry(&line);
+        let ladder = generalize(&hg, &[16, 10, 4]).unwrap();
+        assert_eq!(
+            ladder.iter().map(|(z, _)| *z).collect::<Vec<_>>(),
+            vec![16, 10, 4]
+        );
+        let counts: Vec<usize> = ladder.iter().map(|(_, g)| g.num_coords()).collect();
+        assert_eq!(counts[0], 200);
+        assert!(counts[1] < counts[0] && counts[2] <= counts[1]);
+        assert_eq!(counts[2], 2);
+
+        // Zoom 4 rounds to 3 decimals.
+        let Geometry::LineString(coarse) = decode_geometry(&ladder[2].1) else {
+            panic!("expected linestring");
+        };
+        for c in coarse.coords() {
+            assert!((c.x * 1e3 - (c.x * 1e3).round()).abs() < 0.01);
+        }
+        assert!(generalize(&hg, &[]).unwrap().is_empty());
+        assert!(generalize(&hg, &[10, MAX_ZOOM + 1]).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use bincode::config;
+    use geo_types::{polygon, Geometry};
+    use hilbert_geometry::topology::*;
+    use hilbert_geometry::*;
+
+    #[test]
+    fn test_shared_arcs() {
+        // Two squares sharing the edge x = 1.
+        let left = Geometry::Polygon(polygon![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 0.5),
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 1.0),
+            (x: 0.0, y: 0.0)
+        ]);
+        let right = Geometry::Polygon(polygon![
+            (x: 1.0, y: 0.0),
+            (x: 2.0, y: 0.0),
+            (x: 2.0, y: 1.0),
+            (x: 1.0, y: 1.0),
+            (x: 1.0, y: 0.5),
+            (x: 1.0, y: 0.0)
+        ]);
+        let geoms = vec![encode_geometry(&left), encode_geometry(&right)];
+        let topology = HilbertTopology::build(&geoms).unwrap();
+
+        // The shared edge is stored once: 3 arcs with 3 + 4 + 4 vertices.
+        assert_eq!(topology.arcs().len(), 3);
+        assert_eq!(topology.arcs().iter().map(|a| a.len()).sum::<usize>(), 11);
+        assert_eq!(topology.topo_geometries().len(), 2);
+
+        let config = config::standard();
+        let bytes = topology.encode_bincode(&config).unwrap();
+        let decoded = HilbertTopology::decode_bincode(&bytes, &config).unwrap();
+        for (original, rebuilt) in [left, right].iter().zip(decoded.geometries()) {
+            let (Geometry::Polygon(a), Geometry::Polygon(b)) =
+                (original, decode_geometry(&rebuilt))
+            else {
+                panic!("expected polygons");
+            };
+            let mut a: Vec<_> = a.exterior().coords().map(|c| (c.x, c.y)).collect();
+            let mut b: Vec<_> = b.exterior().coords().map(|c| (c.x, c.y)).collect();
+            assert_eq!(a.len(), b.len());
+            // Rings may start at a different vertex, compare without closing vertices.
+            a.pop();
+            b.pop();
+            a.sort_by(|p, q| p.partial_cmp(q).unwrap());
+            b.sort_by(|p, q| p.partial_cmp(q).unwrap());
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_invalid_arc_ref() {
+        let arcs: Vec<Vec<HilbertPoint>> = vec![];
+        let geometries = vec![TopoGeometry::Polygon(vec![vec![ArcRef(3)]])];
+        let config = config::standard();
+        let bytes = bincode::encode_to_vec((arcs, geometries), config).unwrap();
+        assert!(HilbertTopology::decode_bincode(&bytes, &config).is_err());
+        let decoded: Result<(HilbertTopology, _), _> = bincode::decode_from_slice(&bytes, config);
+        assert!(decoded.is_err());
+    }
+}
@@ -0,0 +1,60 @@
+#![cfg(any(feature = "gpx", feature = "kml"))]
+
+#[cfg(test)]
+mod tests {
+    use hilbert_geometry::trace::*;
+
+    #[cfg(feature = "gpx")]
+    #[test]
+    fn test_read_gpx() {
+        let gpx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1">
+  <wpt lat="60.17" lon="24.94"><name>start</name></wpt>
+  <trk>
+    <name>run</name>
+    <trkseg>
+      <trkpt lat="60.17" lon="24.94"></trkpt>
+      <trkpt lat="60.18" lon="24.95"></trkpt>
+      <trkpt lat="60.19" lon="24.95"></trkpt>
+    </trkseg>
+    <trkseg>
+      <trkpt lat="60.20" lon="24.96"></trkpt>
+      <trkpt lat="60.21" lon="24.97"></trkpt>
+    </trkseg>
+  </trk>
+</gpx>"#;
+        let geoms = read_gpx(gpx.as_bytes()).unwrap();
+        let kinds: Vec<_> = geoms.iter().map(|g| g.kind()).collect();
+        assert_eq!(kinds, ["LineString", "LineString", "Point"]);
+        assert_eq!(geoms[0].num_coords(), 3);
+        let start = geoms[2].bounding_rect().unwrap().min();
+        assert!((start.x - 24.94).abs() < 1e-5 && (start.y - 60.17).abs() < 1e-5);
+
+        assert!(read_gpx("not gpx".as_bytes()).is_err());
+    }
+
+    #[cfg(feature = "kml")]
+    #[test]
+    fn test_read_kml() {
+        let kml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2">
+  <Document>
+    <Placemark>
+      <name>office</name>
+      <Point><coordinates>24.94,60.17,0</coordinates></Point>
+    </Placemark>
+    <Placemark>
+      <name>commute</name>
+      <MultiGeometry>
+        <LineString><coordinates>24.94,60.17 24.95,60.18</coordinates></LineString>
+        <Point><coordinates>24.95,60.18</coordinates></Point>
+      </MultiGeometry>
+    </Placemark>
+  </Document>
+</kml>"#;
+        let geoms = read_kml(kml.as_bytes()).unwrap();
+        let kinds: Vec<_> = geoms.iter().map(|g| g.kind()).collect();
+        assert_eq!(kinds, ["Point", "LineString", "Point"]);
+        assert_eq!(geoms[1].num_coords(), 2);
+    }
+}
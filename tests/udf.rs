@@ -0,0 +1,94 @@
+#![cfg(feature = "datafusion")]
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use datafusion::arrow::array::{Array, AsArray, BinaryArray, Float64Array};
+    use datafusion::arrow::datatypes::{DataType, Field, UInt64Type};
+    use datafusion::config::ConfigOptions;
+    use datafusion::logical_expr::{ColumnarValue, ScalarFunctionArgs, ScalarUDF};
+    use geo_types::{point, Geometry};
+    use hilbert_geometry::ewkb::{geometry_from_ewkb, geometry_to_ewkb};
+    use hilbert_geometry::udf::*;
+    use hilbert_geometry::*;
+
+    fn udf(name: &str) -> ScalarUDF {
+        udfs().into_iter().find(|u| u.name() == name).unwrap()
+    }
+
+    fn invoke(
+        udf: &ScalarUDF,
+        args: Vec<Arc<dyn Array>>,
+        arg_types: &[DataType],
+    ) -> Arc<dyn Array> {
+        let number_rows = args[0].len();
+        let return_type = udf.return_type(arg_types).unwrap();
+        let args = ScalarFunctionArgs {
+            args: args.into_iter().map(ColumnarValue::Array).collect(),
+            arg_fields: arg_types
+                .iter()
+                .map(|t| Arc::new(Field::new("arg", t.clone(), true)))
+                .collect(),
+            number_rows,
+            return_field: Arc::new(Field::new(udf.name(), return_type, true)),
+            config_options: Arc::new(ConfigOptions::default()),
+        };
+        match udf.invoke_with_args(args).unwrap() {
+            ColumnarValue::Array(array) => array,
+            _ => panic!("expected array"),
+        }
+    }
+
+    #[test]
+    fn test_udf_encode_decode() {
+        let geom = Geometry::Point(point!(x: 24.94, y: 60.17));
+        let wkb = BinaryArray::from_iter(vec![Some(geometry_to_ewkb(&geom, None)), None]);
+
+        let encoded = invoke(
+            &udf("hilbert_encode"),
+            vec![Arc::new(wkb)],
+            &[DataType::Binary],
+        );
+        assert!(encoded.is_null(1));
+        let bytes = encoded.as_binary::<i32>().value(0);
+        let expected = HilbertSerializer::new().encode(&geom).unwrap();
+        assert_eq!(bytes, expected);
+
+        let decoded = invoke(&udf("hilbert_decode"), vec![encoded], &[DataType::Binary]);
+        let (decoded, _) = geometry_from_ewkb(decoded.as_binary::<i32>().value(0)).unwrap();
+        assert_eq!(decoded, HilbertSerializer::new().decode(&expected).unwrap());
+    }
+
+    #[test]
+    fn test_udf_key() {
+        let lon = Float64Array::from(vec![Some(24.94), None]);
+        let lat = Float64Array::from(vec![Some(60.17), Some(1.0)]);
+        let keys = invoke(
+            &udf("hilbert_key"),
+            vec![Arc::new(lon), Arc::new(lat)],
+            &[DataType::Float64, DataType::Float64],
+        );
+        let keys = keys.as_primitive::<UInt64Type>();
+        let expected = match encode_geometry(&Geometry::Point(point!(x: 24.94, y: 60.17))) {
+            HilbertGeometry::Point(p) => p.0,
+            _ => unreachable!(),
+        };
+        assert_eq!(keys.value(0), expected);
+        assert!(keys.is_null(1));
+    }
+
+    #[test]
+    fn test_udf_encode_invalid() {
+        let wkb = BinaryArray::from_iter_values([[0u8, 1, 2]]);
+        let udf = udf("hilbert_encode");
+        let args = ScalarFunctionArgs {
+            args: vec![ColumnarValue::Array(Arc::new(wkb))],
+            arg_fields: vec![Arc::new(Field::new("wkb", DataType::Binary, true))],
+            number_rows: 1,
+            return_field: Arc::new(Field::new("out", DataType::Binary, true)),
+            config_options: Arc::new(ConfigOptions::default()),
+        };
+        assert!(udf.invoke_with_args(args).is_err());
+    }
+}
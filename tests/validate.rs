@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, polygon, Geometry, MultiLineString, MultiPolygon, Polygon};
+    use hilbert_geometry::validate::*;
+    use hilbert_geometry::HilbertSerializer;
+
+    const HOLES: ValidationOptions = ValidationOptions {
+        validate_holes: true,
+        validate_simple: false,
+    };
+
+    const SIMPLE: ValidationOptions = ValidationOptions {
+        validate_holes: false,
+        validate_simple: true,
+    };
+
+    fn with_holes(holes: Vec<Vec<(f64, f64)>>) -> Polygon {
+        Polygon::new(
+            vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ]
+            .into(),
+            holes.into_iter().map(Into::into).collect(),
+        )
+    }
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<(f64, f64)> {
+        vec![
+            (x, y),
+            (x + size, y),
+            (x + size, y + size),
+            (x, y + size),
+            (x, y),
+        ]
+    }
+
+    #[test]
+    fn test_validate_holes() {
+        let valid = with_holes(vec![square(1.0, 1.0, 2.0), square(3.0, 1.0, 2.0)]);
+        assert!(validate(&Geometry::Polygon(valid.clone()), &HOLES).is_empty());
+        // Holes touching the shell at a vertex are fine too.
+        let touching = with_holes(vec![vec![(0.0, 0.0), (2.0, 1.0), (1.0, 2.0), (0.0, 0.0)]]);
+        assert!(validate(&Geometry::Polygon(touching), &HOLES).is_empty());
+
+        let invalid = with_holes(vec![
+            square(8.0, 8.0, 4.0),
+            square(1.0, 1.0, 3.0),
+            square(2.0, 2.0, 3.0),
+            square(1.5, 1.5, 1.0),
+            square(20.0, 20.0, 1.0),
+        ]);
+        let mp = Geometry::MultiPolygon(MultiPolygon(vec![valid, invalid]));
+        assert_eq!(
+            validate(&mp, &HOLES),
+            vec![
+                ValidationError::HoleOutsideShell {
+                    polygon: 1,
+                    hole: 0
+                },
+                ValidationError::HoleOutsideShell {
+                    polygon: 1,
+                    hole: 4
+                },
+                ValidationError::HolesOverlap {
+                    polygon: 1,
+                    first: 1,
+                    second: 2
+                },
+                ValidationError::HolesOverlap {
+                    polygon: 1,
+                    first: 1,
+                    second: 3
+                },
+                ValidationError::HolesOverlap {
+                    polygon: 1,
+                    first: 2,
+                    second: 3
+                },
+            ]
+        );
+        assert!(validate(&mp, &ValidationOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_serializer_validation() {
+        let bad = Geometry::Polygon(with_holes(vec![square(8.0, 8.0, 4.0)]));
+        let serializer = HilbertSerializer::new().validate(HOLES);
+        let err = serializer.encode(&bad).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("hole 0 of polygon 0 is outside the shell"));
+        assert!(HilbertSerializer::new().encode(&bad).is_ok());
+        let good =
+            Geometry::Polygon(polygon![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 1.0, y: 1.0)]);
+        assert!(serializer.encode(&good).is_ok());
+    }
+
+    #[test]
+    fn test_validate_simple() {
+        let simple = |geom: Geometry| validate(&geom, &SIMPLE);
+        let crossing = |part, ring, first, second| ValidationError::SelfIntersection {
+            part,
+            ring,
+            first,
+            second,
+        };
+
+        // Closed paths, repeated vertices and collinear runs are simple.
+        let ring = line_string![
+            (x: 0.0, y: 0.0),
+            (x: 2.0, y: 0.0),
+            (x: 2.0, y: 0.0),
+            (x: 4.0, y: 0.0),
+            (x: 4.0, y: 4.0),
+            (x: 0.0, y: 0.0),
+        ];
+        assert!(simple(Geometry::LineString(ring)).is_empty());
+        assert!(simple(Geometry::Polygon(with_holes(vec![square(1.0, 1.0, 2.0)]))).is_empty());
+
+        let bowtie = polygon![
+            (x: 0.0, y: 0.0),
+            (x: 2.0, y: 2.0),
+            (x: 2.0, y: 0.0),
+            (x: 0.0, y: 2.0),
+        ];
+        assert_eq!(
+            simple(Geometry::Polygon(bowtie)),
+            vec![crossing(0, 0, 0, 2)]
+        );
+
+        // Touching an earlier vertex, and doubling back on itself.
+        let touching = line_string![
+            (x: 0.0, y: 0.0),
+            (x: 4.0, y: 0.0),
+            (x: 4.0, y: 2.0),
+            (x: 2.0, y: 0.0),
+        ];
+        let spike = line_string![(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 1.0, y: 0.0)];
+        let straight = line_string![(x: 0.0, y: 1.0), (x: 4.0, y: 1.0)];
+        assert_eq!(
+            simple(Geometry::MultiLineString(MultiLineString(vec![
+                straight, touching, spike
+            ]))),
+            vec![crossing(1, 0, 0, 2), crossing(2, 0, 0, 1)]
+        );
+
+        // Holes are numbered after the exterior ring.
+        let bad_hole = vec![(1.0, 1.0), (3.0, 3.0), (3.0, 1.0), (1.0, 3.0), (1.0, 1.0)];
+        let mp = MultiPolygon(vec![
+            with_holes(vec![]),
+            with_holes(vec![square(5.0, 5.0, 1.0), bad_hole]),
+        ]);
+        assert_eq!(
+            simple(Geometry::MultiPolygon(mp.clone())),
+            vec![crossing(1, 2, 0, 2)]
+        );
+        assert!(validate(&Geometry::MultiPolygon(mp.clone()), &HOLES).is_empty());
+        let err = HilbertSerializer::new()
+            .validate(SIMPLE)
+            .encode(&Geometry::MultiPolygon(mp))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("segments 0 and 2 of ring 2 of part 1 intersect"));
+    }
+}
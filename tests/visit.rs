@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, polygon, Coord, Geometry, MultiLineString, MultiPolygon};
+    use hilbert_geometry::visit::CoordVisitor;
+    use hilbert_geometry::*;
+
+    /// Records the traversal as a list of events.
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl CoordVisitor for Recorder {
+        fn begin_geometry(&mut self, kind: &'static str) {
+            self.0.push(kind.to_string());
+        }
+        fn begin_part(&mut self, index: usize) {
+            self.0.push(format!("part {index}"));
+        }
+        fn begin_ring(&mut self, index: usize) {
+            self.0.push(format!("ring {index}"));
+        }
+        fn coord(&mut self, coord: Coord) {
+            self.0.push(format!("{} {}", coord.x, coord.y));
+        }
+    }
+
+    /// Sums coordinates without any other callbacks.
+    struct Centroid(f64, f64, usize);
+
+    impl CoordVisitor for Centroid {
+        fn coord(&mut self, coord: Coord) {
+            self.0 += coord.x;
+            self.1 += coord.y;
+            self.2 += 1;
+        }
+    }
+
+    #[test]
+    fn test_visit_structure() {
+        let poly = polygon![
+            (x: 0.0, y: 0.0),
+            (x: 1.0, y: 0.0),
+            (x: 1.0, y: 1.0),
+            (x: 0.0, y: 0.0)
+        ];
+        let geom = encode_geometry(&Geometry::MultiPolygon(MultiPolygon(vec![poly])));
+        let mut recorder = Recorder::default();
+        geom.visit(&mut recorder);
+        assert_eq!(
+            recorder.0,
+            [
+                "MultiPolygon",
+                "part 0",
+                "ring 0",
+                "0 0",
+                "1 0",
+                "1 1",
+                "0 0"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_visit_coords() {
+        let geom = encode_geometry(&Geometry::MultiLineString(MultiLineString(vec![
+            line_string![(x: 0.0, y: 0.0), (x: 2.0, y: 2.0)],
+            line_string![(x: 4.0, y: 0.0), (x: 2.0, y: -2.0)],
+        ])));
+        let mut centroid = Centroid(0.0, 0.0, 0);
+        geom.visit(&mut centroid);
+        assert_eq!(centroid.2, geom.num_coords());
+        assert_eq!((centroid.0 / 4.0, centroid.1 / 4.0), (2.0, 0.0));
+    }
+}